@@ -200,6 +200,7 @@ async fn test_multi_integrity() {
                     wasm_hash: wasm_hash.clone(),
                     dependencies: vec![],
                     preserialized_path: None,
+                    post_commit_retry: None,
                 })
                 .into(),
             ),
@@ -209,10 +210,13 @@ async fn test_multi_integrity() {
                     wasm_hash: wasm_hash.clone(),
                     dependencies: vec![],
                     preserialized_path: None,
+                    post_commit_retry: None,
                 })
                 .into(),
             ),
         ],
+        rate_limits: Default::default(),
+        wasm_metering_limit: None,
         coordinator_zomes: vec![
             (
                 "zome3".into(),
@@ -220,6 +224,7 @@ async fn test_multi_integrity() {
                     wasm_hash: wasm_hash2.clone(),
                     dependencies: vec!["zome1".into()],
                     preserialized_path: None,
+                    post_commit_retry: None,
                 })
                 .into(),
             ),
@@ -229,6 +234,7 @@ async fn test_multi_integrity() {
                     wasm_hash: wasm_hash2.clone(),
                     dependencies: vec!["zome1".into(), "zome2".into()],
                     preserialized_path: None,
+                    post_commit_retry: None,
                 })
                 .into(),
             ),
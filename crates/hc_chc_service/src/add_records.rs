@@ -0,0 +1,90 @@
+use super::*;
+use crate::error::ChcServiceResult;
+use crate::store::StoredRecord;
+use holochain_keystore::AgentPubKeyExt;
+
+pub(crate) fn add_records(
+    store: Store,
+) -> impl Filter<Extract = impl warp::Reply + Sized, Error = warp::Rejection> + Clone {
+    warp::post()
+        .and(warp::path!(String / String / "add_records"))
+        .and(warp::body::content_length_limit(SIZE_LIMIT))
+        .and(warp::body::bytes())
+        .and(with_store(store))
+        .and_then(handle_add_records)
+}
+
+async fn handle_add_records(
+    dna_hash: String,
+    agent: String,
+    body: warp::hyper::body::Bytes,
+    store: Store,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    let result = async {
+        let (dna_hash, agent) = parse_cell(dna_hash, agent)?;
+        let request: AddRecordsRequest = holochain_serialized_bytes::decode(&body)?;
+        let mut records = Vec::with_capacity(request.len());
+        for payload in request {
+            records.push(check_payload(&agent, payload).await?);
+        }
+        tokio::task::spawn_blocking(move || store.add_records(&dna_hash, &agent, records))
+            .await
+            .map_err(|e| ChcError::Other(e.to_string()))?
+    }
+    .await;
+    Ok(match result {
+        Ok(()) => reply(StatusCode::OK.as_u16(), Vec::new()),
+        Err(e) => error_reply(e),
+    })
+}
+
+/// Check that a payload was authored and signed by the agent, and that the
+/// hash and signatures all match their content.
+async fn check_payload(
+    agent: &AgentPubKey,
+    payload: AddRecordPayload,
+) -> ChcServiceResult<StoredRecord> {
+    let AddRecordPayload {
+        signed_action_msgpack,
+        signed_action_signature,
+        encrypted_entry,
+    } = payload;
+    let unauthorized = |msg: &str| ChcServiceError::Unauthorized(msg.to_string());
+
+    let action: SignedActionHashed = holochain_serialized_bytes::decode(&signed_action_msgpack)?;
+    if action.action().author() != agent {
+        return Err(unauthorized("action was not authored by this agent"));
+    }
+    if ActionHash::with_data_sync(action.action()) != *action.as_hash() {
+        return Err(unauthorized("action hash does not match the action"));
+    }
+    if !agent
+        .verify_signature(action.signature(), action.action().clone())
+        .await?
+    {
+        return Err(unauthorized("invalid action signature"));
+    }
+    if !agent
+        .verify_signature_raw(
+            &signed_action_signature,
+            signed_action_msgpack.clone().into(),
+        )
+        .await?
+    {
+        return Err(unauthorized("invalid signature of the signed action"));
+    }
+    if let Some((entry, signature)) = &encrypted_entry {
+        if !agent
+            .verify_signature_raw(signature, entry.0.clone().into())
+            .await?
+        {
+            return Err(unauthorized("invalid signature of the encrypted entry"));
+        }
+    }
+
+    Ok(StoredRecord {
+        action,
+        signed_action_msgpack,
+        encrypted_entry,
+    })
+}
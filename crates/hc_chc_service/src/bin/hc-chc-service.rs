@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[clap(version, about, long_about = None)]
+struct Args {
+    /// bind to this interface
+    #[clap(short, long, default_value = "127.0.0.1:0")]
+    interface: String,
+
+    /// path to the SQLite database holding the records,
+    /// created if it doesn't exist
+    #[clap(short, long, default_value = "chc.sqlite3", verbatim_doc_comment)]
+    database: PathBuf,
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() {
+    if std::env::var_os("RUST_LOG").is_some() {
+        holochain_trace::init_fmt(holochain_trace::Output::Log).ok();
+    }
+
+    let args = Args::parse();
+
+    use std::net::ToSocketAddrs;
+    let addr = args
+        .interface
+        .as_str()
+        .to_socket_addrs()
+        .unwrap()
+        .next()
+        .unwrap();
+
+    match hc_chc_service::run(addr, &args.database).await {
+        Ok((driver, addr, _shutdown)) => {
+            println!("http://{}", addr);
+            driver.await;
+        }
+        Err(err) => eprintln!("{}", err),
+    }
+}
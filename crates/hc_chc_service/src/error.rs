@@ -0,0 +1,40 @@
+//! Errors which can occur while serving CHC requests.
+
+use holochain_serialized_bytes::SerializedBytesError;
+use holochain_types::prelude::*;
+
+/// An error while handling a CHC request.
+#[derive(Debug, thiserror::Error)]
+pub enum ChcServiceError {
+    /// The SQLite store failed.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// A request body or a stored value could not be (de)serialized.
+    #[error(transparent)]
+    Serialization(#[from] SerializedBytesError),
+
+    /// A signature could not be checked by the keystore.
+    #[error(transparent)]
+    Keystore(#[from] holochain_keystore::KeystoreError),
+
+    /// A DNA hash or agent key in the URL path could not be parsed.
+    #[error("Invalid path segment: {0}")]
+    InvalidPath(String),
+
+    /// A signature or hash in the request did not match its content,
+    /// or the request was not made by the agent in the URL path.
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// The nonce of a `get_record_data` request was already used.
+    #[error("Nonce has already been used")]
+    NonceReused,
+
+    /// The records would not extend the stored chain. See [`ChcError`].
+    #[error(transparent)]
+    Chc(#[from] ChcError),
+}
+
+/// Result type for the CHC service.
+pub type ChcServiceResult<T> = Result<T, ChcServiceError>;
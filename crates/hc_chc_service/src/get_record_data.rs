@@ -0,0 +1,55 @@
+use super::*;
+use holochain_keystore::AgentPubKeyExt;
+
+pub(crate) fn get_record_data(
+    store: Store,
+) -> impl Filter<Extract = impl warp::Reply + Sized, Error = warp::Rejection> + Clone {
+    warp::post()
+        .and(warp::path!(String / String / "get_record_data"))
+        .and(warp::body::content_length_limit(SIZE_LIMIT))
+        .and(warp::body::bytes())
+        .and(with_store(store))
+        .and_then(handle_get_record_data)
+}
+
+async fn handle_get_record_data(
+    dna_hash: String,
+    agent: String,
+    body: warp::hyper::body::Bytes,
+    store: Store,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    let result = async {
+        let (dna_hash, agent) = parse_cell(dna_hash, agent)?;
+        let GetRecordsRequest { payload, signature } = holochain_serialized_bytes::decode(&body)?;
+
+        // The payload is signed as its msgpack encoding, see `AgentPubKeyExt::sign`.
+        let signed = holochain_serialized_bytes::encode(&payload)?;
+        if !agent
+            .verify_signature_raw(&signature, signed.into())
+            .await?
+        {
+            return Err(ChcServiceError::Unauthorized(
+                "invalid request signature".to_string(),
+            ));
+        }
+
+        tokio::task::spawn_blocking(move || {
+            if !store.use_nonce(&agent, payload.nonce.into_inner(), Timestamp::now())? {
+                return Err(ChcServiceError::NonceReused);
+            }
+            store.get_records(&dna_hash, &agent, payload.since_hash.as_ref())
+        })
+        .await
+        .map_err(|e| ChcError::Other(e.to_string()))?
+    }
+    .await;
+    Ok(match result {
+        Ok(Some(records)) => match holochain_serialized_bytes::encode(&records) {
+            Ok(body) => reply(StatusCode::OK.as_u16(), body),
+            Err(e) => error_reply(e.into()),
+        },
+        // The since_hash is not part of the chain
+        Ok(None) => reply(INVALID_REQUEST, Vec::new()),
+        Err(e) => error_reply(e),
+    })
+}
@@ -0,0 +1,323 @@
+//! A reference HTTP implementation of a Chain Head Coordinator (CHC),
+//! backed by SQLite.
+//!
+//! This is the server counterpart of the `ChcRemote` client in the
+//! `holochain` crate. Point a conductor at it by setting `chc_url` in the
+//! `ConductorConfig` to the URL printed by the `hc-chc-service` binary.
+//!
+//! The service handles two routes per cell, both taking and returning
+//! msgpack-encoded bodies:
+//!
+//! - `POST /{dna_hash}/{agent_pubkey}/add_records` takes an
+//!   [`AddRecordsRequest`](holochain_types::chc::AddRecordsRequest).
+//!   It returns 200 when the records were appended, 409 with the current
+//!   `(seq, hash)` head when the caller is behind the CHC, and 498 with the
+//!   offending sequence number when the records don't form a valid chain.
+//! - `POST /{dna_hash}/{agent_pubkey}/get_record_data` takes a
+//!   [`GetRecordsRequest`](holochain_types::chc::GetRecordsRequest) and
+//!   returns the records after `since_hash`, or 498 if that hash is unknown.
+//!
+//! Every request must be signed by the agent in the path. Requests which
+//! fail signature or hash checks are answered with 401.
+
+// Fixes some warnings introduced by `warp`
+#![allow(opaque_hidden_inferred_bound)]
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use holochain_types::prelude::*;
+use store::Store;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
+
+mod add_records;
+mod get_record_data;
+mod store;
+
+pub mod error;
+
+use error::ChcServiceError;
+
+/// The largest request body accepted.
+const SIZE_LIMIT: u64 = 16 * 1024 * 1024;
+
+/// The non-standard status code the CHC uses for an invalid request.
+const INVALID_REQUEST: u16 = 498;
+
+pub type ChcDriver = futures::future::BoxFuture<'static, ()>;
+
+pub type ChcShutdown = Box<dyn FnOnce() + 'static + Send + Sync>;
+
+/// Run a CHC service bound to the given address.
+///
+/// Records are stored in the SQLite database at `db_path`, which is created
+/// if it doesn't exist.
+pub async fn run(
+    addr: impl Into<SocketAddr> + 'static,
+    db_path: &Path,
+) -> Result<(ChcDriver, SocketAddr, ChcShutdown), String> {
+    let store = Store::open(db_path).map_err(|e| format!("Failed to open database: {e}"))?;
+
+    let chc = routes(store).with(warp::trace::request());
+
+    let (s, r) = tokio::sync::oneshot::channel();
+    let shutdown = Box::new(move || {
+        let _ = s.send(());
+    });
+
+    let bind_result = warp::serve(chc).try_bind_with_graceful_shutdown(addr, async move {
+        let _ = r.await;
+    });
+    match bind_result {
+        Ok((addr, server)) => {
+            let driver = futures::future::FutureExt::boxed(server);
+            Ok((driver, addr, shutdown))
+        }
+        Err(e) => Err(format!("Failed to bind socket: {:?}", e)),
+    }
+}
+
+fn routes(
+    store: Store,
+) -> impl Filter<Extract = impl warp::Reply + Sized, Error = warp::Rejection> + Clone {
+    add_records::add_records(store.clone()).or(get_record_data::get_record_data(store))
+}
+
+fn with_store(
+    store: Store,
+) -> impl Filter<Extract = (Store,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || store.clone())
+}
+
+/// Build a response with the given status and body.
+fn reply(status: u16, body: Vec<u8>) -> warp::reply::Response {
+    let status = StatusCode::from_u16(status).expect("valid status code");
+    warp::reply::with_status(body, status).into_response()
+}
+
+/// Turn an error into the response the `ChcRemote` client expects.
+fn error_reply(err: ChcServiceError) -> warp::reply::Response {
+    let encoded = |r: Result<Vec<u8>, _>, status| match r {
+        Ok(body) => reply(status, body),
+        Err(e) => error_reply(ChcServiceError::Serialization(e)),
+    };
+    match err {
+        ChcServiceError::Chc(ChcError::InvalidChain(seq, hash)) => encoded(
+            holochain_serialized_bytes::encode(&(seq, hash)),
+            StatusCode::CONFLICT.as_u16(),
+        ),
+        ChcServiceError::Chc(ChcError::NoRecordsAdded(seq)) => {
+            encoded(holochain_serialized_bytes::encode(&seq), INVALID_REQUEST)
+        }
+        ChcServiceError::InvalidPath(_) | ChcServiceError::Serialization(_) => {
+            warp::reply::with_status(err.to_string(), StatusCode::BAD_REQUEST).into_response()
+        }
+        ChcServiceError::Unauthorized(_) | ChcServiceError::NonceReused => {
+            warp::reply::with_status(err.to_string(), StatusCode::UNAUTHORIZED).into_response()
+        }
+        ChcServiceError::Sqlite(_) | ChcServiceError::Keystore(_) | ChcServiceError::Chc(_) => {
+            tracing::error!(?err, "CHC request failed");
+            warp::reply::with_status(err.to_string(), StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response()
+        }
+    }
+}
+
+/// Parse the `{dna_hash}/{agent_pubkey}` segments of a request path.
+fn parse_cell(dna_hash: String, agent: String) -> Result<(DnaHash, AgentPubKey), ChcServiceError> {
+    let dna_hash =
+        DnaHash::try_from(dna_hash).map_err(|e| ChcServiceError::InvalidPath(e.to_string()))?;
+    let agent =
+        AgentPubKey::try_from(agent).map_err(|e| ChcServiceError::InvalidPath(e.to_string()))?;
+    Ok((dna_hash, agent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contrafact::Fact;
+    use holochain_keystore::{AgentPubKeyExt, MetaLairClient};
+    use holochain_nonce::Nonce256Bits;
+    use warp::http::Response;
+    use warp::hyper::body::Bytes;
+
+    async fn test_chain(keystore: &MetaLairClient, author: AgentPubKey, n: usize) -> Vec<Record> {
+        let mut g = random_generator();
+        let fact = contrafact::facts![
+            holochain_zome_types::facts::action_and_entry_match(false),
+            contrafact::lens1(
+                "action is valid",
+                |(a, _)| a,
+                holochain_zome_types::facts::valid_chain_action(author.clone()),
+            ),
+        ];
+        let pairs = contrafact::vec_of_length(n, fact).build(&mut g);
+        futures::future::join_all(pairs.into_iter().map(|(a, entry)| async move {
+            Record::new(
+                SignedActionHashed::sign(keystore, ActionHashed::from_content_sync(a))
+                    .await
+                    .unwrap(),
+                entry.into_option(),
+            )
+        }))
+        .await
+    }
+
+    async fn add(
+        store: &Store,
+        keystore: &MetaLairClient,
+        dna_hash: &DnaHash,
+        agent: &AgentPubKey,
+        records: &[Record],
+    ) -> Response<Bytes> {
+        let author = records[0].action().author().clone();
+        let payload = AddRecordPayload::from_records(keystore.clone(), author, records.to_vec())
+            .await
+            .unwrap();
+        warp::test::request()
+            .method("POST")
+            .path(&format!("/{dna_hash}/{agent}/add_records"))
+            .body(holochain_serialized_bytes::encode(&payload).unwrap())
+            .reply(&routes(store.clone()))
+            .await
+    }
+
+    async fn get(
+        store: &Store,
+        keystore: &MetaLairClient,
+        dna_hash: &DnaHash,
+        agent: &AgentPubKey,
+        since_hash: Option<ActionHash>,
+        nonce: u8,
+    ) -> Response<Bytes> {
+        let payload = GetRecordsPayload {
+            since_hash,
+            nonce: Nonce256Bits::from([nonce; 32]),
+        };
+        let signature = agent.sign(keystore, &payload).await.unwrap();
+        let request = GetRecordsRequest { payload, signature };
+        warp::test::request()
+            .method("POST")
+            .path(&format!("/{dna_hash}/{agent}/get_record_data"))
+            .body(holochain_serialized_bytes::encode(&request).unwrap())
+            .reply(&routes(store.clone()))
+            .await
+    }
+
+    fn hashes(res: Response<Bytes>) -> Vec<ActionHash> {
+        assert_eq!(res.status(), 200);
+        let data: store::RecordData = holochain_serialized_bytes::decode(res.body()).unwrap();
+        data.into_iter().map(|(a, _)| a.as_hash().clone()).collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn add_and_get_records() {
+        let keystore = holochain_keystore::test_keystore();
+        let agent = keystore.new_sign_keypair_random().await.unwrap();
+        let dna_hash = ::fixt::fixt!(DnaHash);
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(&dir.path().join("chc.sqlite3")).unwrap();
+
+        let chain = test_chain(&keystore, agent.clone(), 9).await;
+        let hash = |i: usize| chain[i].action_address().clone();
+        let add = |records| add(&store, &keystore, &dna_hash, &agent, records);
+
+        assert_eq!(add(&chain[0..3]).await.status(), 200);
+        assert_eq!(add(&chain[3..6]).await.status(), 200);
+
+        // Behind the head, so the current head is returned
+        let res = add(&chain[2..4]).await;
+        assert_eq!(res.status(), 409);
+        let head: (u32, ActionHash) = holochain_serialized_bytes::decode(res.body()).unwrap();
+        assert_eq!(head, (5, hash(5)));
+
+        // Gap after the head
+        let res = add(&chain[7..9]).await;
+        assert_eq!(res.status(), 498);
+        let seq: u32 = holochain_serialized_bytes::decode(res.body()).unwrap();
+        assert_eq!(seq, 7);
+
+        assert_eq!(add(&chain[6..9]).await.status(), 200);
+
+        let get = |since_hash, nonce| get(&store, &keystore, &dna_hash, &agent, since_hash, nonce);
+        assert_eq!(
+            hashes(get(None, 0).await),
+            (0..9).map(hash).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            hashes(get(Some(hash(3)), 1).await),
+            (4..9).map(hash).collect::<Vec<_>>()
+        );
+        assert!(hashes(get(Some(hash(8)), 2).await).is_empty());
+        assert_eq!(get(Some(::fixt::fixt!(ActionHash)), 3).await.status(), 498);
+
+        // A replayed request is refused
+        assert_eq!(get(None, 0).await.status(), 401);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn refuses_records_of_other_agents() {
+        let keystore = holochain_keystore::test_keystore();
+        let alice = keystore.new_sign_keypair_random().await.unwrap();
+        let bob = keystore.new_sign_keypair_random().await.unwrap();
+        let dna_hash = ::fixt::fixt!(DnaHash);
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(&dir.path().join("chc.sqlite3")).unwrap();
+
+        let chain = test_chain(&keystore, alice.clone(), 3).await;
+
+        let res = add(&store, &keystore, &dna_hash, &bob, &chain).await;
+        assert_eq!(res.status(), 401);
+
+        // Bob can't read Alice's chain either
+        let payload = GetRecordsPayload {
+            since_hash: None,
+            nonce: Nonce256Bits::from([0; 32]),
+        };
+        let signature = bob.sign(&keystore, &payload).await.unwrap();
+        let request = GetRecordsRequest { payload, signature };
+        let res = warp::test::request()
+            .method("POST")
+            .path(&format!("/{dna_hash}/{alice}/get_record_data"))
+            .body(holochain_serialized_bytes::encode(&request).unwrap())
+            .reply(&routes(store.clone()))
+            .await;
+        assert_eq!(res.status(), 401);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn nonces_expire() {
+        let keystore = holochain_keystore::test_keystore();
+        let alice = keystore.new_sign_keypair_random().await.unwrap();
+        let bob = keystore.new_sign_keypair_random().await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(&dir.path().join("chc.sqlite3")).unwrap();
+
+        let t0 = Timestamp::from_micros(1_000_000_000);
+        let later = |d: std::time::Duration| (t0 + d).unwrap();
+
+        assert!(store.use_nonce(&alice, [0; 32], t0).unwrap());
+        // The same nonce is per agent
+        assert!(store.use_nonce(&bob, [0; 32], t0).unwrap());
+        assert!(!store
+            .use_nonce(&alice, [0; 32], later(store::NONCE_RETENTION))
+            .unwrap());
+
+        // Once a nonce is older than the retention period it is pruned
+        assert!(store
+            .use_nonce(
+                &alice,
+                [1; 32],
+                later(store::NONCE_RETENTION + std::time::Duration::from_secs(1))
+            )
+            .unwrap());
+        assert!(store
+            .use_nonce(
+                &bob,
+                [0; 32],
+                later(store::NONCE_RETENTION + std::time::Duration::from_secs(1))
+            )
+            .unwrap());
+    }
+}
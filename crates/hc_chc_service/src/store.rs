@@ -0,0 +1,248 @@
+//! SQLite storage for the records of every chain held by the CHC.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use holochain_types::prelude::*;
+use parking_lot::Mutex;
+use rusqlite::{named_params, Connection, OptionalExtension};
+
+use crate::error::ChcServiceResult;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS ChcRecord (
+    dna_hash         BLOB    NOT NULL,
+    author           BLOB    NOT NULL,
+    seq              INTEGER NOT NULL,
+    hash             BLOB    NOT NULL,
+    signed_action    BLOB    NOT NULL,
+    encrypted_entry  BLOB    NULL,
+    PRIMARY KEY (dna_hash, author, seq)
+);
+CREATE INDEX IF NOT EXISTS ChcRecord_hash_idx ON ChcRecord (dna_hash, author, hash);
+
+CREATE TABLE IF NOT EXISTS ChcNonce (
+    author   BLOB    NOT NULL,
+    nonce    BLOB    NOT NULL,
+    used_at  INTEGER NOT NULL,
+    PRIMARY KEY (author, nonce)
+);
+CREATE INDEX IF NOT EXISTS ChcNonce_used_at_idx ON ChcNonce (used_at);
+";
+
+/// How long a used `get_record_data` nonce is remembered.
+///
+/// A request can be replayed once its nonce has been forgotten, so this is
+/// much longer than a client takes to make a request.
+pub(crate) const NONCE_RETENTION: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// A record as held by the CHC.
+///
+/// The action is kept as the exact msgpack bytes that were signed by the
+/// author, so it can be returned without re-encoding.
+#[derive(Debug)]
+pub(crate) struct StoredRecord {
+    pub action: SignedActionHashed,
+    pub signed_action_msgpack: Vec<u8>,
+    pub encrypted_entry: Option<(Arc<EncryptedEntry>, Signature)>,
+}
+
+/// The records returned for a `get_record_data` request.
+pub(crate) type RecordData = Vec<(SignedActionHashed, Option<(Arc<EncryptedEntry>, Signature)>)>;
+
+/// Handle to the SQLite database of the CHC. Cheap to clone.
+#[derive(Clone)]
+pub(crate) struct Store(Arc<Mutex<Connection>>);
+
+impl Store {
+    /// Open the database at the given path, creating it if necessary.
+    pub fn open(path: &Path) -> ChcServiceResult<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self(Arc::new(Mutex::new(conn))))
+    }
+
+    /// Append records to the chain of this cell.
+    ///
+    /// The records must directly extend the current head of the chain,
+    /// otherwise nothing is written and a [`ChcError`] is returned.
+    pub fn add_records(
+        &self,
+        dna_hash: &DnaHash,
+        author: &AgentPubKey,
+        records: Vec<StoredRecord>,
+    ) -> ChcServiceResult<()> {
+        let mut conn = self.0.lock();
+        let txn = conn.transaction()?;
+        let head = head(&txn, dna_hash, author)?;
+        check_extends_head(records.iter().map(|r| &r.action), head)?;
+        for record in records {
+            let encrypted_entry = record
+                .encrypted_entry
+                .as_ref()
+                .map(holochain_serialized_bytes::encode)
+                .transpose()?;
+            txn.execute(
+                "INSERT INTO ChcRecord
+                    (dna_hash, author, seq, hash, signed_action, encrypted_entry)
+                VALUES
+                    (:dna_hash, :author, :seq, :hash, :signed_action, :encrypted_entry)",
+                named_params! {
+                    ":dna_hash": dna_hash,
+                    ":author": author,
+                    ":seq": record.action.seq(),
+                    ":hash": record.action.get_hash(),
+                    ":signed_action": record.signed_action_msgpack,
+                    ":encrypted_entry": encrypted_entry,
+                },
+            )?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Get the records of this cell after (not including) `since_hash`,
+    /// or all records if no hash is given.
+    ///
+    /// Returns `None` if `since_hash` is not part of the stored chain.
+    pub fn get_records(
+        &self,
+        dna_hash: &DnaHash,
+        author: &AgentPubKey,
+        since_hash: Option<&ActionHash>,
+    ) -> ChcServiceResult<Option<RecordData>> {
+        let conn = self.0.lock();
+        let since_seq: i64 = match since_hash {
+            Some(hash) => {
+                let seq = conn
+                    .query_row(
+                        "SELECT seq FROM ChcRecord
+                        WHERE dna_hash = :dna_hash AND author = :author AND hash = :hash",
+                        named_params! {
+                            ":dna_hash": dna_hash,
+                            ":author": author,
+                            ":hash": hash,
+                        },
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                match seq {
+                    Some(seq) => seq,
+                    None => return Ok(None),
+                }
+            }
+            None => -1,
+        };
+        let mut stmt = conn.prepare(
+            "SELECT signed_action, encrypted_entry FROM ChcRecord
+            WHERE dna_hash = :dna_hash AND author = :author AND seq > :seq
+            ORDER BY seq ASC",
+        )?;
+        let rows = stmt.query_map(
+            named_params! {
+                ":dna_hash": dna_hash,
+                ":author": author,
+                ":seq": since_seq,
+            },
+            |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Option<Vec<u8>>>(1)?)),
+        )?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (action, entry) = row?;
+            let action: SignedActionHashed = holochain_serialized_bytes::decode(&action)?;
+            let entry = entry
+                .map(|e| holochain_serialized_bytes::decode(&e))
+                .transpose()?;
+            records.push((action, entry));
+        }
+        Ok(Some(records))
+    }
+
+    /// Record that a `get_record_data` nonce has been used by this agent at `now`.
+    ///
+    /// Returns `false` if the nonce had already been used. Nonces which were
+    /// used longer than [`NONCE_RETENTION`] ago are pruned.
+    pub fn use_nonce(
+        &self,
+        author: &AgentPubKey,
+        nonce: [u8; 32],
+        now: Timestamp,
+    ) -> ChcServiceResult<bool> {
+        let conn = self.0.lock();
+        conn.execute(
+            "DELETE FROM ChcNonce WHERE used_at < :expired",
+            named_params! {
+                ":expired": now.saturating_sub(&NONCE_RETENTION).as_micros(),
+            },
+        )?;
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO ChcNonce (author, nonce, used_at)
+            VALUES (:author, :nonce, :used_at)",
+            named_params! {
+                ":author": author,
+                ":nonce": nonce,
+                ":used_at": now.as_micros(),
+            },
+        )?;
+        Ok(inserted == 1)
+    }
+}
+
+fn head(
+    conn: &Connection,
+    dna_hash: &DnaHash,
+    author: &AgentPubKey,
+) -> ChcServiceResult<Option<(ActionHash, u32)>> {
+    Ok(conn
+        .query_row(
+            "SELECT hash, seq FROM ChcRecord
+            WHERE dna_hash = :dna_hash AND author = :author
+            ORDER BY seq DESC LIMIT 1",
+            named_params! {
+                ":dna_hash": dna_hash,
+                ":author": author,
+            },
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?)
+}
+
+/// Check that the actions form a chain which directly extends the head.
+///
+/// If the first action is at or behind the head, the caller is out of sync
+/// and gets [`ChcError::InvalidChain`] with the current head. Any other break
+/// in the chain is an invalid request, reported as [`ChcError::NoRecordsAdded`].
+fn check_extends_head<'a>(
+    mut actions: impl Iterator<Item = &'a SignedActionHashed>,
+    head: Option<(ActionHash, u32)>,
+) -> Result<(), ChcError> {
+    let mut prev = match actions.next() {
+        Some(first) => {
+            match &head {
+                Some((head_hash, head_seq)) => {
+                    if first.seq() <= *head_seq {
+                        return Err(ChcError::InvalidChain(*head_seq, head_hash.clone()));
+                    }
+                    if first.seq() != head_seq + 1 || first.prev_hash() != Some(head_hash) {
+                        return Err(ChcError::NoRecordsAdded(first.seq()));
+                    }
+                }
+                None => {
+                    if first.seq() != 0 || first.prev_hash().is_some() {
+                        return Err(ChcError::NoRecordsAdded(first.seq()));
+                    }
+                }
+            }
+            first
+        }
+        None => return Ok(()),
+    };
+    for action in actions {
+        if action.seq() != prev.seq() + 1 || action.prev_hash() != Some(prev.get_hash()) {
+            return Err(ChcError::NoRecordsAdded(action.seq()));
+        }
+        prev = action;
+    }
+    Ok(())
+}
@@ -25,6 +25,7 @@ use holochain_types::prelude::{AgentPubKey, AppBundleSource};
 use holochain_types::prelude::{CellId, InstallAppPayload};
 use holochain_types::prelude::{DnaHash, InstalledAppId};
 use holochain_types::prelude::{DnaSource, NetworkSeed};
+use holochain_types::prelude::{ScheduledFnInfo, ScheduledFnRun};
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use std::convert::TryFrom;
 
@@ -79,10 +80,11 @@ pub enum AdminRequestCli {
     DisableApp(DisableApp),
     DumpState(DumpState),
     DumpConductorState,
-    /// Calls AdminRequest::AddAgentInfo.
-    /// _Unimplemented_.
-    AddAgents,
+    AddAgents(AddAgents),
     ListAgents(ListAgents),
+    ListScheduledFns(ListScheduledFns),
+    TriggerScheduledFn(TriggerScheduledFn),
+    UnscheduleFn(UnscheduleFn),
 }
 
 /// Calls AdminRequest::AddAdminInterfaces
@@ -214,6 +216,33 @@ pub struct DumpState {
     pub agent_key: AgentPubKey,
 }
 
+/// Calls AdminRequest::AddAgentInfo
+/// and adds agent infos to this conductor.
+///
+/// The agent infos are read as a JSON list, like the one
+/// printed by `list-agents --json`, from a file or from stdin.
+/// Alternatively they can be requested directly from another
+/// running sandbox.
+#[derive(Debug, Args, Clone)]
+pub struct AddAgents {
+    /// Path to a file containing the agent infos.
+    /// If neither this nor `--from-sandbox` is given,
+    /// the agent infos are read from stdin.
+    #[arg(short, long, conflicts_with = "from_sandbox")]
+    pub file: Option<PathBuf>,
+
+    /// Request all agent infos from the running sandbox
+    /// with this index in `$(pwd)/.hc`.
+    /// Existing sandboxes and their indices are visible via `hc list`.
+    #[arg(long)]
+    pub from_sandbox: Option<usize>,
+
+    /// Agent infos which have already been loaded from the source,
+    /// so that it is only read once when adding to several conductors.
+    #[arg(skip)]
+    pub agent_infos: Option<Vec<AgentInfoSigned>>,
+}
+
 /// Calls AdminRequest::RequestAgentInfo
 /// and pretty prints the agent info on
 /// this conductor.
@@ -226,6 +255,11 @@ pub struct ListAgents {
     /// Optionally request agent info for a particular cell ID.
     #[arg(short, long, value_parser = parse_dna_hash, requires = "agent_key")]
     pub dna: Option<DnaHash>,
+
+    /// Print the agent infos as a JSON list instead,
+    /// which can be passed to `add-agents`.
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// Calls AdminRequest::ListApps
@@ -238,6 +272,57 @@ pub struct ListApps {
     pub status: Option<AppStatusFilter>,
 }
 
+/// Calls AdminRequest::ListScheduledFunctions
+/// and lists the functions scheduled for a cell.
+#[derive(Debug, Args, Clone)]
+pub struct ListScheduledFns {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+}
+
+/// Calls AdminRequest::TriggerScheduledFunction
+/// and runs a scheduled function right away.
+#[derive(Debug, Args, Clone)]
+pub struct TriggerScheduledFn {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// The zome of the scheduled function.
+    pub zome_name: String,
+
+    /// The name of the scheduled function.
+    pub fn_name: String,
+}
+
+/// Calls AdminRequest::UnscheduleFunction
+/// and removes a function from the schedule of a cell.
+#[derive(Debug, Args, Clone)]
+pub struct UnscheduleFn {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// The zome of the scheduled function.
+    pub zome_name: String,
+
+    /// The name of the scheduled function.
+    pub fn_name: String,
+}
+
 #[doc(hidden)]
 pub async fn call(
     holochain_path: &Path,
@@ -248,7 +333,7 @@ pub async fn call(
     let Call {
         existing,
         running,
-        call,
+        mut call,
     } = req;
     // Force admin ports takes precedence over running. They both specify the same thing but force admin ports
     // is used across other sandbox calls so this makes `call` consistent with others.
@@ -310,6 +395,10 @@ pub async fn call(
         }
         cmds
     };
+    // Load agent infos before calling any conductor, because stdin can only be read once.
+    if let AdminRequestCli::AddAgents(args) = &mut call {
+        args.agent_infos = Some(load_agent_infos(args).await?);
+    }
     for mut cmd in cmds {
         call_inner(&mut cmd.0, call.clone()).await?;
     }
@@ -377,10 +466,40 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let state = dump_conductor_state(cmd).await?;
             msg!("DUMP CONDUCTOR STATE \n{}", state);
         }
-        AdminRequestCli::AddAgents => todo!("Adding agent info via CLI is coming soon"),
+        AdminRequestCli::AddAgents(args) => {
+            let count = add_agents(cmd, args).await?;
+            msg!("Added {} agent infos", count);
+        }
+        AdminRequestCli::ListScheduledFns(args) => {
+            let scheduled_fns = list_scheduled_fns(cmd, args).await?;
+            msg!("Scheduled functions: {:#?}", scheduled_fns);
+        }
+        AdminRequestCli::TriggerScheduledFn(args) => {
+            let run = trigger_scheduled_fn(cmd, args).await?;
+            match run.error {
+                None => msg!(
+                    "Ran {}:{} in {:?}",
+                    run.zome_name,
+                    run.fn_name,
+                    run.duration
+                ),
+                Some(error) => msg!("Run of {}:{} failed: {}", run.zome_name, run.fn_name, error),
+            }
+        }
+        AdminRequestCli::UnscheduleFn(args) => {
+            let name = format!("{}:{}", args.zome_name, args.fn_name);
+            unschedule_fn(cmd, args).await?;
+            msg!("Unscheduled {}", name);
+        }
         AdminRequestCli::ListAgents(args) => {
             use std::fmt::Write;
+            let json = args.json;
             let agent_infos = request_agent_info(cmd, args).await?;
+            if json {
+                // Printed without the `msg!` prefix so the output can be piped into `add-agents`.
+                println!("{}", serde_json::to_string(&agent_infos)?);
+                return Ok(());
+            }
             for info in agent_infos {
                 let mut out = String::new();
                 let cell_info = list_cell_ids(cmd).await?;
@@ -446,6 +565,7 @@ pub async fn add_admin_interface(cmd: &mut CmdRunner, args: AddAdminWs) -> anyho
                 driver: InterfaceDriver::Websocket {
                     port,
                     allowed_origins: AllowedOrigins::Any,
+                    tls: None,
                 },
             },
         ]))
@@ -618,6 +738,10 @@ pub async fn attach_app_interface(cmd: &mut CmdRunner, args: AddAppWs) -> anyhow
             port: args.port,
             allowed_origins: args.allowed_origins,
             installed_app_id: args.installed_app_id,
+            socket_path: None,
+            socket_mode: None,
+            tls: None,
+            signal_replay_buffer_size: None,
         })
         .await?;
     tracing::debug!(?resp);
@@ -662,6 +786,58 @@ pub async fn add_agent_info(cmd: &mut CmdRunner, args: Vec<AgentInfoSigned>) ->
     Ok(())
 }
 
+/// Calls [`AdminRequest::AddAgentInfo`] with the agent infos from the source given in the
+/// arguments.
+///
+/// # Returns
+///
+/// The number of agent infos added.
+pub async fn add_agents(cmd: &mut CmdRunner, args: AddAgents) -> anyhow::Result<usize> {
+    let agent_infos = match args.agent_infos {
+        Some(agent_infos) => agent_infos,
+        None => load_agent_infos(&args).await?,
+    };
+    let count = agent_infos.len();
+    add_agent_info(cmd, agent_infos).await?;
+    Ok(count)
+}
+
+/// Load the agent infos from a file, stdin or another running sandbox.
+async fn load_agent_infos(args: &AddAgents) -> anyhow::Result<Vec<AgentInfoSigned>> {
+    if let Some(index) = args.from_sandbox {
+        let paths = crate::save::load(std::env::current_dir()?)?;
+        let path = paths
+            .get(index)
+            .cloned()
+            .ok_or_else(|| anyhow!("There is no sandbox with index {}", index))?;
+        let port = get_admin_ports(vec![path])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("Sandbox {} has no admin port", index))?;
+        let mut cmd = CmdRunner::try_new(port).await.map_err(|e| {
+            anyhow!(
+                "Failed to connect to sandbox {}, is it running? Error: {:?}",
+                index,
+                e
+            )
+        })?;
+        return request_agent_info(
+            &mut cmd,
+            ListAgents {
+                agent_key: None,
+                dna: None,
+                json: false,
+            },
+        )
+        .await;
+    }
+    let json = match &args.file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => std::io::read_to_string(std::io::stdin())?,
+    };
+    Ok(serde_json::from_str(&json)?)
+}
+
 /// Calls [`AdminRequest::AgentInfo`] and pretty prints the agent info on this conductor.
 pub async fn request_agent_info(
     cmd: &mut CmdRunner,
@@ -675,6 +851,55 @@ pub async fn request_agent_info(
     Ok(expect_match!(resp => AdminResponse::AgentInfo, "Failed to request agent info"))
 }
 
+/// Calls [`AdminRequest::ListScheduledFunctions`] and lists the functions scheduled for a cell.
+pub async fn list_scheduled_fns(
+    cmd: &mut CmdRunner,
+    args: ListScheduledFns,
+) -> anyhow::Result<Vec<ScheduledFnInfo>> {
+    let resp = cmd
+        .command(AdminRequest::ListScheduledFunctions {
+            cell_id: CellId::new(args.dna, args.agent_key),
+        })
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::ScheduledFunctionsListed, "Failed to list scheduled functions"),
+    )
+}
+
+/// Calls [`AdminRequest::TriggerScheduledFunction`] and runs a scheduled function right away.
+pub async fn trigger_scheduled_fn(
+    cmd: &mut CmdRunner,
+    args: TriggerScheduledFn,
+) -> anyhow::Result<ScheduledFnRun> {
+    let resp = cmd
+        .command(AdminRequest::TriggerScheduledFunction {
+            cell_id: CellId::new(args.dna, args.agent_key),
+            zome_name: args.zome_name.into(),
+            fn_name: args.fn_name.into(),
+        })
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::ScheduledFunctionTriggered, "Failed to trigger scheduled function"),
+    )
+}
+
+/// Calls [`AdminRequest::UnscheduleFunction`] and removes a function from the schedule of a cell.
+pub async fn unschedule_fn(cmd: &mut CmdRunner, args: UnscheduleFn) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::UnscheduleFunction {
+            cell_id: CellId::new(args.dna, args.agent_key),
+            zome_name: args.zome_name.into(),
+            fn_name: args.fn_name.into(),
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::FunctionUnscheduled),
+        "Failed to unschedule function, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 fn parse_agent_key(arg: &str) -> anyhow::Result<AgentPubKey> {
     AgentPubKey::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
@@ -712,6 +937,7 @@ impl From<ListAgents> for Option<CellId> {
         let ListAgents {
             agent_key: a,
             dna: d,
+            ..
         } = la;
         d.and_then(|d| a.map(|a| (d, a)))
             .map(|(d, a)| CellId::new(d, a))
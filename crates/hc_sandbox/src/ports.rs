@@ -34,11 +34,8 @@ pub async fn get_admin_ports(paths: Vec<PathBuf>) -> anyhow::Result<Vec<u16>> {
         }
         if let Some(config) = read_config(ConfigRootPath::from(p))? {
             if let Some(ai) = config.admin_interfaces {
-                if let Some(AdminInterfaceConfig {
-                    driver: InterfaceDriver::Websocket { port, .. },
-                }) = ai.first()
-                {
-                    ports.push(*port)
+                if let Some(port) = ai.iter().find_map(|i| i.driver.port()) {
+                    ports.push(port)
                 }
             }
         }
@@ -79,43 +76,56 @@ async fn websocket_client_by_port(
 }
 
 pub(crate) fn random_admin_port(config: &mut ConductorConfig) {
-    match config.admin_interfaces.as_mut().and_then(|i| i.first_mut()) {
-        Some(AdminInterfaceConfig {
-            driver: InterfaceDriver::Websocket { port, .. },
-        }) => {
+    let interfaces = config.admin_interfaces.get_or_insert_with(Vec::new);
+    match first_websocket_port(interfaces) {
+        Some(port) => {
             if *port != 0 {
                 *port = 0;
             }
         }
+        // Admin interfaces on Unix sockets are kept, but the sandbox talks
+        // to the conductor over a websocket.
         None => {
             let port = 0;
-            config.admin_interfaces = Some(vec![AdminInterfaceConfig {
-                driver: InterfaceDriver::Websocket {
-                    port,
-                    allowed_origins: AllowedOrigins::Any,
+            interfaces.insert(
+                0,
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::Websocket {
+                        port,
+                        allowed_origins: AllowedOrigins::Any,
+                        tls: None,
+                    },
                 },
-            }]);
+            );
         }
     }
 }
 
+fn first_websocket_port(interfaces: &mut [AdminInterfaceConfig]) -> Option<&mut u16> {
+    interfaces.iter_mut().find_map(|i| match &mut i.driver {
+        InterfaceDriver::Websocket { port, .. } => Some(port),
+        InterfaceDriver::UnixSocket { .. } => None,
+    })
+}
+
 pub(crate) fn set_admin_port(config: &mut ConductorConfig, port: u16) {
     let p = port;
     let port = AdminInterfaceConfig {
         driver: InterfaceDriver::Websocket {
             port,
             allowed_origins: AllowedOrigins::Any,
+            tls: None,
         },
     };
-    match config
-        .admin_interfaces
-        .as_mut()
-        .and_then(|ai| ai.get_mut(0))
+    let interfaces = config.admin_interfaces.get_or_insert_with(Vec::new);
+    match interfaces
+        .iter_mut()
+        .find(|i| matches!(i.driver, InterfaceDriver::Websocket { .. }))
     {
         Some(admin_interface) => {
             *admin_interface = port;
         }
-        None => config.admin_interfaces = Some(vec![port]),
+        None => interfaces.insert(0, port),
     }
     msg!("Admin port set to: {}", p);
 }
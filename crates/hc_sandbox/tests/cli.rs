@@ -7,6 +7,7 @@ use holochain_types::prelude::{SerializedBytes, SerializedBytesError};
 use holochain_websocket::{
     self as ws, ConnectRequest, WebsocketConfig, WebsocketReceiver, WebsocketSender,
 };
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use matches::assert_matches;
 use std::future::Future;
 use std::net::ToSocketAddrs;
@@ -15,7 +16,7 @@ use std::process::Stdio;
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{ChildStdout, Command};
+use tokio::process::{Child, ChildStdout, Command};
 use which::which;
 
 const WEBSOCKET_TIMEOUT: Duration = Duration::from_secs(3);
@@ -116,7 +117,10 @@ async fn get_app_info(admin_port: u16, installed_app_id: InstalledAppId, port: u
         .await
         .unwrap_or_else(|_| panic!("Failed to connect to conductor on port [{}]", port));
     app_tx
-        .authenticate(AppAuthenticationRequest { token })
+        .authenticate(AppAuthenticationRequest {
+            token,
+            resume_signals_from: None,
+        })
         .await
         .unwrap();
 
@@ -263,6 +267,148 @@ async fn generate_sandbox_and_call_list_dna() {
     assert!(exit_code.success());
 }
 
+/// Lists the agents of one sandbox as JSON and pipes them into `add-agents` of another
+#[tokio::test(flavor = "multi_thread")]
+async fn list_agents_json_piped_into_add_agents() {
+    clean_sandboxes().await;
+    package_fixture_if_not_packaged().await;
+
+    holochain_trace::test_run();
+    let (_source, source_info) = generate_and_run_sandbox().await;
+    let (_target, target_info) = generate_and_run_sandbox().await;
+
+    let json = list_agents_json(source_info.admin_port).await;
+    let source_agents = serde_json::from_str::<Vec<AgentInfoSigned>>(&json).unwrap();
+
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg("call")
+        .arg(format!("--running={}", target_info.admin_port))
+        .arg("add-agents")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit());
+    let mut hc_call = cmd.spawn().expect("Failed to spawn holochain");
+    let mut child_stdin = hc_call.stdin.take().unwrap();
+    child_stdin.write_all(json.as_bytes()).await.unwrap();
+    drop(child_stdin);
+
+    let exit_code = hc_call.wait().await.unwrap();
+    assert!(exit_code.success());
+    assert_agents_added(target_info.admin_port, &source_agents).await;
+}
+
+/// Adds the agents of one running sandbox to another with `add-agents --from-sandbox`
+#[tokio::test(flavor = "multi_thread")]
+async fn add_agents_from_sandbox() {
+    clean_sandboxes().await;
+    package_fixture_if_not_packaged().await;
+
+    holochain_trace::test_run();
+    let (_source, source_info) = generate_and_run_sandbox().await;
+    let (_target, target_info) = generate_and_run_sandbox().await;
+
+    let source_agents = serde_json::from_str::<Vec<AgentInfoSigned>>(
+        &list_agents_json(source_info.admin_port).await,
+    )
+    .unwrap();
+    let source_index = live_sandbox_index(source_info.admin_port);
+
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg("call")
+        .arg(format!("--running={}", target_info.admin_port))
+        .arg("add-agents")
+        .arg(format!("--from-sandbox={source_index}"))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit());
+    let exit_code = cmd.status().await.unwrap();
+    assert!(exit_code.success());
+    assert_agents_added(target_info.admin_port, &source_agents).await;
+}
+
+/// Generates a new sandbox with a single app deployed and runs it
+async fn generate_and_run_sandbox() -> (Child, LaunchInfo) {
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg(format!(
+            "--holochain-path={}",
+            get_holochain_bin_path().to_str().unwrap()
+        ))
+        .arg("--piped")
+        .arg("generate")
+        .arg("--in-process-lair")
+        .arg("--run=0")
+        .arg("tests/fixtures/my-app/")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+
+    let mut hc_admin = cmd.spawn().expect("Failed to spawn holochain");
+    let mut child_stdin = hc_admin.stdin.take().unwrap();
+    child_stdin.write_all(b"test-phrase\n").await.unwrap();
+    drop(child_stdin);
+
+    let mut stdout = hc_admin.stdout.take().unwrap();
+    let launch_info = get_launch_info(&mut stdout).await;
+    (hc_admin, launch_info)
+}
+
+/// Calls `list-agents --json` until the sandbox has published its agent info
+async fn list_agents_json(admin_port: u16) -> String {
+    for _ in 0..30 {
+        let mut cmd = get_sandbox_command();
+        cmd.env("RUST_BACKTRACE", "1")
+            .arg("call")
+            .arg(format!("--running={admin_port}"))
+            .arg("list-agents")
+            .arg("--json")
+            .stdin(Stdio::null())
+            .stderr(Stdio::inherit());
+        let output = cmd.output().await.unwrap();
+        assert!(output.status.success());
+        let json = String::from_utf8(output.stdout).unwrap();
+        let agents = serde_json::from_str::<Vec<AgentInfoSigned>>(&json).unwrap();
+        if !agents.is_empty() {
+            return json;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    panic!("Sandbox on port {admin_port} has no agent infos");
+}
+
+/// Finds the index of the running sandbox with this admin port in `$(pwd)/.hc`
+fn live_sandbox_index(admin_port: u16) -> usize {
+    for entry in std::fs::read_dir(std::env::current_dir().unwrap()).unwrap() {
+        let entry = entry.unwrap();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if let Some(index) = file_name.strip_prefix(".hc_live_") {
+            let live = std::fs::read_to_string(entry.path()).unwrap();
+            if live.lines().next() == Some(admin_port.to_string().as_str()) {
+                return index.parse().unwrap();
+            }
+        }
+    }
+    panic!("No running sandbox with admin port {admin_port}");
+}
+
+async fn assert_agents_added(admin_port: u16, agents: &[AgentInfoSigned]) {
+    let (admin_tx, _admin_rx) = new_websocket_client_for_port::<AdminResponse>(admin_port)
+        .await
+        .unwrap_or_else(|_| panic!("Failed to connect to conductor on port [{}]", admin_port));
+    let response = admin_tx.request(AdminRequest::AgentInfo { cell_id: None });
+    let r: AdminResponse = check_timeout(response).await;
+    let added = match r {
+        AdminResponse::AgentInfo(added) => added,
+        _ => panic!("Unexpected response {:?}", r),
+    };
+    for agent in agents {
+        assert!(added.iter().any(|a| a.agent == agent.agent));
+    }
+}
+
 fn get_hc_command() -> Command {
     Command::new(which("hc").unwrap_or_else(|_| get_hc_built_path().clone()))
 }
@@ -105,8 +105,6 @@ pub fn create_cap_claim(cap_claim_entry: CapClaimEntry) -> ExternResult<ActionHa
 /// access. Special care is required for Unrestricted grants as several may apply to a single
 /// extern at one time, or may apply in addition to a stricter grant. In this case, revoking a
 /// stricter grant, or failing to revoke all Unrestricted grants will leave the function open.
-//
-// @todo administration functions to query active grants
 ///
 /// There is an apparent "chicken or the egg" situation where [`CapGrant`] are required for remote
 /// agents to call externs, so how does an agent request a grant in the first place?
@@ -198,3 +196,43 @@ pub fn update_cap_grant(
         chain_top_ordering: ChainTopOrdering::default(),
     })
 }
+
+/// List the capability grants on the local source chain which are currently in effect.
+///
+/// Grants which have been updated with [`update_cap_grant`] or deleted with
+/// [`delete_cap_grant`] are not included, but the grants which replaced them are.
+/// Each [`CapGrantInfo`] carries the hash of the action which created the grant,
+/// to update or delete it with.
+///
+/// The grant which gives the author access to their own externs is implicit
+/// and never listed.
+pub fn capability_grants() -> ExternResult<Vec<CapGrantInfo>> {
+    HDK.with(|h| h.borrow().capability_grants(()))
+}
+
+/// List the capability claims on the local source chain.
+///
+/// Only the claims matching every field set in the [`CapClaimsInput`] are returned,
+/// e.g. all claims with a tag on grants by a certain agent.
+/// Deleted claims are not included.
+///
+/// ```ignore
+/// let claims = capability_claims(CapClaimsInput {
+///     tag: Some("friends".into()),
+///     ..Default::default()
+/// })?;
+/// ```
+///
+/// There is no guarantee that a returned claim is still usable, see [`create_cap_claim`].
+pub fn capability_claims(input: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+    HDK.with(|h| h.borrow().capability_claims(input))
+}
+
+/// Get the capability grant which authorized the current zome call.
+///
+/// This is [`CapGrant::ChainAuthor`] for calls made by the author of the
+/// local source chain, and the matching [`ZomeCallCapGrant`] for calls made
+/// by other agents. The same grant is part of [`call_info`].
+pub fn capability_info() -> ExternResult<CapGrant> {
+    HDK.with(|h| h.borrow().capability_info(()))
+}
@@ -40,3 +40,13 @@ pub mod anchor;
 ///
 /// If an application knows `[ A B ]` then a link to `C` will be discoverable.
 pub mod path;
+
+/// Range queries over the paths below a [Path](hdi::prelude::Path).
+///
+/// Paths with ordered components, like timestamps or names, can be iterated
+/// between two bounds without fetching the whole tree.
+pub mod range;
+
+/// A time index of entries built out of [Path](hdi::prelude::Path)s bucketed
+/// by year, month, day, hour or minute.
+pub mod time_index;
@@ -1,3 +1,4 @@
+use crate::hash_path::range::{PathRange, PathRangeIter};
 use crate::prelude::*;
 use hdi::hash_path::path::{root_hash, Component, TypedPath};
 
@@ -7,6 +8,7 @@ pub trait HdkPathExt {
     fn children_details(&self) -> ExternResult<holochain_zome_types::link::LinkDetails>;
     fn ensure(&self) -> ExternResult<()>;
     fn exists(&self) -> ExternResult<bool>;
+    fn range(&self, range: PathRange) -> PathRangeIter;
 }
 
 impl HdkPathExt for TypedPath {
@@ -38,18 +40,7 @@ impl HdkPathExt for TypedPath {
         let children = self.children()?;
         let components: ExternResult<Vec<Option<Component>>> = children
             .into_iter()
-            .map(|link| {
-                let component_bytes = &link.tag.0[..];
-                if component_bytes.is_empty() {
-                    Ok(None)
-                } else {
-                    Ok(Some(
-                        SerializedBytes::from(UnsafeBytes::from(component_bytes.to_vec()))
-                            .try_into()
-                            .map_err(|e: SerializedBytesError| wasm_error!(e))?,
-                    ))
-                }
-            })
+            .map(|link| component_from_tag(&link.tag))
             .collect();
         Ok(components?
             .into_iter()
@@ -137,4 +128,24 @@ impl HdkPathExt for TypedPath {
             Ok(exists)
         }
     }
+
+    /// Lazily iterate over the existing paths `range.depth` levels below this
+    /// path whose components fall within the range. See [`PathRange`].
+    fn range(&self, range: PathRange) -> PathRangeIter {
+        PathRangeIter::new(self.clone(), range)
+    }
+}
+
+/// Decode the [`Component`] a path link tag was made from with [`Path::make_tag`].
+pub(crate) fn component_from_tag(tag: &LinkTag) -> ExternResult<Option<Component>> {
+    let component_bytes = &tag.0[..];
+    if component_bytes.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(
+            SerializedBytes::from(UnsafeBytes::from(component_bytes.to_vec()))
+                .try_into()
+                .map_err(|e: SerializedBytesError| wasm_error!(e))?,
+        ))
+    }
 }
@@ -0,0 +1,211 @@
+use crate::hash_path::path::component_from_tag;
+use crate::prelude::*;
+use hdi::hash_path::path::{Component, TypedPath};
+use std::cmp::Ordering;
+
+/// Bounds on the components of the paths below a [`TypedPath`], used to
+/// iterate over a part of the tree with [`HdkPathExt::range`].
+///
+/// Components are compared level by level, by their bytes. Components made
+/// from ASCII strings of equal length, like zero-padded numbers, compare like
+/// their text.
+///
+/// Both bounds are inclusive and may have fewer components than `depth`, in
+/// which case they only constrain the first levels. For example, below a path
+/// with `[year, month, day]` leaves, the range from `["2024", "02"]` to
+/// `["2024", "03"]` covers every day of February and March 2024.
+///
+/// ```ignore
+/// let days = path.range(
+///     PathRange::new(3)
+///         .start(["2024", "02"])
+///         .end(["2024", "03"]),
+/// );
+/// for day in days {
+///     let day = day?;
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PathRange {
+    /// How many levels below the path the iterated paths are.
+    pub depth: usize,
+    /// Lower bound on the components, unbounded if empty.
+    pub start: Vec<Component>,
+    /// Upper bound on the components, unbounded if empty.
+    pub end: Vec<Component>,
+    /// Iterate from the end of the range to its start.
+    pub reverse: bool,
+}
+
+impl PathRange {
+    /// An unbounded range over all paths `depth` levels below the path.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            ..Default::default()
+        }
+    }
+
+    /// Only include paths from these components onwards.
+    pub fn start<C: Into<Component>>(mut self, start: impl IntoIterator<Item = C>) -> Self {
+        self.start = start.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only include paths up to these components.
+    pub fn end<C: Into<Component>>(mut self, end: impl IntoIterator<Item = C>) -> Self {
+        self.end = end.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Iterate in descending order, e.g. to get the most recent entries of a
+    /// time index first.
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Check the component of a child at `level` against the bounds.
+    ///
+    /// `on_start` and `on_end` tell whether all components of the parent are
+    /// equal to the bounds so far. Only then do the bounds constrain the
+    /// child. Returns whether the child is equal to the bounds at this level,
+    /// or `None` if it is outside of the range.
+    fn check(
+        &self,
+        level: usize,
+        component: &Component,
+        on_start: bool,
+        on_end: bool,
+    ) -> Option<(bool, bool)> {
+        let on_start = match self.start.get(level) {
+            Some(start) if on_start => match component.as_ref().cmp(start.as_ref()) {
+                Ordering::Less => return None,
+                ordering => ordering == Ordering::Equal,
+            },
+            _ => false,
+        };
+        let on_end = match self.end.get(level) {
+            Some(end) if on_end => match component.as_ref().cmp(end.as_ref()) {
+                Ordering::Greater => return None,
+                ordering => ordering == Ordering::Equal,
+            },
+            _ => false,
+        };
+        Some((on_start, on_end))
+    }
+}
+
+/// Iterator over the paths within a [`PathRange`], in order.
+///
+/// The tree is walked depth first and the children of a path are only fetched
+/// when the iterator reaches it, so taking the first few paths of a large
+/// range only gets the links it needs. Branches outside of the range are not
+/// walked. Unlike [`HdkPathExt::children_paths`], nothing is written to the
+/// source chain.
+pub struct PathRangeIter {
+    range: PathRange,
+    stack: Vec<Frame>,
+}
+
+/// A path waiting to be walked by a [`PathRangeIter`].
+struct Frame {
+    path: TypedPath,
+    level: usize,
+    on_start: bool,
+    on_end: bool,
+}
+
+impl PathRangeIter {
+    pub(crate) fn new(path: TypedPath, range: PathRange) -> Self {
+        Self {
+            range,
+            stack: vec![Frame {
+                path,
+                level: 0,
+                on_start: true,
+                on_end: true,
+            }],
+        }
+    }
+
+    /// Push the children of this frame which are within the range, so that
+    /// they are popped in order.
+    fn push_children(&mut self, frame: Frame) -> ExternResult<()> {
+        let mut children = get_links(
+            GetLinksInputBuilder::try_new(
+                frame.path.path_entry_hash()?,
+                LinkTypeFilter::single_type(
+                    frame.path.link_type.zome_index,
+                    frame.path.link_type.zome_type,
+                ),
+            )?
+            .build(),
+        )?
+        .into_iter()
+        .filter_map(|link| component_from_tag(&link.tag).transpose())
+        .collect::<ExternResult<Vec<Component>>>()?;
+        children.sort_unstable_by(|a, b| a.as_ref().cmp(b.as_ref()));
+        children.dedup();
+        if !self.range.reverse {
+            children.reverse();
+        }
+        for component in children {
+            if let Some((on_start, on_end)) =
+                self.range
+                    .check(frame.level, &component, frame.on_start, frame.on_end)
+            {
+                let mut path = frame.path.path.clone();
+                path.append_component(component);
+                self.stack.push(Frame {
+                    path: path.into_typed(frame.path.link_type),
+                    level: frame.level + 1,
+                    on_start,
+                    on_end,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for PathRangeIter {
+    type Item = ExternResult<TypedPath>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(frame) = self.stack.pop() {
+            if frame.level >= self.range.depth {
+                return Some(Ok(frame.path));
+            }
+            if let Err(e) = self.push_children(frame) {
+                return Some(Err(e));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn hash_path_range_check() {
+    let range = PathRange::new(3).start(["b", "b"]).end(["d"]);
+    let c = |s: &str| Component::from(s);
+
+    assert_eq!(None, range.check(0, &c("a"), true, true));
+    assert_eq!(Some((true, false)), range.check(0, &c("b"), true, true));
+    assert_eq!(Some((false, false)), range.check(0, &c("c"), true, true));
+    assert_eq!(Some((false, true)), range.check(0, &c("d"), true, true));
+    assert_eq!(None, range.check(0, &c("e"), true, true));
+
+    // Below "b" only the start bound applies.
+    assert_eq!(None, range.check(1, &c("a"), true, false));
+    assert_eq!(Some((true, false)), range.check(1, &c("b"), true, false));
+    assert_eq!(Some((false, false)), range.check(1, &c("z"), true, false));
+
+    // Below "d" the end bound has no more components.
+    assert_eq!(Some((false, false)), range.check(1, &c("z"), false, true));
+
+    // Below "b", "b" the bounds don't apply anymore.
+    assert_eq!(Some((false, false)), range.check(2, &c("a"), true, false));
+}
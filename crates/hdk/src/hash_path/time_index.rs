@@ -0,0 +1,159 @@
+use crate::hash_path::range::{PathRange, PathRangeIter};
+use crate::prelude::*;
+use hdi::hash_path::path::{Component, TypedPath};
+
+/// The size of the buckets of a [`TimeIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeBucket {
+    /// `[year]`
+    Year,
+    /// `[year, month]`
+    Month,
+    /// `[year, month, day]`
+    Day,
+    /// `[year, month, day, hour]`
+    Hour,
+    /// `[year, month, day, hour, minute]`
+    Minute,
+}
+
+impl TimeBucket {
+    /// The number of path components of a bucket.
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Year => 1,
+            Self::Month => 2,
+            Self::Day => 3,
+            Self::Hour => 4,
+            Self::Minute => 5,
+        }
+    }
+}
+
+/// An index of entries by time, built out of [`TypedPath`]s.
+///
+/// Every bucket is a path below the base path, with one zero-padded decimal
+/// component per UTC date and time unit down to the bucket size, e.g.
+/// `[..base, "2024", "02", "29"]` for days. Entries are indexed by linking
+/// them from the bucket of their timestamp, and found by iterating over the
+/// buckets of a time range in chronological order.
+///
+/// ```ignore
+/// let index = TimeIndex::new(Path::from("posts").typed(LinkTypes::Posts)?, TimeBucket::Day);
+/// let bucket = index.ensure_bucket(sys_time()?)?;
+/// create_link(bucket.path_entry_hash()?, post_hash, LinkTypes::Posts, ())?;
+///
+/// for bucket in index.buckets_rev(from, to) {
+///     let posts = get_links(
+///         GetLinksInputBuilder::try_new(bucket?.path_entry_hash()?, LinkTypes::Posts)?.build(),
+///     )?;
+///     // ...
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeIndex {
+    base: TypedPath,
+    bucket: TimeBucket,
+}
+
+impl TimeIndex {
+    /// Create a time index below a base path.
+    pub fn new(base: TypedPath, bucket: TimeBucket) -> Self {
+        Self { base, bucket }
+    }
+
+    /// The path of the bucket a timestamp falls into.
+    /// The path may not exist yet.
+    pub fn bucket(&self, timestamp: Timestamp) -> TypedPath {
+        let mut path = self.base.path.clone();
+        for component in self.components(timestamp) {
+            path.append_component(component);
+        }
+        path.into_typed(self.base.link_type)
+    }
+
+    /// Make sure the bucket of a timestamp exists and return its path,
+    /// to link entries from.
+    pub fn ensure_bucket(&self, timestamp: Timestamp) -> ExternResult<TypedPath> {
+        let bucket = self.bucket(timestamp);
+        bucket.ensure()?;
+        Ok(bucket)
+    }
+
+    /// Lazily iterate over the existing buckets from the one of `start` to the
+    /// one of `end`, inclusive, from oldest to newest.
+    pub fn buckets(&self, start: Timestamp, end: Timestamp) -> PathRangeIter {
+        self.base.range(self.range(start, end))
+    }
+
+    /// Lazily iterate over the existing buckets from the one of `end` to the
+    /// one of `start`, inclusive, from newest to oldest.
+    pub fn buckets_rev(&self, start: Timestamp, end: Timestamp) -> PathRangeIter {
+        self.base.range(self.range(start, end).reverse())
+    }
+
+    fn range(&self, start: Timestamp, end: Timestamp) -> PathRange {
+        PathRange::new(self.bucket.depth())
+            .start(self.components(start))
+            .end(self.components(end))
+    }
+
+    fn components(&self, timestamp: Timestamp) -> Vec<Component> {
+        let (year, month, day, hour, minute) = utc_date_time(timestamp);
+        [
+            format!("{:04}", year),
+            format!("{:02}", month),
+            format!("{:02}", day),
+            format!("{:02}", hour),
+            format!("{:02}", minute),
+        ]
+        .into_iter()
+        .take(self.bucket.depth())
+        .map(Component::from)
+        .collect()
+    }
+}
+
+/// Split a timestamp into its UTC year, month, day, hour and minute.
+fn utc_date_time(timestamp: Timestamp) -> (i64, u32, u32, u32, u32) {
+    let seconds = timestamp.as_micros().div_euclid(1_000_000);
+    let (days, seconds) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    let (year, month, day) = civil_from_days(days);
+    (
+        year,
+        month,
+        day,
+        (seconds / 3_600) as u32,
+        (seconds % 3_600 / 60) as u32,
+    )
+}
+
+/// The proleptic Gregorian date of a number of days since 1970-01-01.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+#[test]
+fn hash_path_time_index_utc_date_time() {
+    let at = |seconds: i64| utc_date_time(Timestamp::from_micros(seconds * 1_000_000));
+
+    assert_eq!((1970, 1, 1, 0, 0), at(0));
+    assert_eq!((1969, 12, 31, 23, 59), at(-1));
+    // 2024-02-29T13:37:00Z
+    assert_eq!((2024, 2, 29, 13, 37), at(1_709_213_820));
+    // 2000-03-01T00:00:59Z
+    assert_eq!((2000, 3, 1, 0, 0), at(951_868_859));
+}
@@ -44,6 +44,13 @@ pub trait HdkT: HdiT {
     // Info
     fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
     fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
+    // Capability
+    fn capability_claims(
+        &self,
+        capability_claims_input: CapClaimsInput,
+    ) -> ExternResult<Vec<CapClaim>>;
+    fn capability_grants(&self, capability_grants_input: ()) -> ExternResult<Vec<CapGrantInfo>>;
+    fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapGrant>;
     // Link
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
     fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
@@ -129,6 +136,11 @@ mockall::mock! {
         // Info
         fn agent_info(&self, agent_info_input: ()) -> ExternResult<AgentInfo>;
         fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
+        // Capability
+        fn capability_claims(&self, capability_claims_input: CapClaimsInput)
+            -> ExternResult<Vec<CapClaim>>;
+        fn capability_grants(&self, capability_grants_input: ()) -> ExternResult<Vec<CapGrantInfo>>;
+        fn capability_info(&self, capability_info_input: ()) -> ExternResult<CapGrant>;
         // Link
         fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
         fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
@@ -348,6 +360,16 @@ impl HdkT for ErrHdk {
     fn call_info(&self, _: ()) -> ExternResult<CallInfo> {
         Self::err()
     }
+    // Capability
+    fn capability_claims(&self, _: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+        Self::err()
+    }
+    fn capability_grants(&self, _: ()) -> ExternResult<Vec<CapGrantInfo>> {
+        Self::err()
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapGrant> {
+        Self::err()
+    }
     // Link
     fn create_link(&self, _: CreateLinkInput) -> ExternResult<ActionHash> {
         Self::err()
@@ -590,6 +612,21 @@ impl HdkT for HostHdk {
     fn call_info(&self, _: ()) -> ExternResult<CallInfo> {
         host_call::<(), CallInfo>(__hc__call_info_1, ())
     }
+    fn capability_claims(
+        &self,
+        capability_claims_input: CapClaimsInput,
+    ) -> ExternResult<Vec<CapClaim>> {
+        host_call::<CapClaimsInput, Vec<CapClaim>>(
+            __hc__capability_claims_1,
+            capability_claims_input,
+        )
+    }
+    fn capability_grants(&self, _: ()) -> ExternResult<Vec<CapGrantInfo>> {
+        host_call::<(), Vec<CapGrantInfo>>(__hc__capability_grants_1, ())
+    }
+    fn capability_info(&self, _: ()) -> ExternResult<CapGrant> {
+        host_call::<(), CapGrant>(__hc__capability_info_1, ())
+    }
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash> {
         host_call::<CreateLinkInput, ActionHash>(__hc__create_link_1, create_link_input)
     }
@@ -147,6 +147,44 @@ pub fn get_links(input: GetLinksInput) -> ExternResult<Vec<Link>> {
         .collect())
 }
 
+/// A page of links, as returned by [ `get_links_page` ].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinksPage {
+    /// The links of this page, ordered by timestamp and then by create link hash.
+    pub links: Vec<Link>,
+    /// The cursor to fetch the next page with, if this page was full.
+    pub next: Option<LinkCursor>,
+}
+
+/// Returns a single page of at most `limit` links that reference a base hash, starting after the
+/// cursor of the input, if any.
+///
+/// The input is filtered like for [ `get_links` ]. To fetch the following page, pass the returned
+/// cursor to [ `GetLinksInputBuilder::cursor` ] of an otherwise identical input.
+///
+/// ```rust,no_run
+/// use hdk::prelude::*;
+///
+/// # fn main() -> ExternResult<()> {
+///     let my_base = ActionHash::from_raw_36(vec![0; 36]); // Some base address, this is a dummy address created for the example!
+///     let mut page = get_links_page(GetLinksInputBuilder::try_new(my_base.clone(), ..)?.build(), 100)?;
+///     while let Some(cursor) = page.next {
+///         page = get_links_page(GetLinksInputBuilder::try_new(my_base.clone(), ..)?.cursor(cursor).build(), 100)?;
+///     }
+/// #   Ok(())
+/// # }
+/// ```
+pub fn get_links_page(mut input: GetLinksInput, limit: u32) -> ExternResult<LinksPage> {
+    input.limit = Some(limit);
+    let links = get_links(input)?;
+    let next = if links.len() >= limit as usize {
+        links.last().map(Link::cursor)
+    } else {
+        None
+    };
+    Ok(LinksPage { links, next })
+}
+
 /// Get all link creates and deletes that reference a base hash, optionally filtered by type or tag.
 ///
 /// Type can be filtered by providing a variant of the link types, or a range of them. To get links of
@@ -59,6 +59,8 @@ impl GetLinksInputBuilder {
             before: None,
             after: None,
             author: None,
+            cursor: None,
+            limit: None,
         }))
     }
 
@@ -92,6 +94,20 @@ impl GetLinksInputBuilder {
         self
     }
 
+    /// Only include links which come after `cursor`.
+    ///
+    /// Links are ordered by their timestamp and then by the hash of their create action.
+    pub fn cursor(mut self, cursor: LinkCursor) -> Self {
+        self.0.cursor = Some(cursor);
+        self
+    }
+
+    /// Include at most `limit` links.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.0.limit = Some(limit);
+        self
+    }
+
     /// Construct the result of the builder.
     pub fn build(self) -> GetLinksInput {
         self.0
@@ -1,3 +1,6 @@
+pub use crate::capability::capability_claims;
+pub use crate::capability::capability_grants;
+pub use crate::capability::capability_info;
 pub use crate::capability::create_cap_claim;
 pub use crate::capability::create_cap_grant;
 pub use crate::capability::delete_cap_grant;
@@ -34,6 +37,10 @@ pub use crate::hash_path::anchor::list_anchor_tags;
 pub use crate::hash_path::anchor::list_anchor_type_addresses;
 pub use crate::hash_path::anchor::TryFromPath;
 pub use crate::hash_path::path::HdkPathExt;
+pub use crate::hash_path::range::PathRange;
+pub use crate::hash_path::range::PathRangeIter;
+pub use crate::hash_path::time_index::TimeBucket;
+pub use crate::hash_path::time_index::TimeIndex;
 pub use crate::hdk::*;
 pub use crate::info::agent_info;
 pub use crate::info::call_info;
@@ -44,8 +51,10 @@ pub use crate::link::create_link;
 pub use crate::link::delete_link;
 pub use crate::link::get_link_details;
 pub use crate::link::get_links;
+pub use crate::link::get_links_page;
 pub use crate::link::GetLinksInputBuilder;
 pub use crate::link::LinkTypeFilterExt;
+pub use crate::link::LinksPage;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
 pub use crate::p2p::call;
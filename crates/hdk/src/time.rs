@@ -136,7 +136,16 @@ pub fn schedule(scheduled_fn: &str) -> ExternResult<()> {
     HDK.with(|h| h.borrow().schedule(String::from(scheduled_fn)))
 }
 
-/// @todo Not implemented
+/// Pause the current call for some time, e.g. to poll for data which is not
+/// yet available, without spending metering points on a busy loop.
+///
+/// The other calls of the cell keep running and committing while a call sleeps.
+/// A zome call, together with the zome calls it makes, may sleep for at most
+/// 30 seconds in total, sleeping for longer returns an error without sleeping.
+///
+/// Only zome calls may sleep, as a sleeping callback would hold up the calls
+/// waiting for it. Neither `init` nor the calls it makes, `post_commit` or
+/// validation may sleep.
 pub fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
     HDK.with(|h| h.borrow().sleep(wake_after))
 }
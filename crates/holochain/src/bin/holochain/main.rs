@@ -52,6 +52,11 @@ struct Opt {
         help = "Display version information such as git revision and HDK version"
     )]
     build_info: bool,
+
+    /// Re-encrypt the databases of the conductor with a new key and exit.
+    /// The conductor must not be running while its key is rotated.
+    #[structopt(long)]
+    rotate_db_key: bool,
 }
 
 fn main() {
@@ -83,6 +88,11 @@ async fn async_main() {
     holochain_trace::init_fmt(opt.structured.clone()).expect("Failed to start contextual logging");
     debug!("holochain_trace initialized");
 
+    if opt.rotate_db_key {
+        rotate_db_key(&opt, config).await;
+        return;
+    }
+
     let data_root_path: DataRootPath = config.data_root_path_or_die();
 
     holochain_metrics::HolochainMetricsConfig::new(data_root_path.as_ref())
@@ -118,9 +128,9 @@ async fn async_main() {
     handle_shutdown(shutdown_result);
 }
 
-async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> ConductorHandle {
-    // read the passphrase to prepare for usage
-    let passphrase = match &config.keystore {
+/// Read the passphrase of the keystore, if it needs one.
+fn read_passphrase(opt: &Opt, config: &ConductorConfig) -> Option<sodoken::BufRead> {
+    match &config.keystore {
         KeystoreConfig::DangerTestKeystore => None,
         KeystoreConfig::LairServer { .. } | KeystoreConfig::LairServerInProc { .. } => {
             if opt.piped {
@@ -129,7 +139,37 @@ async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> Con
 
             Some(holochain_util::pw::pw_get().unwrap())
         }
-    };
+    }
+}
+
+async fn rotate_db_key(opt: &Opt, config: ConductorConfig) {
+    #[cfg(feature = "sqlite-encrypted")]
+    {
+        let passphrase = read_passphrase(opt, &config);
+        match Conductor::builder()
+            .config(config)
+            .passphrase(passphrase)
+            .rotate_db_key()
+            .await
+        {
+            Ok(()) => println!("The databases have been encrypted with a new key."),
+            Err(err) => {
+                println!("Could not rotate the database key: {}", err);
+                std::process::exit(ERROR_CODE);
+            }
+        }
+    }
+    #[cfg(not(feature = "sqlite-encrypted"))]
+    {
+        let _ = (opt, config);
+        println!("This build of holochain does not encrypt its databases.");
+        std::process::exit(ERROR_CODE);
+    }
+}
+
+async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> ConductorHandle {
+    // read the passphrase to prepare for usage
+    let passphrase = read_passphrase(opt, &config);
 
     // Check if database is present
     // In interactive mode give the user a chance to create it, otherwise create it automatically
@@ -22,6 +22,7 @@ pub mod chc;
 pub mod conductor;
 #[allow(missing_docs)]
 pub mod config;
+pub mod db_key;
 pub mod entry_def_store;
 #[allow(missing_docs)]
 pub mod error;
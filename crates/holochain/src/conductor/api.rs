@@ -13,6 +13,8 @@ mod api_cell;
 mod api_external;
 #[allow(missing_docs)]
 pub mod error;
+mod zome_call_policy;
 
 pub use api_cell::*;
 pub use api_external::*;
+pub use zome_call_policy::*;
@@ -256,7 +256,9 @@ impl CellConductorReadHandleT for CellConductorApi {
                 .call_zome_with_workspace(call, workspace_lock)
                 .await
         } else {
-            self.conductor_handle.call_zome(call).await
+            self.conductor_handle
+                .call_zome_from_other_cell(call, workspace_lock.slept().clone())
+                .await
         }
     }
 
@@ -153,6 +153,19 @@ impl AdminInterfaceApi {
                     &dna_definitions,
                 )))
             }
+            InstallAppFromStore(payload) => {
+                let app: InstalledApp = self
+                    .conductor_handle
+                    .clone()
+                    .install_app_from_store(*payload)
+                    .await?
+                    .into();
+                let dna_definitions = self.conductor_handle.get_dna_definitions(&app)?;
+                Ok(AdminResponse::AppInstalled(AppInfo::from_installed_app(
+                    &app,
+                    &dna_definitions,
+                )))
+            }
             UninstallApp { installed_app_id } => {
                 self.conductor_handle
                     .clone()
@@ -220,19 +233,69 @@ impl AdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::AppDisabled)
             }
+            MigrateAgent {
+                installed_app_id,
+                role_name,
+                new_dna_hash,
+                membrane_proof,
+            } => {
+                let cell_id = self
+                    .conductor_handle
+                    .clone()
+                    .migrate_agent(&installed_app_id, &role_name, new_dna_hash, membrane_proof)
+                    .await?;
+                Ok(AdminResponse::AgentMigrated(cell_id))
+            }
+            AttachAppInterface {
+                port,
+                allowed_origins: _,
+                installed_app_id,
+                socket_path: Some(path),
+                socket_mode,
+                tls,
+                signal_replay_buffer_size,
+            } => {
+                if port.is_some() {
+                    return Err(ConductorApiError::other(
+                        "An app interface can't have both a port and a socket path",
+                    ));
+                }
+                if tls.is_some() {
+                    return Err(ConductorApiError::other(
+                        "TLS is not supported for app interfaces on a socket path",
+                    ));
+                }
+                self.conductor_handle
+                    .clone()
+                    .add_app_unix_socket_interface(
+                        None,
+                        path,
+                        socket_mode.unwrap_or(DEFAULT_UNIX_SOCKET_MODE),
+                        installed_app_id,
+                        signal_replay_buffer_size,
+                    )
+                    .await?;
+                Ok(AdminResponse::AppInterfaceAttached { port: 0 })
+            }
             AttachAppInterface {
                 port,
                 allowed_origins,
                 installed_app_id,
+                socket_path: None,
+                tls,
+                signal_replay_buffer_size,
+                ..
             } => {
                 let port = port.unwrap_or(0);
                 let port = self
                     .conductor_handle
                     .clone()
-                    .add_app_interface(
+                    .add_app_interface_with_tls(
                         either::Either::Left(port),
                         allowed_origins,
+                        tls,
                         installed_app_id,
+                        signal_replay_buffer_size,
                     )
                     .await?;
                 Ok(AdminResponse::AppInterfaceAttached { port })
@@ -252,13 +315,31 @@ impl AdminInterfaceApi {
             DumpFullState {
                 cell_id,
                 dht_ops_cursor,
+                dht_ops_filter,
+                dht_ops_limit,
             } => {
                 let state = self
                     .conductor_handle
-                    .dump_full_cell_state(&cell_id, dht_ops_cursor)
+                    .dump_full_cell_state_filtered(
+                        &cell_id,
+                        dht_ops_cursor,
+                        dht_ops_filter,
+                        dht_ops_limit,
+                    )
                     .await?;
                 Ok(AdminResponse::FullStateDumped(state))
             }
+            ExportCell { cell_id } => {
+                let archive = self.conductor_handle.export_cell(&cell_id).await?;
+                Ok(AdminResponse::CellExported(Box::new(archive)))
+            }
+            ImportCell { archive, validate } => {
+                self.conductor_handle
+                    .clone()
+                    .import_cell(*archive, validate)
+                    .await?;
+                Ok(AdminResponse::CellImported)
+            }
             DumpNetworkMetrics { dna_hash } => {
                 let dump = self.conductor_handle.dump_network_metrics(dna_hash).await?;
                 Ok(AdminResponse::NetworkMetricsDumped(dump))
@@ -303,6 +384,49 @@ impl AdminInterfaceApi {
             StorageInfo => Ok(AdminResponse::StorageInfo(
                 self.conductor_handle.storage_info().await?,
             )),
+            ListWarrants { dna_hash } => Ok(AdminResponse::WarrantsListed(
+                self.conductor_handle.list_warrants(dna_hash).await?,
+            )),
+            UnblockWarrantee { dna_hash, op_hash } => {
+                self.conductor_handle
+                    .unblock_warrantee(dna_hash, op_hash)
+                    .await?;
+                Ok(AdminResponse::WarranteeUnblocked)
+            }
+            ListScheduledFunctions { cell_id } => Ok(AdminResponse::ScheduledFunctionsListed(
+                self.conductor_handle
+                    .list_scheduled_functions(&cell_id)
+                    .await?,
+            )),
+            TriggerScheduledFunction {
+                cell_id,
+                zome_name,
+                fn_name,
+            } => Ok(AdminResponse::ScheduledFunctionTriggered(
+                self.conductor_handle
+                    .trigger_scheduled_function(&cell_id, zome_name, fn_name)
+                    .await?,
+            )),
+            UnscheduleFunction {
+                cell_id,
+                zome_name,
+                fn_name,
+            } => {
+                self.conductor_handle
+                    .unschedule_function(&cell_id, zome_name, fn_name)
+                    .await?;
+                Ok(AdminResponse::FunctionUnscheduled)
+            }
+            ListScheduledFunctionRuns {
+                cell_id,
+                zome_name,
+                fn_name,
+                limit,
+            } => Ok(AdminResponse::ScheduledFunctionRunsListed(
+                self.conductor_handle
+                    .list_scheduled_function_runs(&cell_id, zome_name, fn_name, limit)
+                    .await?,
+            )),
             IssueAppAuthenticationToken(payload) => {
                 Ok(AdminResponse::AppAuthenticationTokenIssued(
                     self.conductor_handle
@@ -322,13 +446,18 @@ impl AdminInterfaceApi {
 mod test {
     use super::*;
     use crate::conductor::Conductor;
+    use crate::sweettest::*;
     use anyhow::Result;
     use holochain_state::prelude::*;
     use holochain_trace;
+    use holochain_types::test_utils::fake_cell_id;
     use holochain_types::test_utils::fake_dna_zomes;
     use holochain_types::test_utils::write_fake_dna_file;
     use holochain_wasm_test_utils::TestWasm;
     use matches::assert_matches;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
     use uuid::Uuid;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -460,6 +589,112 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn list_trigger_and_unschedule_scheduled_functions() {
+        holochain_trace::test_run();
+        let runs = Arc::new(AtomicU32::new(0));
+        let zomes = SweetInlineZomes::new(vec![], 0).function("tick", {
+            let runs = runs.clone();
+            move |_api, _: Option<Schedule>| {
+                runs.fetch_add(1, Ordering::SeqCst);
+                Ok(None::<Schedule>)
+            }
+        });
+        let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let cell_id = cell.cell_id().clone();
+        let admin_api = AdminInterfaceApi::new(conductor.raw_handle());
+
+        // Once a year, so that the scheduler itself doesn't run the function.
+        let schedule: Schedule = CronSchedule::new("0 0 0 1 1 * *").into();
+        let author = cell_id.agent_pubkey().clone();
+        cell.authored_db()
+            .write_async({
+                let schedule = schedule.clone();
+                move |txn| {
+                    schedule_fn(
+                        txn,
+                        &author,
+                        ScheduledFn::new(SweetInlineZomes::COORDINATOR.into(), "tick".into()),
+                        Some(schedule),
+                        Timestamp::now(),
+                    )
+                }
+            })
+            .await
+            .unwrap();
+
+        let response = admin_api
+            .handle_admin_request(AdminRequest::ListScheduledFunctions {
+                cell_id: cell_id.clone(),
+            })
+            .await;
+        assert_matches!(
+            response,
+            AdminResponse::ScheduledFunctionsListed(fns)
+                if fns.len() == 1
+                    && fns[0].fn_name == FunctionName::from("tick")
+                    && fns[0].schedule == Some(schedule.clone())
+        );
+
+        let response = admin_api
+            .handle_admin_request(AdminRequest::TriggerScheduledFunction {
+                cell_id: cell_id.clone(),
+                zome_name: SweetInlineZomes::COORDINATOR.into(),
+                fn_name: "tick".into(),
+            })
+            .await;
+        assert_matches!(
+            response,
+            AdminResponse::ScheduledFunctionTriggered(run) if run.error.is_none()
+        );
+        assert_eq!(1, runs.load(Ordering::SeqCst));
+
+        // A function which isn't scheduled can't be triggered.
+        let response = admin_api
+            .handle_admin_request(AdminRequest::TriggerScheduledFunction {
+                cell_id: cell_id.clone(),
+                zome_name: SweetInlineZomes::COORDINATOR.into(),
+                fn_name: "tock".into(),
+            })
+            .await;
+        assert_matches!(response, AdminResponse::Error(_));
+
+        let unschedule = || AdminRequest::UnscheduleFunction {
+            cell_id: cell_id.clone(),
+            zome_name: SweetInlineZomes::COORDINATOR.into(),
+            fn_name: "tick".into(),
+        };
+        let response = admin_api.handle_admin_request(unschedule()).await;
+        assert_matches!(response, AdminResponse::FunctionUnscheduled);
+        let response = admin_api.handle_admin_request(unschedule()).await;
+        assert_matches!(response, AdminResponse::Error(_));
+
+        let response = admin_api
+            .handle_admin_request(AdminRequest::ListScheduledFunctions {
+                cell_id: cell_id.clone(),
+            })
+            .await;
+        assert_matches!(response, AdminResponse::ScheduledFunctionsListed(fns) if fns.is_empty());
+
+        // The functions of a cell which isn't running are not touched.
+        for request in [
+            AdminRequest::ListScheduledFunctions {
+                cell_id: fake_cell_id(1),
+            },
+            AdminRequest::UnscheduleFunction {
+                cell_id: fake_cell_id(1),
+                zome_name: SweetInlineZomes::COORDINATOR.into(),
+                fn_name: "tick".into(),
+            },
+        ] {
+            let response = admin_api.handle_admin_request(request).await;
+            assert_matches!(response, AdminResponse::Error(_));
+        }
+    }
+
     // @todo fix test by using new InstallApp call
     // #[tokio::test(flavor = "multi_thread")]
     // async fn install_list_dna_app() {
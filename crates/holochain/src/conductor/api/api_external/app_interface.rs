@@ -1,8 +1,10 @@
+use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::api::error::ConductorApiResult;
 use crate::conductor::api::error::SerializationError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
 use crate::conductor::ConductorHandle;
+use crate::core::workflow::ZomeCallResult;
 
 use holochain_serialized_bytes::prelude::*;
 
@@ -32,6 +34,11 @@ impl AppInterfaceApi {
             .map_err(InterfaceError::RequestHandler)
     }
 
+    /// The app that an authentication token grants access to, without using the token.
+    pub fn peek_auth(&self, token: &AppAuthenticationToken) -> Option<InstalledAppId> {
+        self.conductor_handle.peek_app_token(token)
+    }
+
     /// Handle an [AppRequest] in the context of an [InstalledAppId], and return an [AppResponse].
     pub async fn handle_request(
         &self,
@@ -79,26 +86,73 @@ impl AppInterfaceApi {
                     .await?,
             )),
             AppRequest::CallZome(call) => {
-                match self.conductor_handle.call_zome(*call.clone()).await? {
-                    Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
-                    Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::ZomeCallUnauthorized(format!(
-                            "Call was not authorized with reason {:?}, cap secret {:?} to call the function {} in zome {}",
-                            zome_call_authorization, call.cap_secret, fn_name, zome_name
-                        )),
-                    )),
-                    Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
-                        "Interface zome calls should never be routed to the network. This is a bug. Got {}",
-                        e
-                    ),
-                    Ok(ZomeCallResponse::CountersigningSession(e)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::CountersigningSessionError(format!(
-                            "A countersigning session has failed to start on this zome call because: {}",
-                            e
-                        )),
-                    )),
-                    Err(e) => Ok(AppResponse::Error(e.into())),
+                self.conductor_handle
+                    .authorize_app_zome_call(&installed_app_id, &call)
+                    .await?;
+                // Calls which set a metering limit also get the points they consumed.
+                if call.metering_limit.is_some() {
+                    let (result, metering_points_used) = self
+                        .conductor_handle
+                        .call_zome_metered(*call.clone())
+                        .await?;
+                    return Ok(match zome_call_output(call.cap_secret.as_ref(), result) {
+                        Ok(output) => AppResponse::ZomeCalledMetered {
+                            output: Box::new(output),
+                            metering_points_used,
+                        },
+                        Err(e) => AppResponse::Error(e),
+                    });
                 }
+                let result = self.conductor_handle.call_zome(*call.clone()).await?;
+                Ok(match zome_call_output(call.cap_secret.as_ref(), result) {
+                    Ok(output) => AppResponse::ZomeCalled(Box::new(output)),
+                    Err(e) => AppResponse::Error(e),
+                })
+            }
+            AppRequest::CallZomeBatch(batch) => {
+                let ZomeCallBatch { calls, atomic } = *batch;
+                let cap_secrets: Vec<_> = calls.iter().map(|call| call.cap_secret).collect();
+                let mut refused = Vec::with_capacity(calls.len());
+                let mut allowed = Vec::with_capacity(calls.len());
+                for call in calls {
+                    match self
+                        .conductor_handle
+                        .authorize_app_zome_call(&installed_app_id, &call)
+                        .await
+                    {
+                        Ok(()) => {
+                            refused.push(None);
+                            allowed.push(call);
+                        }
+                        // An atomic batch is refused as a whole.
+                        Err(e) if atomic => return Err(e),
+                        Err(e) => refused.push(Some(e)),
+                    }
+                }
+                let mut allowed_results = self
+                    .conductor_handle
+                    .call_zome_batch(allowed, atomic)
+                    .await?
+                    .into_iter();
+                let results = refused.into_iter().map(|refused| match refused {
+                    Some(e) => Some(Err(e)),
+                    None => allowed_results.next().flatten(),
+                });
+                Ok(AppResponse::ZomeCallBatchCompleted(
+                    cap_secrets
+                        .iter()
+                        .zip(results)
+                        .map(|(cap_secret, result)| match result {
+                            Some(Ok(result)) => match zome_call_output(cap_secret.as_ref(), result)
+                            {
+                                Ok(output) => ZomeCallBatchResult::Ok(output),
+                                Err(e) => ZomeCallBatchResult::Error(e),
+                            },
+                            Some(Err(e)) => ZomeCallBatchResult::Error(e.into()),
+                            None => ZomeCallBatchResult::Skipped,
+                        })
+                        .collect(),
+                ))
             }
             AppRequest::CreateCloneCell(payload) => {
                 let clone_cell = self
@@ -133,10 +187,43 @@ impl AppInterfaceApi {
             AppRequest::ListWasmHostFunctions => Ok(AppResponse::ListWasmHostFunctions(
                 self.conductor_handle.list_wasm_host_functions().await?,
             )),
+            // Signal subscriptions belong to a connection, so they are handled by the interface
+            // which owns the connection and never reach this point.
+            AppRequest::SetSignalSubscription(_) => Err(ConductorApiError::other(
+                "Signal subscriptions can only be set over an app interface connection",
+            )),
         }
     }
 }
 
+/// Turn the result of a zome call made over an app interface into the output
+/// of the zome function or the error to send back.
+fn zome_call_output(
+    cap_secret: Option<&CapSecret>,
+    result: ZomeCallResult,
+) -> Result<ExternIO, ExternalApiWireError> {
+    match result {
+        Ok(ZomeCallResponse::Ok(output)) => Ok(output),
+        Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => {
+            Err(ExternalApiWireError::ZomeCallUnauthorized(format!(
+                "Call was not authorized with reason {:?}, cap secret {:?} to call the function {} in zome {}",
+                zome_call_authorization, cap_secret, fn_name, zome_name
+            )))
+        }
+        Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
+            "Interface zome calls should never be routed to the network. This is a bug. Got {}",
+            e
+        ),
+        Ok(ZomeCallResponse::CountersigningSession(e)) => Err(
+            ExternalApiWireError::CountersigningSessionError(format!(
+                "A countersigning session has failed to start on this zome call because: {}",
+                e
+            )),
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// The payload for authenticating an app interface connection
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 pub struct AppAuthentication {
@@ -148,3 +235,133 @@ pub struct AppAuthentication {
     /// must be provided by Holochain and not the client.
     pub installed_app_id: Option<InstalledAppId>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sweettest::*;
+    use ::fixt::prelude::*;
+    use holochain_conductor_api::conductor::ZomeCallPolicyConfig;
+    use holochain_nonce::fresh_nonce;
+    use holochain_wasm_test_utils::TestWasm;
+    use matches::assert_matches;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn zome_call_with_metering_limit_reports_points_used() {
+        holochain_trace::test_run();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let app_api = AppInterfaceApi::new(conductor.raw_handle());
+
+        let (conductor, cell, app_api) = (&conductor, &cell, &app_api);
+        let call = |metering_limit| async move {
+            let (nonce, expires_at) = fresh_nonce(Timestamp::now()).unwrap();
+            let call = ZomeCall::try_from_unsigned_zome_call(
+                &conductor.keystore(),
+                ZomeCallUnsigned {
+                    cell_id: cell.cell_id().clone(),
+                    zome_name: TestWasm::Foo.coordinator_zome_name(),
+                    fn_name: "foo".into(),
+                    cap_secret: None,
+                    provenance: cell.agent_pubkey().clone(),
+                    payload: ExternIO::encode(()).unwrap(),
+                    nonce,
+                    expires_at,
+                },
+            )
+            .await
+            .unwrap();
+            app_api
+                .handle_app_request(
+                    "app".into(),
+                    AppRequest::CallZome(Box::new(ZomeCall {
+                        metering_limit,
+                        ..call
+                    })),
+                )
+                .await
+        };
+
+        // A call without a limit gets the plain response
+        assert_matches!(call(None).await, AppResponse::ZomeCalled(_));
+
+        // while a call with a limit also gets the points it consumed.
+        match call(Some(1_000_000_000)).await {
+            AppResponse::ZomeCalledMetered {
+                output,
+                metering_points_used,
+            } => {
+                assert_eq!(output.decode::<String>().unwrap(), "foo");
+                assert!(metering_points_used > 0);
+                assert!(metering_points_used < 1_000_000_000);
+            }
+            other => panic!("unexpected response {other:?}"),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn zome_call_policy_refuses_calls() {
+        holochain_trace::test_run();
+        let mut config = SweetConductorConfig::standard();
+        // A single call per hour.
+        config.zome_call_policy = Some(ZomeCallPolicyConfig::TokenBucket {
+            capacity: 1,
+            refill_amount: 1,
+            refill_interval_ms: 60 * 60 * 1000,
+        });
+        let mut conductor = SweetConductor::from_config(config).await;
+        let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let app_api = AppInterfaceApi::new(conductor.raw_handle());
+
+        let call = || async {
+            let (nonce, expires_at) = fresh_nonce(Timestamp::now()).unwrap();
+            ZomeCall::try_from_unsigned_zome_call(
+                &conductor.keystore(),
+                ZomeCallUnsigned {
+                    cell_id: cell.cell_id().clone(),
+                    zome_name: TestWasm::Foo.coordinator_zome_name(),
+                    fn_name: "foo".into(),
+                    cap_secret: None,
+                    provenance: cell.agent_pubkey().clone(),
+                    payload: ExternIO::encode(()).unwrap(),
+                    nonce,
+                    expires_at,
+                },
+            )
+            .await
+            .unwrap()
+        };
+
+        let response = app_api
+            .handle_app_request("app".into(), AppRequest::CallZome(Box::new(call().await)))
+            .await;
+        assert_matches!(response, AppResponse::ZomeCalled(_));
+
+        // The agent has used up its calls.
+        let response = app_api
+            .handle_app_request("app".into(), AppRequest::CallZome(Box::new(call().await)))
+            .await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::ZomeCallRateLimited { .. })
+        );
+
+        // A call which is not signed by its provenance is not counted against that
+        // agent, nor does it get a bucket of its own, and the cell refuses it.
+        let forged = ZomeCall {
+            provenance: fixt!(AgentPubKey),
+            ..call().await
+        };
+        let response = app_api
+            .handle_app_request("app".into(), AppRequest::CallZome(Box::new(forged)))
+            .await;
+        assert_matches!(
+            response,
+            AppResponse::Error(ExternalApiWireError::ZomeCallUnauthorized(_))
+        );
+    }
+}
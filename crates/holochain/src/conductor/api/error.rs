@@ -32,6 +32,20 @@ pub enum ConductorApiError {
         call_cell_id: CellId,
     },
 
+    /// The calls of an atomic zome call batch were not all made to the same cell.
+    #[error(
+        "All calls of an atomic batch must be made to the same cell, got calls to {0:?} and {1:?}"
+    )]
+    AtomicBatchCellMismatch(CellId, CellId),
+
+    /// The zome call policy of the conductor refused the call.
+    #[error("The zome call was denied by the conductor's zome call policy: {0}")]
+    ZomeCallDenied(String),
+
+    /// The zome call policy of the conductor limited the rate of calls.
+    #[error("The zome call was rate limited, retry after {0:?}")]
+    ZomeCallRateLimited(std::time::Duration),
+
     /// Conductor threw an error during API call.
     #[error("Conductor returned an error while using a ConductorApi: {0:?}")]
     ConductorError(#[from] ConductorError),
@@ -150,6 +164,14 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
+            ConductorApiError::ZomeCallDenied(reason) => {
+                ExternalApiWireError::ZomeCallUnauthorized(reason)
+            }
+            ConductorApiError::ZomeCallRateLimited(retry_after) => {
+                ExternalApiWireError::ZomeCallRateLimited {
+                    retry_after_ms: retry_after.as_millis() as u64,
+                }
+            }
             e => ExternalApiWireError::internal(e),
         }
     }
@@ -0,0 +1,260 @@
+//! Conductor-level policies for zome calls made over app interfaces.
+//!
+//! A [`ZomeCallPolicy`] is asked about every zome call coming in over an app
+//! interface which is signed by its provenance, before the call reaches its
+//! cell, where capability grants and nonces are checked. The policy of a conductor is set with
+//! [`ConductorConfig::zome_call_policy`](crate::conductor::config::ConductorConfig::zome_call_policy),
+//! or with [`ConductorBuilder::zome_call_policy`](crate::conductor::ConductorBuilder::zome_call_policy)
+//! for custom implementations.
+
+use holochain_conductor_api::conductor::ZomeCallPolicyConfig;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How often buckets which are full again are dropped.
+const TOKEN_BUCKET_EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The decision of a [`ZomeCallPolicy`] about a zome call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZomeCallPolicyDecision {
+    /// The call may be made. Capability grants and nonces are still checked.
+    Allow,
+    /// The call is refused, for the given reason.
+    Deny(String),
+    /// Too many calls were made. The call may be made again after the given duration.
+    RateLimited(Duration),
+}
+
+/// Decides whether a zome call made over an app interface may be made,
+/// e.g. depending on the app, the zome function or the calling agent.
+pub trait ZomeCallPolicy: std::fmt::Debug + Send + Sync {
+    /// Decide about a call to a cell of the given app. The signature of the
+    /// call has been checked against its provenance.
+    fn authorize(
+        &self,
+        installed_app_id: &InstalledAppId,
+        call: &ZomeCall,
+    ) -> ZomeCallPolicyDecision;
+}
+
+impl From<&ZomeCallPolicyConfig> for Arc<dyn ZomeCallPolicy> {
+    fn from(config: &ZomeCallPolicyConfig) -> Self {
+        match config {
+            ZomeCallPolicyConfig::TokenBucket {
+                capacity,
+                refill_amount,
+                refill_interval_ms,
+            } => Arc::new(TokenBucketZomeCallPolicy::new(
+                *capacity,
+                *refill_amount,
+                Duration::from_millis(*refill_interval_ms),
+            )),
+        }
+    }
+}
+
+/// A [`ZomeCallPolicy`] which limits the rate of calls with a token bucket
+/// for every app, zome function and calling agent.
+#[derive(Debug)]
+pub struct TokenBucketZomeCallPolicy {
+    capacity: u32,
+    refill_amount: u32,
+    refill_interval: Duration,
+    buckets: parking_lot::Mutex<TokenBuckets>,
+}
+
+#[derive(Debug)]
+struct TokenBuckets {
+    buckets: HashMap<TokenBucketKey, TokenBucket>,
+    /// When idle buckets were last dropped.
+    evicted_at: Instant,
+}
+
+type TokenBucketKey = (InstalledAppId, ZomeName, FunctionName, AgentPubKey);
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: u32,
+    /// When tokens were last added to the bucket.
+    refilled_at: Instant,
+}
+
+impl TokenBucketZomeCallPolicy {
+    /// Allow bursts of `capacity` calls, and `refill_amount` further calls
+    /// every `refill_interval`.
+    pub fn new(capacity: u32, refill_amount: u32, refill_interval: Duration) -> Self {
+        Self {
+            capacity,
+            refill_amount,
+            refill_interval,
+            buckets: parking_lot::Mutex::new(TokenBuckets {
+                buckets: HashMap::new(),
+                evicted_at: Instant::now(),
+            }),
+        }
+    }
+
+    fn authorize_at(&self, key: TokenBucketKey, now: Instant) -> ZomeCallPolicyDecision {
+        let mut buckets = self.buckets.lock();
+        if now.saturating_duration_since(buckets.evicted_at) >= TOKEN_BUCKET_EVICTION_INTERVAL {
+            // Full buckets behave like new ones, so they can be dropped.
+            buckets.buckets.retain(|_, bucket| {
+                self.refill(bucket, now);
+                bucket.tokens < self.capacity
+            });
+            buckets.evicted_at = now;
+        }
+        let bucket = buckets.buckets.entry(key).or_insert_with(|| TokenBucket {
+            tokens: self.capacity,
+            refilled_at: now,
+        });
+        self.refill(bucket, now);
+        if bucket.tokens > 0 {
+            bucket.tokens -= 1;
+            ZomeCallPolicyDecision::Allow
+        } else {
+            ZomeCallPolicyDecision::RateLimited(
+                (bucket.refilled_at + self.refill_interval).saturating_duration_since(now),
+            )
+        }
+    }
+
+    /// Add the tokens of every refill interval which passed since the last refill.
+    fn refill(&self, bucket: &mut TokenBucket, now: Instant) {
+        if self.refill_interval.is_zero() {
+            bucket.tokens = self.capacity;
+            bucket.refilled_at = now;
+            return;
+        }
+        let intervals = (now.saturating_duration_since(bucket.refilled_at).as_nanos()
+            / self.refill_interval.as_nanos())
+        .min(u32::MAX as u128) as u32;
+        if intervals == 0 {
+            return;
+        }
+        bucket.tokens = bucket
+            .tokens
+            .saturating_add(intervals.saturating_mul(self.refill_amount))
+            .min(self.capacity);
+        bucket.refilled_at = if bucket.tokens == self.capacity {
+            now
+        } else {
+            bucket.refilled_at + self.refill_interval * intervals
+        };
+    }
+}
+
+impl ZomeCallPolicy for TokenBucketZomeCallPolicy {
+    fn authorize(
+        &self,
+        installed_app_id: &InstalledAppId,
+        call: &ZomeCall,
+    ) -> ZomeCallPolicyDecision {
+        self.authorize_at(
+            (
+                installed_app_id.clone(),
+                call.zome_name.clone(),
+                call.fn_name.clone(),
+                call.provenance.clone(),
+            ),
+            Instant::now(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::AgentPubKeyFixturator;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn token_bucket_limits_calls_per_key() {
+        let policy = TokenBucketZomeCallPolicy::new(2, 1, Duration::from_secs(1));
+        let key = |fn_name: &str, agent: &AgentPubKey| {
+            (
+                "app".to_string(),
+                ZomeName::from("zome"),
+                FunctionName::from(fn_name),
+                agent.clone(),
+            )
+        };
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let start = Instant::now();
+
+        // A burst of up to the capacity is allowed
+        assert_eq!(
+            ZomeCallPolicyDecision::Allow,
+            policy.authorize_at(key("f", &alice), start)
+        );
+        assert_eq!(
+            ZomeCallPolicyDecision::Allow,
+            policy.authorize_at(key("f", &alice), start)
+        );
+        assert_eq!(
+            ZomeCallPolicyDecision::RateLimited(Duration::from_millis(600)),
+            policy.authorize_at(key("f", &alice), start + Duration::from_millis(400))
+        );
+
+        // Other functions and agents have their own buckets
+        assert_eq!(
+            ZomeCallPolicyDecision::Allow,
+            policy.authorize_at(key("g", &alice), start)
+        );
+        assert_eq!(
+            ZomeCallPolicyDecision::Allow,
+            policy.authorize_at(key("f", &bob), start)
+        );
+
+        // One token is added per interval
+        let later = start + Duration::from_millis(1500);
+        assert_eq!(
+            ZomeCallPolicyDecision::Allow,
+            policy.authorize_at(key("f", &alice), later)
+        );
+        assert_eq!(
+            ZomeCallPolicyDecision::RateLimited(Duration::from_millis(500)),
+            policy.authorize_at(key("f", &alice), later)
+        );
+
+        // The bucket never holds more than its capacity
+        let much_later = start + Duration::from_secs(60);
+        for _ in 0..2 {
+            assert_eq!(
+                ZomeCallPolicyDecision::Allow,
+                policy.authorize_at(key("f", &alice), much_later)
+            );
+        }
+        assert!(matches!(
+            policy.authorize_at(key("f", &alice), much_later),
+            ZomeCallPolicyDecision::RateLimited(_)
+        ));
+    }
+
+    #[test]
+    fn token_bucket_evicts_idle_buckets() {
+        let policy = TokenBucketZomeCallPolicy::new(1, 1, Duration::from_secs(1));
+        let key = |agent: &AgentPubKey| {
+            (
+                "app".to_string(),
+                ZomeName::from("zome"),
+                FunctionName::from("f"),
+                agent.clone(),
+            )
+        };
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let start = Instant::now();
+
+        policy.authorize_at(key(&alice), start);
+        policy.authorize_at(key(&bob), start + TOKEN_BUCKET_EVICTION_INTERVAL);
+        // Alice's bucket was full again, while Bob's is in use
+        let buckets = policy.buckets.lock();
+        assert_eq!(1, buckets.buckets.len());
+        assert!(buckets.buckets.contains_key(&key(&bob)));
+    }
+}
@@ -4,6 +4,7 @@
 //! Records can be added. A constructed Cell is guaranteed to have a valid
 //! SourceChain which has already undergone Genesis.
 
+use std::collections::HashMap;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
@@ -25,8 +26,10 @@ use holochain_p2p::event::CountersigningSessionNegotiationMessage;
 use holochain_p2p::ChcImpl;
 use holochain_p2p::HolochainP2pDna;
 use holochain_sqlite::prelude::*;
+use holochain_state::host_fn_workspace::SleptTime;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
 use holochain_state::prelude::*;
+use holochain_state::schedule::get_schedule;
 use holochain_state::schedule::live_scheduled_fns;
 use holochain_types::db_cache::DhtDbQueryCache;
 
@@ -36,13 +39,18 @@ use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
+use crate::core::ribosome::MeteringPointsUsed;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::call_zome_workflow;
 use crate::core::workflow::countersigning_workflow::countersigning_success;
 use crate::core::workflow::countersigning_workflow::incoming_countersigning;
+use crate::core::workflow::flush_call_zome_workspace;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
 use crate::core::workflow::initialize_zomes_workflow;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentWorkflowArgs;
 use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::GenesisWorkflowArgs;
 use crate::core::workflow::GenesisWorkspace;
@@ -107,6 +115,8 @@ pub struct Cell {
     queue_triggers: QueueTriggers,
     signal_tx: broadcast::Sender<Signal>,
     init_mutex: tokio::sync::Mutex<()>,
+    /// The number of runs of each scheduled function currently in progress.
+    scheduled_fns_in_progress: parking_lot::Mutex<HashMap<ScheduledFn, u32>>,
 }
 
 impl Cell {
@@ -155,6 +165,7 @@ impl Cell {
                     queue_triggers,
                     signal_tx,
                     init_mutex: Default::default(),
+                    scheduled_fns_in_progress: Default::default(),
                 },
                 initial_queue_triggers,
             ))
@@ -260,13 +271,18 @@ impl Cell {
                 // Rescheduling should not fail as the data in the database
                 // should be valid schedules only.
                 reschedule_expired(txn, now, &author)?;
-                let lives = live_scheduled_fns(txn, now, &author);
+                let lives = live_scheduled_fns(txn, now, &author)?;
                 // We know what to run so we can delete the ephemerals.
-                if lives.is_ok() {
-                    // Failing to delete should rollback this attempt.
-                    delete_live_ephemeral_scheduled_fns(txn, now, &author)?;
+                // Failing to delete should rollback this attempt.
+                delete_live_ephemeral_scheduled_fns(txn, now, &author)?;
+                // Cron schedules move on to their next run straight away,
+                // so that they are not dispatched again while in progress.
+                for (scheduled_fn, schedule) in &lives {
+                    if let Some(Schedule::Cron(_)) = schedule {
+                        schedule_fn(txn, &author, scheduled_fn.clone(), schedule.clone(), now)?;
+                    }
                 }
-                lives
+                StateMutationResult::Ok(lives)
             })
             .await;
 
@@ -276,100 +292,232 @@ impl Cell {
                 error!("error calling scheduled fn: {:?}", e);
             }
             Ok(live_fns) => {
-                let mut tasks = vec![];
-                for (scheduled_fn, schedule) in &live_fns {
-                    // Failing to encode a schedule should never happen.
-                    // If it does log the error and bail.
-                    let payload = match ExternIO::encode(schedule) {
-                        Ok(payload) => payload,
-                        Err(e) => {
-                            error!(
-                                "error encoding scheduled fn: {:?} error: {:?}",
-                                scheduled_fn, e
-                            );
-                            continue;
+                self.run_scheduled_fns(authored_db, live_fns, now).await;
+            }
+        }
+    }
+
+    /// Run a scheduled function right away, whether it is due or not.
+    ///
+    /// The function runs with its current schedule and is rescheduled by its result
+    /// as usual. An ephemeral schedule is used up by the run.
+    pub(super) async fn trigger_scheduled_fn(
+        self: Arc<Self>,
+        scheduled_fn: ScheduledFn,
+    ) -> CellResult<Option<ScheduledFnRun>> {
+        let authored_db = self.get_or_create_authored_db()?;
+        let author = self.id.agent_pubkey().clone();
+        let now = Timestamp::now();
+        let schedule = authored_db
+            .write_async({
+                let scheduled_fn = scheduled_fn.clone();
+                move |txn: &mut Transaction| {
+                    let schedule = get_schedule(txn, &scheduled_fn, &author)?;
+                    if matches!(schedule, Some(None | Some(Schedule::Ephemeral(_)))) {
+                        unschedule_fn(txn, &author, &scheduled_fn)?;
+                    }
+                    StateMutationResult::Ok(schedule)
+                }
+            })
+            .await?;
+        let Some(schedule) = schedule else {
+            return Ok(None);
+        };
+        Ok(self
+            .run_scheduled_fns(authored_db, vec![(scheduled_fn, schedule)], now)
+            .await
+            .pop())
+    }
+
+    /// Run scheduled functions, record the runs and apply the schedules they return.
+    ///
+    /// Functions which already have as many runs in progress as their schedule allows
+    /// are skipped. The skipped runs of cron schedules are recorded too.
+    async fn run_scheduled_fns(
+        self: Arc<Self>,
+        authored_db: DbWrite<DbKindAuthored>,
+        scheduled_fns: Vec<(ScheduledFn, Option<Schedule>)>,
+        now: Timestamp,
+    ) -> Vec<ScheduledFnRun> {
+        let mut runs = vec![];
+        let mut dispatched = vec![];
+        let mut tasks = vec![];
+        for (scheduled_fn, schedule) in scheduled_fns {
+            let max_concurrency = schedule.as_ref().map_or(1, Schedule::max_concurrency);
+            {
+                let mut in_progress = self.scheduled_fns_in_progress.lock();
+                let count = in_progress.entry(scheduled_fn.clone()).or_default();
+                if *count >= max_concurrency {
+                    debug!(
+                        ?scheduled_fn,
+                        in_progress = *count,
+                        "skipping scheduled fn, too many runs in progress"
+                    );
+                    // A skipped cron run won't be retried, so it
+                    // goes into the run history.
+                    if let Some(Schedule::Cron(_)) = schedule {
+                        runs.push(ScheduledFnRun {
+                            zome_name: scheduled_fn.zome_name().clone(),
+                            fn_name: scheduled_fn.fn_name().clone(),
+                            started_at: now,
+                            duration: std::time::Duration::ZERO,
+                            error: Some(format!(
+                                "Skipped because {} run(s) are still in progress",
+                                *count
+                            )),
+                        });
+                    }
+                    continue;
+                }
+                *count += 1;
+            }
+            let zome_call = self
+                .scheduled_zome_call(&scheduled_fn, &schedule, now)
+                .await;
+            let this = self.clone();
+            tasks.push(async move {
+                let started = std::time::Instant::now();
+                let result = match zome_call {
+                    Some(zome_call) => Some(this.call_zome(zome_call, None).await),
+                    None => None,
+                };
+                (result, started.elapsed())
+            });
+            dispatched.push(scheduled_fn);
+        }
+        let results = futures::future::join_all(tasks).await;
+
+        let mut next_schedules = vec![];
+        for (scheduled_fn, (result, duration)) in dispatched.iter().zip(results.into_iter()) {
+            let error = match result {
+                Some(Ok(Ok(ZomeCallResponse::Ok(extern_io)))) => {
+                    match extern_io.decode::<Option<Schedule>>() {
+                        Ok(Some(next_schedule)) => {
+                            next_schedules.push((runs.len(), scheduled_fn.clone(), next_schedule));
+                            None
                         }
-                    };
-                    let provenance = self.id.agent_pubkey().clone();
-                    let (nonce, expires_at) = match fresh_nonce(now) {
-                        Ok(v) => v,
+                        Ok(None) => None,
                         Err(e) => {
-                            error!(
-                                "error creating nonce for fn: {:?} error: {:?}",
-                                scheduled_fn, e
-                            );
-                            continue;
+                            error!("scheduled zome call error in ExternIO::decode: {:?}", e);
+                            Some(e.to_string())
                         }
-                    };
-                    let unsigned_zome_call = ZomeCallUnsigned {
-                        provenance,
-                        cell_id: self.id.clone(),
-                        zome_name: scheduled_fn.zome_name().clone(),
-                        fn_name: scheduled_fn.fn_name().clone(),
-                        cap_secret: None,
-                        payload,
-                        nonce,
-                        expires_at,
-                    };
-
-                    tasks.push(
-                        self.call_zome(
-                            match ZomeCall::try_from_unsigned_zome_call(
-                                self.conductor_handle.keystore(),
-                                unsigned_zome_call,
-                            )
-                                .await
-                            {
-                                Ok(zome_call) => zome_call,
-                                Err(e) => {
-                                    error!("scheduled zome call error in try_from_unsigned_zome_call: {:?}", e);
-                                    continue;
-                                }
-                            },
-                            None,
-                        ),
-                    );
+                    }
                 }
-                let results: Vec<CellResult<ZomeCallResult>> =
-                    futures::future::join_all(tasks).await;
-
-                let author = self.id.agent_pubkey().clone();
-                // We don't do anything with errors in here.
-                let _ = authored_db
-                    .write_async(move |txn: &mut Transaction| {
-                        for ((scheduled_fn, _), result) in live_fns.iter().zip(results.iter()) {
-                            match result {
-                                Ok(Ok(ZomeCallResponse::Ok(extern_io))) => {
-                                    let next_schedule: Schedule = match extern_io.decode() {
-                                        Ok(Some(v)) => v,
-                                        Ok(None) => {
-                                            continue;
-                                        }
-                                        Err(e) => {
-                                            error!("scheduled zome call error in ExternIO::decode: {:?}", e);
-                                            continue;
-                                        }
-                                    };
-                                    // Ignore errors so that failing to schedule
-                                    // one function doesn't error others.
-                                    // For example if a zome returns a bad cron.
-                                    if let Err(e) = schedule_fn(
-                                        txn,
-                                        &author,
-                                        scheduled_fn.clone(),
-                                        Some(next_schedule),
-                                        now,
-                                    ) {
-                                        error!("scheduled zome call error in schedule_fn: {:?}", e);
-                                        continue;
-                                    }
-                                }
-                                errorish => error!("scheduled zome call error: {:?}", errorish),
-                            }
-                        }
-                        Result::<(), DatabaseError>::Ok(())
-                    })
-                    .await;
+                // The zome call could not be built, which was logged already.
+                None => Some("Failed to create the zome call".to_string()),
+                Some(errorish) => {
+                    error!("scheduled zome call error: {:?}", errorish);
+                    Some(format!("{:?}", errorish))
+                }
+            };
+            runs.push(ScheduledFnRun {
+                zome_name: scheduled_fn.zome_name().clone(),
+                fn_name: scheduled_fn.fn_name().clone(),
+                started_at: now,
+                duration,
+                error,
+            });
+        }
+
+        let author = self.id.agent_pubkey().clone();
+        // We don't do anything with errors in here.
+        let runs = authored_db
+            .write_async(move |txn: &mut Transaction| {
+                for (i, scheduled_fn, next_schedule) in next_schedules {
+                    // Ignore errors so that failing to schedule
+                    // one function doesn't error others.
+                    // For example if a zome returns a bad cron.
+                    if let Err(e) =
+                        schedule_fn(txn, &author, scheduled_fn, Some(next_schedule), now)
+                    {
+                        error!("scheduled zome call error in schedule_fn: {:?}", e);
+                        runs[i].error = Some(e.to_string());
+                    }
+                }
+                for run in &runs {
+                    if let Err(e) = insert_scheduled_fn_run(txn, &author, run) {
+                        error!("error recording scheduled fn run: {:?}", e);
+                    }
+                }
+                Result::<_, DatabaseError>::Ok(runs)
+            })
+            .await
+            .unwrap_or_else(|e| {
+                error!("error rescheduling scheduled fns: {:?}", e);
+                vec![]
+            });
+
+        // Only now that the next runs are scheduled can the
+        // functions be dispatched again.
+        {
+            let mut in_progress = self.scheduled_fns_in_progress.lock();
+            for scheduled_fn in &dispatched {
+                if let Some(count) = in_progress.get_mut(scheduled_fn) {
+                    *count = count.saturating_sub(1);
+                    if *count == 0 {
+                        in_progress.remove(scheduled_fn);
+                    }
+                }
+            }
+        }
+
+        runs
+    }
+
+    /// Build the signed zome call for a run of a scheduled function,
+    /// or `None` if that fails.
+    async fn scheduled_zome_call(
+        &self,
+        scheduled_fn: &ScheduledFn,
+        schedule: &Option<Schedule>,
+        now: Timestamp,
+    ) -> Option<ZomeCall> {
+        // Failing to encode a schedule should never happen.
+        // If it does log the error and bail.
+        let payload = match ExternIO::encode(schedule) {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!(
+                    "error encoding scheduled fn: {:?} error: {:?}",
+                    scheduled_fn, e
+                );
+                return None;
+            }
+        };
+        let provenance = self.id.agent_pubkey().clone();
+        let (nonce, expires_at) = match fresh_nonce(now) {
+            Ok(v) => v,
+            Err(e) => {
+                error!(
+                    "error creating nonce for fn: {:?} error: {:?}",
+                    scheduled_fn, e
+                );
+                return None;
+            }
+        };
+        let unsigned_zome_call = ZomeCallUnsigned {
+            provenance,
+            cell_id: self.id.clone(),
+            zome_name: scheduled_fn.zome_name().clone(),
+            fn_name: scheduled_fn.fn_name().clone(),
+            cap_secret: None,
+            payload,
+            nonce,
+            expires_at,
+        };
+        match ZomeCall::try_from_unsigned_zome_call(
+            self.conductor_handle.keystore(),
+            unsigned_zome_call,
+        )
+        .await
+        {
+            Ok(zome_call) => Some(zome_call),
+            Err(e) => {
+                error!(
+                    "scheduled zome call error in try_from_unsigned_zome_call: {:?}",
+                    e
+                );
+                None
             }
         }
     }
@@ -874,6 +1022,7 @@ impl Cell {
             fn_name,
             nonce,
             expires_at,
+            metering_limit: None,
         };
         // double ? because
         // - ConductorApiResult
@@ -891,6 +1040,40 @@ impl Cell {
         &self,
         call: ZomeCall,
         workspace_lock: Option<SourceChainWorkspace>,
+    ) -> CellResult<ZomeCallResult> {
+        self.call_zome_in_call_chain(call, workspace_lock, None, Default::default())
+            .await
+    }
+
+    /// Make a zome call, and also return the wasm metering points it consumed.
+    pub async fn call_zome_metered(&self, call: ZomeCall) -> CellResult<(ZomeCallResult, u64)> {
+        let metering_points_used = MeteringPointsUsed::default();
+        let result = self
+            .call_zome_in_call_chain(call, None, None, metering_points_used.clone())
+            .await?;
+        Ok((
+            result,
+            metering_points_used.load(std::sync::atomic::Ordering::Relaxed),
+        ))
+    }
+
+    /// Make a zome call on behalf of a zome call of another cell, which
+    /// shares with it how long the calls of its call chain have slept for.
+    pub async fn call_zome_from_other_cell(
+        &self,
+        call: ZomeCall,
+        slept: SleptTime,
+    ) -> CellResult<ZomeCallResult> {
+        self.call_zome_in_call_chain(call, None, Some(slept), Default::default())
+            .await
+    }
+
+    async fn call_zome_in_call_chain(
+        &self,
+        call: ZomeCall,
+        workspace_lock: Option<SourceChainWorkspace>,
+        slept: Option<SleptTime>,
+        metering_points_used: MeteringPointsUsed,
     ) -> CellResult<ZomeCallResult> {
         // Only check if init has run if this call is not coming from
         // an already running init call.
@@ -906,8 +1089,10 @@ impl Cell {
 
         let conductor_handle = self.conductor_handle.clone();
         let ribosome = self.get_ribosome()?;
-        let invocation =
-            ZomeCallInvocation::try_from_interface_call(self.conductor_api.clone(), call).await?;
+        let invocation = ZomeCallInvocation {
+            metering_points_used,
+            ..ZomeCallInvocation::try_from_interface_call(self.conductor_api.clone(), call).await?
+        };
 
         let dna_def = ribosome.dna_def().as_content().clone();
 
@@ -916,7 +1101,7 @@ impl Cell {
         let workspace_lock = match workspace_lock {
             Some(l) => l,
             None => {
-                SourceChainWorkspace::new(
+                let workspace = SourceChainWorkspace::new(
                     self.get_or_create_authored_db()?,
                     self.dht_db().clone(),
                     self.space.dht_query_cache.clone(),
@@ -925,7 +1110,11 @@ impl Cell {
                     self.id.agent_pubkey().clone(),
                     Arc::new(dna_def),
                 )
-                .await?
+                .await?;
+                match slept {
+                    Some(slept) => workspace.with_slept(slept),
+                    None => workspace,
+                }
             }
         };
 
@@ -949,6 +1138,77 @@ impl Cell {
         .map_err(Box::new)?)
     }
 
+    /// Make several zome calls on this cell as one unit.
+    ///
+    /// The calls are made in order on one workspace, so every call sees what
+    /// the previous calls wrote. The source chain is only written to once all
+    /// calls have succeeded. After a call fails, the remaining calls are not
+    /// made and their results are `None`.
+    pub async fn call_zome_batch_atomic(
+        &self,
+        calls: Vec<ZomeCall>,
+    ) -> CellResult<Vec<Option<CellResult<ZomeCallResult>>>> {
+        self.check_or_run_zome_init().await?;
+
+        let keystore = self.conductor_api.keystore().clone();
+        let ribosome = self.get_ribosome()?;
+        let workspace = SourceChainWorkspace::new(
+            self.get_or_create_authored_db()?,
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            keystore.clone(),
+            self.id.agent_pubkey().clone(),
+            Arc::new(ribosome.dna_def().as_content().clone()),
+        )
+        .await?;
+
+        let mut coordinator_zomes: Vec<CoordinatorZome> = Vec::new();
+        let mut results = Vec::with_capacity(calls.len());
+        let mut failed = false;
+        for call in calls {
+            if failed {
+                results.push(None);
+                continue;
+            }
+            if let Ok(zome) = ribosome.dna_def().get_coordinator_zome(&call.zome_name) {
+                if !coordinator_zomes
+                    .iter()
+                    .any(|z| z.zome_name() == zome.zome_name())
+                {
+                    coordinator_zomes.push(zome);
+                }
+            }
+            // Passing the workspace makes this a nested call, which leaves
+            // writing the workspace to us.
+            let result = self.call_zome(call, Some(workspace.clone())).await;
+            failed = !matches!(result, Ok(Ok(ZomeCallResponse::Ok(_))));
+            results.push(Some(result));
+        }
+
+        if !failed {
+            if let Some(error_response) = flush_call_zome_workspace(
+                workspace,
+                self.holochain_p2p_cell.clone(),
+                keystore,
+                self.conductor_handle.clone(),
+                self.signal_tx.clone(),
+                coordinator_zomes,
+                self.queue_triggers.publish_dht_ops.clone(),
+                self.queue_triggers.integrate_dht_ops.clone(),
+            )
+            .await
+            .map_err(Box::new)?
+            {
+                // Nothing was written, so none of the calls took effect.
+                for result in results.iter_mut() {
+                    *result = Some(Ok(Ok(error_response.clone())));
+                }
+            }
+        }
+        Ok(results)
+    }
+
     /// Check if each Zome's init callback has been run, and if not, run it.
     #[tracing::instrument(skip(self))]
     pub(crate) async fn check_or_run_zome_init(&self) -> CellResult<()> {
@@ -1008,6 +1268,51 @@ impl Cell {
         Ok(())
     }
 
+    /// Run one side of an agent migration on this cell.
+    ///
+    /// The `migrate_agent` callback is invoked and, if it passes, the chain is
+    /// closed towards or opened from `other_dna_hash`.
+    pub(crate) async fn migrate_agent(
+        &self,
+        migrate_agent: MigrateAgent,
+        other_dna_hash: DnaHash,
+    ) -> CellResult<()> {
+        let keystore = self.conductor_api.keystore().clone();
+        let ribosome = self.get_ribosome()?;
+        let dna_def = ribosome.dna_def().clone();
+
+        let workspace = SourceChainWorkspace::new(
+            self.get_or_create_authored_db()?,
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            keystore,
+            self.id.agent_pubkey().clone(),
+            Arc::new(dna_def.into_content()),
+        )
+        .await?;
+
+        let args = MigrateAgentWorkflowArgs {
+            ribosome,
+            migrate_agent,
+            other_dna_hash,
+            integrate_dht_ops_trigger: self.queue_triggers.integrate_dht_ops.clone(),
+        };
+        let result = migrate_agent_workflow(workspace, self.holochain_p2p_cell.clone(), args)
+            .await
+            .map_err(Box::new)?;
+        trace!(?result);
+        match result {
+            MigrateAgentResult::Pass => {}
+            r => return Err(CellError::MigrateAgentFailed(r)),
+        }
+        // Make sure the new actions get published.
+        self.queue_triggers
+            .publish_dht_ops
+            .trigger(&"migrate_agent");
+        Ok(())
+    }
+
     /// Clean up long-running managed tasks.
     pub async fn cleanup(&self) -> CellResult<()> {
         use holochain_p2p::HolochainP2pDnaT;
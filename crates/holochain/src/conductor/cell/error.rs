@@ -3,6 +3,7 @@ use crate::conductor::entry_def_store::error::EntryDefStoreError;
 use crate::conductor::{api::error::ConductorApiError, error::ConductorError};
 use crate::core::ribosome::error::RibosomeError;
 use crate::core::ribosome::guest_callback::init::InitResult;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::workflow::WorkflowError;
 use crate::core::SourceChainError;
 use holochain_cascade::error::CascadeError;
@@ -53,6 +54,8 @@ pub enum CellError {
         INIT_MUTEX_TIMEOUT_SECS
     )]
     InitTimeout,
+    #[error("The cell tried to run the migrate agent callback but failed because {0:?}")]
+    MigrateAgentFailed(MigrateAgentResult),
     #[error("Failed to get or create the cache for this dna {0:?}")]
     FailedToCreateCache(Box<ConductorError>),
     #[error("Failed to get or create the authored db for this dna {0:?}")]
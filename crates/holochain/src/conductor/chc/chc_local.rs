@@ -207,9 +207,11 @@ mod tests {
             let mut dump = conductor
                 .dump_full_cell_state(&cell_id, None)
                 .await
+                .unwrap()
+                .source_chain_dump
                 .unwrap();
-            assert_eq!(dump.source_chain_dump.records.len(), 3);
-            dump.source_chain_dump.records.pop().unwrap().action_address
+            assert_eq!(dump.records.len(), 3);
+            dump.records.pop().unwrap().action_address
         };
 
         let izc = InitZomesComplete {
@@ -244,16 +246,11 @@ mod tests {
         let dump = conductor
             .dump_full_cell_state(&cell_id, None)
             .await
+            .unwrap()
+            .source_chain_dump
             .unwrap();
-        assert_eq!(dump.source_chain_dump.records.len(), 4);
-        assert_eq!(
-            dump.source_chain_dump
-                .records
-                .last()
-                .unwrap()
-                .action_address,
-            new_action_hash,
-        );
+        assert_eq!(dump.records.len(), 4);
+        assert_eq!(dump.records.last().unwrap().action_address, new_action_hash);
     }
 
     // TODO: run this remotely too
@@ -366,9 +363,11 @@ mod tests {
         let dump1 = conductors[1]
             .dump_full_cell_state(&cell_id, None)
             .await
+            .unwrap()
+            .source_chain_dump
             .unwrap();
 
-        assert_eq!(dump1.source_chain_dump.records.len(), 3);
+        assert_eq!(dump1.records.len(), 3);
 
         let c1: SweetCell = conductors[1].get_sweet_cell(cell_id.clone()).unwrap();
         let c2: SweetCell = conductors[2].get_sweet_cell(cell_id.clone()).unwrap();
@@ -414,24 +413,24 @@ mod tests {
         let dump0 = conductors[0]
             .dump_full_cell_state(&cell_id, None)
             .await
+            .unwrap()
+            .source_chain_dump
             .unwrap();
         let dump1 = conductors[1]
             .dump_full_cell_state(&cell_id, None)
             .await
+            .unwrap()
+            .source_chain_dump
             .unwrap();
         let dump2 = conductors[2]
             .dump_full_cell_state(&cell_id, None)
             .await
+            .unwrap()
+            .source_chain_dump
             .unwrap();
 
-        assert_eq!(dump0.source_chain_dump.records.len(), 6);
-        assert_eq!(
-            dump0.source_chain_dump.records,
-            dump1.source_chain_dump.records
-        );
-        assert_eq!(
-            dump1.source_chain_dump.records,
-            dump2.source_chain_dump.records
-        );
+        assert_eq!(dump0.records.len(), 6);
+        assert_eq!(dump0.records, dump1.records);
+        assert_eq!(dump1.records, dump2.records);
     }
 }
@@ -131,14 +131,19 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[tokio::test(flavor = "multi_thread")]
-    #[ignore = "this test requires a remote service, so it should only be run manually"]
     async fn test_add_records_remote() {
+        let dir = tempfile::tempdir().unwrap();
+        let (driver, addr, shutdown) =
+            hc_chc_service::run(([127, 0, 0, 1], 0), &dir.path().join("chc.sqlite3"))
+                .await
+                .unwrap();
+        let service = tokio::spawn(driver);
+
         let keystore = holochain_keystore::test_keystore();
         let agent = fake_agent_pubkey_1();
         let cell_id = CellId::new(::fixt::fixt!(DnaHash), agent.clone());
         let chc = Arc::new(ChcRemote::new(
-            url::Url::parse("http://127.0.0.1:40845/").unwrap(),
-            // url::Url::parse("https://chc.dev.holotest.net/v1/").unwrap(),
+            url::Url::parse(&format!("http://{addr}/")).unwrap(),
             keystore.clone(),
             &cell_id,
         ));
@@ -154,18 +159,7 @@ mod tests {
 
         let hash = |i: usize| chain[i].action_address().clone();
 
-        // dbg!(t0
-        //     .iter()
-        //     .map(|r| (r.action_address(), r.action().prev_action()))
-        //     .collect::<Vec<_>>());
-
-        // dbg!(&t0, &t1, &t2);
-
-        chc.clone()
-            .add_records(t0.to_vec())
-            .await
-            .map_err(|e| e.to_string()[..1024.min(e.to_string().len())].to_string())
-            .unwrap();
+        chc.clone().add_records(t0.to_vec()).await.unwrap();
         assert_eq!(chc.clone().head().await.unwrap().unwrap(), hash(2));
 
         chc.clone().add_records(t1.to_vec()).await.unwrap();
@@ -208,5 +202,8 @@ mod tests {
             chc.clone().get_record_data(Some(hash(13))).await.unwrap(),
             &[]
         );
+
+        shutdown();
+        service.await.unwrap();
     }
 }
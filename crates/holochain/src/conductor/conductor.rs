@@ -50,9 +50,11 @@ use tracing::*;
 
 pub use builder::*;
 use holo_hash::DnaHash;
+use holochain_conductor_api::cell_archive::*;
 use holochain_conductor_api::conductor::KeystoreConfig;
 use holochain_conductor_api::AppInfo;
 use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::DhtOpsFilter;
 use holochain_conductor_api::FullIntegrationStateDump;
 use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
@@ -78,6 +80,7 @@ use kitsune_p2p::agent_store::AgentInfoSigned;
 use crate::conductor::cell::Cell;
 use crate::conductor::conductor::app_auth_token_store::AppAuthTokenStore;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::config::AppStoreConfig;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::metrics::create_p2p_event_duration_metric;
@@ -88,11 +91,15 @@ use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueConsumerMap;
 #[cfg(any(test, feature = "test_utils"))]
 use crate::core::queue_consumer::QueueTriggers;
+use crate::core::queue_consumer::TriggerSender;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitArgs;
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CHANNEL_BOUND;
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CONCURRENT_LIMIT;
 use crate::core::ribosome::real_ribosome::ModuleCacheLock;
 use crate::core::ribosome::RibosomeT;
+use crate::core::workflow::migrate_agent_workflow::migrate_agent_workflow;
+use crate::core::workflow::migrate_agent_workflow::MigrateAgentWorkflowArgs;
 use crate::core::workflow::ZomeCallResult;
 use crate::{
     conductor::api::error::ConductorApiResult, core::ribosome::real_ribosome::RealRibosome,
@@ -100,13 +107,19 @@ use crate::{
 
 use super::api::AppInterfaceApi;
 use super::api::ZomeCall;
+use super::api::ZomeCallPolicy;
+use super::api::ZomeCallPolicyDecision;
 use super::config::AdminInterfaceConfig;
 use super::config::InterfaceDriver;
+use super::config::InterfaceTls;
 use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
+use super::interface::error::InterfaceError;
 use super::interface::error::InterfaceResult;
 use super::interface::websocket::spawn_admin_interface_tasks;
 use super::interface::websocket::spawn_app_interface_task;
+use super::interface::websocket::spawn_app_unix_socket_interface_task;
+use super::interface::websocket::spawn_unix_socket_listener;
 use super::interface::websocket::spawn_websocket_listener;
 use super::manager::TaskManagerResult;
 use super::p2p_agent_store;
@@ -143,6 +156,13 @@ pub mod tests;
 /// of an app having full network access as soon as its UI begins making requests.
 pub const JOIN_NETWORK_WAITING_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
 
+/// How many dispatches of the scheduler may be in progress at the same time.
+///
+/// A dispatch waits for the scheduled functions it runs, so a long running
+/// function keeps its dispatch in progress while the next ticks go on. Ticks
+/// beyond this many dispatches in progress are skipped.
+pub const MAX_CONCURRENT_SCHEDULER_DISPATCHES: usize = 4;
+
 /// A list of Cells which failed to start, and why
 pub type CellStartupErrors = Vec<(CellId, CellError)>;
 
@@ -251,6 +271,9 @@ pub struct Conductor {
 
     /// Container to connect app signals to app interfaces, by installed app id.
     app_broadcast: AppBroadcast,
+
+    /// The policy which zome calls made over app interfaces are checked against.
+    zome_call_policy: Option<Arc<dyn ZomeCallPolicy>>,
 }
 
 impl Conductor {
@@ -289,6 +312,7 @@ mod startup_shutdown_impls {
                 .data_root_path
                 .clone()
                 .map(|path| PathBuf::from(path.deref()));
+            let zome_call_policy = config.zome_call_policy.as_ref().map(Into::into);
 
             Self {
                 spaces,
@@ -310,6 +334,7 @@ mod startup_shutdown_impls {
                 ))),
                 app_auth_token_store: RwShare::default(),
                 app_broadcast: AppBroadcast::default(),
+                zome_call_policy,
             }
         }
 
@@ -359,6 +384,11 @@ mod startup_shutdown_impls {
 
             info!("Conductor startup: DNAs loaded.");
 
+            // Route the signals of each cell to the apps which contain it,
+            // which is kept up to date by every later state update.
+            self.app_broadcast
+                .set_cell_apps(self.get_state().await?.apps_by_cell());
+
             // Start the task manager
             self.outcomes_task.share_mut(|lock| {
                 if lock.is_some() {
@@ -368,6 +398,8 @@ mod startup_shutdown_impls {
                 *lock = Some(task);
             });
 
+            let app_store = self.clone().build_app_store_service().await?;
+
             self.services.share_mut(|services| {
                 let mut dpki = MockDpkiService::new();
                 dpki.expect_is_key_valid()
@@ -375,11 +407,9 @@ mod startup_shutdown_impls {
                 dpki.expect_key_mutation()
                     .returning(|_, _| box_fut_plain(Ok(())));
 
-                let app_store = MockAppStoreService::new();
-
                 *services = Some(ConductorServices {
                     dpki: Arc::new(dpki),
-                    app_store: Arc::new(app_store),
+                    app_store,
                 });
             });
 
@@ -400,6 +430,75 @@ mod startup_shutdown_impls {
 
             res
         }
+
+        /// Construct the app store service as specified by the conductor config.
+        ///
+        /// If no app store is configured, or the configured app store app is not
+        /// installed, an empty app store is used.
+        async fn build_app_store_service(
+            self: Arc<Self>,
+        ) -> ConductorResult<Arc<dyn AppStoreService>> {
+            match self.config.app_store.clone() {
+                Some(AppStoreConfig::LocalDir { path }) => Ok(AppStoreLocalDir::new(path)),
+                Some(AppStoreConfig::InstalledApp {
+                    installed_app_id,
+                    role_name,
+                }) => {
+                    let state = self.get_state().await?;
+                    let cell_id = state
+                        .get_app(&installed_app_id)
+                        .ok()
+                        .and_then(|app| app.role(&role_name).ok())
+                        .and_then(|role| role.provisioned_cell())
+                        .cloned();
+                    match cell_id {
+                        Some(cell_id) => Ok(AppStoreBuiltin::new(self.clone(), cell_id)),
+                        None => {
+                            warn!(
+                                ?installed_app_id,
+                                ?role_name,
+                                "The configured app store app is not installed. No apps can be installed from the app store until the conductor is restarted with the app installed."
+                            );
+                            Ok(Arc::new(mock_app_store()))
+                        }
+                    }
+                }
+                None => Ok(Arc::new(mock_app_store())),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl CellRunner for Conductor {
+        async fn call_zome(
+            &self,
+            provenance: &AgentPubKey,
+            cap_secret: Option<CapSecret>,
+            cell_id: CellId,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+            payload: ExternIO,
+        ) -> anyhow::Result<ExternIO> {
+            let now = Timestamp::now();
+            let (nonce, expires_at) =
+                holochain_nonce::fresh_nonce(now).map_err(|e| anyhow::anyhow!(e))?;
+            let call_unsigned = ZomeCallUnsigned {
+                cell_id,
+                zome_name,
+                fn_name,
+                cap_secret,
+                provenance: provenance.clone(),
+                payload,
+                nonce,
+                expires_at,
+            };
+            let call =
+                ZomeCall::try_from_unsigned_zome_call(self.keystore(), call_unsigned).await?;
+            match Conductor::call_zome(self, call).await?? {
+                ZomeCallResponse::Ok(response) => Ok(response),
+                other => Err(anyhow::anyhow!("{:?}", other)),
+            }
+        }
     }
 }
 
@@ -408,10 +507,13 @@ mod interface_impls {
     use super::*;
     use holochain_conductor_api::AppInterfaceInfo;
     use holochain_types::websocket::AllowedOrigins;
+    use holochain_websocket::TlsServerConfig;
 
     impl Conductor {
         /// Spawn all admin interface tasks, register them with the TaskManager,
-        /// and modify the conductor accordingly, based on the config passed in
+        /// and modify the conductor accordingly, based on the config passed in.
+        ///
+        /// Returns the ports of the websocket interfaces.
         #[tracing::instrument(skip_all)]
         pub async fn add_admin_interfaces(
             self: Arc<Self>,
@@ -422,6 +524,7 @@ mod interface_impls {
 
             // Closure to process each admin config item
             let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
+                let conductor = self.clone();
                 let admin_api = admin_api.clone();
                 let tm = tm.clone();
                 async move {
@@ -429,17 +532,31 @@ mod interface_impls {
                         InterfaceDriver::Websocket {
                             port,
                             allowed_origins,
+                            tls,
                         } => {
-                            let listener = spawn_websocket_listener(port, allowed_origins).await?;
+                            let tls = conductor.interface_tls_config(tls.as_ref()).await?;
+                            let listener =
+                                spawn_websocket_listener(port, allowed_origins, tls).await?;
                             let port = listener.local_addrs()?[0].port();
                             spawn_admin_interface_tasks(
                                 tm.clone(),
                                 listener,
                                 admin_api.clone(),
-                                port,
+                                &format!("port {}", port),
+                            );
+
+                            InterfaceResult::Ok(Some(port))
+                        }
+                        InterfaceDriver::UnixSocket { path, mode } => {
+                            let listener = spawn_unix_socket_listener(&path, mode).await?;
+                            spawn_admin_interface_tasks(
+                                tm.clone(),
+                                listener,
+                                admin_api.clone(),
+                                &format!("socket {}", path.display()),
                             );
 
-                            InterfaceResult::Ok(port)
+                            InterfaceResult::Ok(None)
                         }
                     }
                 }
@@ -453,7 +570,7 @@ mod interface_impls {
                     .into_iter()
                     .collect();
             // Exit if the admin interfaces fail to be created
-            let ports = ports.map_err(Box::new)?;
+            let ports: Vec<u16> = ports.map_err(Box::new)?.into_iter().flatten().collect();
 
             for p in &ports {
                 self.add_admin_port(*p);
@@ -472,6 +589,22 @@ mod interface_impls {
             port: either::Either<u16, AppInterfaceId>,
             allowed_origins: AllowedOrigins,
             installed_app_id: Option<InstalledAppId>,
+        ) -> ConductorResult<u16> {
+            self.add_app_interface_with_tls(port, allowed_origins, None, installed_app_id, None)
+                .await
+        }
+
+        /// Like [`Conductor::add_app_interface`], but the interface only accepts
+        /// TLS connections if `tls` is set, and keeps a replay buffer of signals
+        /// for each app if `signal_replay_buffer_size` is set.
+        #[tracing::instrument(skip_all)]
+        pub async fn add_app_interface_with_tls(
+            self: Arc<Self>,
+            port: either::Either<u16, AppInterfaceId>,
+            allowed_origins: AllowedOrigins,
+            tls: Option<InterfaceTls>,
+            installed_app_id: Option<InstalledAppId>,
+            signal_replay_buffer_size: Option<usize>,
         ) -> ConductorResult<u16> {
             let interface_id = match port {
                 either::Either::Left(port) => AppInterfaceId::new(port),
@@ -482,20 +615,29 @@ mod interface_impls {
             let app_api = AppInterfaceApi::new(self.clone());
 
             let tm = self.task_manager();
+            let tls_config = self
+                .interface_tls_config(tls.as_ref())
+                .await
+                .map_err(Box::new)?;
 
             // TODO: RELIABILITY: Handle this task by restarting it if it fails and log the error
             let port = spawn_app_interface_task(
                 tm.clone(),
                 port,
                 allowed_origins.clone(),
+                tls_config,
                 installed_app_id.clone(),
                 app_api,
                 self.app_broadcast.clone(),
+                signal_replay_buffer_size,
             )
             .await
             .map_err(Box::new)?;
 
-            let config = AppInterfaceConfig::websocket(port, allowed_origins, installed_app_id);
+            let config = AppInterfaceConfig {
+                signal_replay_buffer_size,
+                ..AppInterfaceConfig::websocket(port, allowed_origins, tls, installed_app_id)
+            };
             self.update_state(|mut state| {
                 state.app_interfaces.insert(interface_id, config);
 
@@ -506,6 +648,76 @@ mod interface_impls {
             Ok(port)
         }
 
+        /// Spawn a new app interface task listening on a Unix domain socket,
+        /// register it with the TaskManager, and modify the conductor accordingly.
+        ///
+        /// If no interface id is given, a new one is created.
+        #[tracing::instrument(skip_all)]
+        pub async fn add_app_unix_socket_interface(
+            self: Arc<Self>,
+            interface_id: Option<AppInterfaceId>,
+            path: PathBuf,
+            mode: u32,
+            installed_app_id: Option<InstalledAppId>,
+            signal_replay_buffer_size: Option<usize>,
+        ) -> ConductorResult<()> {
+            let interface_id = interface_id.unwrap_or_default();
+            debug!("Attaching interface at {:?}", path);
+            let app_api = AppInterfaceApi::new(self.clone());
+
+            spawn_app_unix_socket_interface_task(
+                self.task_manager(),
+                &path,
+                mode,
+                installed_app_id.clone(),
+                app_api,
+                self.app_broadcast.clone(),
+                signal_replay_buffer_size,
+            )
+            .await
+            .map_err(Box::new)?;
+
+            let config = AppInterfaceConfig {
+                signal_replay_buffer_size,
+                ..AppInterfaceConfig::unix_socket(path, mode, installed_app_id)
+            };
+            self.update_state(|mut state| {
+                state.app_interfaces.insert(interface_id, config);
+
+                Ok(state)
+            })
+            .await?;
+            Ok(())
+        }
+
+        /// Load the certificate of an interface which is served over TLS.
+        ///
+        /// A lair certificate is created if it doesn't exist yet.
+        async fn interface_tls_config(
+            &self,
+            tls: Option<&InterfaceTls>,
+        ) -> InterfaceResult<Option<TlsServerConfig>> {
+            let config = match tls {
+                None => return Ok(None),
+                Some(InterfaceTls::Pem {
+                    cert_path,
+                    key_path,
+                }) => TlsServerConfig::from_pem_files(cert_path, key_path)?,
+                Some(InterfaceTls::Lair { tag, cert_path }) => {
+                    let (_digest, cert, priv_key) = self
+                        .keystore()
+                        .get_or_create_tls_cert_by_tag(tag.as_str().into())
+                        .await
+                        .map_err(|e| InterfaceError::Other(e.to_string()))?;
+                    if let Some(cert_path) = cert_path {
+                        std::fs::write(cert_path, &cert)?;
+                    }
+                    TlsServerConfig::from_der(vec![cert.to_vec()], priv_key.read_lock().to_vec())?
+                }
+            };
+            Ok(Some(config))
+        }
+
         /// Returns a port which is guaranteed to have a websocket listener with an Admin interface
         /// on it. Useful for specifying port 0 and letting the OS choose a free port.
         pub fn get_arbitrary_admin_websocket_port(&self) -> Option<u16> {
@@ -521,28 +733,51 @@ mod interface_impls {
                 .app_interfaces
                 .values()
                 .map(|config| AppInterfaceInfo {
-                    port: config.driver.port(),
+                    port: config.driver.port().unwrap_or(0),
+                    socket_path: config.driver.socket_path().cloned(),
                     allowed_origins: config.driver.allowed_origins().clone(),
+                    tls: config.driver.tls().is_some(),
                     installed_app_id: config.installed_app_id.clone(),
+                    signal_replay_buffer_size: config.signal_replay_buffer_size,
                 })
                 .collect())
         }
 
         /// Start all app interfaces currently in state.
         /// This should only be run at conductor initialization.
-        #[allow(irrefutable_let_patterns)]
         #[tracing::instrument(skip_all)]
         pub(crate) async fn startup_app_interfaces(self: Arc<Self>) -> ConductorResult<()> {
             for (id, config) in &self.get_state().await?.app_interfaces {
                 debug!("Starting up app interface: {:?}", id);
-                let _ = self
-                    .clone()
-                    .add_app_interface(
-                        either::Right(id.clone()),
-                        config.driver.allowed_origins().clone(),
-                        config.installed_app_id.clone(),
-                    )
-                    .await?;
+                match &config.driver {
+                    InterfaceDriver::Websocket {
+                        allowed_origins,
+                        tls,
+                        ..
+                    } => {
+                        let _ = self
+                            .clone()
+                            .add_app_interface_with_tls(
+                                either::Right(id.clone()),
+                                allowed_origins.clone(),
+                                tls.clone(),
+                                config.installed_app_id.clone(),
+                                config.signal_replay_buffer_size,
+                            )
+                            .await?;
+                    }
+                    InterfaceDriver::UnixSocket { path, mode } => {
+                        self.clone()
+                            .add_app_unix_socket_interface(
+                                Some(id.clone()),
+                                path.clone(),
+                                *mode,
+                                config.installed_app_id.clone(),
+                                config.signal_replay_buffer_size,
+                            )
+                            .await?;
+                    }
+                }
             }
             Ok(())
         }
@@ -800,6 +1035,10 @@ mod dna_impls {
                 // ribosome for dna is already registered in store
                 return Ok(());
             }
+            // DNAs which weren't built from a bundle haven't been checked yet
+            if let Some(bucket_id) = dna.dna_def().never_draining_rate_limit() {
+                return Err(DnaError::RateLimitNeverDrains(bucket_id).into());
+            }
             let ribosome = RealRibosome::new(dna, self.wasmer_module_cache.clone()).await?;
             let entry_defs = self.register_dna_wasm(ribosome.clone()).await?;
 
@@ -820,14 +1059,17 @@ mod network_impls {
     use rusqlite::params;
 
     use holochain_conductor_api::{
-        CellInfo, DnaStorageInfo, NetworkInfo, StorageBlob, StorageInfo,
+        CellInfo, DnaStorageInfo, NetworkInfo, StorageBlob, StorageInfo, WarrantInfo,
     };
     use holochain_p2p::HolochainP2pSender;
     use holochain_sqlite::stats::{get_size_on_disk, get_used_size};
     use holochain_zome_types::block::Block;
+    use holochain_zome_types::block::BlockTarget;
     use holochain_zome_types::block::BlockTargetId;
+    use holochain_zome_types::block::CellBlockReason;
     use kitsune_p2p::KitsuneAgent;
     use kitsune_p2p::KitsuneBinType;
+    use strum::IntoEnumIterator;
 
     use crate::conductor::api::error::{
         zome_call_response_to_conductor_api_result, ConductorApiError,
@@ -1150,6 +1392,120 @@ mod network_impls {
             }))
         }
 
+        /// List the warrants held for a DNA, or for every DNA with a space on this conductor.
+        pub(crate) async fn list_warrants(
+            &self,
+            dna_hash: Option<DnaHash>,
+        ) -> ConductorResult<Vec<WarrantInfo>> {
+            let dna_hashes = match dna_hash {
+                Some(dna_hash) => vec![dna_hash],
+                None => self.spaces.get_from_spaces(|s| (*s.dna_hash).clone()),
+            };
+
+            let mut warrants = Vec::new();
+            for dna_hash in dna_hashes {
+                for (op_hash, warrant_op, validation_status) in
+                    self.query_warrant_ops(&dna_hash).await?
+                {
+                    let warrantee =
+                        CellId::new(dna_hash.clone(), warrant_op.warrant.warrantee().clone());
+                    let warrantee_blocked = self
+                        .spaces
+                        .is_blocked(BlockTargetId::Cell(warrantee), Timestamp::now())
+                        .await?;
+                    warrants.push(WarrantInfo {
+                        dna_hash: dna_hash.clone(),
+                        op_hash,
+                        warrant_op,
+                        validation_status,
+                        warrantee_blocked,
+                    });
+                }
+            }
+            Ok(warrants)
+        }
+
+        /// Lift the block which a warrant put on the agent it was issued against.
+        ///
+        /// The warrant itself is kept, so it is still listed by [`Conductor::list_warrants`].
+        pub(crate) async fn unblock_warrantee(
+            &self,
+            dna_hash: DnaHash,
+            op_hash: DhtOpHash,
+        ) -> ConductorResult<()> {
+            let warrant_op = self
+                .query_warrant_ops(&dna_hash)
+                .await?
+                .into_iter()
+                .find_map(|(hash, warrant_op, _)| (hash == op_hash).then_some(warrant_op))
+                .ok_or_else(|| {
+                    ConductorError::other(format!(
+                        "No warrant with op hash {} is held for DNA {}",
+                        op_hash, dna_hash
+                    ))
+                })?;
+            let warrantee = CellId::new(dna_hash, warrant_op.warrant.warrantee().clone());
+            let interval = InclusiveTimestampInterval::try_new(Timestamp::MIN, Timestamp::MAX)
+                .map_err(ConductorError::other)?;
+            self.spaces
+                .unblock(Block::new(
+                    BlockTarget::Cell(warrantee, CellBlockReason::Warrant(op_hash)),
+                    interval,
+                ))
+                .await?;
+            Ok(())
+        }
+
+        /// Get the warrant ops of every kind held in the DHT database of a DNA.
+        async fn query_warrant_ops(
+            &self,
+            dna_hash: &DnaHash,
+        ) -> ConductorResult<Vec<(DhtOpHash, WarrantOp, Option<ValidationStatus>)>> {
+            let warrant_types = WarrantOpType::iter()
+                .map(|warrant_type| format!("'{}'", warrant_type))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let ops = self
+                .spaces
+                .dht_db(dna_hash)?
+                .read_async(move |txn| -> StateQueryResult<_> {
+                    let mut stmt = txn.prepare(&format!(
+                        "
+                        SELECT
+                        DhtOp.hash AS dht_hash,
+                        DhtOp.type AS dht_type,
+                        DhtOp.validation_status,
+                        Action.blob AS action_blob,
+                        Action.author AS author
+                        FROM DhtOp
+                        JOIN Action ON DhtOp.action_hash = Action.hash
+                        WHERE DhtOp.type IN ({})
+                        ORDER BY DhtOp.authored_timestamp
+                        ",
+                        warrant_types
+                    ))?;
+                    let ops = stmt
+                        .query_and_then([], |row| {
+                            let op_hash: DhtOpHash = row.get("dht_hash")?;
+                            let validation_status: Option<ValidationStatus> =
+                                row.get("validation_status")?;
+                            let op =
+                                holochain_state::query::map_sql_dht_op(false, "dht_type", row)?;
+                            StateQueryResult::Ok((op_hash, op, validation_status))
+                        })?
+                        .collect::<StateQueryResult<Vec<_>>>()?;
+                    Ok(ops)
+                })
+                .await?;
+            Ok(ops
+                .into_iter()
+                .filter_map(|(op_hash, op, validation_status)| match op {
+                    DhtOp::WarrantOp(warrant_op) => Some((op_hash, *warrant_op, validation_status)),
+                    DhtOp::ChainOp(_) => None,
+                })
+                .collect())
+        }
+
         #[instrument(skip(self))]
         pub(crate) async fn dispatch_holochain_p2p_event(
             &self,
@@ -1356,6 +1712,89 @@ mod network_impls {
             Ok(cell.call_zome(call, None).await?)
         }
 
+        /// Invoke a zome function on a Cell, and also return the wasm metering
+        /// points the call consumed.
+        pub async fn call_zome_metered(
+            &self,
+            call: ZomeCall,
+        ) -> ConductorApiResult<(ZomeCallResult, u64)> {
+            let cell = self.cell_by_id(&call.cell_id).await?;
+            Ok(cell.call_zome_metered(call).await?)
+        }
+
+        /// Invoke several zome functions, see [`AppRequest::CallZomeBatch`].
+        ///
+        /// Returns the result of every call in order, or `None` for the calls
+        /// of an atomic batch which were not made because an earlier call failed.
+        pub async fn call_zome_batch(
+            &self,
+            calls: Vec<ZomeCall>,
+            atomic: bool,
+        ) -> ConductorApiResult<Vec<Option<ConductorApiResult<ZomeCallResult>>>> {
+            if !atomic {
+                return Ok(future::join_all(
+                    calls
+                        .into_iter()
+                        .map(|call| async move { Some(self.call_zome(call).await) }),
+                )
+                .await);
+            }
+            let cell_id = match calls.first() {
+                Some(call) => call.cell_id.clone(),
+                None => return Ok(Vec::new()),
+            };
+            if let Some(call) = calls.iter().find(|call| call.cell_id != cell_id) {
+                return Err(ConductorApiError::AtomicBatchCellMismatch(
+                    cell_id,
+                    call.cell_id.clone(),
+                ));
+            }
+            let cell = self.cell_by_id(&cell_id).await?;
+            Ok(cell
+                .call_zome_batch_atomic(calls)
+                .await?
+                .into_iter()
+                .map(|result| result.map(|result| result.map_err(Into::into)))
+                .collect())
+        }
+
+        /// Check a zome call made over an app interface against the zome call
+        /// policy of the conductor, if there is one.
+        ///
+        /// The policy is only asked about calls which are signed by their provenance,
+        /// so that it can tell callers apart by it. The other calls are refused by
+        /// the cell anyway.
+        pub async fn authorize_app_zome_call(
+            &self,
+            installed_app_id: &InstalledAppId,
+            call: &ZomeCall,
+        ) -> ConductorApiResult<()> {
+            use holochain_p2p::AgentPubKeyExt;
+
+            let Some(policy) = &self.zome_call_policy else {
+                return Ok(());
+            };
+            let data_to_sign = ZomeCallUnsigned::from(call.clone())
+                .data_to_sign()
+                .map_err(crate::conductor::api::error::SerializationError::from)?;
+            if !call
+                .provenance
+                .verify_signature_raw(&call.signature, data_to_sign)
+                .await?
+            {
+                return Ok(());
+            }
+            match policy.authorize(installed_app_id, call) {
+                ZomeCallPolicyDecision::Allow => Ok(()),
+                ZomeCallPolicyDecision::Deny(reason) => {
+                    Err(ConductorApiError::ZomeCallDenied(reason))
+                }
+                ZomeCallPolicyDecision::RateLimited(retry_after) => {
+                    Err(ConductorApiError::ZomeCallRateLimited(retry_after))
+                }
+            }
+        }
+
         pub(crate) async fn call_zome_with_workspace(
             &self,
             call: ZomeCall,
@@ -1366,6 +1805,17 @@ mod network_impls {
             Ok(cell.call_zome(call, Some(workspace_lock)).await?)
         }
 
+        /// Make a zome call for a zome call of another cell, as part of its call chain.
+        pub(crate) async fn call_zome_from_other_cell(
+            &self,
+            call: ZomeCall,
+            slept: holochain_state::host_fn_workspace::SleptTime,
+        ) -> ConductorApiResult<ZomeCallResult> {
+            debug!(cell_id = ?call.cell_id);
+            let cell = self.cell_by_id(&call.cell_id).await?;
+            Ok(cell.call_zome_from_other_cell(call, slept).await?)
+        }
+
         /// Make a zome call with deserialization and some error unwrapping built in
         pub async fn easy_call_zome<I, O, Z>(
             &self,
@@ -1455,7 +1905,12 @@ mod app_impls {
             } = payload;
 
             let bundle = {
-                let original_bundle = source.resolve().await?;
+                let original_bundle = match source {
+                    AppBundleSource::Url { url, app_hash } => {
+                        self.fetch_app_bundle(&url, &app_hash).await?
+                    }
+                    source => source.resolve().await?,
+                };
                 if let Some(network_seed) = network_seed {
                     let mut manifest = original_bundle.manifest().to_owned();
                     manifest.set_network_seed(network_seed);
@@ -1473,14 +1928,28 @@ mod app_impls {
             let local_dnas = self
                 .ribosome_store()
                 .share_ref(|store| bundle.get_all_dnas_from_store(store));
+            let state = self.get_state().await?;
+
+            // Roles may only use the cells which other apps created and share,
+            // not cells which are themselves used from another app
+            let shared_cells: HashSet<_> = state
+                .installed_apps()
+                .values()
+                .flat_map(|app| app.shared_cells())
+                .cloned()
+                .collect();
             let ops = bundle
-                .resolve_cells(&local_dnas, agent_key.clone(), membrane_proofs)
+                .resolve_cells(
+                    &local_dnas,
+                    agent_key.clone(),
+                    membrane_proofs,
+                    &shared_cells,
+                )
                 .await?;
 
             let cells_to_create = ops.cells_to_create();
 
             // check if cells_to_create contains a cell identical to an existing one
-            let state = self.get_state().await?;
             let all_cells: HashSet<_> = state
                 .installed_apps()
                 .values()
@@ -1517,7 +1986,9 @@ mod app_impls {
                 // Return the result, which be may an error if no_rollback was specified
                 genesis_result.map(|()| stopped_app)
             } else if let Err(err) = genesis_result {
-                // Rollback created cells on error
+                // Rollback created cells on error. Only the cells which this app would
+                // have created ran genesis, so the existing cells it uses from other apps
+                // are left alone, and so are those apps.
                 self.remove_cells(&cell_ids).await;
                 Err(err)
             } else {
@@ -1525,6 +1996,82 @@ mod app_impls {
             }
         }
 
+        /// Install an app whose bundle is fetched by hash from the app store service
+        #[tracing::instrument(skip_all)]
+        pub async fn install_app_from_store(
+            self: Arc<Self>,
+            payload: InstallAppFromStorePayload,
+        ) -> ConductorResult<StoppedApp> {
+            let InstallAppFromStorePayload {
+                app_hash,
+                agent_key,
+                installed_app_id,
+                membrane_proofs,
+                network_seed,
+            } = payload;
+
+            let app_store = self.services.share_ref(|s| {
+                s.as_ref()
+                    .expect("Conductor services not yet initialized")
+                    .app_store
+                    .clone()
+            });
+            let bundle = app_store
+                .get_app_bundle(app_hash.clone())
+                .await?
+                .ok_or(ConductorError::AppBundleNotInStore(app_hash))?;
+
+            self.install_app_bundle(InstallAppPayload {
+                source: AppBundleSource::Bundle(bundle),
+                agent_key,
+                installed_app_id,
+                membrane_proofs,
+                network_seed,
+                #[cfg(feature = "chc")]
+                ignore_genesis_failure: false,
+            })
+            .await
+        }
+
+        /// Download an app bundle, which must have the given hash.
+        ///
+        /// Downloaded bundles are cached in the data directory by their hash,
+        /// so that each bundle is only downloaded once.
+        async fn fetch_app_bundle(
+            &self,
+            url: &str,
+            app_hash: &AppHash,
+        ) -> ConductorResult<AppBundle> {
+            let cache_dir = self
+                .config
+                .data_root_path
+                .as_ref()
+                .ok_or(ConductorError::NoDataRootPath)?
+                .join(holochain_conductor_api::conductor::paths::APP_BUNDLES_DIRECTORY);
+            let path = cache_dir.join(format!("{}.happ", app_hash));
+
+            match tokio::fs::read(&path).await {
+                Ok(bytes) => match AppBundle::decode_checked(app_hash, &bytes) {
+                    Ok(bundle) => return Ok(bundle),
+                    Err(e) => warn!(?path, ?e, "Discarding invalid cached app bundle"),
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+                Err(e) => return Err(e.into()),
+            }
+
+            let bytes = mr_bundle::resolve_remote(url).await?;
+            let bundle = AppBundle::decode_checked(app_hash, &bytes)?;
+
+            // Write to a temporary file first, so that no partial bundle is cached.
+            // Its name is unique, as the same bundle may be installed concurrently.
+            tokio::fs::create_dir_all(&cache_dir).await?;
+            let tmp_path = cache_dir.join(format!("{}.{}.tmp", app_hash, nanoid::nanoid!()));
+            tokio::fs::write(&tmp_path, bytes.inner()).await?;
+            tokio::fs::rename(tmp_path, &path).await?;
+
+            Ok(bundle)
+        }
+
         /// Uninstall an app
         #[tracing::instrument(skip(self))]
         pub async fn uninstall_app(
@@ -1532,6 +2079,16 @@ mod app_impls {
             installed_app_id: &InstalledAppId,
         ) -> ConductorResult<()> {
             let self_clone = self.clone();
+
+            // The cells of the app must stay around for the apps which use them
+            let dependents = self.get_state().await?.dependent_apps(installed_app_id);
+            if !dependents.is_empty() {
+                return Err(ConductorError::AppHasDependents(
+                    installed_app_id.clone(),
+                    dependents,
+                ));
+            }
+
             let app = self.remove_app_from_db(installed_app_id).await?;
             tracing::debug!(msg = "Removed app from db.", app = ?app);
 
@@ -1588,6 +2145,9 @@ mod app_impls {
         }
 
         /// Get the IDs of all active installed Apps which use this Cell
+        ///
+        /// This includes both the App which created the Cell and the Apps which use it
+        /// as an existing Cell, so that all of them are stopped when the Cell fails.
         #[tracing::instrument(skip_all)]
         pub async fn list_running_apps_for_dependent_cell_id(
             &self,
@@ -1664,6 +2224,187 @@ mod app_impls {
                 }
             }
         }
+
+        /// Migrate the agent of an app role to a new DNA.
+        ///
+        /// The old chain is closed and the new one opened, with the
+        /// `migrate_agent` callback run on both sides. The previous cell is
+        /// stopped, but its databases are left in place. Roles which use the
+        /// cell of another app, or whose cell other apps use, can't be migrated.
+        ///
+        /// Every step which can fail happens before the role is reassigned:
+        /// genesis of the new cell, opening the new chain and closing the old
+        /// one. If any of them fails, the role keeps its old cell. The new chain
+        /// is opened first, so that the old chain is only closed once the new
+        /// DNA has accepted the migration. Steps which were already done are
+        /// skipped, so the migration can be resumed by calling this again.
+        ///
+        /// # Returns
+        ///
+        /// The id of the cell the role is now assigned to.
+        pub async fn migrate_agent(
+            self: Arc<Self>,
+            installed_app_id: &InstalledAppId,
+            role_name: &RoleName,
+            new_dna_hash: DnaHash,
+            membrane_proof: Option<MembraneProof>,
+        ) -> ConductorResult<CellId> {
+            let state = self.get_state().await?;
+            let role = state.get_app(installed_app_id)?.role(role_name)?;
+            // A shared cell belongs to the app which created it, and the apps using
+            // it would be left with a closed chain.
+            if role.is_existing() {
+                return Err(ConductorError::MigrateAgentError(format!(
+                    "role '{role_name}' uses a cell of another app"
+                )));
+            }
+            let old_cell_id = role.provisioned_cell().cloned().ok_or_else(|| {
+                ConductorError::MigrateAgentError(format!(
+                    "role '{role_name}' has no provisioned cell"
+                ))
+            })?;
+            let dependents: Vec<_> = state
+                .apps_by_cell()
+                .remove(&old_cell_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|id| id != installed_app_id)
+                .collect();
+            if !dependents.is_empty() {
+                return Err(ConductorError::MigrateAgentError(format!(
+                    "role '{role_name}' has a cell which these apps use: {dependents:?}"
+                )));
+            }
+            if *old_cell_id.dna_hash() == new_dna_hash {
+                return Err(ConductorError::MigrateAgentError(format!(
+                    "role '{role_name}' already uses DNA {new_dna_hash}"
+                )));
+            }
+            // The new DNA must be registered.
+            self.get_ribosome(&new_dna_hash)?;
+            let new_cell_id = CellId::new(new_dna_hash.clone(), old_cell_id.agent_pubkey().clone());
+            if state
+                .installed_apps()
+                .iter()
+                .flat_map(|(_, app)| app.all_cells())
+                .any(|cell_id| *cell_id == new_cell_id)
+            {
+                return Err(ConductorError::AppError(AppError::DuplicateCellId(
+                    new_cell_id,
+                )));
+            }
+
+            // Run genesis for the new cell, which checks the membrane proof.
+            // Genesis is skipped if it already ran.
+            genesis_cells(self.clone(), vec![(new_cell_id.clone(), membrane_proof)]).await?;
+
+            // Open the new chain first. The new cell is not assigned to the role
+            // yet, so a refusal by the new DNA leaves the old chain open and the
+            // role with its old cell.
+            let opened = self
+                .chain_has_action(&new_cell_id, ActionType::OpenChain, |action| {
+                    matches!(
+                        action,
+                        Action::OpenChain(open) if open.prev_dna_hash == *old_cell_id.dna_hash()
+                    )
+                })
+                .await?;
+            if !opened {
+                self.open_migrated_chain(&new_cell_id, old_cell_id.dna_hash().clone())
+                    .await?;
+            }
+
+            // Close the old chain. The callback of the old DNA may still refuse
+            // the migration here, in which case the role is left as it is.
+            let closed = self
+                .chain_has_action(&old_cell_id, ActionType::CloseChain, |action| {
+                    matches!(
+                        action,
+                        Action::CloseChain(close) if close.new_dna_hash == new_dna_hash
+                    )
+                })
+                .await?;
+            if !closed {
+                self.cell_by_id(&old_cell_id)
+                    .await?
+                    .migrate_agent(MigrateAgent::Close, new_dna_hash.clone())
+                    .await?;
+            }
+
+            // Reassign the role to the new cell.
+            self.update_state({
+                let installed_app_id = installed_app_id.clone();
+                let role_name = role_name.clone();
+                move |mut state| {
+                    state
+                        .get_app_mut(&installed_app_id)?
+                        .migrate_role(&role_name, new_dna_hash)?;
+                    Ok(state)
+                }
+            })
+            .await?;
+            self.remove_cells(&[old_cell_id.clone()]).await;
+            self.create_and_add_initialized_cells_for_running_apps(Some(installed_app_id))
+                .await?;
+
+            Ok(new_cell_id)
+        }
+
+        /// Whether the chain of a cell holds an action of the given type which matches.
+        async fn chain_has_action(
+            &self,
+            cell_id: &CellId,
+            action_type: ActionType,
+            predicate: impl Fn(&Action) -> bool,
+        ) -> ConductorResult<bool> {
+            let space = self.get_or_create_space(cell_id.dna_hash())?;
+            let source_chain: source_chain::SourceChain = space
+                .source_chain(self.keystore().clone(), cell_id.agent_pubkey().clone())
+                .await?;
+            let records = source_chain
+                .query(ChainQueryFilter::new().action_type(action_type))
+                .await?;
+            Ok(records.iter().any(|record| predicate(record.action())))
+        }
+
+        /// Open the chain of a migrated cell which is not running yet.
+        ///
+        /// The `migrate_agent` callback of the new DNA is run and, if it passes,
+        /// an [`OpenChain`] action pointing at the previous DNA is committed.
+        async fn open_migrated_chain(
+            &self,
+            cell_id: &CellId,
+            prev_dna_hash: DnaHash,
+        ) -> ConductorResult<()> {
+            let ribosome = self.get_ribosome(cell_id.dna_hash())?;
+            let workspace = self
+                .get_or_create_space(cell_id.dna_hash())?
+                .source_chain_workspace(
+                    self.keystore().clone(),
+                    cell_id.agent_pubkey().clone(),
+                    Arc::new(ribosome.dna_def().as_content().clone()),
+                )
+                .await?;
+            let network = self.holochain_p2p.to_dna(
+                cell_id.dna_hash().clone(),
+                self.chc(self.keystore().clone(), cell_id),
+            );
+            // The cell's integration workflow also runs when the cell starts.
+            let integrate_dht_ops_trigger = self
+                .get_queue_consumer_workflows()
+                .integration_trigger(Arc::new(cell_id.dna_hash().clone()))
+                .unwrap_or_else(|| TriggerSender::new().0);
+            let args = MigrateAgentWorkflowArgs {
+                ribosome,
+                migrate_agent: MigrateAgent::Open,
+                other_dna_hash: prev_dna_hash,
+                integrate_dht_ops_trigger,
+            };
+            match migrate_agent_workflow(workspace, network, args).await? {
+                MigrateAgentResult::Pass => Ok(()),
+                r => Err(CellError::MigrateAgentFailed(r).into()),
+            }
+        }
     }
 }
 
@@ -2139,7 +2880,9 @@ mod state_impls {
         where
             F: Send + FnOnce(ConductorState) -> ConductorResult<ConductorState> + 'static,
         {
-            self.spaces.update_state(f).await
+            let state = self.spaces.update_state(f).await?;
+            self.app_broadcast.set_cell_apps(state.apps_by_cell());
+            Ok(state)
         }
 
         /// Update the internal state with a pure function mapping old state to new,
@@ -2155,7 +2898,9 @@ mod state_impls {
             O: Send + 'static,
         {
             self.check_running()?;
-            self.spaces.update_state_prime(f).await
+            let (state, output) = self.spaces.update_state_prime(f).await?;
+            self.app_broadcast.set_cell_apps(state.apps_by_cell());
+            Ok((state, output))
         }
     }
 }
@@ -2201,12 +2946,24 @@ mod scheduler_impls {
             let scheduler_handle = self.clone();
             self.set_scheduler(tokio::task::spawn(async move {
                 let mut interval = tokio::time::interval(interval_period);
+                let dispatches = Arc::new(tokio::sync::Semaphore::new(
+                    MAX_CONCURRENT_SCHEDULER_DISPATCHES,
+                ));
                 loop {
                     interval.tick().await;
-                    scheduler_handle
-                        .clone()
-                        .dispatch_scheduled_fns(Timestamp::now())
-                        .await;
+                    let Ok(permit) = dispatches.clone().try_acquire_owned() else {
+                        tracing::debug!("skipping scheduler tick, too many dispatches in progress");
+                        continue;
+                    };
+                    // Dispatch in the background so that a long running
+                    // scheduled function doesn't hold back the others.
+                    let scheduler_handle = scheduler_handle.clone();
+                    tokio::task::spawn(async move {
+                        scheduler_handle
+                            .dispatch_scheduled_fns(Timestamp::now())
+                            .await;
+                        drop(permit);
+                    });
                 }
             }));
 
@@ -2230,6 +2987,88 @@ mod scheduler_impls {
                 .map(|cell_arc| cell_arc.dispatch_scheduled_fns(now));
             futures::future::join_all(tasks).await;
         }
+
+        /// List the functions scheduled for a cell, the next due first.
+        pub(crate) async fn list_scheduled_functions(
+            &self,
+            cell_id: &CellId,
+        ) -> ConductorResult<Vec<ScheduledFnInfo>> {
+            let author = cell_id.agent_pubkey().clone();
+            Ok(self
+                .cell_by_id(cell_id)
+                .await?
+                .get_or_create_authored_db()?
+                .read_async(move |txn| holochain_state::schedule::scheduled_fns(&txn, &author))
+                .await?)
+        }
+
+        /// Run a scheduled function of a cell right away and wait for the run to finish.
+        pub(crate) async fn trigger_scheduled_function(
+            &self,
+            cell_id: &CellId,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+        ) -> ConductorResult<ScheduledFnRun> {
+            let cell = self.cell_by_id(cell_id).await?;
+            cell.trigger_scheduled_fn(ScheduledFn::new(zome_name.clone(), fn_name.clone()))
+                .await?
+                .ok_or_else(|| {
+                    ConductorError::FunctionNotScheduled(cell_id.clone(), zome_name, fn_name)
+                })
+        }
+
+        /// Remove a function from the schedule of a cell.
+        pub(crate) async fn unschedule_function(
+            &self,
+            cell_id: &CellId,
+            zome_name: ZomeName,
+            fn_name: FunctionName,
+        ) -> ConductorResult<()> {
+            let author = cell_id.agent_pubkey().clone();
+            let scheduled_fn = ScheduledFn::new(zome_name.clone(), fn_name.clone());
+            let unscheduled = self
+                .cell_by_id(cell_id)
+                .await?
+                .get_or_create_authored_db()?
+                .write_async(move |txn| unschedule_fn(txn, &author, &scheduled_fn))
+                .await?;
+            if unscheduled {
+                Ok(())
+            } else {
+                Err(ConductorError::FunctionNotScheduled(
+                    cell_id.clone(),
+                    zome_name,
+                    fn_name,
+                ))
+            }
+        }
+
+        /// The most recent runs of the scheduled functions of a cell, newest first.
+        /// Without a limit, as many runs as are kept per function are listed.
+        pub(crate) async fn list_scheduled_function_runs(
+            &self,
+            cell_id: &CellId,
+            zome_name: Option<ZomeName>,
+            fn_name: Option<FunctionName>,
+            limit: Option<u32>,
+        ) -> ConductorResult<Vec<ScheduledFnRun>> {
+            let limit = limit.unwrap_or(holochain_state::mutations::SCHEDULED_FN_RUN_HISTORY_LIMIT);
+            let author = cell_id.agent_pubkey().clone();
+            Ok(self
+                .cell_by_id(cell_id)
+                .await?
+                .get_or_create_authored_db()?
+                .read_async(move |txn| {
+                    holochain_state::schedule::scheduled_fn_runs(
+                        &txn,
+                        &author,
+                        zome_name.as_ref(),
+                        fn_name.as_ref(),
+                        limit,
+                    )
+                })
+                .await?)
+        }
     }
 }
 
@@ -2363,6 +3202,22 @@ mod misc_impls {
             &self,
             cell_id: &CellId,
             dht_ops_cursor: Option<u64>,
+        ) -> ConductorApiResult<FullStateDump> {
+            self.dump_full_cell_state_filtered(cell_id, dht_ops_cursor, None, None)
+                .await
+        }
+
+        /// Full dump of the cell's state, only including the DHT ops which
+        /// match the filter, and at most `dht_ops_limit` of them.
+        ///
+        /// When paging with a limit, the source chain and the peers are only
+        /// dumped on the first page, the one without a cursor.
+        pub async fn dump_full_cell_state_filtered(
+            &self,
+            cell_id: &CellId,
+            dht_ops_cursor: Option<u64>,
+            dht_ops_filter: Option<DhtOpsFilter>,
+            dht_ops_limit: Option<u32>,
         ) -> ConductorApiResult<FullStateDump> {
             let authored_db =
                 self.get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())?;
@@ -2370,16 +3225,30 @@ mod misc_impls {
             let dna_hash = cell_id.dna_hash();
             let p2p_agents_db = self.spaces.p2p_agents_db(dna_hash)?;
 
-            let peer_dump =
-                p2p_agent_store::dump_state(p2p_agents_db.into(), Some(cell_id.clone())).await?;
-            let source_chain_dump =
-                source_chain::dump_state(authored_db.into(), cell_id.agent_pubkey().clone())
-                    .await?;
+            let (peer_dump, source_chain_dump) = if dht_ops_limit.is_some()
+                && dht_ops_cursor.is_some()
+            {
+                (None, None)
+            } else {
+                let peer_dump =
+                    p2p_agent_store::dump_state(p2p_agents_db.into(), Some(cell_id.clone()))
+                        .await?;
+                let source_chain_dump =
+                    source_chain::dump_state(authored_db.into(), cell_id.agent_pubkey().clone())
+                        .await?;
+                (Some(peer_dump), Some(source_chain_dump))
+            };
 
             let out = FullStateDump {
                 peer_dump,
                 source_chain_dump,
-                integration_dump: full_integration_dump(&dht_db, dht_ops_cursor).await?,
+                integration_dump: full_integration_dump(
+                    &dht_db,
+                    dht_ops_cursor,
+                    dht_ops_filter.unwrap_or_default(),
+                    dht_ops_limit,
+                )
+                .await?,
             };
             Ok(out)
         }
@@ -2440,6 +3309,67 @@ mod misc_impls {
             .await
         }
 
+        /// Export the source chain of a cell, together with the integrated DHT ops
+        /// authored by the cell's agent, as an archive signed by that agent.
+        ///
+        /// Ops authored by other agents which this conductor holds for the DNA are
+        /// left out, since they are not part of the cell and can be fetched again
+        /// from the network.
+        pub async fn export_cell(&self, cell_id: &CellId) -> ConductorApiResult<CellArchive> {
+            // Require that the cell is installed.
+            if let Err(ConductorError::CellMissing(_)) = self.cell_by_id(cell_id).await {
+                return Err(ConductorError::CellMissing(cell_id.clone()).into());
+            }
+
+            let space = self.get_or_create_space(cell_id.dna_hash())?;
+            let source_chain: source_chain::SourceChain = space
+                .source_chain(self.keystore().clone(), cell_id.agent_pubkey().clone())
+                .await?;
+            let records = source_chain
+                .query(ChainQueryFilter::new().include_entries(true))
+                .await?;
+            let mut dht_ops = space
+                .dht_db
+                .read_async(|txn| {
+                    query_dht_ops_from_statement(&txn, state_dump::DHT_OPS_INTEGRATED, None)
+                })
+                .await?;
+            dht_ops.retain(|op| &op.author() == cell_id.agent_pubkey());
+
+            let content = CellArchiveContent {
+                version: CELL_ARCHIVE_VERSION,
+                cell_id: cell_id.clone(),
+                exported_at: Timestamp::now(),
+                records,
+                dht_ops,
+            };
+            Ok(CellArchive::sign(self.keystore(), content).await?)
+        }
+
+        /// Import a cell archive produced by [`Conductor::export_cell`].
+        ///
+        /// The archived cell must already be installed in this conductor.
+        /// Its source chain is grafted onto the existing one, as with
+        /// [`Conductor::graft_records_onto_source_chain`], and the archived
+        /// DHT ops are validated and integrated as if they had been published.
+        pub async fn import_cell(
+            self: Arc<Self>,
+            archive: CellArchive,
+            validate: bool,
+        ) -> ConductorApiResult<()> {
+            let content = archive.open().await.map_err(ConductorError::from)?;
+            let cell_id = content.cell_id;
+            self.clone()
+                .graft_records_onto_source_chain(cell_id.clone(), validate, content.records)
+                .await?;
+            if !content.dht_ops.is_empty() {
+                self.spaces
+                    .handle_publish(cell_id.dna_hash(), false, false, content.dht_ops)
+                    .await?;
+            }
+            Ok(())
+        }
+
         /// Update coordinator zomes on an existing dna.
         pub async fn update_coordinators(
             &self,
@@ -2494,16 +3424,18 @@ mod accessor_impls {
         }
 
         /// Get a signal broadcast sender for a cell.
+        ///
+        /// The signals are sent to every app which contains the cell, which is more
+        /// than one app if other apps use the cell of the app which created it.
         pub async fn get_signal_tx(
             &self,
             cell_id: &CellId,
         ) -> ConductorResult<broadcast::Sender<Signal>> {
-            let app = self
-                .find_app_containing_cell(cell_id)
+            self.find_app_containing_cell(cell_id)
                 .await?
                 .ok_or_else(|| ConductorError::CellMissing(cell_id.clone()))?;
 
-            Ok(self.app_broadcast.create_send_handle(app.id().clone()))
+            Ok(self.app_broadcast.create_cell_send_handle(cell_id.clone()))
         }
 
         /// Instantiate a Ribosome for use with a DNA
@@ -2648,6 +3580,13 @@ mod authenticate_token_impls {
                 app_connection_auth.authenticate_token(token, app_id)
             })
         }
+
+        /// The [InstalledAppId] that a valid app interface authentication `token` was issued
+        /// for, without using the token.
+        pub fn peek_app_token(&self, token: &AppAuthenticationToken) -> Option<InstalledAppId> {
+            self.app_auth_token_store
+                .share_ref(|app_connection_auth| app_connection_auth.peek_token(token))
+        }
     }
 }
 
@@ -3174,33 +4113,103 @@ pub async fn integration_dump<Db: ReadAccess<DbKindDht>>(
 pub async fn full_integration_dump(
     vault: &DbRead<DbKindDht>,
     dht_ops_cursor: Option<u64>,
+    dht_ops_filter: DhtOpsFilter,
+    dht_ops_limit: Option<u32>,
 ) -> ConductorApiResult<FullIntegrationStateDump> {
+    // An empty page could never move the cursor forward.
+    if dht_ops_limit == Some(0) {
+        return Err(crate::conductor::api::error::ConductorApiError::other(
+            "dht_ops_limit must be greater than 0",
+        ));
+    }
     vault
         .read_async(move |txn| {
-            let integrated =
-                query_dht_ops_from_statement(&txn, state_dump::DHT_OPS_INTEGRATED, dht_ops_cursor)?;
-
-            let validation_limbo = query_dht_ops_from_statement(
-                &txn,
-                state_dump::DHT_OPS_IN_VALIDATION_LIMBO,
-                dht_ops_cursor,
-            )?;
+            let DhtOpsFilter {
+                op_types,
+                author,
+                authored_after,
+                authored_before,
+                validation_status,
+            } = dht_ops_filter;
+
+            let mut sql = state_dump::DHT_OPS.to_string();
+            let mut params: Vec<Box<dyn rusqlite::ToSql>> =
+                vec![Box::new(dht_ops_cursor.unwrap_or(0))];
+            if let Some(op_types) = op_types {
+                let placeholders = vec!["?"; op_types.len()].join(", ");
+                sql.push_str(&format!(" AND DhtOp.type IN ({})", placeholders));
+                params.extend(
+                    op_types
+                        .into_iter()
+                        .map(|t| Box::new(t) as Box<dyn rusqlite::ToSql>),
+                );
+            }
+            if let Some(author) = author {
+                sql.push_str(" AND Action.author = ?");
+                params.push(Box::new(author));
+            }
+            if let Some(authored_after) = authored_after {
+                sql.push_str(" AND DhtOp.authored_timestamp >= ?");
+                params.push(Box::new(authored_after));
+            }
+            if let Some(authored_before) = authored_before {
+                sql.push_str(" AND DhtOp.authored_timestamp < ?");
+                params.push(Box::new(authored_before));
+            }
+            if let Some(validation_status) = validation_status {
+                sql.push_str(" AND DhtOp.validation_status = ?");
+                params.push(Box::new(validation_status));
+            }
+            sql.push_str(" ORDER BY DhtOp.rowid");
+            if let Some(limit) = dht_ops_limit {
+                // One more than the limit, to know whether there are more ops.
+                sql.push_str(" LIMIT ?");
+                params.push(Box::new(u64::from(limit) + 1));
+            }
 
-            let integration_limbo = query_dht_ops_from_statement(
-                &txn,
-                state_dump::DHT_OPS_IN_INTEGRATION_LIMBO,
-                dht_ops_cursor,
-            )?;
+            let mut validation_limbo = Vec::new();
+            let mut integration_limbo = Vec::new();
+            let mut integrated = Vec::new();
+            let mut count = 0;
+            let mut last_rowid = 0;
+            let mut has_more = false;
+            let mut stmt = txn.prepare(&sql)?;
+            let mut rows = stmt.query(rusqlite::params_from_iter(params))?;
+            while let Some(row) = rows.next()? {
+                if dht_ops_limit.is_some_and(|limit| count >= limit) {
+                    has_more = true;
+                    break;
+                }
+                let op = holochain_state::query::map_sql_dht_op(false, "dht_type", row)?;
+                let when_integrated: Option<Timestamp> = row.get("when_integrated")?;
+                let validation_stage: Option<i64> = row.get("validation_stage")?;
+                // Same stages as the integration state dump.
+                if when_integrated.is_some() {
+                    integrated.push(op);
+                } else if validation_stage == Some(3) {
+                    integration_limbo.push(op);
+                } else {
+                    validation_limbo.push(op);
+                }
+                count += 1;
+                last_rowid = row.get("rowid")?;
+            }
 
-            let dht_ops_cursor = txn
-                .query_row(state_dump::DHT_OPS_ROW_ID, [], |row| row.get(0))
-                .unwrap_or(0);
+            // If there are more ops after the page, the cursor can only move
+            // up to the last op returned.
+            let dht_ops_cursor = if has_more {
+                last_rowid
+            } else {
+                txn.query_row(state_dump::DHT_OPS_ROW_ID, [], |row| row.get(0))
+                    .unwrap_or(0)
+            };
 
             ConductorApiResult::Ok(FullIntegrationStateDump {
                 validation_limbo,
                 integration_limbo,
                 integrated,
                 dht_ops_cursor,
+                has_more,
             })
         })
         .await
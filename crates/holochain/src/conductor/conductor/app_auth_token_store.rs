@@ -100,6 +100,18 @@ impl AppAuthTokenStore {
         }
     }
 
+    /// Look up the `InstalledAppId` that a valid token was issued for, without using the token.
+    ///
+    /// This lets a connection be checked before it is authenticated, so that a single-use
+    /// token is not used up by a connection which is refused for another reason.
+    pub fn peek_token(&self, token: &AppAuthenticationToken) -> Option<InstalledAppId> {
+        let meta = self.issued_tokens.get(token)?;
+        match meta.expires_at {
+            Some(expires_at) if expires_at <= SystemTime::now() => None,
+            _ => Some(meta.installed_app_id.clone()),
+        }
+    }
+
     fn remove_expired_tokens(&mut self) {
         let current_time = SystemTime::now();
 
@@ -225,6 +237,30 @@ mod tests {
         assert_eq!(result.unwrap(), installed_app_id);
     }
 
+    #[test]
+    fn peek_single_use_token() {
+        let mut auth = AppAuthTokenStore::new();
+        let installed_app_id = "test_app".to_string();
+        let (token, _) = auth.issue_token(installed_app_id.clone(), 10, true);
+
+        assert_eq!(Some(installed_app_id.clone()), auth.peek_token(&token));
+
+        // The token can still be used after peeking
+        let authenticated_for_app = auth.authenticate_token(token.clone(), None).unwrap();
+        assert_eq!(authenticated_for_app, installed_app_id);
+        assert_eq!(None, auth.peek_token(&token));
+    }
+
+    #[test]
+    fn peek_expired_token() {
+        let mut auth = AppAuthTokenStore::new();
+        let (token, _) = auth.issue_token("test_app".to_string(), 1, true);
+
+        auth.age_tokens();
+
+        assert_eq!(None, auth.peek_token(&token));
+    }
+
     #[test]
     fn use_expired_token() {
         let mut auth = AppAuthTokenStore::new();
@@ -1,7 +1,9 @@
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_conductor_api::SignalResumePoint;
 use holochain_types::app::InstalledAppId;
 use holochain_types::prelude::*;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
@@ -14,16 +16,76 @@ const SIGNAL_BUFFER_SIZE: usize = 64;
 
 #[derive(Debug, Clone)]
 pub struct AppBroadcast {
-    channels: Arc<parking_lot::Mutex<HashMap<InstalledAppId, broadcast::Sender<Signal>>>>,
+    channels: Arc<parking_lot::Mutex<HashMap<InstalledAppId, AppChannel>>>,
+    /// The apps which receive the signals of each cell.
+    cell_apps: Arc<parking_lot::RwLock<HashMap<CellId, Vec<InstalledAppId>>>>,
+}
+
+/// The signal channel of an app, and its replay buffer if any interface asked for one.
+#[derive(Debug)]
+struct AppChannel {
+    tx: broadcast::Sender<Signal>,
+    replay: Option<Arc<SignalReplay>>,
+}
+
+impl AppChannel {
+    fn new() -> Self {
+        Self {
+            tx: broadcast::channel(SIGNAL_BUFFER_SIZE).0,
+            replay: None,
+        }
+    }
 }
 
 impl AppBroadcast {
     pub(crate) fn new() -> Self {
         Self {
             channels: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            cell_apps: Arc::new(parking_lot::RwLock::new(HashMap::new())),
         }
     }
 
+    /// Create a signal sender for a cell.
+    ///
+    /// Each signal is sent to every app which contains the cell when the signal is sent,
+    /// so that the apps which use a cell created by another app receive its signals too.
+    /// The apps of each cell are set by [`AppBroadcast::set_cell_apps`].
+    pub(crate) fn create_cell_send_handle(&self, cell_id: CellId) -> broadcast::Sender<Signal> {
+        let (tx, mut rx) = broadcast::channel(SIGNAL_BUFFER_SIZE);
+        let app_broadcast = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(signal) => {
+                        let installed_app_ids = app_broadcast
+                            .cell_apps
+                            .read()
+                            .get(&cell_id)
+                            .cloned()
+                            .unwrap_or_default();
+                        for installed_app_id in installed_app_ids {
+                            // No subscribers is fine, nobody is listening to this app.
+                            let _ = app_broadcast
+                                .create_send_handle(installed_app_id)
+                                .send(signal.clone());
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(dropped)) => {
+                        tracing::warn!(?cell_id, "Signal routing missed {dropped} signals");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        tx
+    }
+
+    /// Set the apps which receive the signals of each cell,
+    /// replacing the ones which were set before.
+    pub(crate) fn set_cell_apps(&self, cell_apps: HashMap<CellId, Vec<InstalledAppId>>) {
+        *self.cell_apps.write() = cell_apps;
+    }
+
     /// Create a signal sender for a specific installed app.
     ///
     /// The app does not actually need to be installed to call this and it does not need to be
@@ -32,10 +94,12 @@ impl AppBroadcast {
         &self,
         installed_app_id: InstalledAppId,
     ) -> broadcast::Sender<Signal> {
-        match self.channels.lock().entry(installed_app_id) {
-            Entry::Occupied(e) => e.get().clone(),
-            Entry::Vacant(e) => e.insert(broadcast::channel(SIGNAL_BUFFER_SIZE).0).clone(),
-        }
+        self.channels
+            .lock()
+            .entry(installed_app_id)
+            .or_insert_with(AppChannel::new)
+            .tx
+            .clone()
     }
 
     /// Subscribe to signals for a specific installed app.
@@ -47,12 +111,116 @@ impl AppBroadcast {
         installed_app_id: InstalledAppId,
     ) -> broadcast::Receiver<Signal> {
         match self.channels.lock().entry(installed_app_id) {
-            Entry::Occupied(e) => e.get().subscribe(),
-            Entry::Vacant(e) => {
-                let (tx, rx) = broadcast::channel(SIGNAL_BUFFER_SIZE);
-                e.insert(tx);
+            Entry::Occupied(e) => e.get().tx.subscribe(),
+            Entry::Vacant(e) => e.insert(AppChannel::new()).tx.subscribe(),
+        }
+    }
+
+    /// Subscribe to signals for a specific installed app, only receiving the signals
+    /// which pass the given filters.
+    ///
+    /// The filters can be changed through the [`SignalFilterHandle`] while subscribed.
+    pub(crate) fn subscribe_filtered(
+        &self,
+        installed_app_id: InstalledAppId,
+        filters: SignalFilterHandle,
+    ) -> FilteredSignalReceiver {
+        FilteredSignalReceiver {
+            source: SignalSource::Live(self.subscribe(installed_app_id)),
+            filters,
+        }
+    }
+
+    /// Number the signals of an app and keep the last `buffer_size` of them,
+    /// so that they can be replayed to a client which reconnects.
+    ///
+    /// If the app already has a replay buffer, it is grown to `buffer_size`
+    /// if that is larger.
+    pub(crate) fn enable_replay(&self, installed_app_id: InstalledAppId, buffer_size: usize) {
+        self.replay(installed_app_id, buffer_size);
+    }
 
-                rx
+    /// Like [`AppBroadcast::subscribe_filtered`], but the signals are numbered and
+    /// the app keeps a replay buffer of `buffer_size` signals, see [`AppBroadcast::enable_replay`].
+    ///
+    /// If `resume_from` is given, the buffered signals from that sequence number onwards
+    /// are received first. Signals which have already been dropped from the buffer are
+    /// lost, which the subscriber can tell from the gap in the sequence numbers.
+    ///
+    /// Returns `None` if `resume_from` is not a point in the current epoch of the replay
+    /// buffer, as the signals since then can't be told apart from the new ones.
+    pub(crate) fn subscribe_replayable(
+        &self,
+        installed_app_id: InstalledAppId,
+        filters: SignalFilterHandle,
+        buffer_size: usize,
+        resume_from: Option<SignalResumePoint>,
+    ) -> Option<FilteredSignalReceiver> {
+        let replay = self.replay(installed_app_id, buffer_size);
+        // Signals are buffered and sent under this lock, so every signal
+        // is either replayed or received from the channel, exactly once.
+        let buffer = replay.buffer.lock();
+        let replayed = match resume_from {
+            Some(resume_from) => {
+                if !replay.can_resume_from(&buffer, resume_from) {
+                    return None;
+                }
+                buffer
+                    .signals
+                    .iter()
+                    .filter(|(seq, _)| *seq >= resume_from.seq)
+                    .cloned()
+                    .collect()
+            }
+            None => VecDeque::new(),
+        };
+        let rx = replay.tx.subscribe();
+        Some(FilteredSignalReceiver {
+            source: SignalSource::Sequenced {
+                epoch: replay.epoch,
+                replayed,
+                rx,
+            },
+            filters,
+        })
+    }
+
+    /// Check that signals can be resumed from `resume_from` with
+    /// [`AppBroadcast::subscribe_replayable`], before subscribing.
+    ///
+    /// Returns the current epoch of the replay buffer if they can't.
+    pub(crate) fn check_resume_point(
+        &self,
+        installed_app_id: InstalledAppId,
+        buffer_size: usize,
+        resume_from: SignalResumePoint,
+    ) -> Result<(), u64> {
+        let replay = self.replay(installed_app_id, buffer_size);
+        let buffer = replay.buffer.lock();
+        if replay.can_resume_from(&buffer, resume_from) {
+            Ok(())
+        } else {
+            Err(replay.epoch)
+        }
+    }
+
+    /// Get the replay buffer of an app, creating it or growing it to `buffer_size`.
+    fn replay(&self, installed_app_id: InstalledAppId, buffer_size: usize) -> Arc<SignalReplay> {
+        let mut channels = self.channels.lock();
+        let channel = channels
+            .entry(installed_app_id)
+            .or_insert_with(AppChannel::new);
+        match &channel.replay {
+            Some(replay) => {
+                let mut buffer = replay.buffer.lock();
+                buffer.capacity = buffer.capacity.max(buffer_size);
+                drop(buffer);
+                replay.clone()
+            }
+            None => {
+                let replay = SignalReplay::spawn(channel.tx.subscribe(), buffer_size);
+                channel.replay = Some(replay.clone());
+                replay
             }
         }
     }
@@ -77,12 +245,145 @@ impl Default for AppBroadcast {
     }
 }
 
+/// Numbers the signals of an app and keeps the most recent ones for replay.
+#[derive(Debug)]
+struct SignalReplay {
+    /// Random, so that the numbering of a previous replay buffer of the app,
+    /// such as one from before the conductor restarted, is not mistaken for this one.
+    epoch: u64,
+    buffer: parking_lot::Mutex<ReplayBuffer>,
+    /// The numbered signals, sent after they have been buffered.
+    tx: broadcast::Sender<(u64, Signal)>,
+}
+
+#[derive(Debug)]
+struct ReplayBuffer {
+    capacity: usize,
+    next_seq: u64,
+    signals: VecDeque<(u64, Signal)>,
+}
+
+impl SignalReplay {
+    /// Start numbering the signals received from `rx` until the channel is closed.
+    fn spawn(mut rx: broadcast::Receiver<Signal>, capacity: usize) -> Arc<Self> {
+        let replay = Arc::new(Self {
+            epoch: rand::random(),
+            buffer: parking_lot::Mutex::new(ReplayBuffer {
+                capacity,
+                next_seq: 0,
+                signals: VecDeque::new(),
+            }),
+            tx: broadcast::channel(SIGNAL_BUFFER_SIZE).0,
+        });
+        tokio::spawn({
+            let replay = replay.clone();
+            async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(signal) => replay.push(signal),
+                        Err(broadcast::error::RecvError::Lagged(dropped)) => {
+                            tracing::warn!("Signal replay buffer missed {dropped} signals");
+                            // Skip their sequence numbers, so that subscribers can tell
+                            // from the gap that signals were lost.
+                            replay.buffer.lock().next_seq += dropped;
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }
+        });
+        replay
+    }
+
+    /// Whether `resume_from` is a point in the signals of this replay buffer so far.
+    fn can_resume_from(&self, buffer: &ReplayBuffer, resume_from: SignalResumePoint) -> bool {
+        resume_from.epoch == self.epoch && resume_from.seq <= buffer.next_seq
+    }
+
+    fn push(&self, signal: Signal) {
+        let mut buffer = self.buffer.lock();
+        let seq = buffer.next_seq;
+        buffer.next_seq += 1;
+        buffer.signals.push_back((seq, signal.clone()));
+        while buffer.signals.len() > buffer.capacity {
+            buffer.signals.pop_front();
+        }
+        // No subscribers is fine, the signal is still buffered.
+        let _ = self.tx.send((seq, signal));
+    }
+}
+
+/// The signal filters of a single subscriber, which can be updated while subscribed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SignalFilterHandle(Arc<parking_lot::RwLock<SignalFilterSet>>);
+
+impl SignalFilterHandle {
+    /// Replace the filters of the subscriber.
+    pub(crate) fn set(&self, filters: SignalFilterSet) {
+        *self.0.write() = filters;
+    }
+
+    fn allows(&self, signal: &Signal) -> bool {
+        self.0.read().allows(signal)
+    }
+}
+
+/// A receiver of app signals, which skips all signals that are not allowed by its filters.
+pub(crate) struct FilteredSignalReceiver {
+    source: SignalSource,
+    filters: SignalFilterHandle,
+}
+
+enum SignalSource {
+    Live(broadcast::Receiver<Signal>),
+    Sequenced {
+        epoch: u64,
+        replayed: VecDeque<(u64, Signal)>,
+        rx: broadcast::Receiver<(u64, Signal)>,
+    },
+}
+
+impl FilteredSignalReceiver {
+    /// Receive the next signal which passes the filters.
+    pub(crate) async fn recv(&mut self) -> Result<Signal, broadcast::error::RecvError> {
+        Ok(self.recv_sequenced().await?.1)
+    }
+
+    /// Receive the next signal which passes the filters, along with its epoch and
+    /// sequence number if this receiver was created with [`AppBroadcast::subscribe_replayable`].
+    pub(crate) async fn recv_sequenced(
+        &mut self,
+    ) -> Result<(Option<(u64, u64)>, Signal), broadcast::error::RecvError> {
+        loop {
+            let (seq, signal) = match &mut self.source {
+                SignalSource::Live(rx) => (None, rx.recv().await?),
+                SignalSource::Sequenced {
+                    epoch,
+                    replayed,
+                    rx,
+                } => {
+                    let (seq, signal) = match replayed.pop_front() {
+                        Some(replayed) => replayed,
+                        None => rx.recv().await?,
+                    };
+                    (Some((*epoch, seq)), signal)
+                }
+            };
+            if self.filters.allows(&signal) {
+                return Ok((seq, signal));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ::fixt::prelude::*;
     use hdk::prelude::CellIdFixturator;
     use hdk::prelude::ZomeNameFixturator;
+    use holochain_conductor_api::signal_subscription::SignalFilter;
+    use holochain_conductor_api::signal_subscription::SignalKind;
     use holochain_zome_types::signal::AppSignal;
 
     #[tokio::test]
@@ -169,6 +470,241 @@ mod tests {
         assert_eq!(signal_2, signal_2_rcv_2);
     }
 
+    #[tokio::test]
+    async fn filtered_subscriber_skips_filtered_signals() {
+        let app_broadcast = AppBroadcast::new();
+        let installed_app_id: InstalledAppId = "test".into();
+
+        let filters = SignalFilterHandle::default();
+        let mut rx = app_broadcast.subscribe_filtered(installed_app_id.clone(), filters.clone());
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+
+        let cell_id = fixt!(CellId);
+        let app_signal = Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: fixt!(ZomeName),
+            signal: AppSignal::new(ExternIO::from(vec![])),
+        };
+        let system_signal = holochain_types::signal::test_signal("test");
+
+        // All signals pass by default
+        tx.send(app_signal.clone()).unwrap();
+        assert_eq!(app_signal, rx.recv().await.unwrap());
+
+        // Exclude app signals of the cell, so only the system signal comes through
+        filters.set(SignalFilterSet::Exclude(
+            [(
+                cell_id,
+                SignalFilter {
+                    kind: Some(SignalKind::App),
+                    ..SignalFilter::empty()
+                },
+            )]
+            .into_iter()
+            .collect(),
+        ));
+        tx.send(app_signal).unwrap();
+        tx.send(system_signal.clone()).unwrap();
+        assert_eq!(system_signal, rx.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn replay_buffered_signals() {
+        let app_broadcast = AppBroadcast::new();
+        let installed_app_id: InstalledAppId = "test".into();
+
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+        let mut rx = app_broadcast
+            .subscribe_replayable(
+                installed_app_id.clone(),
+                SignalFilterHandle::default(),
+                2,
+                None,
+            )
+            .unwrap();
+
+        let signals: Vec<_> = (0..4)
+            .map(|i| holochain_types::signal::test_signal(&i.to_string()))
+            .collect();
+        for signal in &signals[0..3] {
+            tx.send(signal.clone()).unwrap();
+        }
+        let (first, signal) = rx.recv_sequenced().await.unwrap();
+        let (epoch, _) = first.unwrap();
+        assert_eq!(signals[0], signal);
+        for (seq, signal) in signals[0..3].iter().enumerate().skip(1) {
+            assert_eq!(
+                (Some((epoch, seq as u64)), signal.clone()),
+                rx.recv_sequenced().await.unwrap()
+            );
+        }
+        let resume_from = |seq| Some(SignalResumePoint { epoch, seq });
+
+        // Only the last two signals are kept
+        let mut resumed = app_broadcast
+            .subscribe_replayable(
+                installed_app_id.clone(),
+                SignalFilterHandle::default(),
+                2,
+                resume_from(0),
+            )
+            .unwrap();
+        tx.send(signals[3].clone()).unwrap();
+        for (seq, signal) in signals.iter().enumerate().skip(1) {
+            assert_eq!(
+                (Some((epoch, seq as u64)), signal.clone()),
+                resumed.recv_sequenced().await.unwrap()
+            );
+        }
+
+        // Resuming from the latest signal only replays that one
+        let mut resumed = app_broadcast
+            .subscribe_replayable(
+                installed_app_id.clone(),
+                SignalFilterHandle::default(),
+                2,
+                resume_from(3),
+            )
+            .unwrap();
+        assert_eq!(
+            (Some((epoch, 3)), signals[3].clone()),
+            resumed.recv_sequenced().await.unwrap()
+        );
+
+        // Signals which were never emitted can't be resumed from
+        assert!(app_broadcast
+            .subscribe_replayable(
+                installed_app_id,
+                SignalFilterHandle::default(),
+                2,
+                resume_from(5),
+            )
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn refuse_to_resume_signals_of_another_epoch() {
+        let installed_app_id: InstalledAppId = "test".into();
+
+        // The replay buffer from before a restart
+        let app_broadcast = AppBroadcast::new();
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+        let mut rx = app_broadcast
+            .subscribe_replayable(
+                installed_app_id.clone(),
+                SignalFilterHandle::default(),
+                2,
+                None,
+            )
+            .unwrap();
+        tx.send(holochain_types::signal::test_signal("before"))
+            .unwrap();
+        let (Some((epoch, seq)), _) = rx.recv_sequenced().await.unwrap() else {
+            panic!("signal is not sequenced");
+        };
+
+        // The same sequence number is refused after the restart
+        let app_broadcast = AppBroadcast::new();
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+        let mut rx = app_broadcast
+            .subscribe_replayable(
+                installed_app_id.clone(),
+                SignalFilterHandle::default(),
+                2,
+                None,
+            )
+            .unwrap();
+        tx.send(holochain_types::signal::test_signal("after"))
+            .unwrap();
+        let (Some((new_epoch, new_seq)), _) = rx.recv_sequenced().await.unwrap() else {
+            panic!("signal is not sequenced");
+        };
+        assert_eq!(seq, new_seq);
+        assert_ne!(epoch, new_epoch);
+        assert_eq!(
+            Err(new_epoch),
+            app_broadcast.check_resume_point(
+                installed_app_id.clone(),
+                2,
+                SignalResumePoint { epoch, seq }
+            )
+        );
+        assert!(app_broadcast
+            .subscribe_replayable(
+                installed_app_id,
+                SignalFilterHandle::default(),
+                2,
+                Some(SignalResumePoint { epoch, seq }),
+            )
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn signals_missed_by_the_replay_buffer_leave_a_gap() {
+        let app_broadcast = AppBroadcast::new();
+        let installed_app_id: InstalledAppId = "test".into();
+
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+        let mut rx = app_broadcast
+            .subscribe_replayable(
+                installed_app_id.clone(),
+                SignalFilterHandle::default(),
+                2,
+                None,
+            )
+            .unwrap();
+
+        // The replay buffer doesn't get to receive any of these before the channel
+        // overflows, so it misses the first few.
+        let missed = 5;
+        for i in 0..SIGNAL_BUFFER_SIZE + missed {
+            tx.send(holochain_types::signal::test_signal(&i.to_string()))
+                .unwrap();
+        }
+
+        let (seq, signal) = rx.recv_sequenced().await.unwrap();
+        assert_eq!(Some(missed as u64), seq.map(|(_, seq)| seq));
+        assert_eq!(
+            holochain_types::signal::test_signal(&missed.to_string()),
+            signal
+        );
+    }
+
+    #[tokio::test]
+    async fn cell_signals_reach_every_app_containing_the_cell() {
+        let app_broadcast = AppBroadcast::new();
+        let cell_id = fixt!(CellId);
+        let provider: InstalledAppId = "provider".into();
+        let user: InstalledAppId = "user".into();
+
+        let mut provider_rx = app_broadcast.subscribe(provider.clone());
+        let mut user_rx = app_broadcast.subscribe(user.clone());
+        let tx = app_broadcast.create_cell_send_handle(cell_id.clone());
+
+        let signal = |name: &str| Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: name.into(),
+            signal: AppSignal::new(ExternIO::from(vec![])),
+        };
+
+        app_broadcast.set_cell_apps([(cell_id.clone(), vec![provider.clone()])].into());
+        tx.send(signal("provider only")).unwrap();
+        assert_eq!(signal("provider only"), provider_rx.recv().await.unwrap());
+
+        // An app which starts using the cell receives its later signals
+        app_broadcast
+            .set_cell_apps([(cell_id.clone(), vec![provider.clone(), user.clone()])].into());
+        tx.send(signal("shared")).unwrap();
+        assert_eq!(signal("shared"), provider_rx.recv().await.unwrap());
+        assert_eq!(signal("shared"), user_rx.recv().await.unwrap());
+
+        // And stops receiving them once it no longer contains the cell
+        app_broadcast.set_cell_apps([(cell_id.clone(), vec![provider])].into());
+        tx.send(signal("provider again")).unwrap();
+        assert_eq!(signal("provider again"), provider_rx.recv().await.unwrap());
+        assert!(user_rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn clean_up_unused_senders() {
         let app_broadcast = AppBroadcast::new();
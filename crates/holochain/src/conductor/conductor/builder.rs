@@ -1,11 +1,15 @@
 use super::*;
+use crate::conductor::db_key;
 use crate::conductor::kitsune_host_impl::KitsuneHostImpl;
 use crate::conductor::manager::OutcomeReceiver;
 use crate::conductor::metrics::{create_post_commit_duration_metric, PostCommitDurationMetric};
 use crate::conductor::paths::DataRootPath;
 use crate::conductor::ribosome_store::RibosomeStore;
 use crate::conductor::ConductorHandle;
+use crate::core::ribosome::guest_callback::post_commit::run_post_commit;
+use holochain_conductor_api::conductor::paths::DatabasesRootPath;
 use holochain_conductor_api::conductor::paths::KeystorePath;
+use holochain_sqlite::db::DbKey;
 
 /// A configurable Builder for Conductor and sometimes ConductorHandle
 #[derive(Default)]
@@ -23,6 +27,8 @@ pub struct ConductorBuilder {
     pub state: Option<ConductorState>,
     /// Skip printing setup info to stdout
     pub no_print_setup: bool,
+    /// Optional zome call policy, overriding the one of the config
+    pub zome_call_policy: Option<Arc<dyn ZomeCallPolicy>>,
 }
 
 impl ConductorBuilder {
@@ -51,6 +57,13 @@ impl ConductorBuilder {
         self
     }
 
+    /// Set the policy which zome calls made over app interfaces are checked
+    /// against, instead of the one set in the config.
+    pub fn zome_call_policy(mut self, policy: Arc<dyn ZomeCallPolicy>) -> Self {
+        self.zome_call_policy = Some(policy);
+        self
+    }
+
     /// Set the data root path for the conductor that will be built.
     pub fn with_data_root_path(mut self, data_root_path: DataRootPath) -> Self {
         self.config.data_root_path = Some(data_root_path);
@@ -62,75 +75,15 @@ impl ConductorBuilder {
     pub async fn build(self) -> ConductorResult<ConductorHandle> {
         tracing::debug!(?self.config);
 
-        let keystore = if let Some(keystore) = self.keystore {
-            keystore
-        } else {
-            pub(crate) fn warn_no_encryption() {
-                #[cfg(not(feature = "sqlite-encrypted"))]
-                {
-                    const MSG: &str = "WARNING: running without local db encryption";
-                    eprintln!("{}", MSG);
-                    println!("{}", MSG);
-                    tracing::warn!("{}", MSG);
-                }
-            }
-            let get_passphrase = || -> ConductorResult<sodoken::BufRead> {
-                match self.passphrase {
-                    None => Err(
-                        one_err::OneErr::new("passphrase required for lair keystore api").into(),
-                    ),
-                    Some(p) => Ok(p),
-                }
-            };
-            match &self.config.keystore {
-                KeystoreConfig::DangerTestKeystore => {
-                    holochain_keystore::spawn_test_keystore().await?
-                }
-                KeystoreConfig::LairServer { connection_url } => {
-                    warn_no_encryption();
-                    let passphrase = get_passphrase()?;
-                    match spawn_lair_keystore(connection_url.clone(), passphrase).await {
-                        Ok(keystore) => keystore,
-                        Err(err) => {
-                            tracing::error!(?err, "Failed to spawn Lair keystore");
-                            return Err(err.into());
-                        }
-                    }
-                }
-                KeystoreConfig::LairServerInProc { lair_root } => {
-                    warn_no_encryption();
-
-                    let keystore_root_path: KeystorePath = match lair_root {
-                        Some(lair_root) => lair_root.clone(),
-                        None => self
-                            .config
-                            .data_root_path
-                            .as_ref()
-                            .ok_or(ConductorError::NoDataRootPath)?
-                            .clone()
-                            .try_into()?,
-                    };
-                    let keystore_config_path = keystore_root_path
-                        .as_ref()
-                        .join("lair-keystore-config.yaml");
-                    let passphrase = get_passphrase()?;
-
-                    match spawn_lair_keystore_in_proc(&keystore_config_path, passphrase).await {
-                        Ok(keystore) => keystore,
-                        Err(err) => {
-                            tracing::error!(?err, "Failed to spawn Lair keystore in process");
-                            return Err(err.into());
-                        }
-                    }
-                }
-            }
-        };
+        let lock_db_key = Self::locks_db_key(&self.keystore, &self.config);
+        let keystore = Self::spawn_keystore(self.keystore, self.passphrase, &self.config).await?;
 
         info!("Conductor startup: passphrase obtained.");
 
         let Self {
             ribosome_store,
             config,
+            zome_call_policy,
             ..
         } = self;
 
@@ -138,7 +91,8 @@ impl ConductorBuilder {
 
         let ribosome_store = RwShare::new(ribosome_store);
 
-        let spaces = Spaces::new(config.clone())?;
+        let db_key = Self::get_or_create_db_key(lock_db_key.then_some(&keystore), &config).await?;
+        let spaces = Spaces::new(config.clone(), db_key)?;
         let tag = spaces.get_state().await?.tag().clone();
 
         let tag_ed: Arc<str> = format!("{}_ed", tag.0).into_boxed_str().into();
@@ -198,7 +152,7 @@ impl ConductorBuilder {
 
         let (outcome_tx, outcome_rx) = futures::channel::mpsc::channel(8);
 
-        let conductor = Conductor::new(
+        let mut conductor = Conductor::new(
             config.clone(),
             ribosome_store,
             keystore,
@@ -208,6 +162,10 @@ impl ConductorBuilder {
             outcome_tx,
         );
 
+        if let Some(zome_call_policy) = zome_call_policy {
+            conductor.zome_call_policy = Some(zome_call_policy);
+        }
+
         let shutting_down = conductor.shutting_down.clone();
 
         #[cfg(any(test, feature = "test_utils"))]
@@ -252,21 +210,29 @@ impl ConductorBuilder {
                 let conductor_handle = conductor_handle.clone();
                 let duration_metric = duration_metric.clone();
                 async move {
-                    let PostCommitArgs {
-                        host_access,
-                        invocation,
-                        cell_id,
-                    } = post_commit_args;
+                    let cell_id = post_commit_args.cell_id.clone();
                     match conductor_handle.clone().get_ribosome(cell_id.dna_hash()) {
                         Ok(ribosome) => {
-                            if let Err(e) = tokio::task::spawn_blocking(move || {
-                                if let Err(e) = ribosome.run_post_commit(host_access, invocation) {
-                                    tracing::error!(?e);
-                                }
-                            })
-                            .await
+                            if let Some((retry, delay)) =
+                                run_post_commit(ribosome, post_commit_args).await
                             {
-                                tracing::error!(?e);
+                                // Wait for the retry outside of this stream, so that
+                                // the other callbacks keep running in the meantime.
+                                let cell_id = cell_id.clone();
+                                tokio::spawn(async move {
+                                    tokio::time::sleep(delay).await;
+                                    match conductor_handle.post_commit_permit().await {
+                                        Ok(permit) => {
+                                            permit.send(retry);
+                                        }
+                                        Err(_) => {
+                                            tracing::warn!(
+                                                ?cell_id,
+                                                "Conductor shut down before post_commit could be retried"
+                                            );
+                                        }
+                                    }
+                                });
                             }
                         }
                         Err(e) => {
@@ -345,6 +311,107 @@ impl ConductorBuilder {
         Ok(conductor)
     }
 
+    /// Use the given keystore, or spawn the keystore of the config.
+    async fn spawn_keystore(
+        keystore: Option<MetaLairClient>,
+        passphrase: Option<sodoken::BufRead>,
+        config: &ConductorConfig,
+    ) -> ConductorResult<MetaLairClient> {
+        if let Some(keystore) = keystore {
+            return Ok(keystore);
+        }
+        pub(crate) fn warn_no_encryption() {
+            #[cfg(not(feature = "sqlite-encrypted"))]
+            {
+                const MSG: &str = "WARNING: running without local db encryption";
+                eprintln!("{}", MSG);
+                println!("{}", MSG);
+                tracing::warn!("{}", MSG);
+            }
+        }
+        let get_passphrase = || -> ConductorResult<sodoken::BufRead> {
+            match passphrase {
+                None => {
+                    Err(one_err::OneErr::new("passphrase required for lair keystore api").into())
+                }
+                Some(p) => Ok(p),
+            }
+        };
+        Ok(match &config.keystore {
+            KeystoreConfig::DangerTestKeystore => holochain_keystore::spawn_test_keystore().await?,
+            KeystoreConfig::LairServer { connection_url } => {
+                warn_no_encryption();
+                let passphrase = get_passphrase()?;
+                match spawn_lair_keystore(connection_url.clone(), passphrase).await {
+                    Ok(keystore) => keystore,
+                    Err(err) => {
+                        tracing::error!(?err, "Failed to spawn Lair keystore");
+                        return Err(err.into());
+                    }
+                }
+            }
+            KeystoreConfig::LairServerInProc { lair_root } => {
+                warn_no_encryption();
+
+                let keystore_root_path: KeystorePath = match lair_root {
+                    Some(lair_root) => lair_root.clone(),
+                    None => config
+                        .data_root_path
+                        .as_ref()
+                        .ok_or(ConductorError::NoDataRootPath)?
+                        .clone()
+                        .try_into()?,
+                };
+                let keystore_config_path = keystore_root_path
+                    .as_ref()
+                    .join("lair-keystore-config.yaml");
+                let passphrase = get_passphrase()?;
+
+                match spawn_lair_keystore_in_proc(&keystore_config_path, passphrase).await {
+                    Ok(keystore) => keystore,
+                    Err(err) => {
+                        tracing::error!(?err, "Failed to spawn Lair keystore in process");
+                        return Err(err.into());
+                    }
+                }
+            }
+        })
+    }
+
+    /// Whether the database key can be locked with the keystore, which it can't
+    /// if the keystore is the danger test keystore spawned for this run only.
+    fn locks_db_key(keystore: &Option<MetaLairClient>, config: &ConductorConfig) -> bool {
+        keystore.is_some() || !matches!(config.keystore, KeystoreConfig::DangerTestKeystore)
+    }
+
+    /// Get the key the databases of the conductor are encrypted with.
+    async fn get_or_create_db_key(
+        keystore: Option<&MetaLairClient>,
+        config: &ConductorConfig,
+    ) -> ConductorResult<DbKey> {
+        let databases_dir: DatabasesRootPath = config
+            .data_root_path
+            .clone()
+            .ok_or(ConductorError::NoDataRootPath)?
+            .try_into()?;
+        db_key::get_or_create_db_key(keystore, &databases_dir).await
+    }
+
+    /// Re-encrypt the databases of the conductor with a new key, instead of
+    /// building the conductor. The conductor must not be running.
+    #[cfg(feature = "sqlite-encrypted")]
+    pub async fn rotate_db_key(self) -> ConductorResult<()> {
+        let lock_db_key = Self::locks_db_key(&self.keystore, &self.config);
+        let keystore = Self::spawn_keystore(self.keystore, self.passphrase, &self.config).await?;
+        let databases_dir: DatabasesRootPath = self
+            .config
+            .data_root_path
+            .clone()
+            .ok_or(ConductorError::NoDataRootPath)?
+            .try_into()?;
+        db_key::rotate_db_key(lock_db_key.then_some(&keystore), &databases_dir).await
+    }
+
     /// Pass a test keystore in, to ensure that generated test agents
     /// are actually available for signing (especially for tryorama compat)
     pub fn with_keystore(mut self, keystore: MetaLairClient) -> Self {
@@ -382,7 +449,9 @@ impl ConductorBuilder {
             .unwrap_or_else(holochain_keystore::test_keystore);
 
         let config = Arc::new(self.config);
-        let spaces = Spaces::new(config.clone())?;
+        // Test conductors may be restarted with another keystore, or with one
+        // which fails every request, so they use a fixed key.
+        let spaces = Spaces::new(config.clone(), DbKey::test())?;
         let tag = spaces.get_state().await?.tag().clone();
 
         let tag_ed: Arc<str> = format!("{}_ed", tag.0).into_boxed_str().into();
@@ -414,7 +483,7 @@ impl ConductorBuilder {
 
         let (outcome_tx, outcome_rx) = futures::channel::mpsc::channel(8);
 
-        let conductor = Conductor::new(
+        let mut conductor = Conductor::new(
             config.clone(),
             ribosome_store,
             keystore,
@@ -424,6 +493,10 @@ impl ConductorBuilder {
             outcome_tx,
         );
 
+        if let Some(zome_call_policy) = self.zome_call_policy {
+            conductor.zome_call_policy = Some(zome_call_policy);
+        }
+
         let conductor = Self::update_fake_state(self.state, conductor).await?;
 
         // Create handle
@@ -8,18 +8,22 @@ use crate::conductor::api::error::ConductorApiError;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::sweettest::*;
 use crate::test_utils::inline_zomes::simple_crud_zome;
+use crate::test_utils::new_zome_call;
 use crate::{
     assert_eq_retry_10s, core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckResult,
 };
 use ::fixt::prelude::*;
 use holochain_conductor_api::AppInfoStatus;
 use holochain_conductor_api::CellInfo;
+use holochain_conductor_api::WarrantInfo;
 use holochain_keystore::crude_mock_keystore::*;
 use holochain_keystore::test_keystore;
 use holochain_types::inline_zome::InlineZomeSet;
+use holochain_types::sql::AsSql;
 use holochain_types::test_utils::fake_cell_id;
 use holochain_wasm_test_utils::TestWasm;
 use holochain_zome_types::op::Op;
+use holochain_zome_types::rate_limit::RateLimit;
 use maplit::hashset;
 use matches::assert_matches;
 
@@ -39,6 +43,7 @@ async fn can_update_state() {
             ..Default::default()
         }
         .into(),
+        DbKey::test(),
     )
     .unwrap();
     let conductor = Conductor::new(
@@ -93,6 +98,7 @@ async fn app_ids_are_unique() {
             ..Default::default()
         }
         .into(),
+        DbKey::test(),
     )
     .unwrap();
     let conductor = Conductor::new(
@@ -224,6 +230,30 @@ async fn mk_dna(
     SweetDnaFile::unique_from_inline_zomes(zomes.into()).await
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn refuse_to_register_a_dna_whose_rate_limit_never_drains() {
+    let conductor = SweetConductor::from_standard_config().await;
+
+    let mut dna_def =
+        DnaDef::unique_from_zomes(vec![TestWasm::Create.into()], vec![TestWasm::Create.into()]);
+    dna_def.rate_limits = [(
+        3,
+        RateLimit {
+            capacity: 10,
+            drain_amount: 0,
+            drain_interval_ms: 1000,
+        },
+    )]
+    .into_iter()
+    .collect();
+    let dna = DnaFile::new(dna_def, Vec::<DnaWasm>::from(TestWasm::Create)).await;
+
+    assert_matches!(
+        conductor.raw_handle().register_dna(dna).await,
+        Err(ConductorError::DnaError(DnaError::RateLimitNeverDrains(3)))
+    );
+}
+
 /// A function that sets up a SweetApp, used in several tests in this module
 async fn common_genesis_test_app(
     conductor: &mut SweetConductor,
@@ -231,18 +261,6 @@ async fn common_genesis_test_app(
 ) -> ConductorApiResult<SweetApp> {
     let hardcoded_zome = InlineIntegrityZome::new_unique(Vec::new(), 0);
 
-    // Just a strong reminder that we need to be careful once we start using existing Cells:
-    // When a Cell panics or fails validation in general, we want to disable all Apps touching that Cell.
-    // However, if the panic/failure happens during Genesis, we want to completely
-    // destroy the app which is attempting to Create that Cell, but *NOT* any other apps
-    // which might be touching that Cell.
-    //
-    // It probably works out to be the same either way, since if we are creating a Cell,
-    // no other app could be possibly referencing it, but just in case we have some kind of complex
-    // behavior like installing two apps which reference each others' Cells at the same time,
-    // we need to be aware of this distinction.
-    holochain_types::app::we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution();
-
     // Create one DNA which always works, and another from a zome that gets passed in
     let (dna_hardcoded, _, _) = mk_dna(("hardcoded", hardcoded_zome)).await;
     let (dna_custom, _, _) = mk_dna(custom_zomes).await;
@@ -1172,3 +1190,785 @@ async fn test_init_concurrency() {
     assert_eq!(num_calls_clone.fetch_add(0, Ordering::SeqCst), 100);
     assert_eq!(num_inits_clone.fetch_add(0, Ordering::SeqCst), 1);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn call_zome_batch() {
+    holochain_trace::test_run();
+    let unit_entry_def = EntryDef::default_from_id("unit");
+    let zome = InlineZomeSet::new_unique_single("integrity", "zome", vec![unit_entry_def], 0)
+        .function("zome", "create", |api, ()| {
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            let hash = api.create(CreateInput::new(
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                EntryVisibility::Public,
+                entry,
+                ChainTopOrdering::Relaxed,
+            ))?;
+            Ok(hash)
+        })
+        .function("zome", "fail", |_api, ()| {
+            Err::<(), _>(InlineZomeError::TestError("intentional failure".into()))
+        })
+        .function("zome", "count", |api, ()| {
+            Ok(api.query(ChainQueryFilter::new())?.len())
+        });
+    let dnas = [mk_dna(zome).await.0];
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &dnas).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    let zome = cell.zome("zome");
+    let count = || {
+        let (conductor, zome) = (&conductor, &zome);
+        async move {
+            let count: usize = conductor.call(zome, "count", ()).await;
+            count
+        }
+    };
+    let batch = |fn_names: &[&str], atomic: bool| {
+        let keystore = conductor.keystore();
+        let cell_id = cell.cell_id().clone();
+        let fn_names: Vec<String> = fn_names.iter().map(|f| f.to_string()).collect();
+        let handle = conductor.raw_handle();
+        async move {
+            let mut calls = Vec::new();
+            for fn_name in fn_names {
+                calls.push(
+                    new_zome_call(&keystore, &cell_id, &fn_name, (), "zome")
+                        .await
+                        .unwrap(),
+                );
+            }
+            handle.call_zome_batch(calls, atomic).await.unwrap()
+        }
+    };
+    let initial = count().await;
+
+    // The calls of an atomic batch are written together
+    let results = batch(&["create", "create"], true).await;
+    assert_eq!(results.len(), 2);
+    assert_matches!(results[0], Some(Ok(Ok(ZomeCallResponse::Ok(_)))));
+    assert_matches!(results[1], Some(Ok(Ok(ZomeCallResponse::Ok(_)))));
+    assert_eq!(count().await, initial + 2);
+
+    // When a call of an atomic batch fails, the remaining calls are skipped
+    // and nothing is written
+    let results = batch(&["create", "fail", "create"], true).await;
+    assert_matches!(results[0], Some(Ok(Ok(ZomeCallResponse::Ok(_)))));
+    assert_matches!(results[1], Some(Ok(Err(_))));
+    assert_matches!(results[2], None);
+    assert_eq!(count().await, initial + 2);
+
+    // Without atomicity every call stands on its own
+    let results = batch(&["create", "fail", "create"], false).await;
+    assert_matches!(results[0], Some(Ok(Ok(ZomeCallResponse::Ok(_)))));
+    assert_matches!(results[1], Some(Ok(Err(_))));
+    assert_matches!(results[2], Some(Ok(Ok(ZomeCallResponse::Ok(_)))));
+    assert_eq!(count().await, initial + 4);
+
+    // All calls of an atomic batch must be to the same cell
+    let call = new_zome_call(&conductor.keystore(), cell.cell_id(), "create", (), "zome")
+        .await
+        .unwrap();
+    let other_cell_call = ZomeCall {
+        cell_id: fake_cell_id(1),
+        ..call.clone()
+    };
+    assert_matches!(
+        conductor
+            .raw_handle()
+            .call_zome_batch(vec![call, other_cell_call], true)
+            .await,
+        Err(ConductorApiError::AtomicBatchCellMismatch(_, _))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn post_commit_failure_is_signalled() {
+    holochain_trace::test_run();
+    let unit_entry_def = EntryDef::default_from_id("unit");
+    let zome = InlineZomeSet::new_unique_single("integrity", "zome", vec![unit_entry_def], 0)
+        .function("zome", "create", |api, ()| {
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            let hash = api.create(CreateInput::new(
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                EntryVisibility::Public,
+                entry,
+                ChainTopOrdering::default(),
+            ))?;
+            Ok(hash)
+        })
+        .function(
+            "zome",
+            "post_commit",
+            |_api, _actions: Vec<SignedActionHashed>| {
+                Err::<(), _>(InlineZomeError::TestError("intentional failure".into()))
+            },
+        );
+    let dnas = [mk_dna(zome).await.0];
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", &dnas).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let mut signals = conductor.subscribe_to_app_signals("app".to_string());
+
+    let hash: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+
+    // The failure of initialization's post_commit may come first
+    let (cell_id, zome_name, attempts) =
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            loop {
+                if let Signal::System(SystemSignal::PostCommitFailed {
+                    cell_id,
+                    zome_name,
+                    action_hashes,
+                    attempts,
+                    ..
+                }) = signals.recv().await.unwrap()
+                {
+                    if action_hashes == vec![hash.clone()] {
+                        return (cell_id, zome_name, attempts);
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+    assert_eq!(cell_id, *cell.cell_id());
+    assert_eq!(zome_name, "zome".into());
+    // Inline zomes have no retry policy, so the callback is only run once
+    assert_eq!(attempts, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn export_and_import_cell() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let hash: ActionHash = conductor
+        .call(&cell.zome(SweetInlineZomes::COORDINATOR), "create_unit", ())
+        .await;
+
+    let archive = conductor.export_cell(cell.cell_id()).await.unwrap();
+    let content = archive.clone().open().await.unwrap();
+    assert_eq!(content.version, CELL_ARCHIVE_VERSION);
+    assert_eq!(&content.cell_id, cell.cell_id());
+    assert_eq!(content.records.last().unwrap().action_address(), &hash);
+
+    // Importing the archive into the cell it came from leaves the chain as it is.
+    conductor
+        .raw_handle()
+        .import_cell(archive, true)
+        .await
+        .unwrap();
+    let records = conductor.export_cell(cell.cell_id()).await.unwrap();
+    let records = records.open().await.unwrap().records;
+    assert_eq!(records, content.records);
+
+    // Cells which are not installed can't be exported.
+    let result = conductor.export_cell(&fake_cell_id(1)).await;
+    assert_matches!(
+        result,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::CellMissing(_)
+        ))
+    );
+}
+
+/// A cell exported from one conductor can be imported into another conductor
+/// with the same agent, and the archive only carries what the cell's agent authored.
+#[cfg(feature = "chc")]
+#[tokio::test(flavor = "multi_thread")]
+async fn export_cell_and_import_into_another_conductor() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut config = ConductorConfig::default();
+    config.chc_url = Some(url2::Url2::parse(
+        crate::conductor::chc::CHC_LOCAL_MAGIC_URL,
+    ));
+    let mut conductor = SweetConductor::from_config(config.clone()).await;
+    let keystore = conductor.keystore();
+    let (alice, bob) = SweetAgents::two(keystore.clone()).await;
+    let ((alice_cell,), (bob_cell,)) = conductor
+        .setup_app_for_agents("app", [&alice, &bob], [&dna])
+        .await
+        .unwrap()
+        .into_tuples();
+    let _: ActionHash = conductor
+        .call(
+            &alice_cell.zome(SweetInlineZomes::COORDINATOR),
+            "create_unit",
+            (),
+        )
+        .await;
+    let _: ActionHash = conductor
+        .call(
+            &bob_cell.zome(SweetInlineZomes::COORDINATOR),
+            "create_unit",
+            (),
+        )
+        .await;
+    await_consistency(10, [&alice_cell, &bob_cell])
+        .await
+        .unwrap();
+
+    // Both agents share the DHT database of the DNA, but an archive only
+    // holds the ops of its own agent.
+    let bob_ops = conductor
+        .export_cell(bob_cell.cell_id())
+        .await
+        .unwrap()
+        .open()
+        .await
+        .unwrap()
+        .dht_ops;
+    assert!(!bob_ops.is_empty());
+    assert!(bob_ops.iter().all(|op| op.author() == bob));
+
+    let archive = conductor.export_cell(alice_cell.cell_id()).await.unwrap();
+    let content = archive.clone().open().await.unwrap();
+    assert!(!content.dht_ops.is_empty());
+    assert!(content.dht_ops.iter().all(|op| op.author() == alice));
+
+    // Move the cell to a second conductor with the same keystore.
+    let other_conductor =
+        SweetConductor::create_with_defaults(config, Some(keystore), None::<DynSweetRendezvous>)
+            .await;
+    other_conductor.register_dna(dna.clone()).await.unwrap();
+    let mut payload = get_install_app_payload_from_dnas("app", alice.clone(), &[(dna, None)]).await;
+    // Genesis fails since the CHC already holds the chain, but this creates
+    // the cell to import the archive into.
+    payload.ignore_genesis_failure = true;
+    assert!(other_conductor
+        .raw_handle()
+        .install_app_bundle(payload)
+        .await
+        .is_err());
+
+    other_conductor
+        .raw_handle()
+        .import_cell(archive, true)
+        .await
+        .unwrap();
+
+    let imported = other_conductor
+        .export_cell(alice_cell.cell_id())
+        .await
+        .unwrap()
+        .open()
+        .await
+        .unwrap();
+    assert_eq!(imported.records, content.records);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn list_warrants_and_unblock_warrantee() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    conductor.setup_app("app", [&dna]).await.unwrap();
+    let dna_hash = dna.dna_hash().clone();
+
+    // Store a warrant as if it had passed sys validation.
+    let warrantee = fixt!(AgentPubKey);
+    let warrant_op = WarrantOp::new(
+        Warrant::ChainIntegrity(ChainIntegrityWarrant::ChainFork {
+            chain_author: warrantee.clone(),
+            action_pair: (
+                (fixt!(ActionHash), fixt!(Signature)),
+                (fixt!(ActionHash), fixt!(Signature)),
+            ),
+        }),
+        fixt!(AgentPubKey),
+        fixt!(Signature),
+        Timestamp::now(),
+    );
+    let op = DhtOpHashed::from_content_sync(DhtOp::from(warrant_op.clone()));
+    let op_hash = op.as_hash().clone();
+    conductor
+        .get_dht_db(&dna_hash)
+        .unwrap()
+        .write_async({
+            let op_hash = op_hash.clone();
+            move |txn| -> StateMutationResult<()> {
+                insert_op(txn, &op)?;
+                set_validation_status(txn, &op_hash, ValidationStatus::Valid)?;
+                set_when_integrated(txn, &op_hash, Timestamp::now())
+            }
+        })
+        .await
+        .unwrap();
+    let now = Timestamp::now();
+    conductor
+        .block(Block::new(
+            BlockTarget::Cell(
+                CellId::new(dna_hash.clone(), warrantee),
+                CellBlockReason::Warrant(op_hash.clone()),
+            ),
+            InclusiveTimestampInterval::try_new(
+                now,
+                now.saturating_add(&std::time::Duration::from_secs(60)),
+            )
+            .unwrap(),
+        ))
+        .await
+        .unwrap();
+
+    let warrants = conductor
+        .list_warrants(Some(dna_hash.clone()))
+        .await
+        .unwrap();
+    assert_eq!(
+        warrants,
+        vec![WarrantInfo {
+            dna_hash: dna_hash.clone(),
+            op_hash: op_hash.clone(),
+            warrant_op,
+            validation_status: Some(ValidationStatus::Valid),
+            warrantee_blocked: true,
+        }]
+    );
+
+    // Lifting the block keeps the warrant.
+    conductor
+        .unblock_warrantee(dna_hash.clone(), op_hash)
+        .await
+        .unwrap();
+    let warrants = conductor
+        .list_warrants(Some(dna_hash.clone()))
+        .await
+        .unwrap();
+    assert_eq!(1, warrants.len());
+    assert!(!warrants[0].warrantee_blocked);
+
+    // Only warrants which are held can be used to lift a block.
+    assert!(conductor
+        .unblock_warrantee(dna_hash, fixt!(DhtOpHash))
+        .await
+        .is_err());
+}
+
+/// The encoded form of a [`CellArchive`], for changing an archive after it was signed.
+#[derive(Serialize, Deserialize)]
+struct RawCellArchive {
+    content: SerializedBytes,
+    signature: Signature,
+}
+
+impl RawCellArchive {
+    fn from_archive(archive: CellArchive) -> Self {
+        holochain_serialized_bytes::decode(SerializedBytes::try_from(archive).unwrap().bytes())
+            .unwrap()
+    }
+
+    fn into_archive(self) -> CellArchive {
+        let bytes = holochain_serialized_bytes::encode(&self).unwrap();
+        CellArchive::try_from(SerializedBytes::from(UnsafeBytes::from(bytes))).unwrap()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_cell_rejects_tampered_archive() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let _: ActionHash = conductor
+        .call(&cell.zome(SweetInlineZomes::COORDINATOR), "create_unit", ())
+        .await;
+
+    let archive = conductor.export_cell(cell.cell_id()).await.unwrap();
+    let mut content = archive.clone().open().await.unwrap();
+
+    // Drop the last record but keep the original signature.
+    content.records.pop();
+    let mut raw = RawCellArchive::from_archive(archive);
+    raw.content = SerializedBytes::try_from(content.clone()).unwrap();
+    let result = conductor
+        .raw_handle()
+        .import_cell(raw.into_archive(), true)
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::CellArchiveError(CellArchiveError::InvalidSignature(agent))
+        )) if &agent == cell.agent_pubkey()
+    );
+
+    // The chain is left as it was.
+    let records = conductor.export_cell(cell.cell_id()).await.unwrap();
+    let records = records.open().await.unwrap().records;
+    assert_eq!(records.len(), content.records.len() + 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn import_cell_rejects_archive_signed_by_another_agent() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    let archive = conductor.export_cell(cell.cell_id()).await.unwrap();
+
+    // Sign the unchanged content with the key of another agent.
+    let other_agent = SweetAgents::one(conductor.keystore()).await;
+    let mut raw = RawCellArchive::from_archive(archive);
+    raw.signature = other_agent
+        .sign_raw(
+            &conductor.keystore(),
+            Arc::from(raw.content.bytes().as_slice()),
+        )
+        .await
+        .unwrap();
+    let result = conductor
+        .raw_handle()
+        .import_cell(raw.into_archive(), true)
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorApiError::ConductorError(
+            ConductorError::CellArchiveError(CellArchiveError::InvalidSignature(agent))
+        )) if &agent == cell.agent_pubkey()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_to_new_dna() {
+    holochain_trace::test_run();
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&old_dna]).await.unwrap();
+    let (old_cell,) = app.into_tuple();
+    conductor.register_dna(new_dna.clone()).await.unwrap();
+
+    let new_cell_id = conductor
+        .raw_handle()
+        .migrate_agent(
+            &"app".to_string(),
+            &old_dna.dna_hash().to_string(),
+            new_dna.dna_hash().clone(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(new_cell_id.dna_hash(), new_dna.dna_hash());
+    assert_eq!(new_cell_id.agent_pubkey(), old_cell.agent_pubkey());
+
+    // The role is reassigned and only the new cell is running.
+    let state = conductor.get_state().await.unwrap();
+    let app = state.get_app(&"app".to_string()).unwrap();
+    assert_eq!(
+        app.role(&old_dna.dna_hash().to_string()).unwrap().cell_id(),
+        &new_cell_id
+    );
+    let running_cell_ids = conductor.running_cell_ids();
+    assert!(running_cell_ids.contains(&new_cell_id));
+    assert!(!running_cell_ids.contains(old_cell.cell_id()));
+
+    // The new chain is opened from the old DNA.
+    let new_records = conductor.export_cell(&new_cell_id).await.unwrap();
+    let new_records = new_records.open().await.unwrap().records;
+    assert_matches!(
+        new_records.last().unwrap().action(),
+        Action::OpenChain(OpenChain { prev_dna_hash, .. }) if prev_dna_hash == old_dna.dna_hash()
+    );
+
+    // The old chain was closed towards the new DNA and kept.
+    let old_chain_top: SignedAction = old_cell
+        .authored_db()
+        .read_async(|txn| -> StateQueryResult<_> {
+            let blob = txn.query_row(
+                "SELECT blob FROM Action ORDER BY seq DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )?;
+            from_blob(blob)
+        })
+        .await
+        .unwrap();
+    assert_matches!(
+        old_chain_top.action(),
+        Action::CloseChain(CloseChain { new_dna_hash, .. }) if new_dna_hash == new_dna.dna_hash()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_refused_by_callback() {
+    holochain_trace::test_run();
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentFail]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&old_dna]).await.unwrap();
+    let (old_cell,) = app.into_tuple();
+    conductor.register_dna(new_dna.clone()).await.unwrap();
+
+    let result = conductor
+        .raw_handle()
+        .migrate_agent(
+            &"app".to_string(),
+            &old_dna.dna_hash().to_string(),
+            new_dna.dna_hash().clone(),
+            None,
+        )
+        .await;
+    assert_matches!(
+        result,
+        Err(ConductorError::InternalCellError(
+            CellError::MigrateAgentFailed(_)
+        ))
+    );
+
+    // Nothing changed.
+    let state = conductor.get_state().await.unwrap();
+    let app = state.get_app(&"app".to_string()).unwrap();
+    assert_eq!(
+        app.role(&old_dna.dna_hash().to_string()).unwrap().cell_id(),
+        old_cell.cell_id()
+    );
+    assert!(conductor.running_cell_ids().contains(old_cell.cell_id()));
+}
+
+/// Count the CloseChain actions on the chain of a cell.
+async fn count_close_chain_actions(cell: &SweetCell) -> u32 {
+    cell.authored_db()
+        .read_async(|txn| -> StateQueryResult<_> {
+            Ok(txn.query_row(
+                "SELECT COUNT(*) FROM Action WHERE type = :type",
+                named_params! { ":type": ActionType::CloseChain.as_sql() },
+                |row| row.get(0),
+            )?)
+        })
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_refused_by_new_dna() {
+    holochain_trace::test_run();
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass, TestWasm::Create])
+            .await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentFail]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&old_dna]).await.unwrap();
+    let (old_cell,) = app.into_tuple();
+    conductor.register_dna(new_dna.clone()).await.unwrap();
+
+    for _ in 0..2 {
+        let result = conductor
+            .raw_handle()
+            .migrate_agent(
+                &"app".to_string(),
+                &old_dna.dna_hash().to_string(),
+                new_dna.dna_hash().clone(),
+                None,
+            )
+            .await;
+        assert_matches!(
+            result,
+            Err(ConductorError::InternalCellError(
+                CellError::MigrateAgentFailed(_)
+            ))
+        );
+
+        // The role keeps the old cell.
+        let state = conductor.get_state().await.unwrap();
+        let app = state.get_app(&"app".to_string()).unwrap();
+        assert_eq!(
+            app.role(&old_dna.dna_hash().to_string()).unwrap().cell_id(),
+            old_cell.cell_id()
+        );
+        assert!(conductor.running_cell_ids().contains(old_cell.cell_id()));
+    }
+
+    // The new DNA refused before the old chain was closed, so it is still writable.
+    assert_eq!(0, count_close_chain_actions(&old_cell).await);
+    let _: ActionHash = conductor
+        .call(&old_cell.zome(TestWasm::Create), "create_entry", ())
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_fails_genesis_of_new_cell() {
+    holochain_trace::test_run();
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let bad_zome = InlineZomeSet::new_unique_single("integrity", "custom", Vec::new(), 0).function(
+        "integrity",
+        "genesis_self_check",
+        |_api, _data: GenesisSelfCheckData| {
+            Ok(GenesisSelfCheckResult::Invalid(
+                "intentional invalid result for testing".into(),
+            ))
+        },
+    );
+    let (new_dna, _, _) = SweetDnaFile::unique_from_inline_zomes(bad_zome).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&old_dna]).await.unwrap();
+    let (old_cell,) = app.into_tuple();
+    conductor.register_dna(new_dna.clone()).await.unwrap();
+
+    let result = conductor
+        .raw_handle()
+        .migrate_agent(
+            &"app".to_string(),
+            &old_dna.dna_hash().to_string(),
+            new_dna.dna_hash().clone(),
+            None,
+        )
+        .await;
+    assert_matches!(result, Err(ConductorError::GenesisFailed { errors }) if errors.len() == 1);
+
+    // The old chain is still open and the role keeps the old cell.
+    assert_eq!(0, count_close_chain_actions(&old_cell).await);
+    let state = conductor.get_state().await.unwrap();
+    let app = state.get_app(&"app".to_string()).unwrap();
+    assert_eq!(
+        app.role(&old_dna.dna_hash().to_string()).unwrap().cell_id(),
+        old_cell.cell_id()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_resumes_after_old_chain_was_closed() {
+    holochain_trace::test_run();
+    let (old_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&old_dna]).await.unwrap();
+    let (old_cell,) = app.into_tuple();
+    conductor.register_dna(new_dna.clone()).await.unwrap();
+
+    // A migration which was interrupted after the old chain was closed.
+    conductor
+        .raw_handle()
+        .cell_by_id(old_cell.cell_id())
+        .await
+        .unwrap()
+        .migrate_agent(MigrateAgent::Close, new_dna.dna_hash().clone())
+        .await
+        .unwrap();
+
+    let new_cell_id = conductor
+        .raw_handle()
+        .migrate_agent(
+            &"app".to_string(),
+            &old_dna.dna_hash().to_string(),
+            new_dna.dna_hash().clone(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(new_cell_id.dna_hash(), new_dna.dna_hash());
+    assert!(conductor.running_cell_ids().contains(&new_cell_id));
+
+    // The old chain is not closed a second time.
+    assert_eq!(1, count_close_chain_actions(&old_cell).await);
+    let new_records = conductor.export_cell(&new_cell_id).await.unwrap();
+    let new_records = new_records.open().await.unwrap().records;
+    assert_matches!(
+        new_records.last().unwrap().action(),
+        Action::OpenChain(OpenChain { prev_dna_hash, .. }) if prev_dna_hash == old_dna.dna_hash()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scheduled_fn_runs_are_bounded_by_max_concurrency() {
+    holochain_trace::test_run();
+    let started = Arc::new(AtomicU32::new(0));
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    let release_rx = Arc::new(parking_lot::Mutex::new(release_rx));
+    let zomes = SweetInlineZomes::new(vec![], 0).function("slow", {
+        let started = started.clone();
+        move |_api, _: Option<Schedule>| {
+            started.fetch_add(1, Ordering::SeqCst);
+            // Stay in progress until the test lets the run finish.
+            release_rx.lock().recv().ok();
+            Ok(None::<Schedule>)
+        }
+    });
+    let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let cell_id = cell.cell_id().clone();
+
+    // Once a year, so that the scheduler itself doesn't run the function.
+    let schedule: Schedule = CronSchedule::new("0 0 0 1 1 * *")
+        .with_max_concurrency(2)
+        .into();
+    let author = cell_id.agent_pubkey().clone();
+    cell.authored_db()
+        .write_async(move |txn| {
+            holochain_state::mutations::schedule_fn(
+                txn,
+                &author,
+                ScheduledFn::new(SweetInlineZomes::COORDINATOR.into(), "slow".into()),
+                Some(schedule),
+                Timestamp::now(),
+            )
+        })
+        .await
+        .unwrap();
+
+    let trigger = || {
+        let conductor = conductor.raw_handle();
+        let cell_id = cell_id.clone();
+        async move {
+            conductor
+                .trigger_scheduled_function(
+                    &cell_id,
+                    SweetInlineZomes::COORDINATOR.into(),
+                    "slow".into(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+    let first = tokio::spawn(trigger());
+    let second = tokio::spawn(trigger());
+    tokio::time::timeout(std::time::Duration::from_secs(30), async {
+        while started.load(Ordering::SeqCst) < 2 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    // A third run would exceed the limit, so it is skipped and recorded as such.
+    let skipped = trigger().await;
+    assert!(skipped.error.unwrap().starts_with("Skipped"));
+    assert_eq!(2, started.load(Ordering::SeqCst));
+
+    release_tx.send(()).unwrap();
+    release_tx.send(()).unwrap();
+    assert_eq!(None, first.await.unwrap().error);
+    assert_eq!(None, second.await.unwrap().error);
+
+    let runs = conductor
+        .raw_handle()
+        .list_scheduled_function_runs(&cell_id, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(3, runs.len());
+
+    // The runs of an unknown cell are not listed.
+    assert_matches!(
+        conductor
+            .raw_handle()
+            .list_scheduled_function_runs(&fake_cell_id(1), None, None, None)
+            .await,
+        Err(ConductorError::CellMissing(_))
+    );
+}
@@ -0,0 +1,317 @@
+//! The key the databases of a conductor are encrypted with.
+//!
+//! Every conductor has its own random [`DbKey`]. It is stored in the databases
+//! directory, encrypted with a secret which never leaves the keystore, so the
+//! databases can only be read with access to the conductor's keystore.
+//!
+//! The danger test keystore is spawned anew, with new secrets, every time the
+//! conductor starts, so with it the key is stored unencrypted instead.
+
+use super::error::ConductorError;
+use super::error::ConductorResult;
+use holochain_keystore::MetaLairClient;
+use holochain_sqlite::db::DbKey;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Name of the file in the databases directory which holds the locked database key.
+pub const DB_KEY_FILE: &str = "db.key";
+
+/// Name of the file holding the next database key while the key is rotated.
+#[cfg(feature = "sqlite-encrypted")]
+const NEXT_DB_KEY_FILE: &str = "db.key.next";
+
+/// A [`DbKey`] as it is stored in the databases directory.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StoredDbKey {
+    /// Encrypted with a secret in the keystore.
+    Locked {
+        /// The tag of the keystore secret the key is encrypted with.
+        lair_tag: Arc<str>,
+        nonce: [u8; 24],
+        cipher: Vec<u8>,
+    },
+    /// Not encrypted, for a keystore which doesn't keep its secrets.
+    Unlocked { key: [u8; 32] },
+}
+
+/// Get the key of the databases in a databases directory, creating a new one
+/// if there is none yet.
+///
+/// The key is locked with the given keystore, or stored unlocked without one.
+pub async fn get_or_create_db_key(
+    keystore: Option<&MetaLairClient>,
+    databases_dir: &Path,
+) -> ConductorResult<DbKey> {
+    let path = databases_dir.join(DB_KEY_FILE);
+    match read_db_key(keystore, &path).await? {
+        Some(key) => Ok(key),
+        None => {
+            let key = DbKey::generate()?;
+            write_db_key(keystore, &path, &key).await?;
+            Ok(key)
+        }
+    }
+}
+
+/// Re-encrypt all databases in a databases directory with a new key.
+///
+/// The conductor using the databases must not be running. If the rotation is
+/// interrupted, running it again completes it.
+#[cfg(feature = "sqlite-encrypted")]
+pub async fn rotate_db_key(
+    keystore: Option<&MetaLairClient>,
+    databases_dir: &Path,
+) -> ConductorResult<()> {
+    let path = databases_dir.join(DB_KEY_FILE);
+    let next_path = databases_dir.join(NEXT_DB_KEY_FILE);
+    let key = get_or_create_db_key(keystore, databases_dir).await?;
+
+    // The next key is stored before any database is rekeyed, so that it isn't
+    // lost if the rotation is interrupted.
+    let next_key = match read_db_key(keystore, &next_path).await? {
+        Some(next_key) => next_key,
+        None => {
+            let next_key = DbKey::generate()?;
+            write_db_key(keystore, &next_path, &next_key).await?;
+            next_key
+        }
+    };
+
+    let mut databases = Vec::new();
+    find_databases(databases_dir, &mut databases)?;
+    for database in databases {
+        tracing::info!(?database, "Rekeying database");
+        holochain_sqlite::db::rekey_database(&database, &key, &next_key)?;
+    }
+
+    std::fs::rename(next_path, path)?;
+    Ok(())
+}
+
+async fn read_db_key(
+    keystore: Option<&MetaLairClient>,
+    path: &Path,
+) -> ConductorResult<Option<DbKey>> {
+    let stored = match std::fs::read(path) {
+        Ok(stored) => stored,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let stored: StoredDbKey = serde_json::from_slice(&stored).map_err(ConductorError::other)?;
+    match (stored, keystore) {
+        (
+            StoredDbKey::Locked {
+                lair_tag,
+                nonce,
+                cipher,
+            },
+            Some(keystore),
+        ) => {
+            let key = keystore
+                .shared_secret_decrypt(lair_tag, nonce, cipher.into())
+                .await?;
+            let key = <[u8; 32]>::try_from(&key[..]).map_err(|_| {
+                ConductorError::other(format!("Invalid database key in {:?}", path))
+            })?;
+            Ok(Some(DbKey::from_bytes(key)))
+        }
+        (StoredDbKey::Unlocked { key }, None) => Ok(Some(DbKey::from_bytes(key))),
+        // A key is never silently downgraded to, or upgraded from, an unlocked one.
+        (StoredDbKey::Locked { .. }, None) => Err(ConductorError::other(format!(
+            "The database key in {:?} is locked by a keystore, but the conductor uses the danger test keystore",
+            path
+        ))),
+        (StoredDbKey::Unlocked { .. }, Some(_)) => Err(ConductorError::other(format!(
+            "The database key in {:?} is not locked by the keystore of the conductor",
+            path
+        ))),
+    }
+}
+
+async fn write_db_key(
+    keystore: Option<&MetaLairClient>,
+    path: &Path,
+    key: &DbKey,
+) -> ConductorResult<()> {
+    let stored = match keystore {
+        Some(keystore) => {
+            let lair_tag: Arc<str> = format!("db_key_{}", nanoid::nanoid!()).into();
+            keystore.new_shared_secret(lair_tag.clone()).await?;
+            let (nonce, cipher) = keystore
+                .shared_secret_encrypt(lair_tag.clone(), key.as_bytes().to_vec().into())
+                .await?;
+            StoredDbKey::Locked {
+                lair_tag,
+                nonce,
+                cipher: cipher.to_vec(),
+            }
+        }
+        None => StoredDbKey::Unlocked {
+            key: *key.as_bytes(),
+        },
+    };
+
+    // Write to a temporary file first, so that the key is never half written.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(
+        &tmp_path,
+        serde_json::to_vec(&stored).map_err(ConductorError::other)?,
+    )?;
+    std::fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
+/// Collect the paths of all database files below a directory.
+#[cfg(feature = "sqlite-encrypted")]
+fn find_databases(dir: &Path, databases: &mut Vec<std::path::PathBuf>) -> ConductorResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_databases(&path, databases)?;
+        } else if path.extension().map_or(false, |ext| ext == "sqlite3") {
+            databases.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conductor::config::ConductorConfig;
+    use crate::conductor::Conductor;
+    use holochain_conductor_api::conductor::paths::DataRootPath;
+    use holochain_conductor_api::conductor::paths::DatabasesRootPath;
+    use holochain_conductor_api::conductor::KeystoreConfig;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn db_key_is_stored_locked_by_the_keystore() {
+        let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let key = get_or_create_db_key(Some(&keystore), dir.path())
+            .await
+            .unwrap();
+        assert_eq!(
+            key,
+            get_or_create_db_key(Some(&keystore), dir.path())
+                .await
+                .unwrap()
+        );
+
+        // The stored key can't be unlocked by another keystore, nor read without one
+        let other_keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        assert!(get_or_create_db_key(Some(&other_keystore), dir.path())
+            .await
+            .is_err());
+        assert!(get_or_create_db_key(None, dir.path()).await.is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn conductor_with_danger_test_keystore_restarts_with_its_db_key() {
+        holochain_trace::test_run();
+        let dir = tempfile::tempdir().unwrap();
+        let data_root_path = DataRootPath::from(dir.path().to_path_buf());
+        let databases_dir = DatabasesRootPath::try_from(data_root_path.clone()).unwrap();
+        let config = || ConductorConfig {
+            data_root_path: Some(data_root_path.clone()),
+            keystore: KeystoreConfig::DangerTestKeystore,
+            ..Default::default()
+        };
+
+        let conductor = Conductor::builder().config(config()).build().await.unwrap();
+        let key = get_or_create_db_key(None, &databases_dir).await.unwrap();
+        conductor.shutdown().await.unwrap().unwrap();
+
+        // The keystore of the restarted conductor has other secrets
+        let conductor = Conductor::builder().config(config()).build().await.unwrap();
+        assert_eq!(
+            key,
+            get_or_create_db_key(None, &databases_dir).await.unwrap()
+        );
+        conductor.shutdown().await.unwrap().unwrap();
+    }
+
+    #[cfg(feature = "sqlite-encrypted")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rotate_db_key_rekeys_databases_and_resumes() {
+        use holochain_sqlite::db::DbKindT;
+        use holochain_sqlite::db::DbKindWasm;
+        use holochain_sqlite::db::DbSyncLevel;
+        use holochain_sqlite::db::DbWrite;
+        use holochain_sqlite::error::DatabaseResult;
+
+        let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let open = |key: DbKey| {
+            DbWrite::new(
+                Some(dir.path()),
+                DbKindWasm,
+                DbSyncLevel::default(),
+                key,
+                None,
+            )
+        };
+        let count = |db: DbWrite<DbKindWasm>| async move {
+            db.read_async(|txn| {
+                DatabaseResult::Ok(txn.query_row("SELECT COUNT(rowid) FROM Wasm", [], |row| {
+                    row.get::<_, usize>(0)
+                })?)
+            })
+            .await
+            .unwrap()
+        };
+
+        let key = get_or_create_db_key(Some(&keystore), dir.path())
+            .await
+            .unwrap();
+        let db = open(key.clone()).unwrap();
+        db.write_async(|txn| {
+            txn.execute(
+                "INSERT INTO Wasm (hash, blob) VALUES(?, ?)",
+                [vec![0], vec![0]],
+            )?;
+            DatabaseResult::Ok(())
+        })
+        .await
+        .unwrap();
+        drop(db);
+
+        rotate_db_key(Some(&keystore), dir.path()).await.unwrap();
+        let new_key = get_or_create_db_key(Some(&keystore), dir.path())
+            .await
+            .unwrap();
+        assert_ne!(key, new_key);
+        assert!(open(key).is_err());
+        assert_eq!(1, count(open(new_key.clone()).unwrap()).await);
+
+        // Interrupt a rotation after the database was rekeyed, but before the
+        // next key replaced the current one.
+        let next_key = DbKey::generate().unwrap();
+        write_db_key(
+            Some(&keystore),
+            &dir.path().join(NEXT_DB_KEY_FILE),
+            &next_key,
+        )
+        .await
+        .unwrap();
+        holochain_sqlite::db::rekey_database(
+            &dir.path().join(DbKindWasm.filename()),
+            &new_key,
+            &next_key,
+        )
+        .unwrap();
+
+        rotate_db_key(Some(&keystore), dir.path()).await.unwrap();
+        assert_eq!(
+            next_key,
+            get_or_create_db_key(Some(&keystore), dir.path())
+                .await
+                .unwrap()
+        );
+        assert!(!dir.path().join(NEXT_DB_KEY_FILE).exists());
+        assert_eq!(1, count(open(next_key).unwrap()).await);
+    }
+}
@@ -40,6 +40,9 @@ pub enum ConductorError {
     #[error("Error while cloning cell: {0}")]
     CloneCellError(String),
 
+    #[error("Error while migrating agent: {0}")]
+    MigrateAgentError(String),
+
     #[error(transparent)]
     ConductorConfigError(#[from] ConductorConfigError),
 
@@ -86,6 +89,15 @@ pub enum ConductorError {
     #[error("Tried to access an app that was not installed: {0}")]
     AppNotInstalled(InstalledAppId),
 
+    #[error("The app {0} can't be uninstalled while these apps use its cells: {1:?}")]
+    AppHasDependents(InstalledAppId, Vec<InstalledAppId>),
+
+    #[error("The app store does not contain an app bundle with hash: {0}")]
+    AppBundleNotInStore(AppHash),
+
+    #[error(transparent)]
+    AppStoreServiceError(#[from] holochain_conductor_services::AppStoreServiceError),
+
     #[error("Tried to install an app using an already-used InstalledAppId: {0}")]
     AppAlreadyInstalled(InstalledAppId),
 
@@ -125,6 +137,12 @@ pub enum ConductorError {
     #[error(transparent)]
     RibosomeError(#[from] crate::core::ribosome::error::RibosomeError),
 
+    #[error(transparent)]
+    CellArchiveError(#[from] holochain_conductor_api::cell_archive::CellArchiveError),
+
+    #[error("The function {1}:{2} is not scheduled for cell {0:?}")]
+    FunctionNotScheduled(CellId, ZomeName, FunctionName),
+
     #[error("Authentication failed with reason: {0}")]
     FailedAuthenticationError(String),
 
@@ -2,8 +2,9 @@
 //! and dispatch them to the appropriate handlers within Holochain.
 //! They also allow emitting responses and one-way Signals.
 //!
-//! Currently, the only InterfaceDriver is a Websocket-based one, whose
-//! implementation can be found in the `websocket` module here.
+//! Both InterfaceDrivers are Websocket-based, listening either on a TCP port
+//! or on a Unix domain socket. Their implementation can be found in the
+//! `websocket` module here.
 
 #[allow(missing_docs)]
 pub mod error;
@@ -1,28 +1,34 @@
 //! Module for establishing Websocket-based Interfaces,
 //! i.e. those configured with `InterfaceDriver::Websocket`
+//! or `InterfaceDriver::UnixSocket`
 
 use super::error::InterfaceResult;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::conductor::app_broadcast::FilteredSignalReceiver;
+use crate::conductor::conductor::app_broadcast::SignalFilterHandle;
 use crate::conductor::manager::TaskManagerClient;
 use holochain_serialized_bytes::SerializedBytes;
-use holochain_types::signal::Signal;
 use holochain_websocket::ReceiveMessage;
+use holochain_websocket::TlsServerConfig;
 use holochain_websocket::WebsocketConfig;
 use holochain_websocket::WebsocketListener;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
 use std::io::ErrorKind;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+use std::path::Path;
 
 use crate::conductor::api::{AdminInterfaceApi, AppAuthentication, AppInterfaceApi};
+use holochain_conductor_api::signal_subscription::SignalSubscription;
 use holochain_conductor_api::{
     AdminRequest, AdminResponse, AppAuthenticationRequest, AppRequest, AppResponse,
+    ExternalApiWireError, SequencedSignal,
 };
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::{Signal, SystemSignal};
 use holochain_types::websocket::AllowedOrigins;
 use std::sync::Arc;
 use tokio::pin;
-use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tracing::*;
 
@@ -35,15 +41,18 @@ const CONCURRENCY_COUNT: usize = 128;
 /// The maximum number of connections allowed to the admin interface
 pub const MAX_CONNECTIONS: usize = 400;
 
-/// Create a WebsocketListener to be used in interfaces
+/// Create a WebsocketListener to be used in interfaces,
+/// which only accepts TLS connections if `tls` is set
 pub async fn spawn_websocket_listener(
     port: u16,
     allowed_origins: AllowedOrigins,
+    tls: Option<TlsServerConfig>,
 ) -> InterfaceResult<WebsocketListener> {
     trace!("Initializing Admin interface");
 
     let mut config = WebsocketConfig::LISTENER_DEFAULT;
     config.allowed_origins = Some(allowed_origins);
+    config.tls = tls;
 
     let listener = WebsocketListener::dual_bind(
         Arc::new(config),
@@ -55,6 +64,33 @@ pub async fn spawn_websocket_listener(
     Ok(listener)
 }
 
+/// Create a WebsocketListener on a Unix domain socket to be used in interfaces.
+///
+/// Access is controlled by the permissions of the socket file, so any origin is allowed.
+pub async fn spawn_unix_socket_listener(
+    path: &Path,
+    mode: u32,
+) -> InterfaceResult<WebsocketListener> {
+    trace!("Initializing Unix socket interface");
+
+    #[cfg(unix)]
+    {
+        let listener =
+            WebsocketListener::bind_unix(Arc::new(WebsocketConfig::LISTENER_DEFAULT), path, mode)
+                .await?;
+        trace!("LISTENING AT: {:?}", path);
+        Ok(listener)
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = mode;
+        Err(super::error::InterfaceError::Other(format!(
+            "Cannot listen on {path:?}, Unix socket interfaces are only supported on Unix"
+        )))
+    }
+}
+
 type TaskListInner = Arc<parking_lot::Mutex<Vec<JoinHandle<()>>>>;
 
 /// Abort tokio tasks on Drop.
@@ -77,14 +113,16 @@ impl TaskList {
 }
 
 /// Create an Admin Interface, which only receives AdminRequest messages
-/// from the external client
+/// from the external client.
+///
+/// The `interface` describes where the listener is bound, e.g. `port 1234`.
 pub fn spawn_admin_interface_tasks(
     tm: TaskManagerClient,
     listener: WebsocketListener,
     api: AdminInterfaceApi,
-    port: u16,
+    interface: &str,
 ) {
-    tm.add_conductor_task_ignored(&format!("admin interface, port {}", port), move || {
+    tm.add_conductor_task_ignored(&format!("admin interface, {}", interface), move || {
         async move {
             let mut task_list = TaskList::default();
             // establish a new connection to a client
@@ -116,18 +154,25 @@ pub fn spawn_admin_interface_tasks(
 
 /// Create an App Interface, which includes the ability to receive signals
 /// from Cells via a broadcast channel
+///
+/// If `signal_replay_buffer_size` is set, the last signals of each app are kept
+/// so that clients can resume receiving signals after reconnecting.
+#[allow(clippy::too_many_arguments)]
 pub async fn spawn_app_interface_task(
     tm: TaskManagerClient,
     port: u16,
     allowed_origins: AllowedOrigins,
+    tls: Option<TlsServerConfig>,
     installed_app_id: Option<InstalledAppId>,
     api: AppInterfaceApi,
     app_broadcast: AppBroadcast,
+    signal_replay_buffer_size: Option<usize>,
 ) -> InterfaceResult<u16> {
     trace!("Initializing App interface");
 
     let mut config = WebsocketConfig::LISTENER_DEFAULT;
     config.allowed_origins = Some(allowed_origins);
+    config.tls = tls;
 
     let listener = WebsocketListener::dual_bind(
         Arc::new(config),
@@ -139,6 +184,60 @@ pub async fn spawn_app_interface_task(
     trace!("LISTENING AT: {:?}", addrs);
     let port = addrs[0].port();
 
+    spawn_app_connection_handler(
+        tm,
+        listener,
+        format!("port {port}").into(),
+        installed_app_id,
+        api,
+        app_broadcast,
+        signal_replay_buffer_size,
+    );
+    Ok(port)
+}
+
+/// Create an App Interface on a Unix domain socket, which includes the ability
+/// to receive signals from Cells via a broadcast channel.
+pub async fn spawn_app_unix_socket_interface_task(
+    tm: TaskManagerClient,
+    path: &Path,
+    mode: u32,
+    installed_app_id: Option<InstalledAppId>,
+    api: AppInterfaceApi,
+    app_broadcast: AppBroadcast,
+    signal_replay_buffer_size: Option<usize>,
+) -> InterfaceResult<()> {
+    let listener = spawn_unix_socket_listener(path, mode).await?;
+    spawn_app_connection_handler(
+        tm,
+        listener,
+        format!("socket {}", path.display()).into(),
+        installed_app_id,
+        api,
+        app_broadcast,
+        signal_replay_buffer_size,
+    );
+    Ok(())
+}
+
+/// Accept connections to an App Interface and authenticate them.
+fn spawn_app_connection_handler(
+    tm: TaskManagerClient,
+    listener: WebsocketListener,
+    interface: Arc<str>,
+    installed_app_id: Option<InstalledAppId>,
+    api: AppInterfaceApi,
+    app_broadcast: AppBroadcast,
+    signal_replay_buffer_size: Option<usize>,
+) {
+    let signal_replay_buffer_size = signal_replay_buffer_size.filter(|size| *size > 0);
+    // An interface bound to an app keeps its signals from the start, not only
+    // from the first client connecting.
+    if let (Some(installed_app_id), Some(buffer_size)) =
+        (&installed_app_id, signal_replay_buffer_size)
+    {
+        app_broadcast.enable_replay(installed_app_id.clone(), buffer_size);
+    }
     tm.add_conductor_task_ignored("app interface new connection handler", move || {
         async move {
             let task_list = TaskList::default();
@@ -153,7 +252,8 @@ pub async fn spawn_app_interface_task(
                             app_broadcast.clone(),
                             tx_to_iface,
                             installed_app_id.clone(),
-                            port,
+                            interface.clone(),
+                            signal_replay_buffer_size,
                         );
                     }
                     Err(err) => {
@@ -163,7 +263,6 @@ pub async fn spawn_app_interface_task(
             }
         }
     });
-    Ok(port)
 }
 
 /// Polls for messages coming in from the external client.
@@ -205,6 +304,7 @@ async fn recv_incoming_admin_msgs(api: AdminInterfaceApi, rx_from_iface: Websock
 /// connection is dropped.
 /// If the authentication succeeds, then message handling tasks are spawned to handle normal
 /// communication with the client.
+#[allow(clippy::too_many_arguments)]
 fn authenticate_incoming_app_connection(
     task_list: TaskListInner,
     api: AppInterfaceApi,
@@ -212,36 +312,25 @@ fn authenticate_incoming_app_connection(
     app_broadcast: AppBroadcast,
     tx_to_iface: WebsocketSender,
     installed_app_id: Option<InstalledAppId>,
-    port: u16,
+    interface: Arc<str>,
+    signal_replay_buffer_size: Option<usize>,
 ) {
     let join_handle = tokio::task::spawn({
         let task_list = task_list.clone();
         async move {
             let auth_payload_result = tokio::time::timeout(std::time::Duration::from_secs(10), async {
-                if let Ok(msg) = rx_from_iface.recv::<AppRequest>().await {
-                    return match msg {
-                        ReceiveMessage::Authenticate(auth_payload) => {
-                            Ok(auth_payload)
+                loop {
+                    let auth_payload = match rx_from_iface.recv::<AppRequest>().await {
+                        Ok(ReceiveMessage::Authenticate(auth_payload)) => auth_payload,
+                        Ok(_) => {
+                            warn!("Connection to Holochain app {interface} tried to send a message before authenticating. Dropping connection.");
+                            return Err(());
                         }
-                        _ => {
-                            warn!("Connection to Holochain app port {port} tried to send a message before authenticating. Dropping connection.");
-                            Err(())
+                        Err(_) => {
+                            warn!("Could not receive authentication message, the client either disconnected or sent a message that didn't decode to an authentication request. Dropping connection.");
+                            return Err(());
                         }
-                    }
-                }
-
-                warn!("Could not receive authentication message, the client either disconnected or sent a message that didn't decode to an authentication request. Dropping connection.");
-                Err(())
-            }).await;
-
-            match auth_payload_result {
-                Err(_) => {
-                    warn!("Connection to Holochain app port {port} timed out while awaiting authentication. Dropping connection.");
-                }
-                Ok(Err(_)) => {
-                    // Already logged, continue to drop connection
-                }
-                Ok(Ok(auth_payload)) => {
+                    };
                     let payload: AppAuthenticationRequest = match SerializedBytes::from(
                         holochain_serialized_bytes::UnsafeBytes::from(auth_payload),
                     )
@@ -249,11 +338,45 @@ fn authenticate_incoming_app_connection(
                     {
                         Ok(payload) => payload,
                         Err(e) => {
-                            warn!("Holochain app port {port} received a payload that failed to decode into an authentication payload: {e}. Dropping connection.");
-                            return;
+                            warn!("Holochain app {interface} received a payload that failed to decode into an authentication payload: {e}. Dropping connection.");
+                            return Err(());
                         }
                     };
 
+                    // Signals can't be resumed from a point which is not in the current epoch.
+                    // This is checked before the token is used, so that the client can
+                    // authenticate again with the same token without resuming.
+                    if let (Some(buffer_size), Some(resume_from)) =
+                        (signal_replay_buffer_size, payload.resume_signals_from)
+                    {
+                        if let Some(token_app_id) = api.peek_auth(&payload.token) {
+                            if let Err(epoch) = app_broadcast.check_resume_point(
+                                token_app_id,
+                                buffer_size,
+                                resume_from,
+                            ) {
+                                warn!("Connection to Holochain app {interface} tried to resume signals from {resume_from:?}, which is not in the current epoch.");
+                                let refused = SystemSignal::SignalResumeRefused { epoch };
+                                if tx_to_iface.signal(Signal::from(refused)).await.is_err() {
+                                    return Err(());
+                                }
+                                continue;
+                            }
+                        }
+                    }
+
+                    return Ok(payload);
+                }
+            }).await;
+
+            match auth_payload_result {
+                Err(_) => {
+                    warn!("Connection to Holochain app {interface} timed out while awaiting authentication. Dropping connection.");
+                }
+                Ok(Err(_)) => {
+                    // Already logged, continue to drop connection
+                }
+                Ok(Ok(payload)) => {
                     match api
                         .auth(AppAuthentication {
                             token: payload.token,
@@ -264,13 +387,35 @@ fn authenticate_incoming_app_connection(
                         Ok(installed_app_id) => {
                             // Once authentication passes we know which app this connection is for,
                             // so we can subscribe to app signals now.
-                            let rx_from_cell = app_broadcast.subscribe(installed_app_id.clone());
+                            // Each connection has its own signal filters, which the client can
+                            // change by setting a signal subscription.
+                            // If the interface keeps a replay buffer, the client may resume
+                            // from the sequence number of a signal it received before.
+                            let signal_filters = SignalFilterHandle::default();
+                            let rx_from_cell = match signal_replay_buffer_size {
+                                Some(buffer_size) => match app_broadcast.subscribe_replayable(
+                                    installed_app_id.clone(),
+                                    signal_filters.clone(),
+                                    buffer_size,
+                                    payload.resume_signals_from,
+                                ) {
+                                    Some(rx_from_cell) => rx_from_cell,
+                                    None => {
+                                        warn!("Connection to Holochain app {interface} tried to resume signals from {:?}, which is not in the current epoch. Dropping connection.", payload.resume_signals_from);
+                                        return;
+                                    }
+                                },
+                                None => app_broadcast.subscribe_filtered(
+                                    installed_app_id.clone(),
+                                    signal_filters.clone(),
+                                ),
+                            };
 
                             spawn_app_signals_handler(
                                 task_list.clone(),
                                 rx_from_cell,
                                 tx_to_iface.clone(),
-                                port,
+                                interface,
                                 installed_app_id.clone(),
                             );
                             spawn_recv_incoming_app_msgs(
@@ -278,10 +423,11 @@ fn authenticate_incoming_app_connection(
                                 api,
                                 rx_from_iface,
                                 installed_app_id,
+                                signal_filters,
                             );
                         }
                         Err(e) => {
-                            warn!("Connection to Holochain app port {port} failed to authenticate: {e}. Dropping connection.");
+                            warn!("Connection to Holochain app {interface} failed to authenticate: {e}. Dropping connection.");
                         }
                     }
                 }
@@ -297,21 +443,22 @@ fn authenticate_incoming_app_connection(
 /// the connected client via `tx_to_iface`.
 fn spawn_app_signals_handler(
     task_list: TaskListInner,
-    rx_from_cell: broadcast::Receiver<Signal>,
+    rx_from_cell: FilteredSignalReceiver,
     tx_to_iface: WebsocketSender,
-    port: u16,
+    interface: Arc<str>,
     installed_app_id: InstalledAppId,
 ) {
     use futures::stream::StreamExt;
 
     let rx_from_cell = futures::stream::unfold(rx_from_cell, move |mut rx_from_cell| {
+        let interface = interface.clone();
         let installed_app_id = installed_app_id.clone();
         async move {
             loop {
-                match rx_from_cell.recv().await {
+                match rx_from_cell.recv_sequenced().await {
                     // We missed some signals, but the channel is still open
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(dropped)) => {
-                        warn!("Holochain app port {port} dropped {dropped} signals. The app '{installed_app_id}' is emitting signals too fast.");
+                        warn!("Holochain app {interface} dropped {dropped} signals. The app '{installed_app_id}' is emitting signals too fast.");
                         continue;
                     }
                     Ok(item) => return Some((item, rx_from_cell)),
@@ -327,9 +474,17 @@ fn spawn_app_signals_handler(
     task_list.lock().push(tokio::task::spawn(async move {
         pin!(rx_from_cell);
         loop {
-            if let Some(signal) = rx_from_cell.next().await {
-                trace!(msg = "Sending signal!", ?signal);
-                if let Err(err) = tx_to_iface.signal(signal).await {
+            if let Some((seq, signal)) = rx_from_cell.next().await {
+                trace!(msg = "Sending signal!", ?seq, ?signal);
+                let sent = match seq {
+                    Some((epoch, seq)) => {
+                        tx_to_iface
+                            .signal(SequencedSignal { epoch, seq, signal })
+                            .await
+                    }
+                    None => tx_to_iface.signal(signal).await,
+                };
+                if let Err(err) = sent {
                     if err.kind() == ErrorKind::Other && err.to_string() == "WebsocketClosed" {
                         info!(
                             "Client has closed their websocket connection, closing signal handler"
@@ -355,6 +510,7 @@ fn spawn_recv_incoming_app_msgs(
     api: AppInterfaceApi,
     rx_from_iface: WebsocketReceiver,
     installed_app_id: InstalledAppId,
+    signal_filters: SignalFilterHandle,
 ) {
     use futures::stream::StreamExt;
 
@@ -379,8 +535,11 @@ fn spawn_recv_incoming_app_msgs(
             move |msg| {
                 let installed_app_id = installed_app_id.clone();
                 let api = api.clone();
+                let signal_filters = signal_filters.clone();
                 async move {
-                    if let Err(err) = handle_incoming_app_message(msg, installed_app_id, api).await
+                    if let Err(err) =
+                        handle_incoming_app_message(msg, installed_app_id, api, signal_filters)
+                            .await
                     {
                         error!(?err, "error handling app websocket message");
                     }
@@ -429,6 +588,7 @@ async fn handle_incoming_app_message(
     ws_msg: ReceiveMessage<AppRequest>,
     installed_app_id: InstalledAppId,
     api: AppInterfaceApi,
+    signal_filters: SignalFilterHandle,
 ) -> InterfaceResult<()> {
     match ws_msg {
         ReceiveMessage::Signal(_) => {
@@ -441,7 +601,12 @@ async fn handle_incoming_app_message(
         }
         ReceiveMessage::Request(data, respond) => {
             use holochain_serialized_bytes::SerializedBytesError;
-            let result: AppResponse = api.handle_request(installed_app_id, Ok(data)).await?;
+            let result: AppResponse = match data {
+                AppRequest::SetSignalSubscription(subscription) => {
+                    set_signal_subscription(*subscription, &installed_app_id, &signal_filters)
+                }
+                data => api.handle_request(installed_app_id, Ok(data)).await?,
+            };
             // Have to jump through some hoops, because our response type
             // only implements try_into, but the responder needs try_from.
             let result = result.try_into();
@@ -460,6 +625,23 @@ async fn handle_incoming_app_message(
     }
 }
 
+/// Apply a signal subscription to the connection owning the signal filters.
+fn set_signal_subscription(
+    subscription: SignalSubscription,
+    installed_app_id: &InstalledAppId,
+    signal_filters: &SignalFilterHandle,
+) -> AppResponse {
+    if subscription.installed_app_id() != installed_app_id {
+        return AppResponse::Error(ExternalApiWireError::internal(format!(
+            "Cannot set a signal subscription for app '{}' on a connection for app '{}'",
+            subscription.installed_app_id(),
+            installed_app_id
+        )));
+    }
+    signal_filters.set(subscription.into_filters());
+    AppResponse::SignalSubscriptionSet
+}
+
 /// Test items needed by other crates
 #[cfg(any(test, feature = "test_utils"))]
 pub use crate::test_utils::setup_app_in_new_conductor;
@@ -473,6 +655,8 @@ pub mod test {
     use crate::conductor::api::AdminResponse;
     use crate::conductor::api::AppInterfaceApi;
     use crate::conductor::conductor::ConductorBuilder;
+    use crate::conductor::config::AppStoreConfig;
+    use crate::conductor::config::ConductorConfig;
     use crate::conductor::state::ConductorState;
     use crate::conductor::Conductor;
     use crate::conductor::ConductorHandle;
@@ -485,12 +669,14 @@ pub mod test {
     use crate::test_utils::install_app_in_conductor;
     use ::fixt::prelude::*;
     use holochain_conductor_api::*;
+    use holochain_conductor_services::AppStoreLocalDir;
     use holochain_keystore::test_keystore;
     use holochain_p2p::{AgentPubKeyExt, DnaHashExt};
     use holochain_serialized_bytes::prelude::*;
     use holochain_sqlite::prelude::*;
     use holochain_state::prelude::*;
     use holochain_trace;
+    use holochain_types::signal::Signal;
     use holochain_types::test_utils::fake_agent_pubkey_1;
     use holochain_types::test_utils::fake_dna_zomes;
     use holochain_wasm_test_utils::TestWasm;
@@ -544,6 +730,7 @@ pub mod test {
                 driver: InterfaceDriver::Websocket {
                     port: 0,
                     allowed_origins: AllowedOrigins::Any,
+                    tls: None,
                 },
             }])
             .await
@@ -596,6 +783,10 @@ pub mod test {
             port: None,
             allowed_origins: AllowedOrigins::Any,
             installed_app_id: None,
+            socket_path: None,
+            socket_mode: None,
+            tls: None,
+            signal_replay_buffer_size: None,
         };
         let response: AdminResponse = admin_tx.request(request).await.unwrap();
         let app_port = match response {
@@ -1154,6 +1345,10 @@ pub mod test {
             port: None,
             allowed_origins: AllowedOrigins::Any,
             installed_app_id: None,
+            socket_path: None,
+            socket_mode: None,
+            tls: None,
+            signal_replay_buffer_size: None,
         };
         let msg = msg.try_into().unwrap();
         let respond = |response: AdminResponse| {
@@ -1165,6 +1360,71 @@ pub mod test {
         conductor_handle.shutdown().await.unwrap().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn install_app_from_store() {
+        holochain_trace::test_run();
+        let db_dir = test_db_dir();
+        let store_dir = tempfile::tempdir().unwrap();
+        let store = AppStoreLocalDir::new(store_dir.path().to_path_buf());
+        let conductor_handle = ConductorBuilder::new()
+            .config(ConductorConfig {
+                app_store: Some(AppStoreConfig::LocalDir {
+                    path: store_dir.path().to_path_buf(),
+                }),
+                ..Default::default()
+            })
+            .with_data_root_path(db_dir.path().to_path_buf().into())
+            .test(&[])
+            .await
+            .unwrap();
+        let admin_api = AdminInterfaceApi::new(conductor_handle.clone());
+
+        let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+        let bundle = app_bundle_from_dnas(&[("role".to_string(), dna)]).await;
+        let app_hash = store.put_app_bundle(&bundle).await.unwrap();
+        let agent_key = fake_agent_pubkey_1();
+        let install = |app_hash: AppHash| {
+            AdminRequest::InstallAppFromStore(Box::new(InstallAppFromStorePayload {
+                app_hash,
+                agent_key: agent_key.clone(),
+                installed_app_id: Some("app".into()),
+                membrane_proofs: HashMap::new(),
+                network_seed: None,
+            }))
+        };
+
+        // A bundle which is not in the store can't be installed
+        let missing = AppHash::from_bundle_bytes(b"missing");
+        let response = admin_api
+            .handle_admin_request(install(missing.clone()))
+            .await;
+        assert_matches!(
+            response,
+            AdminResponse::Error(ExternalApiWireError::InternalError(e))
+                if e.contains(&missing.to_string())
+        );
+
+        // A bundle which doesn't match its hash is rejected
+        let tampered = AppHash::from_bundle_bytes(b"tampered");
+        ffs::write(&store.app_bundle_path(&tampered), &bundle.encode().unwrap())
+            .await
+            .unwrap();
+        let response = admin_api.handle_admin_request(install(tampered)).await;
+        assert_matches!(
+            response,
+            AdminResponse::Error(ExternalApiWireError::InternalError(e))
+                if e.contains("does not match the requested hash")
+        );
+
+        let response = admin_api.handle_admin_request(install(app_hash)).await;
+        assert_matches!(
+            response,
+            AdminResponse::AppInstalled(info) if info.installed_app_id == "app"
+        );
+
+        conductor_handle.shutdown().await.unwrap().unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn dump_state() {
         holochain_trace::test_run();
@@ -1216,6 +1476,8 @@ pub mod test {
                     .map(TestZomes::from)
                     .map(|z| z.integrity.into_inner())
                     .collect(),
+                rate_limits: Default::default(),
+                wasm_metering_limit: None,
                 coordinator_zomes: zomes
                     .clone()
                     .into_iter()
@@ -34,8 +34,9 @@ use holochain_p2p::{
     event::FetchOpDataQuery,
 };
 use holochain_sqlite::prelude::{
-    DatabaseResult, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht, DbKindP2pAgents,
-    DbKindP2pMetrics, DbKindWasm, DbSyncLevel, DbSyncStrategy, DbWrite, ReadAccess,
+    DatabaseResult, DbKey, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht,
+    DbKindP2pAgents, DbKindP2pMetrics, DbKindWasm, DbSyncLevel, DbSyncStrategy, DbWrite,
+    ReadAccess,
 };
 use holochain_state::{
     host_fn_workspace::SourceChainWorkspace,
@@ -67,6 +68,8 @@ pub struct Spaces {
     pub(crate) queue_consumer_map: QueueConsumerMap,
     pub(crate) conductor_db: DbWrite<DbKindConductor>,
     pub(crate) wasm_db: DbWrite<DbKindWasm>,
+    /// The key all databases are encrypted with.
+    pub(crate) db_key: DbKey,
 }
 
 #[derive(Clone)]
@@ -115,6 +118,8 @@ pub struct Space {
     pub incoming_ops_batch: IncomingOpsBatch,
 
     root_db_dir: Arc<PathBuf>,
+
+    db_key: DbKey,
 }
 
 #[cfg(test)]
@@ -130,8 +135,9 @@ pub struct TestSpace {
 }
 
 impl Spaces {
-    /// Create a new empty set of [`DnaHash`] spaces.
-    pub fn new(config: Arc<ConductorConfig>) -> ConductorResult<Self> {
+    /// Create a new empty set of [`DnaHash`] spaces, with databases encrypted
+    /// with the given key.
+    pub fn new(config: Arc<ConductorConfig>, db_key: DbKey) -> ConductorResult<Self> {
         let root_db_dir: DatabasesRootPath = config
             .data_root_path
             .clone()
@@ -143,10 +149,18 @@ impl Spaces {
             DbSyncStrategy::Resilient => DbSyncLevel::Normal,
         };
 
-        let conductor_db =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindConductor, db_sync_level)?;
-        let wasm_db =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindWasm, db_sync_level)?;
+        let conductor_db = DbWrite::open_with_sync_level(
+            root_db_dir.as_ref(),
+            DbKindConductor,
+            db_sync_level,
+            db_key.clone(),
+        )?;
+        let wasm_db = DbWrite::open_with_sync_level(
+            root_db_dir.as_ref(),
+            DbKindWasm,
+            db_sync_level,
+            db_key.clone(),
+        )?;
         Ok(Spaces {
             map: RwShare::new(HashMap::new()),
             db_dir: Arc::new(root_db_dir),
@@ -154,6 +168,7 @@ impl Spaces {
             queue_consumer_map: QueueConsumerMap::new(),
             conductor_db,
             wasm_db,
+            db_key,
         })
     }
 
@@ -334,6 +349,7 @@ impl Spaces {
                             Arc::new(dna_hash.clone()),
                             self.db_dir.to_path_buf(),
                             self.config.db_sync_strategy,
+                            self.db_key.clone(),
                         )?;
 
                         let r = f(&space);
@@ -564,7 +580,7 @@ impl Spaces {
     ) -> ConductorResult<Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>> {
         let mut sql = "
             SELECT DhtOp.hash, DhtOp.type AS dht_type,
-            Action.blob AS action_blob, Entry.blob AS entry_blob
+            Action.blob AS action_blob, Action.author AS author, Entry.blob AS entry_blob
             FROM DHtOp
             LEFT JOIN Action ON DhtOp.action_hash = Action.hash
             LEFT JOIN Entry ON Action.entry_hash = Entry.hash
@@ -681,6 +697,7 @@ impl Space {
         dna_hash: Arc<DnaHash>,
         root_db_dir: PathBuf,
         db_sync_strategy: DbSyncStrategy,
+        db_key: DbKey,
     ) -> DatabaseResult<Self> {
         let space = dna_hash.to_kitsune();
         let db_sync_level = match db_sync_strategy {
@@ -691,24 +708,32 @@ impl Space {
             root_db_dir.as_ref(),
             DbKindCache(dna_hash.clone()),
             db_sync_level,
+            db_key.clone(),
         )?;
         let dht_db = DbWrite::open_with_sync_level(
             root_db_dir.as_ref(),
             DbKindDht(dna_hash.clone()),
             db_sync_level,
+            db_key.clone(),
         )?;
         let p2p_agents_db = DbWrite::open_with_sync_level(
             root_db_dir.as_ref(),
             DbKindP2pAgents(space.clone()),
             db_sync_level,
+            db_key.clone(),
         )?;
         let p2p_metrics_db = DbWrite::open_with_sync_level(
             root_db_dir.as_ref(),
             DbKindP2pMetrics(space),
             db_sync_level,
+            db_key.clone(),
+        )?;
+        let conductor_db: DbWrite<DbKindConductor> = DbWrite::open_with_sync_level(
+            root_db_dir.as_ref(),
+            DbKindConductor,
+            db_sync_level,
+            db_key.clone(),
         )?;
-        let conductor_db: DbWrite<DbKindConductor> =
-            DbWrite::open_with_sync_level(root_db_dir.as_ref(), DbKindConductor, db_sync_level)?;
 
         let (tx, rx) = tokio::sync::mpsc::channel(100);
         tokio::spawn(p2p_agent_store::p2p_put_all_batch(
@@ -735,6 +760,7 @@ impl Space {
             dht_query_cache,
             conductor_db,
             root_db_dir: Arc::new(root_db_dir),
+            db_key,
         };
         Ok(r)
     }
@@ -786,6 +812,7 @@ impl Space {
                     self.root_db_dir.as_ref(),
                     DbKindAuthored(Arc::new(CellId::new((*self.dna_hash).clone(), author))),
                     DbSyncLevel::Normal,
+                    self.db_key.clone(),
                 )?;
 
                 entry.insert(db.clone());
@@ -844,6 +871,7 @@ impl TestSpaces {
                 ..Default::default()
             }
             .into(),
+            DbKey::test(),
         )
         .unwrap();
         spaces.map.share_mut(|map| {
@@ -874,6 +902,7 @@ impl TestSpace {
                 Arc::new(dna_hash),
                 temp_dir.path().to_path_buf(),
                 Default::default(),
+                DbKey::test(),
             )
             .unwrap(),
             _temp_dir: temp_dir,
@@ -8,6 +8,7 @@ use holochain_cascade::test_utils::fill_db;
 use holochain_conductor_api::conductor::ConductorConfig;
 use holochain_keystore::test_keystore;
 use holochain_p2p::dht::prelude::*;
+use holochain_sqlite::db::DbKey;
 use holochain_types::facts::valid_chain_op;
 use holochain_types::prelude::*;
 use kitsune_p2p_types::dht::ArqStrat;
@@ -42,6 +43,7 @@ async fn test_region_queries() {
             ..Default::default()
         }
         .into(),
+        DbKey::test(),
     )
     .unwrap();
     let keystore = test_keystore();
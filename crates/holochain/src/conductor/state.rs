@@ -2,6 +2,7 @@
 //! startups and shutdowns
 
 use holochain_conductor_api::config::InterfaceDriver;
+use holochain_conductor_api::config::InterfaceTls;
 use holochain_conductor_api::signal_subscription::SignalSubscription;
 use holochain_p2p::NetworkCompatParams;
 use holochain_types::prelude::*;
@@ -9,6 +10,8 @@ use holochain_types::websocket::AllowedOrigins;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use super::error::{ConductorError, ConductorResult};
@@ -197,10 +200,45 @@ impl ConductorState {
     }
 
     /// Find the app which contains the given cell by its [CellId].
+    ///
+    /// If the cell is shared by several apps, this is the app which created it.
     pub fn find_app_containing_cell(&self, cell_id: &CellId) -> Option<&InstalledApp> {
         self.installed_apps
             .values()
-            .find(|app| app.all_cells().any(|id| id == cell_id))
+            .filter(|app| app.all_cells().any(|id| id == cell_id))
+            .min_by_key(|app| app.existing_cells().any(|id| id == cell_id))
+    }
+
+    /// The IDs of all the apps which contain each cell, including the apps which
+    /// use a cell created by another app.
+    pub fn apps_by_cell(&self) -> HashMap<CellId, Vec<InstalledAppId>> {
+        let mut apps_by_cell: HashMap<CellId, Vec<InstalledAppId>> = HashMap::new();
+        for (installed_app_id, app) in self.installed_apps.iter() {
+            for cell_id in app.all_cells() {
+                apps_by_cell
+                    .entry(cell_id.clone())
+                    .or_default()
+                    .push(installed_app_id.clone());
+            }
+        }
+        apps_by_cell
+    }
+
+    /// The IDs of the other apps which use cells created by the given app.
+    pub fn dependent_apps(&self, installed_app_id: &InstalledAppId) -> Vec<InstalledAppId> {
+        let owned_cells: HashSet<&CellId> = match self.installed_apps.get(installed_app_id) {
+            Some(app) => app.owned_cells().collect(),
+            None => return Vec::new(),
+        };
+        let mut dependents: Vec<InstalledAppId> = self
+            .installed_apps
+            .iter()
+            .filter(|(id, _)| *id != installed_app_id)
+            .filter(|(_, app)| app.existing_cells().any(|id| owned_cells.contains(id)))
+            .map(|(id, _)| id.clone())
+            .collect();
+        dependents.sort();
+        dependents
     }
 
     /// Get network compability params
@@ -220,9 +258,7 @@ impl ConductorState {
 /// GUIs, browser based web UIs, local native UIs, other local applications and scripts.
 /// We currently have:
 /// * websockets
-///
-/// We will also soon develop
-/// * Unix domain sockets
+/// * websockets over Unix domain sockets
 ///
 /// The cells (referenced by ID) that are to be made available via that interface should be listed.
 #[derive(Clone, Deserialize, Serialize, Debug)]
@@ -238,6 +274,12 @@ pub struct AppInterfaceConfig {
 
     /// The driver for the interface, e.g. Websocket
     pub driver: InterfaceDriver,
+
+    /// The number of signals kept per app, so that clients which reconnect
+    /// can resume receiving signals where they left off.
+    /// If `None`, signals are not kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_replay_buffer_size: Option<usize>,
 }
 
 impl AppInterfaceConfig {
@@ -245,6 +287,7 @@ impl AppInterfaceConfig {
     pub fn websocket(
         port: u16,
         allowed_origins: AllowedOrigins,
+        tls: Option<InterfaceTls>,
         installed_app_id: Option<InstalledAppId>,
     ) -> Self {
         Self {
@@ -253,7 +296,19 @@ impl AppInterfaceConfig {
             driver: InterfaceDriver::Websocket {
                 port,
                 allowed_origins,
+                tls,
             },
+            signal_replay_buffer_size: None,
+        }
+    }
+
+    /// Create config for an interface on a Unix domain socket
+    pub fn unix_socket(path: PathBuf, mode: u32, installed_app_id: Option<InstalledAppId>) -> Self {
+        Self {
+            signal_subscriptions: HashMap::new(),
+            installed_app_id,
+            driver: InterfaceDriver::UnixSocket { path, mode },
+            signal_replay_buffer_size: None,
         }
     }
 }
@@ -1,10 +1,16 @@
 #![allow(dead_code)]
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    sync::Arc,
+};
 
 use crate::conductor::api::error::ConductorApiError;
 use crate::{conductor::error::ConductorError, sweettest::*};
 use ::fixt::prelude::strum_macros;
 use holo_hash::{AgentPubKey, DnaHash};
+use holochain_conductor_api::conductor::paths::APP_BUNDLES_DIRECTORY;
 use holochain_types::prelude::*;
 use holochain_wasm_test_utils::TestWasm;
 use matches::assert_matches;
@@ -34,6 +40,7 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
                 location: Some(DnaLocation::Bundled(path.clone())),
                 modifiers: modifiers.clone(),
                 installed_hash: Some(installed_dna_hash.into()),
+                resource_hash: None,
                 clone_limit,
             },
             provisioning: Some(CellProvisioning::CloneOnly),
@@ -143,6 +150,431 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
     // TODO: test that the cell can't be provisioned later
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn use_existing_provisioning_shares_the_cell_of_another_app() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+    let bob = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), alice.clone());
+
+    let make_payload = |agent_key: AgentPubKey, app_id: &str, provisioning: CellProvisioning| {
+        // Only the provider shares its role
+        let shared_roles = match provisioning {
+            CellProvisioning::Create { .. } => vec!["role_0".to_string()],
+            _ => vec![],
+        };
+        app_payload(
+            agent_key,
+            app_id.to_string(),
+            vec![(dna.clone(), provisioning)],
+            shared_roles,
+        )
+    };
+
+    // Using a cell which doesn't exist yet fails
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(
+                make_payload(
+                    alice.clone(),
+                    "user",
+                    CellProvisioning::UseExisting { deferred: false }
+                )
+                .await
+            )
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::CellResolutionFailure(_, _))
+    );
+
+    conductor
+        .clone()
+        .install_app_bundle(
+            make_payload(
+                alice.clone(),
+                "provider",
+                CellProvisioning::Create { deferred: false },
+            )
+            .await,
+        )
+        .await
+        .unwrap();
+
+    // Both provisioning strategies bind to the cell of the provider
+    for (app_id, provisioning) in [
+        ("user", CellProvisioning::UseExisting { deferred: false }),
+        (
+            "other_user",
+            CellProvisioning::CreateIfNotExists { deferred: false },
+        ),
+    ] {
+        let app = conductor
+            .clone()
+            .install_app_bundle(make_payload(alice.clone(), app_id, provisioning).await)
+            .await
+            .unwrap();
+        assert_eq!(app.all_cells().collect::<Vec<_>>(), vec![&cell_id]);
+        assert!(app.role(&"role_0".into()).unwrap().is_existing());
+    }
+
+    // The cells of other agents can't be used
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(
+                make_payload(
+                    bob.clone(),
+                    "bob_user",
+                    CellProvisioning::UseExisting { deferred: false }
+                )
+                .await
+            )
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::CellResolutionFailure(_, _))
+    );
+
+    // The provider can only be uninstalled once no other app uses its cell
+    assert_matches!(
+        conductor
+            .raw_handle()
+            .uninstall_app(&"provider".to_string())
+            .await
+            .unwrap_err(),
+        ConductorError::AppHasDependents(_, dependents) if dependents == vec!["other_user".to_string(), "user".to_string()]
+    );
+    for app_id in ["user", "other_user", "provider"] {
+        conductor
+            .raw_handle()
+            .uninstall_app(&app_id.to_string())
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn use_existing_provisioning_requires_the_role_to_be_shared() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+
+    // The provider doesn't share its role
+    conductor
+        .clone()
+        .install_app_bundle(
+            app_payload(
+                alice.clone(),
+                "provider".to_string(),
+                vec![(dna.clone(), CellProvisioning::Create { deferred: false })],
+                vec![],
+            )
+            .await,
+        )
+        .await
+        .unwrap();
+
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(
+                app_payload(
+                    alice.clone(),
+                    "user".to_string(),
+                    vec![(
+                        dna.clone(),
+                        CellProvisioning::UseExisting { deferred: false }
+                    )],
+                    vec![],
+                )
+                .await
+            )
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::CellResolutionFailure(_, _))
+    );
+    // The cell isn't used, so the app would create it again
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(
+                app_payload(
+                    alice.clone(),
+                    "other_user".to_string(),
+                    vec![(
+                        dna.clone(),
+                        CellProvisioning::CreateIfNotExists { deferred: false }
+                    )],
+                    vec![],
+                )
+                .await
+            )
+            .await
+            .unwrap_err(),
+        ConductorError::CellAlreadyExists(_)
+    );
+
+    // A manifest can only share its own roles
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(
+                app_payload(
+                    alice.clone(),
+                    "unknown_role".to_string(),
+                    vec![],
+                    vec!["role_0".to_string()],
+                )
+                .await
+            )
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::AppManifestError(
+            AppManifestError::UnknownSharedRole(_)
+        ))
+    );
+
+    let state = conductor.get_state_from_handle().await.unwrap();
+    assert!(state.get_app(&"user".to_string()).is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_agent_refuses_shared_cells() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    let (new_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::MigrateAgentPass]).await;
+    conductor.register_dna(new_dna.clone()).await.unwrap();
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), alice.clone());
+
+    for (app_id, provisioning) in [
+        ("provider", CellProvisioning::Create { deferred: false }),
+        ("user", CellProvisioning::UseExisting { deferred: false }),
+    ] {
+        conductor
+            .clone()
+            .install_app_bundle(
+                app_payload(
+                    alice.clone(),
+                    app_id.to_string(),
+                    vec![(dna.clone(), provisioning)],
+                    vec!["role_0".to_string()],
+                )
+                .await,
+            )
+            .await
+            .unwrap();
+        conductor.enable_app(app_id.to_string()).await.unwrap();
+    }
+
+    // Neither the app using the cell nor the app which created it can migrate it
+    for app_id in ["user", "provider"] {
+        assert_matches!(
+            conductor
+                .raw_handle()
+                .migrate_agent(
+                    &app_id.to_string(),
+                    &"role_0".to_string(),
+                    new_dna.dna_hash().clone(),
+                    None,
+                )
+                .await,
+            Err(ConductorError::MigrateAgentError(_))
+        );
+    }
+    let state = conductor.get_state_from_handle().await.unwrap();
+    for app_id in ["user", "provider"] {
+        let app = state.get_app(&app_id.to_string()).unwrap();
+        assert_eq!(app.role(&"role_0".into()).unwrap().cell_id(), &cell_id);
+    }
+
+    // Once the cell is no longer used from another app, it can be migrated
+    conductor
+        .raw_handle()
+        .uninstall_app(&"user".to_string())
+        .await
+        .unwrap();
+    let new_cell_id = conductor
+        .raw_handle()
+        .migrate_agent(
+            &"provider".to_string(),
+            &"role_0".to_string(),
+            new_dna.dna_hash().clone(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(new_cell_id.dna_hash(), new_dna.dna_hash());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signals_of_a_shared_cell_reach_every_app_using_it() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal]).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), alice.clone());
+
+    for (app_id, provisioning) in [
+        ("provider", CellProvisioning::Create { deferred: false }),
+        ("user", CellProvisioning::UseExisting { deferred: false }),
+    ] {
+        conductor
+            .clone()
+            .install_app_bundle(
+                app_payload(
+                    alice.clone(),
+                    app_id.to_string(),
+                    vec![(dna.clone(), provisioning)],
+                    vec!["role_0".to_string()],
+                )
+                .await,
+            )
+            .await
+            .unwrap();
+        conductor.enable_app(app_id.to_string()).await.unwrap();
+    }
+    let mut provider_signals = conductor.subscribe_to_app_signals("provider".to_string());
+    let mut user_signals = conductor.subscribe_to_app_signals("user".to_string());
+
+    let cell = conductor.get_sweet_cell(cell_id.clone()).unwrap();
+    let () = conductor
+        .call(&cell.zome(TestWasm::EmitSignal), "emit", ())
+        .await;
+
+    for signals in [&mut provider_signals, &mut user_signals] {
+        let signal = tokio::time::timeout(std::time::Duration::from_secs(10), signals.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(signal, Signal::App { cell_id: id, .. } if id == cell_id);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn failed_genesis_leaves_the_cells_used_from_other_apps_alone() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), alice.clone());
+    // Genesis of this DNA fails, as its properties are not set
+    let (failing_dna, _, _) =
+        SweetDnaFile::unique_from_test_wasms(vec![TestWasm::GenesisSelfCheckRequiresProperties])
+            .await;
+    let failing_cell_id = CellId::new(failing_dna.dna_hash().to_owned(), alice.clone());
+
+    conductor
+        .clone()
+        .install_app_bundle(
+            app_payload(
+                alice.clone(),
+                "provider".to_string(),
+                vec![(dna.clone(), CellProvisioning::Create { deferred: false })],
+                vec!["role_0".to_string()],
+            )
+            .await,
+        )
+        .await
+        .unwrap();
+    conductor.enable_app("provider".to_string()).await.unwrap();
+
+    // Only the cell which the failed app would have created is rolled back
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(
+                app_payload(
+                    alice.clone(),
+                    "user".to_string(),
+                    vec![
+                        (dna.clone(), CellProvisioning::UseExisting { deferred: false }),
+                        (failing_dna, CellProvisioning::Create { deferred: false }),
+                    ],
+                    vec![],
+                )
+                .await,
+            )
+            .await
+            .unwrap_err(),
+        ConductorError::GenesisFailed { errors } if errors.iter().map(|(id, _)| id).collect::<Vec<_>>() == vec![&failing_cell_id]
+    );
+    let state = conductor.get_state_from_handle().await.unwrap();
+    assert!(state.get_app(&"user".to_string()).is_err());
+    assert!(state
+        .get_app(&"provider".to_string())
+        .unwrap()
+        .status()
+        .is_running());
+    assert!(conductor.running_cell_ids().contains(&cell_id));
+
+    let cell = conductor.get_sweet_cell(cell_id).unwrap();
+    let _: ActionHash = conductor
+        .call(&cell.zome(TestWasm::Create), "create_entry", ())
+        .await;
+}
+
+/// The payload of an app with a role for each of the given DNAs,
+/// named after the DNAs' position in the list.
+async fn app_payload(
+    agent_key: AgentPubKey,
+    app_id: InstalledAppId,
+    role_dnas: Vec<(DnaFile, CellProvisioning)>,
+    shared_roles: Vec<RoleName>,
+) -> InstallAppPayload {
+    let mut roles = Vec::new();
+    let mut resources = Vec::new();
+    for (i, (dna, provisioning)) in role_dnas.into_iter().enumerate() {
+        let path = PathBuf::from(format!("{}", dna.dna_hash()));
+        roles.push(AppRoleManifest {
+            name: format!("role_{i}"),
+            dna: AppRoleDnaManifest {
+                location: Some(DnaLocation::Bundled(path.clone())),
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: Some(DnaHash::with_data_sync(dna.dna_def()).into()),
+                resource_hash: None,
+                clone_limit: 0,
+            },
+            provisioning: Some(provisioning),
+        });
+        resources.push((path, DnaBundle::from_dna_file(dna).unwrap()));
+    }
+    let manifest = AppManifestCurrentBuilder::default()
+        .name(app_id.clone())
+        .description(None)
+        .roles(roles)
+        .shared_roles(shared_roles)
+        .build()
+        .unwrap();
+    let bundle = AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+        .await
+        .unwrap();
+    InstallAppPayload {
+        agent_key,
+        source: AppBundleSource::Bundle(bundle),
+        installed_app_id: Some(app_id),
+        network_seed: None,
+        membrane_proofs: HashMap::new(),
+        #[cfg(feature = "chc")]
+        ignore_genesis_failure: false,
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn reject_duplicate_app_for_same_agent() {
     let conductor = SweetConductor::from_standard_config().await;
@@ -160,6 +592,7 @@ async fn reject_duplicate_app_for_same_agent() {
             location: Some(DnaLocation::Bundled(path.clone())),
             modifiers: modifiers.clone(),
             installed_hash: Some(installed_dna_hash.into()),
+            resource_hash: None,
             clone_limit: 0,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
@@ -254,6 +687,191 @@ async fn reject_duplicate_app_for_same_agent() {
     assert!(valid_install_of_second_app.is_ok());
 }
 
+/// Serve the given bytes over HTTP at any path, returning the URL to get them
+/// from and the number of requests made so far.
+async fn serve_bytes(body: Vec<u8>) -> (String, Arc<AtomicUsize>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/app.happ", listener.local_addr().unwrap());
+    let requests = Arc::new(AtomicUsize::new(0));
+    let requests_2 = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            requests_2.fetch_add(1, Ordering::SeqCst);
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await;
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(head.as_bytes()).await;
+            let _ = stream.write_all(&body).await;
+        }
+    });
+    (url, requests)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn install_app_from_url_checks_and_caches_the_bundle() {
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let path = PathBuf::from(format!("{}", dna.dna_hash()));
+    let bundle_bytes = {
+        let roles = vec![AppRoleManifest {
+            name: "name".into(),
+            dna: AppRoleDnaManifest {
+                location: Some(DnaLocation::Bundled(path.clone())),
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                resource_hash: None,
+                clone_limit: 0,
+            },
+            provisioning: Some(CellProvisioning::Create { deferred: false }),
+        }];
+        let manifest = AppManifestCurrentBuilder::default()
+            .name("test_app".into())
+            .description(None)
+            .roles(roles)
+            .build()
+            .unwrap();
+        let resources = vec![(path, DnaBundle::from_dna_file(dna).unwrap())];
+        AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+            .await
+            .unwrap()
+            .encode()
+            .unwrap()
+    };
+    let app_hash = AppHash::from_bundle_bytes(&bundle_bytes);
+    let (url, requests) = serve_bytes(bundle_bytes).await;
+
+    let payload = |app_hash: AppHash| InstallAppPayload {
+        source: AppBundleSource::Url {
+            url: url.clone(),
+            app_hash,
+        },
+        agent_key: alice.clone(),
+        installed_app_id: Some("app".into()),
+        membrane_proofs: HashMap::new(),
+        network_seed: None,
+        #[cfg(feature = "chc")]
+        ignore_genesis_failure: false,
+    };
+
+    // A bundle with another hash than the expected one is rejected
+    let wrong_hash = AppHash::from_bundle_bytes(&[]);
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload(wrong_hash))
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::AppHashMismatch { actual, .. })
+            if actual == app_hash
+    );
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    conductor
+        .clone()
+        .install_app_bundle(payload(app_hash.clone()))
+        .await
+        .unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+    assert!(conductor
+        .db_path()
+        .join(APP_BUNDLES_DIRECTORY)
+        .join(format!("{}.happ", app_hash))
+        .is_file());
+
+    // Installing the bundle again uses the cached bundle
+    conductor
+        .clone()
+        .uninstall_app(&"app".into())
+        .await
+        .unwrap();
+    conductor
+        .clone()
+        .install_app_bundle(payload(app_hash))
+        .await
+        .unwrap();
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn install_app_with_a_dna_pinned_by_its_resource_hash() {
+    let conductor = SweetConductor::from_standard_config().await;
+    let alice = SweetAgents::one(conductor.keystore()).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+    let dna_hash = dna.dna_hash().clone();
+    let dna_bytes = DnaBundle::from_dna_file(dna).unwrap().encode().unwrap();
+    let (url, _) = serve_bytes(dna_bytes.clone()).await;
+
+    let payload = |app_id: &str, resource_hash: mr_bundle::ResourceHash| {
+        let roles = vec![AppRoleManifest {
+            name: "name".into(),
+            dna: AppRoleDnaManifest {
+                location: Some(DnaLocation::Url(url.clone())),
+                modifiers: DnaModifiersOpt::none(),
+                installed_hash: None,
+                resource_hash: Some(resource_hash),
+                clone_limit: 0,
+            },
+            provisioning: Some(CellProvisioning::Create { deferred: false }),
+        }];
+        let manifest = AppManifestCurrentBuilder::default()
+            .name(app_id.to_string())
+            .description(None)
+            .roles(roles)
+            .build()
+            .unwrap();
+        let app_id = app_id.to_string();
+        let agent_key = alice.clone();
+        async move {
+            let resources: Vec<(PathBuf, DnaBundle)> = vec![];
+            let bundle = AppBundle::new(manifest.into(), resources, PathBuf::from("."))
+                .await
+                .unwrap();
+            InstallAppPayload {
+                source: AppBundleSource::Bundle(bundle),
+                agent_key,
+                installed_app_id: Some(app_id),
+                membrane_proofs: HashMap::new(),
+                network_seed: None,
+                #[cfg(feature = "chc")]
+                ignore_genesis_failure: false,
+            }
+        }
+    };
+
+    // A DNA with another hash than the pinned one is rejected
+    let wrong_hash = mr_bundle::ResourceHash::of(&[]);
+    assert_matches!(
+        conductor
+            .clone()
+            .install_app_bundle(payload("wrong", wrong_hash).await)
+            .await
+            .unwrap_err(),
+        ConductorError::AppBundleError(AppBundleError::MrBundleError(
+            mr_bundle::error::MrBundleError::BundleError(
+                mr_bundle::error::BundleError::ResourceHashMismatch { expected, .. }
+            )
+        )) if expected == wrong_hash
+    );
+
+    let app = conductor
+        .clone()
+        .install_app_bundle(payload("app", mr_bundle::ResourceHash::of(&dna_bytes)).await)
+        .await
+        .unwrap();
+    assert_eq!(
+        app.all_cells().collect::<Vec<_>>(),
+        vec![&CellId::new(dna_hash, alice.clone())]
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_info() {
     let conductor = SweetConductor::from_standard_config().await;
@@ -272,6 +890,7 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
             modifiers: modifiers.clone(),
             // Note that there is no installed hash provided. We'll check that this changes later.
             installed_hash: None,
+            resource_hash: None,
             clone_limit: 0,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
@@ -375,6 +994,7 @@ async fn network_seed_regression() {
                 location: Some(DnaLocation::Path(dna_path)),
                 modifiers: DnaModifiersOpt::default(),
                 installed_hash: None,
+                resource_hash: None,
                 clone_limit: 0,
             },
             provisioning: None,
@@ -606,6 +1226,7 @@ impl TestCase {
                         location: Some(DnaLocation::Bundled(hashpath.clone())),
                         modifiers: dna_modifiers.clone(),
                         installed_hash: None,
+                        resource_hash: None,
                         clone_limit: 10,
                     },
                     provisioning: Some(CellProvisioning::Create { deferred: false }),
@@ -630,6 +1251,7 @@ impl TestCase {
                         location: Some(DnaLocation::Path(dna_path.clone())),
                         modifiers: dna_modifiers.clone(),
                         installed_hash: Some(dna_hash.clone().into()),
+                        resource_hash: None,
                         clone_limit: 0,
                     },
                     provisioning: None,
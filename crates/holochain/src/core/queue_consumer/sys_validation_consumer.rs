@@ -4,6 +4,7 @@ use super::*;
 use crate::core::workflow::sys_validation_workflow::sys_validation_workflow;
 use crate::core::workflow::sys_validation_workflow::validation_deps::ValidationDependencies;
 use crate::core::workflow::sys_validation_workflow::SysValidationWorkspace;
+use futures::FutureExt;
 use parking_lot::Mutex;
 use tracing::*;
 
@@ -37,6 +38,14 @@ pub fn spawn_sys_validation_consumer(
                 trigger_self.clone(),
                 network.clone(),
                 config.clone(),
+                conductor.running_cell_ids(),
+                {
+                    let conductor = conductor.clone();
+                    move |block| {
+                        let conductor = conductor.clone();
+                        async move { conductor.block(block).await }.boxed()
+                    }
+                },
             )
         },
     );
@@ -69,6 +69,8 @@ use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
+use crate::core::ribosome::guest_callback::weigh::WeighInvocation;
+use crate::core::ribosome::guest_callback::weigh::WeighResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use derive_more::Constructor;
 use error::RibosomeResult;
@@ -77,6 +79,7 @@ use guest_callback::init::InitHostAccess;
 use guest_callback::migrate_agent::MigrateAgentHostAccess;
 use guest_callback::post_commit::PostCommitHostAccess;
 use guest_callback::validate::ValidateHostAccess;
+use guest_callback::weigh::WeighHostAccess;
 use holo_hash::AgentPubKey;
 use holochain_keystore::MetaLairClient;
 use holochain_nonce::*;
@@ -86,6 +89,7 @@ use holochain_serialized_bytes::prelude::*;
 use holochain_state::host_fn_workspace::HostFnWorkspace;
 use holochain_state::host_fn_workspace::HostFnWorkspaceRead;
 use holochain_state::nonce::WitnessNonceResult;
+use holochain_state::source_chain::SourceChainResult;
 use holochain_types::prelude::*;
 use holochain_types::zome_types::GlobalZomeTypes;
 use holochain_zome_types::block::BlockTargetId;
@@ -151,6 +155,7 @@ pub enum HostContext {
     MigrateAgent(MigrateAgentHostAccess),
     PostCommit(PostCommitHostAccess), // MAYBE: add emit_signal access here?
     Validate(ValidateHostAccess),
+    Weigh(WeighHostAccess),
     ZomeCall(ZomeCallHostAccess),
 }
 
@@ -165,6 +170,7 @@ impl From<&HostContext> for HostFnAccess {
             HostContext::EntryDefs(access) => access.into(),
             HostContext::MigrateAgent(access) => access.into(),
             HostContext::PostCommit(access) => access.into(),
+            HostContext::Weigh(access) => access.into(),
         }
     }
 }
@@ -359,8 +365,18 @@ pub trait Invocation: Clone + Send + Sync {
     /// careful about cloning invocations
     fn host_input(self) -> Result<ExternIO, SerializedBytesError>;
     fn auth(&self) -> InvocationAuth;
+    /// The wasm metering limit of this invocation, if it overrides the limit of the DNA.
+    fn metering_limit(&self) -> Option<u64> {
+        None
+    }
+    /// Record the wasm metering points consumed by a call for this invocation.
+    fn record_metering_points(&self, _points: u64) {}
 }
 
+/// The wasm metering points consumed by a zome call, shared with the caller
+/// so that they can be reported back.
+pub type MeteringPointsUsed = Arc<std::sync::atomic::AtomicU64>;
+
 impl ZomeCallInvocation {
     pub async fn verify_signature(&self) -> RibosomeResult<ZomeCallAuthorization> {
         Ok(
@@ -514,6 +530,11 @@ pub struct ZomeCallInvocation {
     pub nonce: Nonce256Bits,
     /// This call MUST NOT be respected after this time, in the opinion of the callee.
     pub expires_at: Timestamp,
+    /// The wasm metering limit of this call, overriding the limit of the DNA.
+    pub metering_limit: Option<u64>,
+    /// The wasm metering points the call has consumed.
+    #[serde(skip)]
+    pub metering_points_used: MeteringPointsUsed,
 }
 
 impl Invocation for ZomeCallInvocation {
@@ -529,6 +550,13 @@ impl Invocation for ZomeCallInvocation {
     fn auth(&self) -> InvocationAuth {
         InvocationAuth::Cap(self.provenance.clone(), self.cap_secret)
     }
+    fn metering_limit(&self) -> Option<u64> {
+        self.metering_limit
+    }
+    fn record_metering_points(&self, points: u64) {
+        self.metering_points_used
+            .fetch_add(points, std::sync::atomic::Ordering::Relaxed);
+    }
 }
 
 impl ZomeCallInvocation {
@@ -546,6 +574,7 @@ impl ZomeCallInvocation {
             signature,
             nonce,
             expires_at,
+            metering_limit,
         } = call;
         let zome = conductor_api
             .get_zome(cell_id.dna_hash(), &zome_name)
@@ -560,6 +589,8 @@ impl ZomeCallInvocation {
             signature,
             nonce,
             expires_at,
+            metering_limit,
+            metering_points_used: Default::default(),
         })
     }
 }
@@ -576,6 +607,8 @@ impl From<ZomeCallInvocation> for ZomeCall {
             signature,
             nonce,
             expires_at,
+            metering_limit,
+            ..
         } = inv;
         Self {
             cell_id,
@@ -587,6 +620,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
             signature,
             nonce,
             expires_at,
+            metering_limit,
         }
     }
 }
@@ -732,6 +766,12 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
         invocation: PostCommitInvocation,
     ) -> RibosomeResult<()>;
 
+    fn run_weigh(
+        &self,
+        access: WeighHostAccess,
+        invocation: WeighInvocation,
+    ) -> RibosomeResult<WeighResult>;
+
     /// Helper function for running a validation callback. Calls
     /// private fn `do_callback!` under the hood.
     fn run_validate(
@@ -752,10 +792,41 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
 }
 
 /// Placeholder for weighing. Currently produces zero weight.
+/// Countersigned entries are still weighed this way, since all parties to the
+/// session must agree on the weight before the action is built.
 pub fn weigh_placeholder() -> EntryRateWeight {
     EntryRateWeight::default()
 }
 
+/// Weigh an action which is about to be committed by running the `weigh`
+/// callback of the integrity zome which defines its type.
+///
+/// Weighing is only done for DNAs which define rate limits, since the weight
+/// of an action has no effect otherwise, so the input is only built when needed.
+/// Actions which no zome weighs get the default weight, which is never limited.
+pub fn weigh_action(
+    ribosome: &impl RibosomeT,
+    input: impl FnOnce() -> SourceChainResult<WeighInput>,
+) -> RibosomeResult<RateWeight> {
+    if ribosome.dna_def().rate_limits.is_empty() {
+        return Ok(RateWeight::default());
+    }
+    let input = input()?;
+    let zome_index = match &input {
+        WeighInput::Link(link) => Some(link.zome_index),
+        WeighInput::Create(Create { entry_type, .. }, _)
+        | WeighInput::Update(Update { entry_type, .. }, _) => match entry_type {
+            EntryType::App(app_entry_def) => Some(app_entry_def.zome_index),
+            _ => None,
+        },
+        WeighInput::Delete(_) => None,
+    };
+    let zome = zome_index.and_then(|zome_index| ribosome.get_integrity_zome(&zome_index));
+    Ok(ribosome
+        .run_weigh(WeighHostAccess, WeighInvocation::new(zome, input))?
+        .0)
+}
+
 #[cfg(test)]
 pub mod wasm_test {
     use crate::core::ribosome::FnComponents;
@@ -94,6 +94,10 @@ pub enum RibosomeError {
     #[error("Host function {2} cannot be called from zome function {1} in zome {0}")]
     HostFnPermissions(ZomeName, FunctionName, String),
 
+    /// The call used up all of its wasm metering points.
+    #[error("Wasm call ran out of its {0} metering points")]
+    MeteringExhausted(u64),
+
     /// An attempt to was made to perform a clone operation on a cell that is not provisioned or belongs to another app.
     #[error("Invalid request to modify a cell which belongs to another app")]
     InvalidCloneTarget,
@@ -5,6 +5,7 @@ use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostContext;
 use crate::core::ribosome::Invocation;
 use crate::core::ribosome::InvocationAuth;
+use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomesToInvoke;
 use derive_more::Constructor;
 use holochain_keystore::MetaLairClient;
@@ -108,6 +109,7 @@ pub async fn send_post_commit(
                 },
                 invocation: PostCommitInvocation::new(zome, actions.clone()),
                 cell_id: cell_id.clone(),
+                attempts: 0,
             });
     }
     Ok(())
@@ -118,15 +120,93 @@ pub struct PostCommitArgs {
     pub host_access: PostCommitHostAccess,
     pub invocation: PostCommitInvocation,
     pub cell_id: Arc<CellId>,
+    /// How many times the callback has been run already.
+    pub attempts: u32,
+}
+
+/// Run the `post_commit` callback of a zome once.
+///
+/// If it fails and the [`PostCommitRetryPolicy`] of the zome allows another attempt,
+/// the arguments to retry with are returned, along with how long to wait first. The
+/// caller waits for the retry, so that waiting doesn't hold up the other callbacks.
+///
+/// If the callback fails for the last time, the app is sent a [`SystemSignal::PostCommitFailed`],
+/// so that it doesn't miss that e.g. its remote signals were not sent.
+pub async fn run_post_commit<R>(
+    ribosome: R,
+    args: PostCommitArgs,
+) -> Option<(PostCommitArgs, std::time::Duration)>
+where
+    R: RibosomeT + 'static,
+{
+    let PostCommitArgs {
+        host_access,
+        invocation,
+        cell_id,
+        attempts,
+    } = args;
+    let attempts = attempts + 1;
+    let result = tokio::task::spawn_blocking({
+        let host_access = host_access.clone();
+        let invocation = invocation.clone();
+        move || ribosome.run_post_commit(host_access, invocation)
+    })
+    .await;
+    let error = match result {
+        Ok(Ok(())) => return None,
+        Ok(Err(e)) => e.to_string(),
+        Err(e) => e.to_string(),
+    };
+    let policy = invocation
+        .zome
+        .def
+        .post_commit_retry()
+        .copied()
+        .unwrap_or_default();
+    let zome_name = invocation.zome.zome_name().clone();
+    if attempts > policy.retries() {
+        tracing::error!(?cell_id, %zome_name, attempts, %error, "post_commit failed");
+        let signal = SystemSignal::PostCommitFailed {
+            cell_id: (*cell_id).clone(),
+            zome_name,
+            action_hashes: invocation
+                .actions
+                .iter()
+                .map(|action| action.as_hash().clone())
+                .collect(),
+            attempts,
+            error,
+        };
+        // No subscribers is fine, the failure has been logged.
+        let _ = host_access.signal_tx.send(signal.into());
+        return None;
+    }
+    tracing::warn!(?cell_id, %zome_name, attempts, %error, "post_commit failed, retrying");
+    Some((
+        PostCommitArgs {
+            host_access,
+            invocation,
+            cell_id,
+            attempts,
+        },
+        policy.delay(attempts - 1),
+    ))
 }
 
 #[cfg(test)]
 mod test {
+    use super::run_post_commit;
+    use super::PostCommitArgs;
+    use crate::core::ribosome::error::RibosomeError;
     use crate::core::ribosome::Invocation;
+    use crate::core::ribosome::MockRibosomeT;
     use crate::core::ribosome::ZomesToInvoke;
     use crate::fixt::PostCommitHostAccessFixturator;
     use crate::fixt::PostCommitInvocationFixturator;
+    use ::fixt::prelude::*;
     use holochain_types::prelude::*;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     #[tokio::test(flavor = "multi_thread")]
     async fn post_commit_invocation_access() {
@@ -175,6 +255,74 @@ mod test {
             ExternIO::encode(ActionHashVecFixturator::new(::fixt::Empty).next().unwrap()).unwrap(),
         );
     }
+
+    fn failing_ribosome() -> MockRibosomeT {
+        let mut ribosome = MockRibosomeT::new();
+        ribosome
+            .expect_run_post_commit()
+            .times(1)
+            .returning(|_, _| Err(RibosomeError::InvalidCloneTarget));
+        ribosome
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn post_commit_is_retried_as_the_policy_allows() {
+        let mut host_access = PostCommitHostAccessFixturator::new(::fixt::Empty)
+            .next()
+            .unwrap();
+        let (signal_tx, mut signal_rx) = tokio::sync::broadcast::channel(10);
+        host_access.signal_tx = signal_tx;
+        let mut invocation = PostCommitInvocationFixturator::new(::fixt::Empty)
+            .next()
+            .unwrap();
+        invocation.zome = CoordinatorZome::new(
+            "zome".into(),
+            ZomeDef::Wasm(WasmZome {
+                wasm_hash: fixt!(WasmHash),
+                dependencies: vec![],
+                preserialized_path: None,
+                post_commit_retry: Some(PostCommitRetryPolicy {
+                    max_retries: 2,
+                    backoff_ms: 10,
+                }),
+            })
+            .into(),
+        );
+        let mut args = PostCommitArgs {
+            host_access,
+            invocation,
+            cell_id: Arc::new(fixt!(CellId)),
+            attempts: 0,
+        };
+
+        // Each failure is retried, waiting twice as long as before.
+        for (attempts, delay) in [(1, 10), (2, 20)] {
+            let (retry, wait) = run_post_commit(failing_ribosome(), args).await.unwrap();
+            assert_eq!(attempts, retry.attempts);
+            assert_eq!(Duration::from_millis(delay), wait);
+            args = retry;
+        }
+
+        // Until there are no retries left.
+        assert!(run_post_commit(failing_ribosome(), args).await.is_none());
+        match signal_rx.recv().await.unwrap() {
+            Signal::System(SystemSignal::PostCommitFailed { attempts, .. }) => {
+                assert_eq!(3, attempts)
+            }
+            signal => panic!("unexpected signal {signal:?}"),
+        }
+    }
+
+    #[test]
+    fn post_commit_retry_policy_is_capped() {
+        let policy = PostCommitRetryPolicy {
+            max_retries: u32::MAX,
+            backoff_ms: u64::MAX,
+        };
+        assert_eq!(MAX_POST_COMMIT_RETRIES, policy.retries());
+        assert_eq!(MAX_POST_COMMIT_RETRY_DELAY, policy.delay(0));
+        assert_eq!(MAX_POST_COMMIT_RETRY_DELAY, policy.delay(u32::MAX));
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,102 @@
+use crate::core::ribosome::FnComponents;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::Invocation;
+use crate::core::ribosome::InvocationAuth;
+use crate::core::ribosome::ZomesToInvoke;
+use derive_more::Constructor;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::prelude::*;
+
+#[derive(Clone, Debug)]
+pub struct WeighInvocation {
+    /// The integrity zome which defines the type being weighed, if known.
+    /// If not known, all integrity zomes are asked in turn.
+    pub zome: Option<IntegrityZome>,
+    pub input: WeighInput,
+}
+
+impl WeighInvocation {
+    pub fn new(zome: Option<IntegrityZome>, input: WeighInput) -> Self {
+        Self { zome, input }
+    }
+}
+
+#[derive(Clone, Constructor, Debug)]
+pub struct WeighHostAccess;
+
+impl From<WeighHostAccess> for HostContext {
+    fn from(weigh_host_access: WeighHostAccess) -> Self {
+        Self::Weigh(weigh_host_access)
+    }
+}
+
+impl From<&WeighHostAccess> for HostFnAccess {
+    fn from(_: &WeighHostAccess) -> Self {
+        let mut access = Self::none();
+        access.bindings_deterministic = Permission::Allow;
+        access
+    }
+}
+
+impl Invocation for WeighInvocation {
+    fn zomes(&self) -> ZomesToInvoke {
+        match &self.zome {
+            Some(zome) => ZomesToInvoke::OneIntegrity(zome.clone()),
+            None => ZomesToInvoke::AllIntegrity,
+        }
+    }
+    fn fn_components(&self) -> FnComponents {
+        vec!["weigh".into()].into()
+    }
+    fn host_input(self) -> Result<ExternIO, SerializedBytesError> {
+        ExternIO::encode(self.input)
+    }
+    fn auth(&self) -> InvocationAuth {
+        InvocationAuth::LocalCallback
+    }
+}
+
+/// The weight of an action, as determined by the first zome which weighed it.
+/// Actions which no zome weighs are weightless.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct WeighResult(pub RateWeight);
+
+impl From<Vec<(ZomeName, RateWeight)>> for WeighResult {
+    fn from(callback_results: Vec<(ZomeName, RateWeight)>) -> Self {
+        Self(
+            callback_results
+                .into_iter()
+                .next()
+                .map(|(_, weight)| weight)
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WeighHostAccess;
+    use super::WeighResult;
+    use holochain_types::prelude::*;
+
+    #[test]
+    fn weigh_callback_result_fold() {
+        assert_eq!(WeighResult::default(), vec![].into());
+
+        let weight = RateWeight {
+            bucket_id: 1,
+            units: 10,
+        };
+        assert_eq!(
+            WeighResult(weight.clone()),
+            vec![("a".into(), weight), ("b".into(), RateWeight::default())].into(),
+        );
+    }
+
+    #[test]
+    fn weigh_host_access() {
+        let mut access = HostFnAccess::none();
+        access.bindings_deterministic = Permission::Allow;
+        assert_eq!(HostFnAccess::from(&WeighHostAccess), access);
+    }
+}
@@ -81,14 +81,14 @@ host_fn_api_impls! {
 
     fn call (Vec<zt::call::Call>) -> Vec<zt::prelude::ZomeCallResponse>;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the local capability claims matching the input.
+    fn capability_claims (zt::capability::CapClaimsInput) -> Vec<zt::capability::CapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the local capability grants which have not been updated or deleted.
+    fn capability_grants (()) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability grant which authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapGrant;
 
     // The EntryDefId determines how a create is handled on the host side.
     // CapGrant and CapClaim are handled natively.
@@ -190,7 +190,7 @@ host_fn_api_impls! {
     // @todo
     fn schedule (String) -> ();
 
-    // @todo
+    // Pause the call for some time.
     fn sleep (core::time::Duration) -> ();
 
     // @todo
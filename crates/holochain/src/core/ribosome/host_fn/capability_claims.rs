@@ -1,13 +1,145 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_state::source_chain::SourceChainError;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::collections::HashSet;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
-/// lists all the local claims filtered by tag
+/// Lists all the local claims filtered by tag and grantor.
 pub fn capability_claims(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+    call_context: Arc<CallContext>,
+    input: CapClaimsInput,
+) -> Result<Vec<CapClaim>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let workspace = call_context.host_context.workspace();
+            let source_chain = workspace
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to query the source chain");
+            let query_error = |source_chain_error: SourceChainError| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+            };
+            let claims = source_chain
+                .query(
+                    ChainQueryFilter::new()
+                        .entry_type(EntryType::CapClaim)
+                        .include_entries(true),
+                )
+                .await
+                .map_err(query_error)?;
+            let deleted: HashSet<ActionHash> = source_chain
+                .query(ChainQueryFilter::new().action_type(ActionType::Delete))
+                .await
+                .map_err(query_error)?
+                .into_iter()
+                .filter_map(|record| match record.action() {
+                    Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                    _ => None,
+                })
+                .collect();
+
+            Ok(claims
+                .into_iter()
+                .filter(|record| !deleted.contains(record.action_address()))
+                .filter_map(|record| match record.entry.into_option() {
+                    Some(Entry::CapClaim(claim)) => Some(claim),
+                    _ => None,
+                })
+                .filter(|claim| {
+                    input.tag.as_ref().map_or(true, |tag| &claim.tag == tag)
+                        && input
+                            .grantor
+                            .as_ref()
+                            .map_or(true, |grantor| &claim.grantor == grantor)
+                })
+                .collect())
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_claims".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_claims_are_filtered() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            bob,
+            alice_pubkey,
+            bob_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Capability).await;
+
+        // Bob sends alice a claim, which she commits
+        let _: () = conductor
+            .call(&bob, "send_assigned_cap_claim", alice_pubkey.clone())
+            .await;
+
+        let claims: Vec<CapClaim> = conductor
+            .call(&alice, "list_cap_claims", CapClaimsInput::default())
+            .await;
+        assert_eq!(claims.len(), 1);
+        assert_eq!(claims[0].tag, "has_cap_claim");
+        assert_eq!(claims[0].grantor, bob_pubkey);
+
+        let claims: Vec<CapClaim> = conductor
+            .call(
+                &alice,
+                "list_cap_claims",
+                CapClaimsInput {
+                    tag: Some("has_cap_claim".into()),
+                    grantor: Some(bob_pubkey),
+                },
+            )
+            .await;
+        assert_eq!(claims.len(), 1);
+
+        let claims: Vec<CapClaim> = conductor
+            .call(
+                &alice,
+                "list_cap_claims",
+                CapClaimsInput {
+                    tag: Some("other".into()),
+                    grantor: None,
+                },
+            )
+            .await;
+        assert!(claims.is_empty());
+
+        let claims: Vec<CapClaim> = conductor
+            .call(
+                &alice,
+                "list_cap_claims",
+                CapClaimsInput {
+                    tag: None,
+                    grantor: Some(alice_pubkey),
+                },
+            )
+            .await;
+        assert!(claims.is_empty());
+    }
 }
@@ -1,16 +1,87 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_state::source_chain::SourceChainError;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::collections::HashSet;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
-/// list all the grants stored locally in the chain filtered by tag
-/// this is only the current grants as per local CRUD
+/// List all the grants stored locally in the chain.
+/// This is only the current grants as per local CRUD.
 pub fn capability_grants(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+) -> Result<Vec<CapGrantInfo>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let workspace = call_context.host_context.workspace();
+            let source_chain = workspace
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to query the source chain");
+            let query_error = |source_chain_error: SourceChainError| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+            };
+            let grants = source_chain
+                .query(
+                    ChainQueryFilter::new()
+                        .entry_type(EntryType::CapGrant)
+                        .include_entries(true),
+                )
+                .await
+                .map_err(query_error)?;
+            let deletes = source_chain
+                .query(ChainQueryFilter::new().action_type(ActionType::Delete))
+                .await
+                .map_err(query_error)?;
+
+            // A grant is replaced by its updates and revoked by its deletes.
+            let mut revoked: HashSet<ActionHash> = deletes
+                .iter()
+                .filter_map(|record| match record.action() {
+                    Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                    _ => None,
+                })
+                .collect();
+            revoked.extend(grants.iter().filter_map(|record| match record.action() {
+                Action::Update(update) => Some(update.original_action_address.clone()),
+                _ => None,
+            }));
+
+            Ok(grants
+                .into_iter()
+                .filter(|record| !revoked.contains(record.action_address()))
+                .filter_map(|record| {
+                    let created_at = record.action().timestamp();
+                    let action_hash = record.action_address().clone();
+                    match record.entry.into_option() {
+                        Some(Entry::CapGrant(cap_grant)) => Some(CapGrantInfo {
+                            action_hash,
+                            created_at,
+                            cap_grant,
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect())
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_grants".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +129,50 @@ pub mod wasm_test {
         assert_eq!(entry_secret, secret);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_grants_are_current() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::Capability).await;
+
+        // The unrestricted grant committed in init
+        let grants: Vec<CapGrantInfo> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].cap_grant.access, CapAccess::Unrestricted);
+
+        let secret: CapSecret = conductor.call(&alice, "cap_secret", ()).await;
+        let original_grant_hash: ActionHash = conductor
+            .call(&alice, "transferable_cap_grant", secret)
+            .await;
+        let grants: Vec<CapGrantInfo> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(grants.len(), 2);
+        assert!(grants
+            .iter()
+            .any(|grant| grant.action_hash == original_grant_hash));
+
+        // An updated grant is replaced by its update
+        let new_grant_hash: ActionHash = conductor
+            .call(&alice, "roll_cap_grant", original_grant_hash.clone())
+            .await;
+        let grants: Vec<CapGrantInfo> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(grants.len(), 2);
+        assert!(grants
+            .iter()
+            .any(|grant| grant.action_hash == new_grant_hash));
+        assert!(!grants
+            .iter()
+            .any(|grant| grant.action_hash == original_grant_hash));
+
+        // A deleted grant is gone
+        let _: ActionHash = conductor
+            .call(&alice, "delete_cap_grant", new_grant_hash)
+            .await;
+        let grants: Vec<CapGrantInfo> = conductor.call(&alice, "list_cap_grants", ()).await;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].cap_grant.access, CapAccess::Unrestricted);
+    }
+
     // MAYBE: [ B-03669 ] can move this to an integration test (may need to switch to using a RibosomeStore)
     #[tokio::test(flavor = "multi_thread")]
     async fn ribosome_authorized_call() -> anyhow::Result<()> {
@@ -1,14 +1,99 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::InvocationAuth;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
-/// return the access info used for this call
-/// also return who is originated the call (pubkey)
+/// Return the capability grant used to authorize this call.
 pub fn capability_info(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
+    call_context: Arc<CallContext>,
     _input: (),
-) -> Result<(), RuntimeError> {
-    unimplemented!();
+) -> Result<CapGrant, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            bindings: Permission::Allow,
+            ..
+        } => Ok(call_provenance_and_cap_grant(&call_context)?.1),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "capability_info".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+/// The agent who made the call and the capability grant which authorized it.
+/// Must only be called with bindings access.
+pub(crate) fn call_provenance_and_cap_grant(
+    call_context: &Arc<CallContext>,
+) -> Result<(AgentPubKey, CapGrant), RuntimeError> {
+    match call_context.auth() {
+        InvocationAuth::Cap(provenance, cap_secret) => {
+            let check_function = (
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+            );
+            let check_agent = provenance.clone();
+            let call_context = call_context.clone();
+            let cap_grant = tokio_helper::block_forever_on(async move {
+                call_context
+                    .host_context
+                    .workspace()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if bindings access is given")
+                    .valid_cap_grant(check_function, check_agent, cap_secret)
+                    .await
+                    .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))
+            })?
+            // This is really a problem.
+            // It means that the host function calling into `capability_info` or `call_info`
+            // is using a cap secret that never had authorization to call in the first place.
+            // The host must NEVER allow this so `None` is a critical bug.
+            .expect("The host is using an unauthorized cap_secret, which should never happen");
+            Ok((provenance, cap_grant))
+        }
+        InvocationAuth::LocalCallback => {
+            let author = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain if bindings access is given")
+                .agent_pubkey()
+                .clone();
+            Ok((author.clone(), CapGrant::ChainAuthor(author)))
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_capability_info_of_author_call() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Capability).await;
+
+        let cap_grant: CapGrant = conductor.call(&alice, "cap_info", ()).await;
+        assert_eq!(cap_grant, CapGrant::ChainAuthor(alice_pubkey));
+    }
 }
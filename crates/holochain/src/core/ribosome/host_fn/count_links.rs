@@ -29,6 +29,8 @@ pub fn count_links<'a>(
                 before: query.before,
                 after: query.after,
                 author: query.author,
+                cursor: query.cursor,
+                limit: query.limit,
             };
 
             CascadeImpl::from_workspace_and_network(
@@ -1,3 +1,4 @@
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::weigh_placeholder;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
@@ -11,7 +12,7 @@ use wasmer::RuntimeError;
 /// create record
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn create<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: CreateInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -68,6 +69,25 @@ pub fn create<'a>(
                         entry_hash,
                     };
 
+                    // weigh the action for rate limiting
+                    let weight = {
+                        let source_chain = call_context
+                            .host_context
+                            .workspace_write()
+                            .source_chain()
+                            .as_ref()
+                            .expect("Must have source chain if write_workspace access is given");
+                        weigh_action(ribosome.as_ref(), || {
+                            Ok(WeighInput::Create(
+                                source_chain.preview_unweighed(action_builder.clone())?,
+                                entry.clone(),
+                            ))
+                        })
+                        .map_err(|ribosome_error| -> RuntimeError {
+                            wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+                        })?
+                    };
+
                     // return the hash of the committed entry
                     // note that validation is handled by the workflow
                     // if the validation fails this commit will be rolled back by virtue of the DB transaction
@@ -80,7 +100,12 @@ pub fn create<'a>(
                             .source_chain()
                             .as_ref()
                             .expect("Must have source chain if write_workspace access is given")
-                            .put_weightless(action_builder, Some(entry), chain_top_ordering)
+                            .put_weighed(
+                                action_builder,
+                                Some(entry),
+                                chain_top_ordering,
+                                weight.into(),
+                            )
                             .await
                             .map_err(|source_chain_error| -> RuntimeError {
                                 wasm_error!(WasmErrorInner::Host(source_chain_error.to_string()))
@@ -1,3 +1,4 @@
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -10,7 +11,7 @@ use wasmer::RuntimeError;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn create_link<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: CreateLinkInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -32,6 +33,22 @@ pub fn create_link<'a>(
             let action_builder =
                 builder::CreateLink::new(base_address, target_address, zome_index, link_type, tag);
 
+            // weigh the action for rate limiting
+            let weight = weigh_action(ribosome.as_ref(), || {
+                Ok(WeighInput::Link(
+                    call_context
+                        .host_context
+                        .workspace_write()
+                        .source_chain()
+                        .as_ref()
+                        .expect("Must have source chain if write_workspace access is given")
+                        .preview_unweighed(action_builder.clone())?,
+                ))
+            })
+            .map_err(|ribosome_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+            })?;
+
             let action_hash = tokio_helper::block_forever_on(tokio::task::spawn(async move {
                 // push the action into the source chain
                 let action_hash = call_context
@@ -40,7 +57,7 @@ pub fn create_link<'a>(
                     .source_chain()
                     .as_ref()
                     .expect("Must have source chain if write_workspace access is given")
-                    .put_weightless(action_builder, None, chain_top_ordering)
+                    .put_weighed(action_builder, None, chain_top_ordering, weight)
                     .await?;
                 Ok::<ActionHash, RibosomeError>(action_hash)
             }))
@@ -1,4 +1,5 @@
 use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::RibosomeT;
 use holochain_cascade::error::CascadeError;
@@ -14,7 +15,7 @@ use wasmer::RuntimeError;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn delete<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: DeleteInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -31,6 +32,25 @@ pub fn delete<'a>(
                 get_original_entry_data(call_context.clone(), deletes_action_hash.clone())?;
 
             let host_access = call_context.host_context();
+            let action_builder = builder::Delete {
+                deletes_address: deletes_action_hash,
+                deletes_entry_address,
+            };
+
+            // weigh the action for rate limiting
+            let weight = weigh_action(ribosome.as_ref(), || {
+                Ok(WeighInput::Delete(
+                    host_access
+                        .workspace_write()
+                        .source_chain()
+                        .as_ref()
+                        .expect("Must have source chain if write_workspace access is given")
+                        .preview_unweighed(action_builder.clone())?,
+                ))
+            })
+            .map_err(|ribosome_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+            })?;
 
             // handle timeouts at the source chain layer
             tokio_helper::block_forever_on(async move {
@@ -39,12 +59,8 @@ pub fn delete<'a>(
                     .source_chain()
                     .as_ref()
                     .expect("Must have source chain if write_workspace access is given");
-                let action_builder = builder::Delete {
-                    deletes_address: deletes_action_hash,
-                    deletes_entry_address,
-                };
                 let action_hash = source_chain
-                    .put_weightless(action_builder, None, chain_top_ordering)
+                    .put_weighed(action_builder, None, chain_top_ordering, weight)
                     .await
                     .map_err(|source_chain_error| {
                         wasm_error!(WasmErrorInner::Host(source_chain_error.to_string()))
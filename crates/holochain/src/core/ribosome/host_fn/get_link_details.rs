@@ -39,6 +39,8 @@ pub fn get_link_details<'a>(
                             after: None,
                             before: None,
                             author: None,
+                            cursor: None,
+                            limit: None,
                         };
                         Ok(CascadeImpl::from_workspace_and_network(
                             &call_context.host_context.workspace(),
@@ -38,6 +38,8 @@ pub fn get_links<'a>(
                                     after,
                                     before,
                                     author,
+                                    cursor,
+                                    limit,
                                 } = input;
 
                                 let key = WireLinkKey {
@@ -47,6 +49,8 @@ pub fn get_links<'a>(
                                     after,
                                     before,
                                     author,
+                                    cursor,
+                                    limit,
                                 };
                                 Ok(CascadeImpl::from_workspace_and_network(
                                     &call_context.host_context.workspace(),
@@ -150,6 +154,142 @@ pub mod slow_tests {
         assert_eq!(links[1].target, foo_baz);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn hash_path_range() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::HashPath).await;
+
+        for path in ["index.c.c", "index.a.c", "index.b.b", "index.a.a", "index.c.a"] {
+            let _: () = conductor.call(&alice, "ensure", path.to_string()).await;
+        }
+        // A branch without any leaves
+        let _: () = conductor
+            .call(&alice, "ensure", "index.e".to_string())
+            .await;
+
+        let range = |depth: usize, start: &[&str], end: &[&str], reverse: bool| {
+            let input = (
+                "index".to_string(),
+                depth,
+                start.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+                end.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+                reverse,
+            );
+            let conductor = &conductor;
+            let alice = &alice;
+            async move {
+                let paths: Vec<String> = conductor.call(alice, "range", input).await;
+                paths
+            }
+        };
+
+        assert_eq!(
+            vec!["index.a", "index.b", "index.c", "index.e"],
+            range(1, &[], &[], false).await
+        );
+        assert_eq!(
+            vec!["index.a.a", "index.a.c", "index.b.b", "index.c.a", "index.c.c"],
+            range(2, &[], &[], false).await
+        );
+
+        // Bounds which exist are included
+        assert_eq!(
+            vec!["index.a.c", "index.b.b", "index.c.a"],
+            range(2, &["a", "c"], &["c", "a"], false).await
+        );
+        assert_eq!(
+            vec!["index.c.a", "index.b.b", "index.a.c"],
+            range(2, &["a", "c"], &["c", "a"], true).await
+        );
+        // and bounds which don't exist don't have to
+        assert_eq!(
+            vec!["index.a.c", "index.b.b", "index.c.a"],
+            range(2, &["a", "b"], &["c", "b"], false).await
+        );
+        // Bounds with fewer components cover whole branches
+        assert_eq!(
+            vec!["index.b.b"],
+            range(2, &["b"], &["b"], false).await
+        );
+
+        // Empty branches and ranges have no paths
+        assert!(range(2, &["d"], &[], false).await.is_empty());
+        assert!(range(2, &["b", "c"], &["c", "0"], false).await.is_empty());
+        assert!(range(2, &["c"], &["a"], false).await.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn hash_path_time_index() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::HashPath).await;
+
+        // 2024-02-29T00:00:00Z
+        let day = 1_709_164_800;
+        let at = |seconds: i64| Timestamp::from_micros((day + seconds) * 1_000_000);
+        let hour = 3_600;
+
+        for (timestamp, bucket) in [
+            (at(-60), "time.2024.02.28.23"),
+            (at(0), "time.2024.02.29.00"),
+            (at(5 * hour + 1_800), "time.2024.02.29.05"),
+            (at(23 * hour), "time.2024.02.29.23"),
+            (at(24 * hour), "time.2024.03.01.00"),
+        ] {
+            let path: String = conductor
+                .call(&alice, "ensure_time_bucket", timestamp)
+                .await;
+            assert_eq!(bucket, path);
+        }
+
+        let buckets = |start: Timestamp, end: Timestamp, reverse: bool| {
+            let conductor = &conductor;
+            let alice = &alice;
+            async move {
+                let paths: Vec<String> = conductor
+                    .call(alice, "time_buckets", (start, end, reverse))
+                    .await;
+                paths
+            }
+        };
+
+        // The buckets of the start and end are included, even when the
+        // timestamps are in the middle of them
+        assert_eq!(
+            vec![
+                "time.2024.02.29.00",
+                "time.2024.02.29.05",
+                "time.2024.02.29.23"
+            ],
+            buckets(at(1_800), at(24 * hour - 1), false).await
+        );
+        assert_eq!(
+            vec![
+                "time.2024.03.01.00",
+                "time.2024.02.29.23",
+                "time.2024.02.29.05",
+                "time.2024.02.29.00",
+                "time.2024.02.28.23"
+            ],
+            buckets(at(-hour), at(24 * hour), true).await
+        );
+        // A single bucket
+        assert_eq!(
+            vec!["time.2024.02.29.05"],
+            buckets(at(5 * hour), at(5 * hour), false).await
+        );
+        // Hours, days and years without buckets
+        assert!(buckets(at(6 * hour), at(23 * hour - 1), false)
+            .await
+            .is_empty());
+        assert!(buckets(at(2 * 24 * hour), at(400 * 24 * hour), false)
+            .await
+            .is_empty());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn hash_path_anchor_list_anchors() {
         holochain_trace::test_run();
@@ -48,6 +48,7 @@ pub fn must_get_entry<'a>(
                         | HostContext::GenesisSelfCheckV2(_)
                         | HostContext::MigrateAgent(_)
                         | HostContext::PostCommit(_)
+                        | HostContext::Weigh(_)
                         | HostContext::ZomeCall(_) => Err(wasm_error!(WasmErrorInner::Host(
                             format!("Failed to get EntryHashed {}", entry_hash)
                         ))
@@ -195,6 +195,217 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn schedule_test_cron_low_level() -> anyhow::Result<()> {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            alice_pubkey,
+            alice_host_fn_caller,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Schedule).await;
+
+        alice_host_fn_caller
+            .authored_db
+            .write_async(move |txn: &mut Transaction| {
+                // Midnight UTC, which is 09:00 in Tokyo.
+                let now = Timestamp::from_micros(1_700_006_400_000_000);
+                let hour = std::time::Duration::from_secs(60 * 60);
+
+                let cron_scheduled_fn = ScheduledFn::new("foo".into(), "bar".into());
+                let cron_schedule: Schedule = CronSchedule::new("0 0 10 * * * *")
+                    .with_timezone("Asia/Tokyo")
+                    .with_jitter(std::time::Duration::from_secs(10))
+                    .into();
+                schedule_fn(
+                    txn,
+                    &alice_pubkey,
+                    cron_scheduled_fn.clone(),
+                    Some(cron_schedule.clone()),
+                    now,
+                )
+                .unwrap();
+
+                // Due at 10:00 in Tokyo, give or take the jitter.
+                assert!(
+                    live_scheduled_fns(txn, (now + hour / 2).unwrap(), &alice_pubkey)
+                        .unwrap()
+                        .is_empty()
+                );
+                let after_jitter = (now + hour + std::time::Duration::from_secs(10)).unwrap();
+                assert_eq!(
+                    vec![(cron_scheduled_fn.clone(), Some(cron_schedule))],
+                    live_scheduled_fns(txn, after_jitter, &alice_pubkey).unwrap(),
+                );
+
+                // An unknown timezone is refused.
+                assert!(schedule_fn(
+                    txn,
+                    &alice_pubkey,
+                    cron_scheduled_fn.clone(),
+                    Some(
+                        CronSchedule::new("* * * * * * *")
+                            .with_timezone("Nowhere")
+                            .into()
+                    ),
+                    now,
+                )
+                .is_err());
+
+                for i in 0..(SCHEDULED_FN_RUN_HISTORY_LIMIT + 5) {
+                    insert_scheduled_fn_run(
+                        txn,
+                        &alice_pubkey,
+                        &ScheduledFnRun {
+                            zome_name: "foo".into(),
+                            fn_name: "bar".into(),
+                            started_at: (now + std::time::Duration::from_secs(i as u64)).unwrap(),
+                            duration: std::time::Duration::from_millis(5),
+                            error: (i % 2 == 1).then(|| "boom".to_string()),
+                        },
+                    )
+                    .unwrap();
+                }
+                insert_scheduled_fn_run(
+                    txn,
+                    &alice_pubkey,
+                    &ScheduledFnRun {
+                        zome_name: "baz".into(),
+                        fn_name: "bar".into(),
+                        started_at: now,
+                        duration: std::time::Duration::from_millis(5),
+                        error: None,
+                    },
+                )
+                .unwrap();
+
+                // Only the most recent runs are kept, newest first.
+                let runs = holochain_state::schedule::scheduled_fn_runs(
+                    txn,
+                    &alice_pubkey,
+                    Some(&"foo".into()),
+                    None,
+                    1000,
+                )
+                .unwrap();
+                assert_eq!(SCHEDULED_FN_RUN_HISTORY_LIMIT as usize, runs.len());
+                assert_eq!(
+                    (now + std::time::Duration::from_secs(
+                        SCHEDULED_FN_RUN_HISTORY_LIMIT as u64 + 4
+                    ))
+                    .unwrap(),
+                    runs[0].started_at
+                );
+                assert_eq!(Some("boom".to_string()), runs[1].error);
+                assert_eq!(std::time::Duration::from_millis(5), runs[0].duration);
+
+                let runs = holochain_state::schedule::scheduled_fn_runs(
+                    txn,
+                    &alice_pubkey,
+                    None,
+                    Some(&"bar".into()),
+                    3,
+                )
+                .unwrap();
+                assert_eq!(3, runs.len());
+
+                Result::<(), DatabaseError>::Ok(())
+            })
+            .await
+            .unwrap();
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn schedule_test_list_and_unschedule() -> anyhow::Result<()> {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            alice_pubkey,
+            alice_host_fn_caller,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Schedule).await;
+
+        alice_host_fn_caller
+            .authored_db
+            .write_async(move |txn: &mut Transaction| {
+                let now = Timestamp::now();
+                let ephemeral_scheduled_fn = ScheduledFn::new("foo".into(), "bar".into());
+                let persisted_scheduled_fn = ScheduledFn::new("1".into(), "2".into());
+                let persisted_schedule = Schedule::Persisted("* * * * * * *".into());
+
+                schedule_fn(
+                    txn,
+                    &alice_pubkey,
+                    persisted_scheduled_fn.clone(),
+                    Some(persisted_schedule.clone()),
+                    now,
+                )
+                .unwrap();
+                schedule_fn(
+                    txn,
+                    &alice_pubkey,
+                    ephemeral_scheduled_fn.clone(),
+                    None,
+                    now,
+                )
+                .unwrap();
+
+                // The ephemeral function is due now, the persisted one within a second.
+                let scheduled =
+                    holochain_state::schedule::scheduled_fns(txn, &alice_pubkey).unwrap();
+                assert_eq!(2, scheduled.len());
+                assert_eq!(ZomeName::from("foo"), scheduled[0].zome_name);
+                assert_eq!(now, scheduled[0].next_run);
+                assert!(scheduled[0].ephemeral);
+                assert_eq!(Some(persisted_schedule.clone()), scheduled[1].schedule);
+                assert!(scheduled[1].next_run > now);
+                assert!(!scheduled[1].ephemeral);
+
+                assert_eq!(
+                    Some(Some(persisted_schedule)),
+                    holochain_state::schedule::get_schedule(
+                        txn,
+                        &persisted_scheduled_fn,
+                        &alice_pubkey
+                    )
+                    .unwrap()
+                );
+                assert_eq!(
+                    Some(None),
+                    holochain_state::schedule::get_schedule(
+                        txn,
+                        &ephemeral_scheduled_fn,
+                        &alice_pubkey
+                    )
+                    .unwrap()
+                );
+
+                assert!(unschedule_fn(txn, &alice_pubkey, &persisted_scheduled_fn).unwrap());
+                assert!(!unschedule_fn(txn, &alice_pubkey, &persisted_scheduled_fn).unwrap());
+                assert_eq!(
+                    None,
+                    holochain_state::schedule::get_schedule(
+                        txn,
+                        &persisted_scheduled_fn,
+                        &alice_pubkey
+                    )
+                    .unwrap()
+                );
+                assert_eq!(
+                    1,
+                    holochain_state::schedule::scheduled_fns(txn, &alice_pubkey)
+                        .unwrap()
+                        .len()
+                );
+
+                Result::<(), DatabaseError>::Ok(())
+            })
+            .await
+            .unwrap();
+        Ok(())
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[cfg(feature = "test_utils")]
     async fn schedule_test_wasm() -> anyhow::Result<()> {
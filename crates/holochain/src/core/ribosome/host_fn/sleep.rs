@@ -1,12 +1,230 @@
 use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallHostAccess;
+use holochain_types::access::Permission;
+use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
+use std::time::Duration;
 use wasmer::RuntimeError;
 
+/// The longest a zome call, together with the zome calls it makes, may sleep for in total.
+pub const MAX_SLEEP_PER_CALL: Duration = Duration::from_secs(30);
+
+/// Pause the call without consuming metering points.
+///
+/// Commits are only written to the source chain once the call returns,
+/// so a sleeping call doesn't keep other calls from writing to the chain.
+///
+/// Only zome calls may sleep. A callback which sleeps would hold up the calls
+/// waiting for it, like the other `post_commit` callbacks or the zome calls
+/// waiting for `init`, which is also why the calls made by `init` may not sleep.
 pub fn sleep(
     _ribosome: Arc<impl RibosomeT>,
-    _call_context: Arc<CallContext>,
-    _input: core::time::Duration,
+    call_context: Arc<CallContext>,
+    input: Duration,
 ) -> Result<(), RuntimeError> {
-    unimplemented!()
+    match (
+        HostFnAccess::from(&call_context.host_context()),
+        call_context.host_context(),
+    ) {
+        (
+            HostFnAccess {
+                non_determinism: Permission::Allow,
+                ..
+            },
+            HostContext::ZomeCall(ZomeCallHostAccess { workspace, .. }),
+        ) => {
+            {
+                // Shared by all calls of the call chain, so that a call can't
+                // sleep for longer by making more calls.
+                let mut slept = workspace.slept().lock();
+                let total = slept.saturating_add(input);
+                if total > MAX_SLEEP_PER_CALL {
+                    return Err(wasm_error!(WasmErrorInner::Host(format!(
+                        "Sleeping for {:?} would exceed the maximum of {:?} per zome call, including the calls it makes",
+                        input, MAX_SLEEP_PER_CALL
+                    )))
+                    .into());
+                }
+                *slept = total;
+            }
+            tokio_helper::block_forever_on(tokio::time::sleep(input));
+            Ok(())
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "sleep".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+pub mod wasm_test {
+    use super::MAX_SLEEP_PER_CALL;
+    use crate::sweettest::*;
+    use holochain_types::prelude::*;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "slow_tests")]
+    async fn invoke_import_sleep_test() {
+        use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+        use holochain_wasm_test_utils::TestWasm;
+
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor, alice, ..
+        } = RibosomeTestFixture::new(TestWasm::SysTime).await;
+
+        let start = Instant::now();
+        let _: () = conductor
+            .call(&alice, "sleep", Duration::from_millis(100))
+            .await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        // A call can't sleep for longer than the maximum
+        assert!(conductor
+            .call_fallible::<_, ()>(
+                &alice,
+                "sleep",
+                MAX_SLEEP_PER_CALL + Duration::from_millis(1)
+            )
+            .await
+            .is_err());
+    }
+
+    fn create_zome() -> SweetInlineZomes {
+        SweetInlineZomes::new(vec![EntryDef::default_from_id("unit")], 0).function(
+            "create",
+            |api, ()| {
+                Ok(api.create(CreateInput::new(
+                    InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                    EntryVisibility::Public,
+                    Entry::app(().try_into().unwrap()).unwrap(),
+                    ChainTopOrdering::default(),
+                ))?)
+            },
+        )
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn calls_keep_committing_while_a_call_sleeps() {
+        holochain_trace::test_run();
+        let zomes = create_zome().function("sleep", |api, wake_after: Duration| {
+            api.sleep(wake_after)?;
+            Ok(())
+        });
+        let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome(SweetInlineZomes::COORDINATOR);
+
+        let sleeping = {
+            let conductor = conductor.sweet_handle();
+            let zome = zome.clone();
+            tokio::spawn(async move {
+                let () = conductor.call(&zome, "sleep", Duration::from_secs(2)).await;
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        for _ in 0..3 {
+            let _: ActionHash = conductor.call(&zome, "create", ()).await;
+        }
+        assert!(!sleeping.is_finished());
+        sleeping.await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn calls_made_by_a_call_share_its_sleep() {
+        holochain_trace::test_run();
+        let zomes = SweetInlineZomes::new(vec![], 0)
+            .function("sleep", |api, wake_after: Duration| {
+                api.sleep(wake_after)?;
+                Ok(())
+            })
+            .function("sleep_and_call", |api, wake_after: Duration| {
+                api.sleep(wake_after)?;
+                let responses = api.call(vec![Call::new(
+                    CallTarget::ConductorCell(CallTargetCell::Local),
+                    SweetInlineZomes::COORDINATOR.into(),
+                    "sleep".into(),
+                    None,
+                    ExternIO::encode(MAX_SLEEP_PER_CALL - wake_after / 2).unwrap(),
+                )])?;
+                Ok(responses)
+            });
+        let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        // The called function would sleep for longer than the rest of the
+        // time, so it fails without sleeping.
+        let start = Instant::now();
+        let result = conductor
+            .call_fallible::<_, Vec<ZomeCallResponse>>(
+                &cell.zome(SweetInlineZomes::COORDINATOR),
+                "sleep_and_call",
+                Duration::from_millis(100),
+            )
+            .await;
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn callbacks_may_not_sleep() {
+        holochain_trace::test_run();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let init_tx = tx.clone();
+        let zomes = create_zome()
+            .function("sleep", |api, wake_after: Duration| {
+                api.sleep(wake_after)?;
+                Ok(())
+            })
+            .function("init", move |api, ()| {
+                let _ = init_tx.send(("init", api.sleep(Duration::from_millis(1)).is_err()));
+                let called = api.call(vec![Call::new(
+                    CallTarget::ConductorCell(CallTargetCell::Local),
+                    SweetInlineZomes::COORDINATOR.into(),
+                    "sleep".into(),
+                    None,
+                    ExternIO::encode(Duration::from_millis(1)).unwrap(),
+                )]);
+                let _ = init_tx.send(("a call made by init", called.is_err()));
+                Ok(InitCallbackResult::Pass)
+            })
+            .function("post_commit", move |api, _: Vec<SignedActionHashed>| {
+                let _ = tx.send(("post_commit", api.sleep(Duration::from_millis(1)).is_err()));
+                Ok(())
+            });
+        let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        let _: ActionHash = conductor
+            .call(&cell.zome(SweetInlineZomes::COORDINATOR), "create", ())
+            .await;
+        let mut refused = std::collections::HashSet::new();
+        while refused.len() < 3 {
+            let (callback, sleep_failed) = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(sleep_failed, "{} was allowed to sleep", callback);
+            refused.insert(callback);
+        }
+    }
 }
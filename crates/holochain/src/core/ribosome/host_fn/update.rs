@@ -1,4 +1,5 @@
 use super::delete::get_original_entry_data;
+use crate::core::ribosome::weigh_action;
 use crate::core::ribosome::weigh_placeholder;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
@@ -12,7 +13,7 @@ use std::sync::Arc;
 
 #[allow(clippy::extra_unused_lifetimes)]
 pub fn update<'a>(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: UpdateInput,
 ) -> Result<ActionHash, RuntimeError> {
@@ -61,6 +62,21 @@ pub fn update<'a>(
                     };
                     let workspace = call_context.host_context.workspace_write();
 
+                    // weigh the action for rate limiting
+                    let weight = weigh_action(ribosome.as_ref(), || {
+                        Ok(WeighInput::Update(
+                            workspace
+                                .source_chain()
+                                .as_ref()
+                                .expect("Must have source chain if write_workspace access is given")
+                                .preview_unweighed(action_builder.clone())?,
+                            entry.clone(),
+                        ))
+                    })
+                    .map_err(|ribosome_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(ribosome_error.to_string())).into()
+                    })?;
+
                     // return the hash of the updated entry
                     // note that validation is handled by the workflow
                     // if the validation fails this update will be rolled back by virtue of the DB transaction
@@ -72,7 +88,12 @@ pub fn update<'a>(
                             .expect("Must have source chain if write_workspace access is given");
                         // push the action and the entry into the source chain
                         let action_hash = source_chain
-                            .put_weightless(action_builder, Some(entry), chain_top_ordering)
+                            .put_weighed(
+                                action_builder,
+                                Some(entry),
+                                chain_top_ordering,
+                                weight.into(),
+                            )
                             .await
                             .map_err(|source_chain_error| -> RuntimeError {
                                 wasm_error!(WasmErrorInner::Host(source_chain_error.to_string()))
@@ -3,6 +3,7 @@ use super::guest_callback::init::InitHostAccess;
 use super::guest_callback::migrate_agent::MigrateAgentHostAccess;
 use super::guest_callback::post_commit::PostCommitHostAccess;
 use super::guest_callback::validate::ValidateHostAccess;
+use super::guest_callback::weigh::WeighHostAccess;
 use super::host_fn::delete_clone_cell::delete_clone_cell;
 use super::host_fn::disable_clone_cell::disable_clone_cell;
 use super::host_fn::enable_clone_cell::enable_clone_cell;
@@ -27,6 +28,8 @@ use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
+use crate::core::ribosome::guest_callback::weigh::WeighInvocation;
+use crate::core::ribosome::guest_callback::weigh::WeighResult;
 use crate::core::ribosome::guest_callback::CallIterator;
 use crate::core::ribosome::host_fn::accept_countersigning_preflight_request::accept_countersigning_preflight_request;
 use crate::core::ribosome::host_fn::agent_info::agent_info;
@@ -457,6 +460,8 @@ impl RealRibosome {
                 quantum_time: Default::default(),
             },
             integrity_zomes: Default::default(),
+            rate_limits: Default::default(),
+            wasm_metering_limit: None,
             coordinator_zomes: Default::default(),
         };
         let empty_dna_file = DnaFile::new(empty_dna_def, vec![]).await;
@@ -741,6 +746,20 @@ impl RealRibosome {
     ) -> RibosomeResult<GenesisSelfCheckResultV1> {
         do_callback!(self, host_access, invocation, ValidateCallbackResult)
     }
+
+    /// The number of metering points a call for this invocation may consume.
+    ///
+    /// This is the limit of the DNA, which defaults to [`WASM_METERING_LIMIT`].
+    /// The limit of a zome call is not signed, so it may only lower it.
+    fn metering_limit<I: Invocation>(&self, invocation: &I) -> u64 {
+        let limit = self
+            .dna_def()
+            .wasm_metering_limit
+            .unwrap_or(WASM_METERING_LIMIT);
+        invocation
+            .metering_limit()
+            .map_or(limit, |call_limit| call_limit.min(limit))
+    }
 }
 
 impl RibosomeT for RealRibosome {
@@ -857,29 +876,42 @@ impl RibosomeT for RealRibosome {
                     }
 
                     let instance = instance_with_store.instance.clone();
+                    let metering_limit = self.metering_limit(invocation);
                     {
                         let mut store_lock = instance_with_store.store.lock();
                         let mut store_mut = store_lock.as_store_mut();
-                        set_remaining_points(
-                            &mut store_mut,
-                            instance.as_ref(),
-                            WASM_METERING_LIMIT,
-                        );
+                        set_remaining_points(&mut store_mut, instance.as_ref(), metering_limit);
                     }
 
-                    let result = self
+                    let mut result = self
                         .call_zome_fn::<I>(invocation, zome, fn_name, instance_with_store.clone())
                         .map(Some);
 
                     {
                         let mut store_lock = instance_with_store.store.lock();
                         let mut store_mut = store_lock.as_store_mut();
-                        let points_used =
+                        let (points_used, exhausted) =
                             match get_remaining_points(&mut store_mut, instance.as_ref()) {
-                                MeteringPoints::Remaining(points) => WASM_METERING_LIMIT - points,
-                                MeteringPoints::Exhausted => WASM_METERING_LIMIT,
+                                MeteringPoints::Remaining(points) => {
+                                    (metering_limit - points, false)
+                                }
+                                MeteringPoints::Exhausted => (metering_limit, true),
                             };
+                        tracing::debug!(
+                            %fn_name,
+                            points_used,
+                            metering_limit,
+                            exhausted,
+                            "wasm metering"
+                        );
+                        otel_info.push(opentelemetry_api::KeyValue::new("exhausted", exhausted));
                         self.usage_meter.add(points_used, &otel_info);
+                        invocation.record_metering_points(points_used);
+                        // Running out of points traps the guest, which is
+                        // reported as such rather than as a generic wasm error.
+                        if exhausted && result.is_err() {
+                            result = Err(RibosomeError::MeteringExhausted(metering_limit));
+                        }
                     }
 
                     // remove context from map after call
@@ -1025,6 +1057,14 @@ impl RibosomeT for RealRibosome {
         do_callback!(self, host_access, invocation, ValidateCallbackResult)
     }
 
+    fn run_weigh(
+        &self,
+        host_access: WeighHostAccess,
+        invocation: WeighInvocation,
+    ) -> RibosomeResult<WeighResult> {
+        do_callback!(self, host_access, invocation, RateWeight)
+    }
+
     fn run_init(
         &self,
         host_access: InitHostAccess,
@@ -1074,6 +1114,7 @@ impl RibosomeT for RealRibosome {
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod wasm_test {
+    use crate::core::ribosome::error::RibosomeError;
     use crate::core::ribosome::real_ribosome::CONTEXT_MAP;
     use crate::core::ribosome::wasm_test::RibosomeTestFixture;
     use crate::core::ribosome::ZomeCall;
@@ -1085,6 +1126,8 @@ pub mod wasm_test {
     use hdk::prelude::*;
     use holochain_nonce::fresh_nonce;
     use holochain_types::prelude::AgentPubKeyFixturator;
+    use holochain_types::prelude::DnaDef;
+    use holochain_types::prelude::DnaFile;
     use holochain_wasm_test_utils::TestWasm;
     use holochain_zome_types::zome_io::ZomeCallUnsigned;
     use std::sync::Arc;
@@ -1340,4 +1383,98 @@ pub mod wasm_test {
         .await;
         assert!(create_result.unwrap().is_err());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn zome_call_metering_limit() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::TheIncredibleHalt).await;
+
+        let (nonce, expires_at) = fresh_nonce(Timestamp::now()).unwrap();
+        let call = ZomeCall::try_from_unsigned_zome_call(
+            conductor.raw_handle().keystore(),
+            ZomeCallUnsigned {
+                cell_id: alice.cell_id().clone(),
+                zome_name: alice.name().clone(),
+                fn_name: "smash".into(),
+                cap_secret: None,
+                provenance: alice_pubkey,
+                payload: ExternIO::encode(()).unwrap(),
+                nonce,
+                expires_at,
+            },
+        )
+        .await
+        .unwrap();
+
+        // The infinite loop is trapped as soon as the small limit of the call is used up.
+        let (result, metering_points_used) = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            conductor.raw_handle().call_zome_metered(ZomeCall {
+                metering_limit: Some(1_000_000),
+                ..call
+            }),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(matches!(
+            result,
+            Err(RibosomeError::MeteringExhausted(1_000_000))
+        ));
+        assert_eq!(metering_points_used, 1_000_000);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dna_wasm_metering_limit() {
+        holochain_trace::test_run();
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_test_wasms(vec![TestWasm::TheIncredibleHalt]).await;
+        let dna_def = DnaDef {
+            wasm_metering_limit: Some(1_000_000),
+            ..dna_file.dna_def().clone()
+        };
+        let dna_file = DnaFile::new(dna_def, dna_file.code().values().cloned()).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (alice,) = app.into_tuple();
+        let zome = alice.zome(TestWasm::TheIncredibleHalt);
+
+        let (nonce, expires_at) = fresh_nonce(Timestamp::now()).unwrap();
+        let call = ZomeCall::try_from_unsigned_zome_call(
+            conductor.raw_handle().keystore(),
+            ZomeCallUnsigned {
+                cell_id: alice.cell_id().clone(),
+                zome_name: zome.name().clone(),
+                fn_name: "smash".into(),
+                cap_secret: None,
+                provenance: alice.agent_pubkey().clone(),
+                payload: ExternIO::encode(()).unwrap(),
+                nonce,
+                expires_at,
+            },
+        )
+        .await
+        .unwrap();
+
+        // The limit of the DNA applies, and a call can't raise it.
+        let result = tokio::time::timeout(
+            std::time::Duration::from_secs(10),
+            conductor.raw_handle().call_zome(ZomeCall {
+                metering_limit: Some(u64::MAX),
+                ..call
+            }),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            result,
+            Ok(Err(RibosomeError::MeteringExhausted(1_000_000)))
+        ));
+    }
 }
@@ -239,11 +239,49 @@ pub async fn check_chain_rollback(
 }
 
 /// Placeholder for future spam check.
-/// Check action timestamps don't exceed MAX_PUBLISH_FREQUENCY
+#[deprecated = "Spam is limited by the rate limits of the DNA instead, see `check_rate_limit`"]
 pub async fn check_spam(_action: &Action) -> SysValidationResult<()> {
     Ok(())
 }
 
+/// The earliest timestamp at which a previous action by the same author can
+/// affect the rate limit check for this action.
+/// Returns `None` if the action's bucket has no rate limit in this DNA.
+pub fn rate_limit_window_start(action: &Action, rate_limits: &RateLimits) -> Option<Timestamp> {
+    let limit = rate_limits.get(&action.rate_data().bucket_id)?;
+    Some(action.timestamp().saturating_sub(&limit.drain_window()))
+}
+
+/// Check that the weight of this action does not overflow its rate limiting bucket.
+/// The previous actions of the author since [`rate_limit_window_start`] must
+/// be provided, oldest first.
+pub fn check_rate_limit<'a>(
+    action: &Action,
+    previous_actions: impl IntoIterator<Item = &'a Action>,
+    rate_limits: &RateLimits,
+) -> SysValidationResult<()> {
+    let RateWeight { bucket_id, units } = action.rate_data();
+    let limit = match rate_limits.get(&bucket_id) {
+        Some(limit) => *limit,
+        None => return Ok(()),
+    };
+    let mut bucket = RateBucket::new(limit);
+    for previous_action in previous_actions {
+        let weight = previous_action.rate_data();
+        if weight.bucket_id == bucket_id {
+            // The previous actions have already passed this check, so an
+            // overflow can only be due to starting with an empty bucket.
+            let _ = bucket.add(weight.units, previous_action.timestamp());
+        }
+    }
+    match bucket.add(units, action.timestamp()) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            Err(ValidationOutcome::RateLimitExceeded(bucket_id, Box::new(action.clone()), e).into())
+        }
+    }
+}
+
 /// Check that created agents are always paired with an AgentValidationPkg and vice versa
 pub fn check_agent_validation_pkg_predecessor(
     action: &Action,
@@ -143,6 +143,8 @@ pub enum ValidationOutcome {
     PrevActionError(#[from] PrevActionError),
     #[error("Private entry data should never be included in any op other than StoreEntry.")]
     PrivateEntryLeaked,
+    #[error("The action {1:?} exceeds the rate limit of bucket {0}. Reason: {2}")]
+    RateLimitExceeded(RateBucketId, Box<Action>, RateBucketError),
     #[error("The DNA does not belong in this space! Action has {0:?}, expected {1:?}")]
     WrongDna(DnaHash, DnaHash),
     #[error("Update original: {0:?} doesn't match new: {1:?}")]
@@ -521,6 +521,58 @@ fn check_entry_type_test() {
     }
 }
 
+#[test]
+fn check_rate_limit_test() {
+    let mut g = random_generator();
+    let rate_limits: RateLimits = [(
+        0,
+        RateLimit {
+            capacity: 10,
+            drain_amount: 5,
+            drain_interval_ms: 1000,
+        },
+    )]
+    .into_iter()
+    .collect();
+
+    let mut link_at = |millis: i64, bucket_id: RateBucketId, units: RateUnits| -> Action {
+        let mut link = CreateLink::arbitrary(&mut g).unwrap();
+        link.timestamp = Timestamp::from_micros(millis * 1000);
+        link.weight = RateWeight { bucket_id, units };
+        link.into()
+    };
+
+    let previous = vec![link_at(0, 0, 6), link_at(100, 1, 200), link_at(200, 0, 4)];
+
+    // The bucket is full
+    let action = link_at(300, 0, 1);
+    assert_eq!(
+        rate_limit_window_start(&action, &rate_limits),
+        Some(Timestamp::from_micros(-1_700_000))
+    );
+    assert_matches!(
+        check_rate_limit(&action, previous.iter(), &rate_limits),
+        Err(SysValidationError::ValidationOutcome(
+            ValidationOutcome::RateLimitExceeded(0, _, RateBucketError::BucketOverflow { .. })
+        ))
+    );
+
+    // After one interval there is room again
+    let action = link_at(1000, 0, 5);
+    assert_matches!(
+        check_rate_limit(&action, previous.iter(), &rate_limits),
+        Ok(())
+    );
+
+    // Buckets without a limit are never limited
+    let action = link_at(300, 1, 255);
+    assert_eq!(rate_limit_window_start(&action, &rate_limits), None);
+    assert_matches!(
+        check_rate_limit(&action, previous.iter(), &rate_limits),
+        Ok(())
+    );
+}
+
 /// Hash integrity check. The hash of an entry always matches what's in the action.
 #[test]
 fn check_entry_hash_test() {
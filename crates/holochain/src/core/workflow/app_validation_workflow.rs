@@ -36,6 +36,13 @@
 //! Having established the relevant integrity zomes for validating an op, each
 //! zome's validation callback is invoked.
 //!
+//! ### Warrant validation
+//!
+//! Warrants which claim that an action fails app validation are checked by
+//! fetching the warranted record and validating its ops. A warrant is only
+//! accepted if one of the ops is invalid, in which case the agent it was issued
+//! against is blocked for the DNA like for warrants accepted by sys validation.
+//!
 //! ### Outcome
 //!
 //! An op can be valid or invalid, which is considered "validated", or it could not be
@@ -96,6 +103,8 @@
 
 use super::error::WorkflowResult;
 use super::sys_validation_workflow::validation_query;
+use super::sys_validation_workflow::warrant_block;
+use super::sys_validation_workflow::warranted_action;
 
 use crate::conductor::entry_def_store::get_entry_def;
 use crate::conductor::Conductor;
@@ -244,7 +253,64 @@ async fn app_validation_workflow_inner(
         let (dht_op, dht_op_hash) = sorted_dht_op.into_inner();
         let chain_op = match dht_op {
             DhtOp::ChainOp(chain_op) => chain_op,
-            _ => continue,
+            DhtOp::WarrantOp(warrant_op) => {
+                let validation_outcome = validate_warrant_op(
+                    dna_hash.clone(),
+                    &warrant_op,
+                    &conductor,
+                    &workspace,
+                    network,
+                    cascade.clone(),
+                    validation_dependencies.clone(),
+                )
+                .await;
+                let outcome = match validation_outcome {
+                    Ok(outcome) | Err(OutcomeOrError::Outcome(outcome)) => outcome,
+                    Err(OutcomeOrError::Err(err)) => {
+                        tracing::error!(
+                            ?warrant_op,
+                            ?err,
+                            "App validation error when validating warrant op."
+                        );
+                        failed_ops.lock().insert(dht_op_hash);
+                        continue;
+                    }
+                };
+                // Block BEFORE we integrate the warrant because this is not atomic
+                // and if something goes wrong we know the warrant will be validated again.
+                if let Outcome::Accepted = outcome {
+                    block_warrantee(&conductor, &dna_hash, &warrant_op, &dht_op_hash).await;
+                }
+
+                let accepted_ops = accepted_ops.clone();
+                let awaiting_ops = awaiting_ops.clone();
+                let rejected_ops = rejected_ops.clone();
+                let write_result = workspace
+                    .dht_db
+                    .write_async(move |txn| match outcome {
+                        Outcome::Accepted => {
+                            accepted_ops.fetch_add(1, Ordering::SeqCst);
+                            put_integrated(txn, &dht_op_hash, ValidationStatus::Valid)
+                        }
+                        Outcome::AwaitingDeps(deps) => {
+                            awaiting_ops.fetch_add(1, Ordering::SeqCst);
+                            put_validation_limbo(
+                                txn,
+                                &dht_op_hash,
+                                ValidationStage::AwaitingAppDeps(deps),
+                            )
+                        }
+                        Outcome::Rejected(_) => {
+                            rejected_ops.fetch_add(1, Ordering::SeqCst);
+                            put_integrated(txn, &dht_op_hash, ValidationStatus::Rejected)
+                        }
+                    })
+                    .await;
+                if let Err(err) = write_result {
+                    tracing::error!(?warrant_op, ?err, "Error updating warrant op in database.");
+                }
+                continue;
+            }
         };
         let op_type = chain_op.get_type();
         let action = chain_op.action();
@@ -502,6 +568,91 @@ async fn chain_op_to_op(chain_op: ChainOp, cascade: Arc<impl Cascade>) -> AppVal
     Ok(op)
 }
 
+/// Validate a warrant which claims that an action fails app validation.
+///
+/// The warranted record is fetched and its ops are validated. For a false validation
+/// receipt only the receipted op is validated. The warrant is accepted if one of the
+/// ops is invalid and rejected if all of them are valid.
+async fn validate_warrant_op(
+    dna_hash: Arc<DnaHash>,
+    warrant_op: &WarrantOp,
+    conductor_handle: &ConductorHandle,
+    workspace: &AppValidationWorkspace,
+    network: &HolochainP2pDna,
+    cascade: Arc<impl Cascade>,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> AppValidationOutcome<Outcome> {
+    let action_hash = match warranted_action(&warrant_op.warrant) {
+        Some(((action_hash, _), ValidationType::App)) => action_hash,
+        // Other warrants are not app validated.
+        _ => return Ok(Outcome::Accepted),
+    };
+    let receipted_op = match &warrant_op.warrant {
+        Warrant::ValidationReceipt(ValidationReceiptWarrant::InvalidOpReceipted {
+            dht_op_hash,
+            ..
+        }) => Some(dht_op_hash),
+        _ => None,
+    };
+
+    let (record, _) = cascade
+        .retrieve(action_hash.clone().into(), Default::default())
+        .await?
+        .ok_or_else(|| Outcome::awaiting(action_hash))?;
+    for op_type in action_to_op_types(record.action()) {
+        // The entry of a private record is not available for validation.
+        if op_type == ChainOpType::StoreEntry && record.entry().as_option().is_none() {
+            continue;
+        }
+        let (op, op_hash, _) = record_to_op(record.clone(), op_type, cascade.clone()).await?;
+        if receipted_op.map_or(false, |receipted_op| *receipted_op != op_hash) {
+            continue;
+        }
+        match validate_op_outer(
+            dna_hash.clone(),
+            &op,
+            &op_hash,
+            conductor_handle,
+            workspace,
+            network,
+            validation_dependencies.clone(),
+        )
+        .await?
+        {
+            Outcome::Accepted => {}
+            Outcome::Rejected(_) => return Ok(Outcome::Accepted),
+            awaiting @ Outcome::AwaitingDeps(_) => return Ok(awaiting),
+        }
+    }
+    Ok(Outcome::Rejected("warranted action is valid".into()))
+}
+
+/// Block the agent an accepted warrant was issued against, unless they are running on this conductor.
+async fn block_warrantee(
+    conductor: &Conductor,
+    dna_hash: &DnaHash,
+    warrant_op: &WarrantOp,
+    warrant_op_hash: &DhtOpHash,
+) {
+    let warrantee = warrant_op.warrant.warrantee();
+    let cell_id = CellId::new(dna_hash.clone(), warrantee.clone());
+    if conductor.running_cell_ids().contains(&cell_id) {
+        return;
+    }
+    let warrant_block_duration = conductor
+        .config
+        .conductor_tuning_params()
+        .warrant_block_duration();
+    if let Some(block) = warrant_block(cell_id, warrant_op_hash.clone(), warrant_block_duration) {
+        if let Err(e) = conductor.block(block).await {
+            error!(
+                "Failed to apply block to warranted agent {:?}: {:?}",
+                warrantee, e
+            )
+        }
+    }
+}
+
 async fn validate_op_outer(
     dna_hash: Arc<DnaHash>,
     op: &Op,
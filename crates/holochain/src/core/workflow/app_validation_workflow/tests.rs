@@ -26,7 +26,9 @@ use holochain_types::dht_op::DhtOpHashed;
 use holochain_types::inline_zome::InlineZomeSet;
 use holochain_types::prelude::*;
 use holochain_wasm_test_utils::{TestWasm, TestWasmPair, TestZomes};
-use holochain_zome_types::fixt::{CreateFixturator, DeleteFixturator, SignatureFixturator};
+use holochain_zome_types::fixt::{
+    CreateFixturator, CreateLinkFixturator, DeleteFixturator, SignatureFixturator,
+};
 use holochain_zome_types::timestamp::Timestamp;
 use holochain_zome_types::Action;
 use matches::assert_matches;
@@ -660,6 +662,113 @@ async fn handle_error_in_op_validation() {
     assert_eq!(ops_to_validate, 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn warrants_are_only_accepted_for_invalid_actions() {
+    holochain_trace::test_run();
+
+    // Actions at sequence number 1000 are invalid.
+    let zomes = SweetInlineZomes::new(vec![], 0).integrity_function(
+        "validate",
+        move |_, op: Op| match op {
+            Op::RegisterAgentActivity(RegisterAgentActivity { action, .. })
+                if action.action().action_seq() == 1000 =>
+            {
+                Ok(ValidateCallbackResult::Invalid("bad seq".to_string()))
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+    );
+
+    let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+    let dna_hash = dna_file.dna_hash().clone();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("", &[dna_file.clone()]).await.unwrap();
+    let cell_id = app.cells()[0].cell_id().clone();
+
+    let app_validation_workspace = Arc::new(AppValidationWorkspace::new(
+        conductor
+            .get_or_create_authored_db(&dna_hash, cell_id.agent_pubkey().clone())
+            .unwrap()
+            .into(),
+        conductor.get_dht_db(&dna_hash).unwrap(),
+        conductor.get_dht_db_cache(&dna_hash).unwrap(),
+        conductor.get_cache_db(&cell_id).await.unwrap(),
+        conductor.keystore(),
+        Arc::new(dna_file.dna_def().clone()),
+    ));
+
+    // A warrant against an author of an invalid action and a forged one against
+    // an author of a valid action.
+    let mut warrantees = Vec::new();
+    for action_seq in [1000, 1001] {
+        let mut create_link = fixt!(CreateLink);
+        create_link.action_seq = action_seq;
+        create_link.zome_index = 0.into();
+        let action = Action::CreateLink(create_link);
+        let action_hash = ActionHash::with_data_sync(&action);
+        let store_record_op = DhtOpHashed::from_content_sync(ChainOp::StoreRecord(
+            fixt!(Signature),
+            action.clone(),
+            RecordEntry::NA,
+        ));
+        app_validation_workspace.cache.test_write(move |txn| {
+            insert_op(txn, &store_record_op).unwrap();
+            put_validation_limbo(txn, &store_record_op.hash, ValidationStage::Pending).unwrap();
+        });
+
+        let warrant_op = DhtOpHashed::from_content_sync(WarrantOp::new(
+            Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+                action_author: action.author().clone(),
+                action: (action_hash, fixt!(Signature)),
+                validation_type: ValidationType::App,
+            }),
+            fixt!(AgentPubKey),
+            fixt!(Signature),
+            Timestamp::now(),
+        ));
+        app_validation_workspace.dht_db.test_write(move |txn| {
+            insert_op(txn, &warrant_op).unwrap();
+            put_validation_limbo(txn, &warrant_op.hash, ValidationStage::SysValidated).unwrap();
+        });
+        warrantees.push(action.author().clone());
+    }
+
+    let outcome_summary = app_validation_workflow_inner(
+        Arc::new(dna_hash.clone()),
+        app_validation_workspace.clone(),
+        conductor.raw_handle(),
+        &conductor.holochain_p2p().to_dna(dna_hash.clone(), None),
+        conductor
+            .get_or_create_space(&dna_hash)
+            .unwrap()
+            .dht_query_cache,
+        Arc::new(Mutex::new(ValidationDependencies::new())),
+    )
+    .await
+    .unwrap();
+    assert_matches!(
+        outcome_summary,
+        OutcomeSummary {
+            ops_to_validate: 2,
+            validated: 2,
+            accepted: 1,
+            rejected: 1,
+            missing: 0,
+            failed: empty_set,
+        } if empty_set == HashSet::<DhtOpHash>::new()
+    );
+
+    let is_blocked = |agent: AgentPubKey| {
+        conductor.is_blocked(
+            BlockTargetId::Cell(CellId::new(dna_hash.clone(), agent)),
+            Timestamp::now(),
+        )
+    };
+    assert!(is_blocked(warrantees[0].clone()).await.unwrap());
+    assert!(!is_blocked(warrantees[1].clone()).await.unwrap());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[ignore = "deal with the invalid data that leads to blocks being enforced"]
 async fn app_validation_workflow_test() {
@@ -869,6 +978,8 @@ async fn check_app_entry_def_test() {
                 quantum_time: holochain_p2p::dht::spacetime::STANDARD_QUANTUM_TIME,
             },
             integrity_zomes: vec![TestZomes::from(TestWasm::EntryDefs).integrity.into_inner()],
+            rate_limits: Default::default(),
+            wasm_metering_limit: None,
             coordinator_zomes: vec![TestZomes::from(TestWasm::EntryDefs)
                 .coordinator
                 .into_inner()],
@@ -75,57 +75,84 @@ where
 
     // commit the workspace
     if should_write {
-        let countersigning_op = workspace.source_chain().countersigning_op()?;
-        match workspace.source_chain().flush(&network).await {
-            Ok(flushed_actions) => {
-                // Skip if nothing was written
-                if !flushed_actions.is_empty() {
-                    match countersigning_op {
-                        Some(op) => {
-                            if let Err(error_response) =
-                                super::countersigning_workflow::countersigning_publish(
-                                    &network,
-                                    op,
-                                    (*workspace.author().ok_or_else(|| {
-                                        WorkflowError::Other("author required".into())
-                                    })?)
-                                    .clone(),
-                                )
-                                .await
-                            {
-                                return Ok(Ok(error_response));
-                            }
-                        }
-                        None => {
-                            trigger_publish_dht_ops.trigger(&"call_zome_workflow");
-                            trigger_integrate_dht_ops.trigger(&"call_zome_workflow");
-                        }
-                    }
-
-                    // Only send post commit if this is a coordinator zome.
-                    if let Some(coordinator_zome) = coordinator_zome {
-                        send_post_commit(
-                            conductor_handle,
-                            workspace,
-                            network,
-                            keystore,
-                            flushed_actions,
-                            vec![coordinator_zome],
-                            signal_tx,
-                        )
-                        .await?;
-                    }
-                }
-            }
-            err => {
-                err?;
-            }
+        if let Some(error_response) = flush_call_zome_workspace(
+            workspace,
+            network,
+            keystore,
+            conductor_handle,
+            signal_tx,
+            coordinator_zome.into_iter().collect(),
+            trigger_publish_dht_ops,
+            trigger_integrate_dht_ops,
+        )
+        .await?
+        {
+            return Ok(Ok(error_response));
         }
     };
 
     Ok(result)
 }
 
+/// Write the workspace of one or more zome calls to the source chain,
+/// publish the new ops and send `post_commit` to the given coordinator zomes.
+///
+/// Returns the response to give instead of the results of the calls
+/// if a countersigning session failed to start.
+#[allow(clippy::too_many_arguments)]
+pub async fn flush_call_zome_workspace(
+    workspace: SourceChainWorkspace,
+    network: HolochainP2pDna,
+    keystore: MetaLairClient,
+    conductor_handle: ConductorHandle,
+    signal_tx: broadcast::Sender<Signal>,
+    coordinator_zomes: Vec<CoordinatorZome>,
+    trigger_publish_dht_ops: TriggerSender,
+    trigger_integrate_dht_ops: TriggerSender,
+) -> WorkflowResult<Option<ZomeCallResponse>> {
+    let countersigning_op = workspace.source_chain().countersigning_op()?;
+    let flushed_actions = workspace.source_chain().flush(&network).await?;
+    // Skip if nothing was written
+    if flushed_actions.is_empty() {
+        return Ok(None);
+    }
+    match countersigning_op {
+        Some(op) => {
+            if let Err(error_response) = super::countersigning_workflow::countersigning_publish(
+                &network,
+                op,
+                (*workspace
+                    .author()
+                    .ok_or_else(|| WorkflowError::Other("author required".into()))?)
+                .clone(),
+            )
+            .await
+            {
+                return Ok(Some(error_response));
+            }
+        }
+        None => {
+            trigger_publish_dht_ops.trigger(&"call_zome_workflow");
+            trigger_integrate_dht_ops.trigger(&"call_zome_workflow");
+        }
+    }
+
+    // Only send post commit to coordinator zomes.
+    if !coordinator_zomes.is_empty() {
+        send_post_commit(
+            conductor_handle,
+            workspace,
+            network,
+            keystore,
+            flushed_actions,
+            coordinator_zomes,
+            signal_tx,
+        )
+        .await?;
+    }
+    Ok(None)
+}
+
 async fn call_zome_workflow_inner<Ribosome>(
     workspace: SourceChainWorkspace,
     network: HolochainP2pDna,
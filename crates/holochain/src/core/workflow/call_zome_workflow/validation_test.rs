@@ -31,6 +31,8 @@ async fn direct_validation_test() {
                 quantum_time: holochain_p2p::dht::spacetime::STANDARD_QUANTUM_TIME,
             },
             integrity_zomes: vec![TestZomes::from(TestWasm::Update).integrity.into_inner()],
+            rate_limits: Default::default(),
+            wasm_metering_limit: None,
             coordinator_zomes: vec![TestZomes::from(TestWasm::Update).coordinator.into_inner()],
         },
         [integrity, coordinator],
@@ -0,0 +1,105 @@
+//! Runs one side of an agent migration between two DNAs.
+//!
+//! The `migrate_agent` callback is invoked on the cell first. If every zome
+//! passes, the chain is closed with a [`CloseChain`] action pointing at the new
+//! DNA, or opened with an [`OpenChain`] action pointing at the previous DNA.
+
+use super::error::WorkflowResult;
+use crate::core::queue_consumer::TriggerSender;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentHostAccess;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentInvocation;
+use crate::core::ribosome::guest_callback::migrate_agent::MigrateAgentResult;
+use crate::core::ribosome::RibosomeT;
+use derive_more::Constructor;
+use holochain_p2p::HolochainP2pDna;
+use holochain_state::host_fn_workspace::SourceChainWorkspace;
+use holochain_types::prelude::*;
+use holochain_zome_types::action::builder;
+
+#[derive(Constructor)]
+pub struct MigrateAgentWorkflowArgs<Ribosome>
+where
+    Ribosome: RibosomeT + 'static,
+{
+    pub ribosome: Ribosome,
+    /// Which side of the migration this cell is on.
+    pub migrate_agent: MigrateAgent,
+    /// The DNA on the other side of the migration, i.e. the new DNA when
+    /// closing and the previous DNA when opening.
+    pub other_dna_hash: DnaHash,
+    pub integrate_dht_ops_trigger: TriggerSender,
+}
+
+pub async fn migrate_agent_workflow<Ribosome>(
+    workspace: SourceChainWorkspace,
+    network: HolochainP2pDna,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<MigrateAgentResult>
+where
+    Ribosome: RibosomeT + Clone + 'static,
+{
+    let integrate_dht_ops_trigger = args.integrate_dht_ops_trigger.clone();
+    let result = migrate_agent_workflow_inner(workspace.clone(), args).await?;
+
+    // --- END OF WORKFLOW, BEGIN FINISHER BOILERPLATE ---
+
+    // only commit if the result was successful
+    if result == MigrateAgentResult::Pass {
+        workspace.source_chain().flush(&network).await?;
+        integrate_dht_ops_trigger.trigger(&"migrate_agent_workflow");
+    }
+    Ok(result)
+}
+
+async fn migrate_agent_workflow_inner<Ribosome>(
+    workspace: SourceChainWorkspace,
+    args: MigrateAgentWorkflowArgs<Ribosome>,
+) -> WorkflowResult<MigrateAgentResult>
+where
+    Ribosome: RibosomeT + 'static,
+{
+    let MigrateAgentWorkflowArgs {
+        ribosome,
+        migrate_agent,
+        other_dna_hash,
+        ..
+    } = args;
+    let dna_def = ribosome.dna_def().as_content().clone();
+
+    let result = {
+        let host_access = MigrateAgentHostAccess::new(workspace.clone().into());
+        let invocation = MigrateAgentInvocation::new(dna_def, migrate_agent.clone());
+        ribosome.run_migrate_agent(host_access, invocation)?
+    };
+    if result != MigrateAgentResult::Pass {
+        return Ok(result);
+    }
+
+    let ws = workspace.clone();
+    tokio::task::spawn(async move {
+        let source_chain = ws.source_chain();
+        match migrate_agent {
+            MigrateAgent::Close => {
+                source_chain
+                    .put_weightless(
+                        builder::CloseChain::new(other_dna_hash),
+                        None,
+                        ChainTopOrdering::Strict,
+                    )
+                    .await
+            }
+            MigrateAgent::Open => {
+                source_chain
+                    .put_weightless(
+                        builder::OpenChain::new(other_dna_hash),
+                        None,
+                        ChainTopOrdering::Strict,
+                    )
+                    .await
+            }
+        }
+    })
+    .await??;
+
+    Ok(result)
+}
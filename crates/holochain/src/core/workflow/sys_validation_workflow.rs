@@ -23,6 +23,7 @@
 //!    - Check that the [`Action`] is either a [`Action::Dna`] at sequence number 0, or has a previous action with sequence number strictly greater than 0.
 //!    - If the [`Action`] is a [`Action::Dna`], then verify the contained DNA hash matches the DNA hash that sys validation is being run for.
 //!    - Check that the previous action is never a [`Action::CloseChain`], since this is always required to be the last action in a chain.
+//!    - If the DNA defines rate limits, check that the weight of the [`Action`] does not overflow its rate limiting bucket, given the author's previous actions in the bucket's drain window.
 //!    - Run the [store record checks](#store-record-checks).
 //! - For a [`ChainOp::RegisterUpdatedContent`]
 //!    - The [`Update::original_action_address`] reference to the [`Action`] being updated must point to an [`Action`] that can be found locally. Once the [`Action`] address has been resolved, the [`Update::original_entry_address`] is checked against the entry address that the referenced [`Action`] specified.
@@ -67,6 +68,12 @@
 //!     - The dependencies of these ops are then concurrently fetched from any of the local databases. Missing dependencies are handled later.
 //!     - The [validation checks](#validation-checks) are run for each op.
 //!     - For any ops that passed validation, they will be marked as ready for app validation in the database.
+//!     - A warrant for an invalid action is only valid if the action is invalid, so the warranted action is validated again.
+//!       Actions which were warranted for failing app validation are validated again by the app validation workflow, so these
+//!       warrants are marked as ready for app validation like other ops.
+//!     - All other warrants are not app validated, so any warrants that passed validation are integrated straight away. The agents
+//!       they were issued against are blocked for the DNA for the conductor's warrant block duration, unless they are running on
+//!       this conductor.
 //!     - Any ops which were rejected will be marked rejected in the database.
 //! - If any ops passed validation, then app validation will be triggered.
 //! - For actions that were not found locally, the workflow will then attempt to fetch them from the network.
@@ -80,6 +87,7 @@ use crate::core::queue_consumer::WorkComplete;
 use crate::core::sys_validate::*;
 use crate::core::validation::*;
 use crate::core::workflow::error::WorkflowResult;
+use futures::future::BoxFuture;
 use futures::FutureExt;
 use futures::StreamExt;
 use holo_hash::DhtOpHash;
@@ -90,8 +98,13 @@ use holochain_p2p::GenericNetwork;
 use holochain_p2p::HolochainP2pDnaT;
 use holochain_sqlite::prelude::*;
 use holochain_state::prelude::*;
+use holochain_zome_types::block::Block;
+use holochain_zome_types::block::BlockTarget;
+use holochain_zome_types::block::CellBlockReason;
 use parking_lot::Mutex;
 use rusqlite::Transaction;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
@@ -122,21 +135,31 @@ mod validate_op_tests;
     trigger_app_validation,
     trigger_self,
     network,
-    config
+    config,
+    running_cell_ids,
+    apply_block
 ))]
-pub async fn sys_validation_workflow<Network: HolochainP2pDnaT + 'static>(
+#[allow(clippy::too_many_arguments)]
+pub async fn sys_validation_workflow<Network: HolochainP2pDnaT + 'static, B>(
     workspace: Arc<SysValidationWorkspace>,
     current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
     trigger_app_validation: TriggerSender,
     trigger_self: TriggerSender,
     network: Network,
     config: Arc<ConductorConfig>,
-) -> WorkflowResult<WorkComplete> {
+    running_cell_ids: HashSet<CellId>,
+    apply_block: B,
+) -> WorkflowResult<WorkComplete>
+where
+    B: Fn(Block) -> BoxFuture<'static, DatabaseResult<()>>,
+{
     // Run the actual sys validation using data we have locally
     let outcome_summary = sys_validation_workflow_inner(
         workspace.clone(),
         current_validation_dependencies.clone(),
         config,
+        running_cell_ids,
+        apply_block,
     )
     .await?;
 
@@ -215,14 +238,20 @@ pub async fn sys_validation_workflow<Network: HolochainP2pDnaT + 'static>(
     }
 }
 
-async fn sys_validation_workflow_inner(
+async fn sys_validation_workflow_inner<B>(
     workspace: Arc<SysValidationWorkspace>,
     current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
     config: Arc<ConductorConfig>,
-) -> WorkflowResult<OutcomeSummary> {
+    running_cell_ids: HashSet<CellId>,
+    apply_block: B,
+) -> WorkflowResult<OutcomeSummary>
+where
+    B: Fn(Block) -> BoxFuture<'static, DatabaseResult<()>>,
+{
     let db = workspace.dht_db.clone();
     let sorted_ops = validation_query::get_ops_to_sys_validate(&db).await?;
     let sleuth_id = config.sleuth_id();
+    let warrant_block_duration = config.conductor_tuning_params().warrant_block_duration();
 
     // Forget what dependencies are currently in use
     current_validation_dependencies.lock().clear_retained_deps();
@@ -251,6 +280,51 @@ async fn sys_validation_workflow_inner(
     )
     .await;
 
+    // Actions warranted for failing sys validation are validated again, which needs their dependencies.
+    let warranted_actions = {
+        let validation_dependencies = current_validation_dependencies.lock();
+        sorted_ops
+            .iter()
+            .filter_map(|op| match &op.content {
+                DhtOp::WarrantOp(op) => warranted_action(&op.warrant),
+                DhtOp::ChainOp(_) => None,
+            })
+            .filter(|(_, validation_type)| **validation_type == ValidationType::Sys)
+            .filter_map(|((action_hash, _), _)| {
+                validation_dependencies
+                    .get(action_hash)
+                    .and_then(|s| s.as_action())
+                    .cloned()
+            })
+            .collect::<Vec<_>>()
+    };
+    fetch_previous_actions(
+        current_validation_dependencies.clone(),
+        cascade.clone(),
+        warranted_actions.iter().cloned(),
+    )
+    .await;
+
+    if !dna_def.rate_limits.is_empty() {
+        let activity = sorted_ops
+            .iter()
+            .filter_map(|op| match &op.content {
+                DhtOp::ChainOp(op) => match &**op {
+                    ChainOp::RegisterAgentActivity(_, action) => Some(action),
+                    _ => None,
+                },
+                DhtOp::WarrantOp(_) => None,
+            })
+            .chain(warranted_actions.iter());
+        retrieve_rate_limit_actions(
+            current_validation_dependencies.clone(),
+            cascade.clone(),
+            &dna_def.rate_limits,
+            activity,
+        )
+        .await;
+    }
+
     // Now drop all the dependencies that we didn't just try to access while searching the current set of ops to validate.
     current_validation_dependencies.lock().purge_held_deps();
 
@@ -266,20 +340,55 @@ async fn sys_validation_workflow_inner(
         // In most cases this will be a fast synchronous call.
         let r = validate_op(&op, &dna_def, current_validation_dependencies.clone()).await;
 
+        // Warrants which are not app validated have their final outcome after sys validation.
+        let warrantee = match &op {
+            DhtOp::WarrantOp(op) if !warrant_needs_app_validation(&op.warrant) => {
+                Some(op.warrant.warrantee().clone())
+            }
+            _ => None,
+        };
+
         match r {
-            Ok(outcome) => validation_outcomes.push((op_hash, outcome, dependency)),
+            Ok(outcome) => validation_outcomes.push((op_hash, outcome, dependency, warrantee)),
             Err(e) => {
                 tracing::error!(error = ?e, "Error validating op");
             }
         }
     }
 
+    // Block the agents that valid warrants were issued against, unless they are running on this conductor.
+    // The block lasts for the configured warrant block duration and can be lifted by an admin.
+    // Block BEFORE we integrate the warrants because this is not atomic
+    // and if something goes wrong we know the warrant will be validated again.
+    for (op_hash, outcome, _, warrantee) in &validation_outcomes {
+        let (Outcome::Accepted, Some(warrantee)) = (outcome, warrantee) else {
+            continue;
+        };
+        let cell_id = CellId::new(dna_def.hash.clone(), warrantee.clone());
+        if running_cell_ids.contains(&cell_id) {
+            continue;
+        }
+        let Some(block) = warrant_block(cell_id, op_hash.clone(), warrant_block_duration) else {
+            continue;
+        };
+        if let Err(e) = apply_block(block).await {
+            error!(
+                "Failed to apply block to warranted agent {:?}: {:?}",
+                warrantee, e
+            )
+        }
+    }
+
     let summary: OutcomeSummary = workspace
         .dht_db
         .write_async(move |txn| {
             let mut summary = OutcomeSummary::default();
-            for (op_hash, outcome, dependency) in validation_outcomes {
+            for (op_hash, outcome, dependency, warrantee) in validation_outcomes {
                 match outcome {
+                    Outcome::Accepted if warrantee.is_some() => {
+                        summary.accepted += 1;
+                        put_integrated(txn, &op_hash, ValidationStatus::Valid)?;
+                    }
                     Outcome::Accepted => {
                         summary.accepted += 1;
                         put_validation_limbo(txn, &op_hash, ValidationStage::SysValidated)?;
@@ -316,6 +425,47 @@ async fn sys_validation_workflow_inner(
     Ok(summary)
 }
 
+/// The block of a warranted agent for the warrant block duration, starting now.
+pub(crate) fn warrant_block(
+    cell_id: CellId,
+    warrant_op_hash: DhtOpHash,
+    warrant_block_duration: Duration,
+) -> Option<Block> {
+    let now = Timestamp::now();
+    match InclusiveTimestampInterval::try_new(now, now.saturating_add(&warrant_block_duration)) {
+        Ok(interval) => Some(Block::new(
+            BlockTarget::Cell(cell_id, CellBlockReason::Warrant(warrant_op_hash)),
+            interval,
+        )),
+        Err(e) => {
+            error!("Failed to create timestamp interval: {:?}", e);
+            None
+        }
+    }
+}
+
+/// The action which a warrant claims to be invalid, and how it is claimed to be invalid.
+pub(crate) fn warranted_action(warrant: &Warrant) -> Option<(&ActionHashAndSig, &ValidationType)> {
+    match warrant {
+        Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+            action,
+            validation_type,
+            ..
+        })
+        | Warrant::ValidationReceipt(ValidationReceiptWarrant::InvalidOpReceipted {
+            action,
+            validation_type,
+            ..
+        }) => Some((action, validation_type)),
+        Warrant::ChainIntegrity(ChainIntegrityWarrant::ChainFork { .. }) => None,
+    }
+}
+
+/// Warrants for actions which fail app validation can only be checked by app validation.
+fn warrant_needs_app_validation(warrant: &Warrant) -> bool {
+    matches!(warranted_action(warrant), Some((_, ValidationType::App)))
+}
+
 async fn retrieve_actions(
     current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
     cascade: Arc<impl Cascade + Send + Sync>,
@@ -480,6 +630,12 @@ fn get_dependency_hashes_from_ops(ops: impl Iterator<Item = DhtOpHashed>) -> Vec
                             ..
                         } => Some(vec![a1.clone(), a2.clone()]),
                     },
+                    Warrant::ValidationReceipt(warrant) => match warrant {
+                        ValidationReceiptWarrant::InvalidOpReceipted {
+                            action: (action_hash, _),
+                            ..
+                        } => Some(vec![action_hash.clone()]),
+                    },
                 },
             }
         })
@@ -502,6 +658,81 @@ async fn retrieve_previous_actions_for_ops(
     .await;
 }
 
+/// Rate limit checks for [`ChainOp::RegisterAgentActivity`] ops need the author's
+/// previous actions within the drain window of the action's bucket.
+/// Walk back along each author's chain from all of their actions at once,
+/// retrieving actions until the earliest window of their actions is left. Any action
+/// which cannot be found locally is left to be fetched from the network with the
+/// other missing dependencies.
+async fn retrieve_rate_limit_actions<'a>(
+    current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+    cascade: Arc<impl Cascade + Send + Sync>,
+    rate_limits: &RateLimits,
+    actions: impl Iterator<Item = &'a Action>,
+) {
+    // The earliest window start and the actions to walk back from, per author
+    let mut walks: HashMap<AgentPubKey, (Timestamp, Vec<ActionHash>)> = HashMap::new();
+    for action in actions {
+        let window_start = match rate_limit_window_start(action, rate_limits) {
+            Some(window_start) => window_start,
+            None => continue,
+        };
+        if let Some(prev_action) = action.prev_action() {
+            let walk = walks
+                .entry(action.author().clone())
+                .or_insert((window_start, Vec::new()));
+            walk.0 = walk.0.min(window_start);
+            walk.1.push(prev_action.clone());
+        }
+    }
+
+    futures::future::join_all(walks.into_values().map(|(window_start, heads)| {
+        retrieve_chain_window(
+            current_validation_dependencies.clone(),
+            cascade.clone(),
+            window_start,
+            heads,
+        )
+    }))
+    .await;
+}
+
+/// Retrieve the actions of one author's chain from the given actions backwards,
+/// until the actions are older than `window_start`. Each step retrieves the
+/// next actions of all the walks together, and each action is retrieved once.
+async fn retrieve_chain_window(
+    current_validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+    cascade: Arc<impl Cascade + Send + Sync>,
+    window_start: Timestamp,
+    heads: Vec<ActionHash>,
+) {
+    let mut visited = HashSet::new();
+    let mut next: Vec<ActionHash> = heads
+        .into_iter()
+        .filter(|hash| visited.insert(hash.clone()))
+        .collect();
+    while !next.is_empty() {
+        retrieve_actions(
+            current_validation_dependencies.clone(),
+            cascade.clone(),
+            next.iter().cloned(),
+        )
+        .await;
+        let validation_dependencies = current_validation_dependencies.lock();
+        next = next
+            .iter()
+            .filter_map(|hash| {
+                validation_dependencies
+                    .get(hash)
+                    .and_then(|s| s.as_action())
+            })
+            .filter(|action| action.timestamp() >= window_start)
+            .filter_map(|action| action.prev_action().cloned())
+            .filter(|hash| visited.insert(hash.clone()))
+            .collect();
+    }
+}
+
 /// Validate a single DhtOp, using the supplied Cascade to draw dependencies from
 pub(crate) async fn validate_op(
     op: &DhtOp,
@@ -650,6 +881,7 @@ async fn validate_chain_op(
         }
         ChainOp::RegisterAgentActivity(_, action) => {
             register_agent_activity(action, validation_dependencies.clone(), dna_def)?;
+            check_agent_activity_rate_limit(action, validation_dependencies.clone(), dna_def)?;
             store_record(action, validation_dependencies)
         }
         ChainOp::RegisterUpdatedContent(_, action, entry) => {
@@ -693,7 +925,7 @@ async fn validate_chain_op(
 
 async fn validate_warrant_op(
     op: &WarrantOp,
-    _dna_def: &DnaDefHashed,
+    dna_def: &DnaDefHashed,
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
 ) -> SysValidationResult<()> {
     match &op.warrant {
@@ -701,29 +933,24 @@ async fn validate_warrant_op(
             ChainIntegrityWarrant::InvalidChainOp {
                 action: (action_hash, action_sig),
                 action_author,
-                ..
+                validation_type,
             } => {
-                let action = {
-                    let deps = validation_dependencies.lock();
-                    let action = deps
-                        .get(action_hash)
-                        .and_then(|s| s.as_action())
-                        .ok_or_else(|| {
-                            ValidationOutcome::DepMissingFromDht(action_hash.clone().into())
-                        })?;
-
-                    if action.author() != action_author {
-                        return Err(ValidationOutcome::InvalidWarrantOp(
-                            op.clone(),
-                            "action author mismatch".into(),
-                        )
-                        .into());
-                    }
-                    action.clone()
-                };
-                verify_action_signature(action_sig, &action).await?;
-
-                Ok(())
+                let action = get_warranted_action(action_hash, &validation_dependencies)?;
+                if action.author() != action_author {
+                    return Err(ValidationOutcome::InvalidWarrantOp(
+                        op.clone(),
+                        "action author mismatch".into(),
+                    )
+                    .into());
+                }
+                verify_warranted_action_signature(op, action_sig, &action).await?;
+                check_warranted_action_is_invalid(
+                    op,
+                    &action,
+                    validation_type,
+                    dna_def,
+                    validation_dependencies,
+                )
             }
             ChainIntegrityWarrant::ChainFork {
                 action_pair: ((a1, a1_sig), (a2, a2_sig)),
@@ -776,12 +1003,144 @@ async fn validate_warrant_op(
                     (action1.clone(), action2.clone())
                 };
 
-                verify_action_signature(a1_sig, &action1).await?;
-                verify_action_signature(a2_sig, &action2).await?;
+                verify_warranted_action_signature(op, a1_sig, &action1).await?;
+                verify_warranted_action_signature(op, a2_sig, &action2).await?;
 
                 Ok(())
             }
         },
+        Warrant::ValidationReceipt(warrant) => match warrant {
+            ValidationReceiptWarrant::InvalidOpReceipted {
+                validator,
+                dht_op_hash,
+                validators,
+                when_integrated,
+                receipt_signature,
+                action: (action_hash, action_sig),
+                validation_type,
+            } => {
+                if !validators.contains(validator) {
+                    return Err(ValidationOutcome::InvalidWarrantOp(
+                        op.clone(),
+                        "validator is not listed in the receipt".into(),
+                    )
+                    .into());
+                }
+                // Only receipts which state that the op is valid can be false.
+                let receipt = ValidationReceipt {
+                    dht_op_hash: dht_op_hash.clone(),
+                    validation_status: ValidationStatus::Valid,
+                    validators: validators.clone(),
+                    when_integrated: *when_integrated,
+                };
+                if !validator
+                    .verify_signature(receipt_signature, receipt)
+                    .await?
+                {
+                    return Err(ValidationOutcome::InvalidWarrantOp(
+                        op.clone(),
+                        "receipt signature mismatch".into(),
+                    )
+                    .into());
+                }
+
+                let action = get_warranted_action(action_hash, &validation_dependencies)?;
+                verify_warranted_action_signature(op, action_sig, &action).await?;
+                let is_op_of_action = action_to_op_types(&action).into_iter().any(|op_type| {
+                    DhtOpUniqueForm::op_hash(op_type, action.clone())
+                        .map_or(false, |(_, hash)| &hash == dht_op_hash)
+                });
+                if !is_op_of_action {
+                    return Err(ValidationOutcome::InvalidWarrantOp(
+                        op.clone(),
+                        "receipted op is not an op of the action".into(),
+                    )
+                    .into());
+                }
+                check_warranted_action_is_invalid(
+                    op,
+                    &action,
+                    validation_type,
+                    dna_def,
+                    validation_dependencies,
+                )
+            }
+        },
+    }
+}
+
+fn get_warranted_action(
+    action_hash: &ActionHash,
+    validation_dependencies: &Mutex<ValidationDependencies>,
+) -> SysValidationResult<Action> {
+    Ok(validation_dependencies
+        .lock()
+        .get(action_hash)
+        .and_then(|s| s.as_action())
+        .ok_or_else(|| ValidationOutcome::DepMissingFromDht(action_hash.clone().into()))?
+        .clone())
+}
+
+/// A warrant with a counterfeit action is invalid, rather than being counterfeit itself.
+async fn verify_warranted_action_signature(
+    op: &WarrantOp,
+    signature: &Signature,
+    action: &Action,
+) -> SysValidationResult<()> {
+    match verify_action_signature(signature, action).await {
+        Err(SysValidationError::ValidationOutcome(ValidationOutcome::CounterfeitAction(..))) => {
+            Err(
+                ValidationOutcome::InvalidWarrantOp(op.clone(), "action signature mismatch".into())
+                    .into(),
+            )
+        }
+        result => result,
+    }
+}
+
+/// Anyone can claim that an action is invalid, so a warrant is only valid if
+/// validating the action again shows that it is.
+/// Actions which were warranted for failing app validation are validated again
+/// by the app validation workflow.
+fn check_warranted_action_is_invalid(
+    op: &WarrantOp,
+    action: &Action,
+    validation_type: &ValidationType,
+    dna_def: &DnaDefHashed,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> SysValidationResult<()> {
+    if *validation_type == ValidationType::App {
+        return Ok(());
+    }
+    match sys_validate_action(action, dna_def, validation_dependencies) {
+        Ok(()) => Err(ValidationOutcome::InvalidWarrantOp(
+            op.clone(),
+            "warranted action is valid".into(),
+        )
+        .into()),
+        Err(SysValidationError::ValidationOutcome(ValidationOutcome::DepMissingFromDht(dep))) => {
+            Err(ValidationOutcome::DepMissingFromDht(dep).into())
+        }
+        Err(SysValidationError::ValidationOutcome(_)) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Run the sys validation checks of an action which do not need its entry.
+fn sys_validate_action(
+    action: &Action,
+    dna_def: &DnaDefHashed,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+) -> SysValidationResult<()> {
+    register_agent_activity(action, validation_dependencies.clone(), dna_def)?;
+    check_agent_activity_rate_limit(action, validation_dependencies.clone(), dna_def)?;
+    store_record(action, validation_dependencies.clone())?;
+    match action {
+        Action::Update(action) => register_updated_content(action, validation_dependencies),
+        Action::Delete(action) => register_deleted_entry_action(action, validation_dependencies),
+        Action::CreateLink(action) => register_add_link(action),
+        Action::DeleteLink(action) => register_delete_link(action, validation_dependencies),
+        _ => Ok(()),
     }
 }
 
@@ -925,6 +1284,37 @@ fn register_agent_activity(
     }
 }
 
+fn check_agent_activity_rate_limit(
+    action: &Action,
+    validation_dependencies: Arc<Mutex<ValidationDependencies>>,
+    dna_def: &DnaDefHashed,
+) -> SysValidationResult<()> {
+    let window_start = match rate_limit_window_start(action, &dna_def.rate_limits) {
+        Some(window_start) => window_start,
+        None => return Ok(()),
+    };
+
+    // Collect the author's previous actions within the window, newest first
+    let mut previous_actions = Vec::new();
+    {
+        let validation_dependencies = validation_dependencies.lock();
+        let mut next = action.prev_action();
+        while let Some(hash) = next {
+            let prev_action = validation_dependencies
+                .get(hash)
+                .and_then(|s| s.as_action())
+                .ok_or_else(|| ValidationOutcome::DepMissingFromDht(hash.clone().into()))?;
+            if prev_action.timestamp() < window_start {
+                break;
+            }
+            previous_actions.push(prev_action.clone());
+            next = prev_action.prev_action();
+        }
+    }
+
+    check_rate_limit(action, previous_actions.iter().rev(), &dna_def.rate_limits)
+}
+
 fn store_record(
     action: &Action,
     validation_dependencies: Arc<Mutex<ValidationDependencies>>,
@@ -6,40 +6,60 @@ use crate::conductor::space::TestSpace;
 use crate::core::queue_consumer::TriggerReceiver;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
+use crate::prelude::ActionHashFixturator;
 use crate::prelude::AgentPubKeyFixturator;
 use crate::prelude::AgentValidationPkgFixturator;
 use crate::prelude::CreateFixturator;
+use crate::prelude::CreateLinkFixturator;
 use crate::prelude::SignatureFixturator;
 use fixt::*;
+use futures::FutureExt;
 use hdk::prelude::Dna as HdkDna;
 use holo_hash::AgentPubKey;
 use holo_hash::DhtOpHash;
 use holo_hash::DnaHash;
 use holo_hash::HasHash;
 use holochain_conductor_api::conductor::ConductorConfig;
+use holochain_conductor_api::conductor::ConductorTuningParams;
+use holochain_keystore::AgentPubKeyExt;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::MockHolochainP2pDnaT;
 use holochain_sqlite::db::DbKindCache;
 use holochain_sqlite::db::DbKindDht;
 use holochain_sqlite::db::DbKindT;
 use holochain_sqlite::db::DbWrite;
+use holochain_sqlite::error::DatabaseResult;
 use holochain_state::mutations::StateMutationResult;
 use holochain_types::dht_op::ChainOp;
+use holochain_types::dht_op::ChainOpType;
 use holochain_types::dht_op::DhtOp;
 use holochain_types::dht_op::DhtOpHashed;
+use holochain_types::dht_op::DhtOpUniqueForm;
 use holochain_types::dht_op::WireOps;
 use holochain_types::record::SignedActionHashedExt;
 use holochain_types::record::WireRecordOps;
+use holochain_types::validation_receipt::ValidationReceipt;
+use holochain_types::warrant::WarrantOp;
 use holochain_zome_types::action::ActionHashed;
 use holochain_zome_types::action::AppEntryDef;
 use holochain_zome_types::action::EntryType;
+use holochain_zome_types::block::Block;
+use holochain_zome_types::block::BlockTarget;
+use holochain_zome_types::block::CellBlockReason;
 use holochain_zome_types::dna_def::{DnaDef, DnaDefHashed};
 use holochain_zome_types::entry_def::EntryVisibility;
 use holochain_zome_types::judged::Judged;
+use holochain_zome_types::link::LinkTag;
 use holochain_zome_types::record::SignedActionHashed;
 use holochain_zome_types::timestamp::Timestamp;
+use holochain_zome_types::validate::ValidationStatus;
+use holochain_zome_types::warrant::ChainIntegrityWarrant;
+use holochain_zome_types::warrant::ValidationReceiptWarrant;
+use holochain_zome_types::warrant::ValidationType;
+use holochain_zome_types::warrant::Warrant;
 use holochain_zome_types::Action;
 use parking_lot::Mutex;
+use rusqlite::OptionalExtension;
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -274,6 +294,257 @@ async fn validate_op_with_wrong_sequence_number_rejected_and_not_forwarded_to_ap
     test_case.expect_app_validation_not_triggered().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn valid_warrant_is_integrated_and_blocks_warrantee() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    // Two actions with the same previous action prove a fork of the agent's chain.
+    let prev_action = fixt!(ActionHash);
+    let mut forked_actions = Vec::new();
+    for _ in 0..2 {
+        let mut create_action = fixt!(Create);
+        create_action.author = test_case.agent.clone();
+        create_action.action_seq = 10;
+        create_action.prev_action = prev_action.clone();
+        create_action.entry_type = EntryType::App(AppEntryDef {
+            entry_index: 0.into(),
+            zome_index: 0.into(),
+            visibility: EntryVisibility::Public,
+        });
+        let action = test_case.sign_action(Action::Create(create_action)).await;
+        let op =
+            ChainOp::RegisterAgentActivity(action.signature().clone(), action.action().clone());
+        test_case
+            .save_op_to_db(test_case.cache_db_handle(), op.into())
+            .await
+            .unwrap();
+        forked_actions.push((action.as_hash().clone(), action.signature().clone()));
+    }
+
+    let warrant = Warrant::ChainIntegrity(ChainIntegrityWarrant::ChainFork {
+        chain_author: test_case.agent.clone(),
+        action_pair: (forked_actions[0].clone(), forked_actions[1].clone()),
+    });
+    let op = WarrantOp::new(
+        warrant,
+        fixt!(AgentPubKey),
+        fixt!(Signature),
+        Timestamp::now(),
+    );
+    let op_hash = test_case
+        .save_op_to_db(test_case.dht_db_handle(), op.into())
+        .await
+        .unwrap();
+
+    test_case.run().await;
+
+    // Warrants skip app validation.
+    let ops_to_app_validate = test_case.get_ops_pending_app_validation().await;
+    assert!(!ops_to_app_validate.contains(&op_hash));
+    let validation_status = test_case
+        .dht_db_handle()
+        .read_async({
+            let op_hash = op_hash.clone();
+            move |txn| -> DatabaseResult<ValidationStatus> {
+                Ok(txn.query_row(
+                    "SELECT validation_status FROM DhtOp WHERE hash = ? AND when_integrated IS NOT NULL",
+                    [op_hash],
+                    |row| row.get(0),
+                )?)
+            }
+        })
+        .await
+        .unwrap();
+    assert_eq!(ValidationStatus::Valid, validation_status);
+
+    let blocks = test_case.blocks.lock();
+    assert_eq!(1, blocks.len());
+    // The block only lasts for the warrant block duration.
+    let block_duration = ConductorTuningParams::default().warrant_block_duration();
+    assert!(blocks[0].start() <= Timestamp::now());
+    assert_eq!(
+        blocks[0].end(),
+        blocks[0].start().saturating_add(&block_duration)
+    );
+    match blocks[0].target() {
+        BlockTarget::Cell(cell_id, reason) => {
+            assert_eq!(CellBlockReason::Warrant(op_hash), *reason);
+            assert_eq!(test_case.agent, *cell_id.agent_pubkey());
+        }
+        _ => unreachable!("Only expect a cell block"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn warrant_for_valid_action_is_rejected_and_does_not_block() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    let action = test_case.save_create_link_with_prev_action(11).await;
+    let warrant = Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+        action_author: test_case.agent.clone(),
+        action: (action.as_hash().clone(), action.signature().clone()),
+        validation_type: ValidationType::Sys,
+    });
+    let op_hash = test_case.save_warrant_op(warrant).await;
+
+    test_case.run().await;
+
+    let ops_to_app_validate = test_case.get_ops_pending_app_validation().await;
+    assert!(!ops_to_app_validate.contains(&op_hash));
+    assert_eq!(
+        Some(ValidationStatus::Rejected),
+        test_case.get_validation_status(op_hash).await
+    );
+    assert!(test_case.blocks.lock().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn warrant_for_invalid_action_is_integrated_and_blocks_warrantee() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    // The action skips sequence numbers after its previous action.
+    let action = test_case.save_create_link_with_prev_action(15).await;
+    let warrant = Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+        action_author: test_case.agent.clone(),
+        action: (action.as_hash().clone(), action.signature().clone()),
+        validation_type: ValidationType::Sys,
+    });
+    let op_hash = test_case.save_warrant_op(warrant).await;
+
+    test_case.run().await;
+
+    assert_eq!(
+        Some(ValidationStatus::Valid),
+        test_case.get_validation_status(op_hash).await
+    );
+    let blocks = test_case.blocks.lock();
+    assert_eq!(1, blocks.len());
+    match blocks[0].target() {
+        BlockTarget::Cell(cell_id, _) => assert_eq!(test_case.agent, *cell_id.agent_pubkey()),
+        _ => unreachable!("Only expect a cell block"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn warrant_with_counterfeit_action_signature_is_rejected() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    let action = test_case.save_create_link_with_prev_action(15).await;
+    let warrant = Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+        action_author: test_case.agent.clone(),
+        action: (action.as_hash().clone(), fixt!(Signature)),
+        validation_type: ValidationType::Sys,
+    });
+    let op_hash = test_case.save_warrant_op(warrant).await;
+
+    test_case.run().await;
+
+    assert_eq!(
+        Some(ValidationStatus::Rejected),
+        test_case.get_validation_status(op_hash).await
+    );
+    assert!(test_case.blocks.lock().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn app_validation_warrant_is_forwarded_to_app_validation() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    let action = test_case.save_create_link_with_prev_action(11).await;
+    let warrant = Warrant::ChainIntegrity(ChainIntegrityWarrant::InvalidChainOp {
+        action_author: test_case.agent.clone(),
+        action: (action.as_hash().clone(), action.signature().clone()),
+        validation_type: ValidationType::App,
+    });
+    let op_hash = test_case.save_warrant_op(warrant).await;
+
+    test_case.run().await;
+
+    let ops_to_app_validate = test_case.get_ops_pending_app_validation().await;
+    assert!(ops_to_app_validate.contains(&op_hash));
+    assert!(test_case.blocks.lock().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn receipt_warrant_for_invalid_op_blocks_validator() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    let action = test_case.save_create_link_with_prev_action(15).await;
+    let validator = test_case.keystore.new_sign_keypair_random().await.unwrap();
+    let (_, dht_op_hash) =
+        DhtOpUniqueForm::op_hash(ChainOpType::RegisterAgentActivity, action.action().clone())
+            .unwrap();
+    let receipt = ValidationReceipt {
+        dht_op_hash,
+        validation_status: ValidationStatus::Valid,
+        validators: vec![validator.clone()],
+        when_integrated: Timestamp::now(),
+    };
+    let receipt_signature = validator
+        .sign(&test_case.keystore, receipt.clone())
+        .await
+        .unwrap();
+
+    // A receipt signature by another agent does not prove anything about the validator.
+    let forged_warrant = Warrant::ValidationReceipt(ValidationReceiptWarrant::InvalidOpReceipted {
+        validator: validator.clone(),
+        dht_op_hash: receipt.dht_op_hash.clone(),
+        validators: receipt.validators.clone(),
+        when_integrated: receipt.when_integrated,
+        receipt_signature: test_case
+            .agent
+            .sign(&test_case.keystore, receipt.clone())
+            .await
+            .unwrap(),
+        action: (action.as_hash().clone(), action.signature().clone()),
+        validation_type: ValidationType::Sys,
+    });
+    let forged_op_hash = test_case.save_warrant_op(forged_warrant).await;
+
+    let warrant = Warrant::ValidationReceipt(ValidationReceiptWarrant::InvalidOpReceipted {
+        validator: validator.clone(),
+        dht_op_hash: receipt.dht_op_hash,
+        validators: receipt.validators,
+        when_integrated: receipt.when_integrated,
+        receipt_signature,
+        action: (action.as_hash().clone(), action.signature().clone()),
+        validation_type: ValidationType::Sys,
+    });
+    let op_hash = test_case.save_warrant_op(warrant).await;
+
+    test_case.run().await;
+
+    assert_eq!(
+        Some(ValidationStatus::Rejected),
+        test_case.get_validation_status(forged_op_hash).await
+    );
+    assert_eq!(
+        Some(ValidationStatus::Valid),
+        test_case.get_validation_status(op_hash.clone()).await
+    );
+    let blocks = test_case.blocks.lock();
+    assert_eq!(1, blocks.len());
+    match blocks[0].target() {
+        BlockTarget::Cell(cell_id, reason) => {
+            assert_eq!(CellBlockReason::Warrant(op_hash), *reason);
+            assert_eq!(validator, *cell_id.agent_pubkey());
+        }
+        _ => unreachable!("Only expect a cell block"),
+    }
+}
+
 struct TestCase {
     dna_def: DnaDef,
     dna_hash: DnaDefHashed,
@@ -284,6 +555,7 @@ struct TestCase {
     app_validation_trigger: (TriggerSender, TriggerReceiver),
     self_trigger: (TriggerSender, TriggerReceiver),
     actual_network: Option<MockHolochainP2pDnaT>,
+    blocks: Arc<Mutex<Vec<Block>>>,
 }
 
 impl TestCase {
@@ -306,6 +578,7 @@ impl TestCase {
             app_validation_trigger: TriggerSender::new(),
             self_trigger: TriggerSender::new(),
             actual_network: None,
+            blocks: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -353,6 +626,66 @@ impl TestCase {
         Ok(test_op_hash)
     }
 
+    /// Save a link creation at the given sequence number to the cache, along with
+    /// its previous action at sequence number 10.
+    async fn save_create_link_with_prev_action(&self, action_seq: u32) -> SignedActionHashed {
+        let mut prev_action = fixt!(Create);
+        prev_action.author = self.agent.clone();
+        prev_action.action_seq = 10;
+        prev_action.timestamp = Timestamp::from_micros(1_000_000);
+        prev_action.entry_type = EntryType::App(AppEntryDef {
+            entry_index: 0.into(),
+            zome_index: 0.into(),
+            visibility: EntryVisibility::Public,
+        });
+        let prev_action = self.sign_action(Action::Create(prev_action)).await;
+
+        let mut create_link = fixt!(CreateLink);
+        create_link.author = self.agent.clone();
+        create_link.action_seq = action_seq;
+        create_link.prev_action = prev_action.as_hash().clone();
+        create_link.timestamp = Timestamp::from_micros(2_000_000);
+        create_link.tag = LinkTag::new(vec![]);
+        let action = self.sign_action(Action::CreateLink(create_link)).await;
+
+        for action in [&prev_action, &action] {
+            let op =
+                ChainOp::RegisterAgentActivity(action.signature().clone(), action.action().clone());
+            self.save_op_to_db(self.cache_db_handle(), op.into())
+                .await
+                .unwrap();
+        }
+        action
+    }
+
+    async fn save_warrant_op(&self, warrant: Warrant) -> DhtOpHash {
+        let op = WarrantOp::new(
+            warrant,
+            fixt!(AgentPubKey),
+            fixt!(Signature),
+            Timestamp::now(),
+        );
+        self.save_op_to_db(self.dht_db_handle(), op.into())
+            .await
+            .unwrap()
+    }
+
+    async fn get_validation_status(&self, op_hash: DhtOpHash) -> Option<ValidationStatus> {
+        self.dht_db_handle()
+            .read_async(move |txn| -> DatabaseResult<Option<ValidationStatus>> {
+                Ok(txn
+                    .query_row(
+                        "SELECT validation_status FROM DhtOp WHERE hash = ?",
+                        [op_hash],
+                        |row| row.get(0),
+                    )
+                    .optional()?
+                    .flatten())
+            })
+            .await
+            .unwrap()
+    }
+
     async fn run(&mut self) -> WorkComplete {
         let workspace = SysValidationWorkspace::new(
             self.test_space
@@ -385,6 +718,14 @@ impl TestCase {
             self.self_trigger.0.clone(),
             actual_network,
             config,
+            HashSet::new(),
+            {
+                let blocks = self.blocks.clone();
+                move |block| {
+                    blocks.lock().push(block);
+                    async move { Ok(()) }.boxed()
+                }
+            },
         )
         .await
         .unwrap()
@@ -515,6 +515,8 @@ fixturator!(
         signature: SignatureFixturator::new(Empty).next().unwrap(),
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new(Empty).next().unwrap()),
         expires_at: TimestampFixturator::new(Empty).next().unwrap(),
+        metering_limit: None,
+        metering_points_used: Default::default(),
     };
     curve Unpredictable ZomeCallInvocation {
         cell_id: CellIdFixturator::new(Unpredictable).next().unwrap(),
@@ -527,6 +529,8 @@ fixturator!(
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new(Unpredictable).next().unwrap()),
         // @todo should this be less predictable?
         expires_at: (Timestamp::now() + std::time::Duration::from_secs(30)).unwrap(),
+        metering_limit: None,
+        metering_points_used: Default::default(),
     };
     curve Predictable ZomeCallInvocation {
         cell_id: CellIdFixturator::new_indexed(Predictable, get_fixt_index!())
@@ -551,6 +555,8 @@ fixturator!(
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap()),
         // @todo should this be more predictable?
         expires_at: (Timestamp::now() + std::time::Duration::from_secs(30)).unwrap(),
+        metering_limit: None,
+        metering_points_used: Default::default(),
     };
 );
 
@@ -472,6 +472,8 @@ async fn setup(
                 .map(TestZomes::from)
                 .map(|z| z.integrity.into_inner())
                 .collect(),
+            rate_limits: Default::default(),
+            wasm_metering_limit: None,
             coordinator_zomes: zomes
                 .clone()
                 .into_iter()
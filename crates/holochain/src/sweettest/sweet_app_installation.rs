@@ -22,6 +22,7 @@ pub async fn app_bundle_from_dnas<'a>(
                     location: Some(DnaLocation::Bundled(path.clone())),
                     modifiers,
                     installed_hash: Some(installed_dna_hash.into()),
+                    resource_hash: None,
                     clone_limit: 255,
                 },
                 provisioning: Some(CellProvisioning::Create { deferred: false }),
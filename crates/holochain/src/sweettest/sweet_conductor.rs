@@ -127,7 +127,7 @@ impl SweetConductor {
         // to actually access those databases.
         // As a TODO, we can remove the need for TestEnvs in sweettest or have
         // some other better integration between the two.
-        let spaces = Spaces::new(config.clone()).unwrap();
+        let spaces = Spaces::new(config.clone(), handle.spaces.db_key.clone()).unwrap();
 
         let keystore = handle.keystore().clone();
 
@@ -826,7 +826,10 @@ pub async fn authenticate_app_ws_client(
     };
 
     app_sender
-        .authenticate(AppAuthenticationRequest { token })
+        .authenticate(AppAuthenticationRequest {
+            token,
+            resume_signals_from: None,
+        })
         .await
         .unwrap();
 }
@@ -444,6 +444,7 @@ pub async fn setup_app_inner(
             driver: InterfaceDriver::Websocket {
                 port: 0,
                 allowed_origins: AllowedOrigins::Any,
+                tls: None,
             },
         }]),
         network: network.unwrap_or_default(),
@@ -860,6 +861,8 @@ where
         signature,
         nonce,
         expires_at,
+        metering_limit: None,
+        metering_points_used: Default::default(),
     })
 }
 
@@ -141,6 +141,7 @@ async fn app_interface_requires_auth() {
     let err = app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap_err();
@@ -153,6 +154,7 @@ async fn app_interface_requires_auth() {
     app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -190,6 +192,7 @@ async fn app_interface_can_handle_bad_auth_payload() {
     let err = app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap_err();
@@ -203,6 +206,7 @@ async fn app_interface_can_handle_bad_auth_payload() {
     app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -243,6 +247,7 @@ async fn app_interfaces_can_be_bound_to_apps() {
     app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -262,6 +267,7 @@ async fn app_interfaces_can_be_bound_to_apps() {
     app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -328,6 +334,7 @@ async fn signals_are_not_sent_until_after_auth() {
     app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -422,6 +429,7 @@ async fn signals_are_restricted_by_app() {
     app_1_tx
         .authenticate(AppAuthenticationRequest {
             token: token_1.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -430,6 +438,7 @@ async fn signals_are_restricted_by_app() {
     app_2_tx
         .authenticate(AppAuthenticationRequest {
             token: token_2.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -438,6 +447,7 @@ async fn signals_are_restricted_by_app() {
     app_3_tx_app_1
         .authenticate(AppAuthenticationRequest {
             token: token_3.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -446,6 +456,7 @@ async fn signals_are_restricted_by_app() {
     app_3_tx_app_2
         .authenticate(AppAuthenticationRequest {
             token: token_4.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -520,6 +531,7 @@ async fn revoke_app_auth_token() {
     app_tx
         .authenticate(AppAuthenticationRequest {
             token: token.clone(),
+            resume_signals_from: None,
         })
         .await
         .unwrap();
@@ -538,7 +550,10 @@ async fn revoke_app_auth_token() {
     let _app_rx = WsPollRecv::new::<AppResponse>(app_rx);
 
     app_tx
-        .authenticate(AppAuthenticationRequest { token })
+        .authenticate(AppAuthenticationRequest {
+            token,
+            resume_signals_from: None,
+        })
         .await
         .unwrap();
 
@@ -569,7 +584,10 @@ async fn check_app_port(port: u16, origin: &str, token: AppAuthenticationToken)
     let _rx = WsPollRecv::new::<AppResponse>(rx);
 
     client
-        .authenticate(AppAuthenticationRequest { token })
+        .authenticate(AppAuthenticationRequest {
+            token,
+            resume_signals_from: None,
+        })
         .await
         .unwrap();
 
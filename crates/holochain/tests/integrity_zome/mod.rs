@@ -90,6 +90,7 @@ async fn test_coordinator_zome_update_multi_integrity() {
             wasm_hash,
             mut dependencies,
             preserialized_path,
+            post_commit_retry,
         }) => {
             dependencies.clear();
             dependencies.push("2".into());
@@ -100,6 +101,7 @@ async fn test_coordinator_zome_update_multi_integrity() {
                     wasm_hash,
                     dependencies,
                     preserialized_path,
+                    post_commit_retry,
                 })
                 .into(),
             )
@@ -182,6 +184,7 @@ async fn test_coordinator_zome_update_multi_integrity() {
         wasm_hash,
         dependencies: vec!["2".into()],
         preserialized_path: None,
+        post_commit_retry: None,
     })
     .into();
 
@@ -234,7 +237,9 @@ async fn test_update_admin_interface() {
             name: TestCoordinatorWasm::CoordinatorZomeUpdate.into(),
             hash: None,
             dylib: None,
+            post_commit_retry: None,
             location: ZomeLocation::Bundled(TestCoordinatorWasm::CoordinatorZomeUpdate.into()),
+            resource_hash: None,
             dependencies: Some(vec![ZomeDependency {
                 name: TestIntegrityWasm::IntegrityZome.into(),
             }]),
@@ -49,6 +49,7 @@ async fn test_new_lair_conductor_integration() {
         driver: InterfaceDriver::Websocket {
             port: ADMIN_PORT,
             allowed_origins: AllowedOrigins::Any,
+            tls: None,
         },
     }]);
     conductor_config.data_root_path = Some(tmp.path().to_owned().into());
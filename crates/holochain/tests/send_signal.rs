@@ -5,12 +5,20 @@ use holochain::sweettest::{
     authenticate_app_ws_client, SweetConductor, SweetConductorConfig, SweetDnaFile,
     SweetLocalRendezvous,
 };
-use holochain_conductor_api::AppResponse;
+use holochain_conductor_api::signal_subscription::{
+    SignalFilter, SignalFilterSet, SignalKind, SignalSubscription,
+};
+use holochain_conductor_api::{
+    AppAuthenticationRequest, AppRequest, AppResponse, IssueAppAuthenticationTokenPayload,
+    SequencedSignal, SignalResumePoint,
+};
 use holochain_types::prelude::InstalledAppId;
-use holochain_types::signal::Signal;
+use holochain_types::signal::{Signal, SystemSignal};
 use holochain_types::websocket::AllowedOrigins;
 use holochain_wasm_test_utils::TestWasm;
-use holochain_websocket::{ConnectRequest, WebsocketConfig};
+use holochain_websocket::{
+    ConnectRequest, ReceiveMessage, WebsocketConfig, WebsocketReceiver, WebsocketSender,
+};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn send_signal_after_conductor_restart() {
@@ -178,3 +186,335 @@ async fn send_signal_after_conductor_restart() {
         panic!("not the expected app signal");
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signal_subscription_filters_signals_of_a_connection() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::from_config_rendezvous(
+        SweetConductorConfig::rendezvous(true),
+        SweetLocalRendezvous::new().await,
+    )
+    .await;
+    let (dna_file, _, _) = SweetDnaFile::from_test_wasms(
+        "network_seed".to_string(),
+        vec![TestWasm::EmitSignal],
+        Default::default(),
+    )
+    .await;
+    let installed_app_id: InstalledAppId = "app_id".into();
+    let app = conductor
+        .setup_app(&installed_app_id, &[dna_file])
+        .await
+        .unwrap();
+    let alice = app.agent().clone();
+    let alice_cell_id = app.cells()[0].cell_id().to_owned();
+    let zome_name = TestWasm::EmitSignal.coordinator_zome_name();
+
+    let app_interface_port = (*conductor)
+        .clone()
+        .add_app_interface(either::Either::Left(0), AllowedOrigins::Any, None)
+        .await
+        .unwrap();
+    let admin_port = conductor
+        .get_arbitrary_admin_websocket_port()
+        .expect("No admin port on this conductor");
+
+    // Forward the signals received by a connection, so that requests can be
+    // made on the sender while the receiver is being polled.
+    async fn connect(
+        port: u16,
+    ) -> (
+        WebsocketSender,
+        tokio::sync::mpsc::UnboundedReceiver<Signal>,
+    ) {
+        let (tx, mut rx): (WebsocketSender, WebsocketReceiver) = holochain_websocket::connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new(
+                format!("localhost:{port}")
+                    .to_socket_addrs()
+                    .unwrap()
+                    .next()
+                    .unwrap(),
+            ),
+        )
+        .await
+        .unwrap();
+        let (signal_tx, signal_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::task::spawn(async move {
+            while let Ok(msg) = rx.recv::<AppResponse>().await {
+                if let ReceiveMessage::Signal(v) = msg {
+                    let _ = signal_tx.send(Signal::try_from_vec(v).unwrap());
+                }
+            }
+        });
+        (tx, signal_rx)
+    }
+
+    let (filtered_tx, mut filtered_rx) = connect(app_interface_port).await;
+    authenticate_app_ws_client(filtered_tx.clone(), admin_port, installed_app_id.clone()).await;
+    let (unfiltered_tx, mut unfiltered_rx) = connect(app_interface_port).await;
+    authenticate_app_ws_client(unfiltered_tx, admin_port, installed_app_id.clone()).await;
+
+    let set_subscription = |filter: SignalFilter| {
+        let filtered_tx = filtered_tx.clone();
+        let subscription = SignalSubscription::new(
+            installed_app_id.clone(),
+            SignalFilterSet::Include([(alice_cell_id.clone(), filter)].into_iter().collect()),
+        );
+        async move {
+            filtered_tx
+                .request::<_, AppResponse>(AppRequest::SetSignalSubscription(Box::new(
+                    subscription,
+                )))
+                .await
+                .unwrap()
+        }
+    };
+    let emit = || {
+        conductor.easy_call_zome::<_, (), _>(
+            &alice,
+            None,
+            alice_cell_id.clone(),
+            zome_name.clone(),
+            "emit",
+            (),
+        )
+    };
+
+    // Only include signals of another zome
+    let response = set_subscription(SignalFilter {
+        zome_names: ["other_zome".into()].into_iter().collect(),
+        ..SignalFilter::empty()
+    })
+    .await;
+    assert!(matches!(response, AppResponse::SignalSubscriptionSet));
+
+    emit().await.unwrap();
+
+    // The connection without a subscription receives the signal
+    let signal = tokio::time::timeout(std::time::Duration::from_secs(10), unfiltered_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(signal, Signal::App { .. }));
+    // while the filtered connection does not
+    assert!(
+        tokio::time::timeout(std::time::Duration::from_millis(500), filtered_rx.recv())
+            .await
+            .is_err()
+    );
+
+    // Include the app signals of the emitting zome
+    let response = set_subscription(SignalFilter {
+        zome_names: [zome_name.clone()].into_iter().collect(),
+        kind: Some(SignalKind::App),
+        ..SignalFilter::empty()
+    })
+    .await;
+    assert!(matches!(response, AppResponse::SignalSubscriptionSet));
+
+    emit().await.unwrap();
+
+    let signal = tokio::time::timeout(std::time::Duration::from_secs(10), filtered_rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    match signal {
+        Signal::App {
+            cell_id,
+            zome_name: signal_zome_name,
+            ..
+        } => {
+            assert_eq!(cell_id, alice_cell_id);
+            assert_eq!(signal_zome_name, zome_name);
+        }
+        _ => panic!("not the expected app signal"),
+    }
+    unfiltered_rx.recv().await.unwrap();
+
+    // A connection cannot set the subscription of another app
+    let response: AppResponse = filtered_tx
+        .request(AppRequest::SetSignalSubscription(Box::new(
+            SignalSubscription::new("other_app".into(), SignalFilterSet::allow_all()),
+        )))
+        .await
+        .unwrap();
+    assert!(matches!(response, AppResponse::Error(_)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reconnect_and_resume_signals_from_replay_buffer() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_config_rendezvous(
+        SweetConductorConfig::rendezvous(true),
+        SweetLocalRendezvous::new().await,
+    )
+    .await;
+    let (dna_file, _, _) = SweetDnaFile::from_test_wasms(
+        "network_seed".to_string(),
+        vec![TestWasm::EmitSignal],
+        Default::default(),
+    )
+    .await;
+    let installed_app_id: InstalledAppId = "app_id".into();
+    let app = conductor
+        .setup_app(&installed_app_id, &[dna_file])
+        .await
+        .unwrap();
+    let alice = app.agent().clone();
+    let alice_cell_id = app.cells()[0].cell_id().to_owned();
+
+    let app_interface_port = (*conductor)
+        .clone()
+        .add_app_interface_with_tls(
+            either::Either::Left(0),
+            AllowedOrigins::Any,
+            None,
+            None,
+            Some(16),
+        )
+        .await
+        .unwrap();
+
+    // Forward the messages received by a connection as signals, so that they can
+    // be decoded as sequenced signals or as system signals.
+    async fn connect(
+        port: u16,
+    ) -> (
+        WebsocketSender,
+        tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
+    ) {
+        let (tx, mut rx): (WebsocketSender, WebsocketReceiver) = holochain_websocket::connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new(
+                format!("localhost:{port}")
+                    .to_socket_addrs()
+                    .unwrap()
+                    .next()
+                    .unwrap(),
+            ),
+        )
+        .await
+        .unwrap();
+        let (signal_tx, signal_rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::task::spawn(async move {
+            while let Ok(msg) = rx.recv::<AppResponse>().await {
+                if let ReceiveMessage::Signal(v) = msg {
+                    if signal_tx.send(v).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        (tx, signal_rx)
+    }
+    async fn next_signal(rx: &mut tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>) -> Vec<u8> {
+        tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+    let next_sequenced = |bytes: Vec<u8>| -> SequencedSignal {
+        holochain_serialized_bytes::SerializedBytes::from(
+            holochain_serialized_bytes::UnsafeBytes::from(bytes),
+        )
+        .try_into()
+        .unwrap()
+    };
+    let issue_token = |single_use: bool| {
+        let mut payload =
+            IssueAppAuthenticationTokenPayload::for_installed_app_id(installed_app_id.clone());
+        payload.single_use = single_use;
+        conductor
+            .issue_app_authentication_token(payload)
+            .unwrap()
+            .token
+    };
+    let emit = || {
+        conductor.easy_call_zome::<_, (), _>(
+            &alice,
+            None,
+            alice_cell_id.clone(),
+            TestWasm::EmitSignal.coordinator_zome_name(),
+            "emit",
+            (),
+        )
+    };
+
+    // Receive a signal on a first connection, then disconnect
+    let (tx, mut rx) = connect(app_interface_port).await;
+    tx.authenticate(AppAuthenticationRequest {
+        token: issue_token(true),
+        resume_signals_from: None,
+    })
+    .await
+    .unwrap();
+    // Wait for the connection to be authenticated before emitting
+    let response: AppResponse = tx.request(AppRequest::AppInfo).await.unwrap();
+    assert!(matches!(response, AppResponse::AppInfo(Some(_))));
+    emit().await.unwrap();
+    let first = next_sequenced(next_signal(&mut rx).await);
+    assert!(matches!(first.signal, Signal::App { .. }));
+    drop(tx);
+    drop(rx);
+
+    // Signals emitted while disconnected are kept in the replay buffer
+    emit().await.unwrap();
+    emit().await.unwrap();
+
+    // Reconnect and resume after the last received signal
+    let (tx, mut rx) = connect(app_interface_port).await;
+    tx.authenticate(AppAuthenticationRequest {
+        token: issue_token(true),
+        resume_signals_from: Some(SignalResumePoint {
+            epoch: first.epoch,
+            seq: first.seq + 1,
+        }),
+    })
+    .await
+    .unwrap();
+    for expected_seq in [first.seq + 1, first.seq + 2] {
+        let replayed = next_sequenced(next_signal(&mut rx).await);
+        assert_eq!(replayed.epoch, first.epoch);
+        assert_eq!(replayed.seq, expected_seq);
+    }
+    // and keep receiving new signals
+    emit().await.unwrap();
+    let live = next_sequenced(next_signal(&mut rx).await);
+    assert_eq!(live.seq, first.seq + 3);
+    drop(tx);
+    drop(rx);
+
+    // Resuming from another epoch is refused, without using up the single-use token
+    let token = issue_token(true);
+    let (tx, mut rx) = connect(app_interface_port).await;
+    tx.authenticate(AppAuthenticationRequest {
+        token: token.clone(),
+        resume_signals_from: Some(SignalResumePoint {
+            epoch: first.epoch + 1,
+            seq: 0,
+        }),
+    })
+    .await
+    .unwrap();
+    let refused = Signal::try_from_vec(next_signal(&mut rx).await).unwrap();
+    assert_eq!(
+        refused,
+        Signal::System(SystemSignal::SignalResumeRefused { epoch: first.epoch })
+    );
+
+    // so the connection can authenticate with the same token without resuming
+    tx.authenticate(AppAuthenticationRequest {
+        token,
+        resume_signals_from: None,
+    })
+    .await
+    .unwrap();
+    let response: AppResponse = tx.request(AppRequest::AppInfo).await.unwrap();
+    assert!(matches!(response, AppResponse::AppInfo(Some(_))));
+    emit().await.unwrap();
+    let live = next_sequenced(next_signal(&mut rx).await);
+    assert_eq!(live.seq, first.seq + 4);
+}
@@ -52,6 +52,8 @@ async fn ser_regression_test() {
             integrity_zomes: vec![TestZomes::from(TestWasm::SerRegression)
                 .integrity
                 .into_inner()],
+            rate_limits: Default::default(),
+            wasm_metering_limit: None,
             coordinator_zomes: vec![TestZomes::from(TestWasm::SerRegression)
                 .coordinator
                 .into_inner()],
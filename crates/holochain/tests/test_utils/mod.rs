@@ -5,6 +5,7 @@ use arbitrary::Arbitrary;
 use ed25519_dalek::{Signer, SigningKey};
 use holochain::conductor::ConductorHandle;
 use holochain_conductor_api::conductor::paths::DataRootPath;
+use holochain_conductor_api::DhtOpsFilter;
 use holochain_conductor_api::FullStateDump;
 use holochain_websocket::WebsocketReceiver;
 use holochain_websocket::WebsocketSender;
@@ -152,6 +153,7 @@ pub async fn call_zome_fn<S>(
         nonce: zome_call_unsigned.nonce,
         expires_at: zome_call_unsigned.expires_at,
         signature: Signature::from(signature.to_bytes()),
+        metering_limit: None,
     };
     let request = AppRequest::CallZome(Box::new(call));
     let response = app_tx.request(request);
@@ -165,6 +167,10 @@ pub async fn attach_app_interface(client: &mut WebsocketSender, port: Option<u16
         port,
         allowed_origins: AllowedOrigins::Any,
         installed_app_id: None,
+        socket_path: None,
+        socket_mode: None,
+        tls: None,
+        signal_replay_buffer_size: None,
     };
     let response = client.request(request);
     let response = check_timeout(response, 3000).await.unwrap();
@@ -259,6 +265,7 @@ pub async fn register_and_install_dna_named(
             location: Some(DnaLocation::Bundled(dna_path.clone())),
             modifiers: mods,
             installed_hash: Some(dna_hash.clone().into()),
+            resource_hash: None,
             clone_limit: 0,
         },
         provisioning: Some(CellProvisioning::Create { deferred: false }),
@@ -347,6 +354,7 @@ pub fn create_config(port: u16, data_root_path: DataRootPath) -> ConductorConfig
             driver: InterfaceDriver::Websocket {
                 port,
                 allowed_origins: AllowedOrigins::Any,
+                tls: None,
             },
         }]),
         data_root_path: Some(data_root_path),
@@ -388,10 +396,14 @@ pub async fn dump_full_state(
     client: &mut WebsocketSender,
     cell_id: CellId,
     dht_ops_cursor: Option<u64>,
+    dht_ops_filter: Option<DhtOpsFilter>,
+    dht_ops_limit: Option<u32>,
 ) -> std::io::Result<FullStateDump> {
     let request = AdminRequest::DumpFullState {
         cell_id: Box::new(cell_id),
         dht_ops_cursor,
+        dht_ops_filter,
+        dht_ops_limit,
     };
     let response = client.request(request);
     let response = check_timeout(response, 3000).await?;
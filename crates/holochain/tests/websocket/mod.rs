@@ -18,7 +18,10 @@ use holochain::{
 use std::net::{Ipv4Addr, Ipv6Addr, ToSocketAddrs};
 
 use either::Either;
-use holochain_conductor_api::{AdminInterfaceConfig, AppRequest, InterfaceDriver};
+use holochain_conductor_api::config::InterfaceTls;
+use holochain_conductor_api::{
+    AdminInterfaceConfig, AppRequest, DhtOpsFilter, FullStateDump, InterfaceDriver,
+};
 use holochain_types::websocket::AllowedOrigins;
 use holochain_types::{
     prelude::*,
@@ -812,7 +815,7 @@ async fn full_state_dump_cursor_works() {
 
     let (mut client, _rx) = conductor.admin_ws_client::<AppResponse>().await;
 
-    let full_state = dump_full_state(&mut client, cell_id.clone(), None)
+    let full_state = dump_full_state(&mut client, cell_id.clone(), None, None, None)
         .await
         .unwrap();
 
@@ -829,6 +832,8 @@ async fn full_state_dump_cursor_works() {
         &mut client,
         cell_id,
         Some(full_state.integration_dump.dht_ops_cursor - 1),
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -843,6 +848,120 @@ async fn full_state_dump_cursor_works() {
     assert_eq!(1, new_all_dht_ops_count);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn full_state_dump_filter_and_limit_work() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+
+    let agent = SweetAgents::one(conductor.keystore()).await;
+
+    let dna_file = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal])
+        .await
+        .0;
+
+    let app = conductor
+        .setup_app_for_agent("app", agent.clone(), &[dna_file])
+        .await
+        .unwrap();
+
+    let cell_id = app.into_cells()[0].cell_id().clone();
+
+    let (mut client, _rx) = conductor.admin_ws_client::<AppResponse>().await;
+
+    let all_ops = |dump: &FullStateDump| {
+        let d = &dump.integration_dump;
+        d.integrated
+            .iter()
+            .chain(d.validation_limbo.iter())
+            .chain(d.integration_limbo.iter())
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    // Page through the 7 genesis ops, 3 at a time.
+    let mut cursor = None;
+    let mut page_sizes = Vec::new();
+    loop {
+        let dump = dump_full_state(&mut client, cell_id.clone(), cursor, None, Some(3))
+            .await
+            .unwrap();
+        page_sizes.push(all_ops(&dump).len());
+        // The source chain and the peers are only dumped on the first page.
+        assert_eq!(cursor.is_none(), dump.source_chain_dump.is_some());
+        assert_eq!(cursor.is_none(), dump.peer_dump.is_some());
+        cursor = Some(dump.integration_dump.dht_ops_cursor);
+        if !dump.integration_dump.has_more {
+            break;
+        }
+    }
+    assert_eq!(vec![3, 3, 1], page_sizes);
+
+    // A page which ends exactly at the last op has no more after it.
+    let dump = dump_full_state(&mut client, cell_id.clone(), None, None, Some(7))
+        .await
+        .unwrap();
+    assert_eq!(7, all_ops(&dump).len());
+    assert!(!dump.integration_dump.has_more);
+    assert_eq!(cursor, Some(dump.integration_dump.dht_ops_cursor));
+
+    // A limit of 0 would never make progress, so it is refused.
+    assert!(
+        dump_full_state(&mut client, cell_id.clone(), None, None, Some(0))
+            .await
+            .is_err()
+    );
+
+    // Only agent activity ops are returned when filtering by that type.
+    let activity: DhtOpType = ChainOpType::RegisterAgentActivity.into();
+    let dump = dump_full_state(
+        &mut client,
+        cell_id.clone(),
+        None,
+        Some(DhtOpsFilter {
+            op_types: Some(vec![activity]),
+            ..Default::default()
+        }),
+        None,
+    )
+    .await
+    .unwrap();
+    let ops = all_ops(&dump);
+    assert!(!ops.is_empty());
+    assert!(ops.iter().all(|op| op.get_type() == activity));
+    assert!(!dump.integration_dump.has_more);
+
+    // No ops were authored by another agent.
+    let dump = dump_full_state(
+        &mut client,
+        cell_id.clone(),
+        None,
+        Some(DhtOpsFilter {
+            author: Some(fixt!(AgentPubKey)),
+            ..Default::default()
+        }),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(all_ops(&dump).is_empty());
+
+    // Nor any after now.
+    let dump = dump_full_state(
+        &mut client,
+        cell_id,
+        None,
+        Some(DhtOpsFilter {
+            authored_after: Some(Timestamp::now()),
+            ..Default::default()
+        }),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(all_ops(&dump).is_empty());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn admin_allowed_origins() {
     holochain_trace::test_run();
@@ -855,6 +974,7 @@ async fn admin_allowed_origins() {
             driver: InterfaceDriver::Websocket {
                 port: 0,
                 allowed_origins: "http://localhost:3000".to_string().into(),
+                tls: None,
             },
         }])
         .await
@@ -896,6 +1016,163 @@ async fn admin_allowed_origins() {
     let _: AdminResponse = client.request(request).await.unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn interfaces_with_lair_tls_certificate() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let tmp_dir = TempDir::new().unwrap();
+    let admin_cert_path = tmp_dir.path().join("admin.der");
+    let app_cert_path = tmp_dir.path().join("app.der");
+
+    let ports = conductor
+        .clone()
+        .add_admin_interfaces(vec![AdminInterfaceConfig {
+            driver: InterfaceDriver::Websocket {
+                port: 0,
+                allowed_origins: AllowedOrigins::Any,
+                tls: Some(InterfaceTls::Lair {
+                    tag: "interface-tls".to_string(),
+                    cert_path: Some(admin_cert_path.clone()),
+                }),
+            },
+        }])
+        .await
+        .unwrap();
+    let admin_port = *ports.first().unwrap();
+    let addr = format!("localhost:{admin_port}")
+        .to_socket_addrs()
+        .unwrap()
+        .next()
+        .unwrap();
+
+    // The interface only accepts TLS connections
+    assert!(connect(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        ConnectRequest::new(addr)
+    )
+    .await
+    .is_err());
+
+    // with the certificate which lair created and which was written to the cert path
+    let cert = std::fs::read(&admin_cert_path).unwrap();
+    let (client, rx) = connect(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        ConnectRequest::new(addr)
+            .with_tls(TlsClientConfig::with_pinned_cert(cert.clone()).unwrap()),
+    )
+    .await
+    .unwrap();
+    let _rx = WsPollRecv::new::<AdminResponse>(rx);
+    let response: AdminResponse = client
+        .request(AdminRequest::ListAppInterfaces)
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::AppInterfacesListed(_));
+
+    // Another interface with the same tag uses the same certificate
+    let app_port = conductor
+        .clone()
+        .add_app_interface_with_tls(
+            Either::Left(0),
+            AllowedOrigins::Any,
+            Some(InterfaceTls::Lair {
+                tag: "interface-tls".to_string(),
+                cert_path: Some(app_cert_path.clone()),
+            }),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(cert, std::fs::read(&app_cert_path).unwrap());
+    let interfaces = conductor.list_app_interfaces().await.unwrap();
+    assert!(interfaces.iter().any(|i| i.port == app_port && i.tls));
+    connect(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        ConnectRequest::new(
+            format!("localhost:{app_port}")
+                .to_socket_addrs()
+                .unwrap()
+                .next()
+                .unwrap(),
+        )
+        .with_tls(TlsClientConfig::with_pinned_cert(cert).unwrap()),
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(unix)]
+async fn interfaces_on_unix_sockets() {
+    use std::os::unix::fs::PermissionsExt;
+
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let admin_port = conductor.get_arbitrary_admin_websocket_port().unwrap();
+    let tmp_dir = TempDir::new().unwrap();
+    let admin_path = tmp_dir.path().join("admin.sock");
+    let app_path = tmp_dir.path().join("app.sock");
+
+    // An admin interface on a socket doesn't have a port
+    let ports = conductor
+        .clone()
+        .add_admin_interfaces(vec![AdminInterfaceConfig {
+            driver: InterfaceDriver::UnixSocket {
+                path: admin_path.clone(),
+                mode: 0o600,
+            },
+        }])
+        .await
+        .unwrap();
+    assert!(ports.is_empty());
+    let mode = std::fs::metadata(&admin_path).unwrap().permissions().mode();
+    assert_eq!(0o600, mode & 0o777);
+
+    let (admin_sender, rx) = connect_unix(Arc::new(WebsocketConfig::CLIENT_DEFAULT), &admin_path)
+        .await
+        .unwrap();
+    let _admin_rx = WsPollRecv::new::<AdminResponse>(rx);
+    let response: AdminResponse = admin_sender
+        .request(AdminRequest::ListAppInterfaces)
+        .await
+        .unwrap();
+    assert_matches!(response, AdminResponse::AppInterfacesListed(interfaces) if interfaces.is_empty());
+
+    // An app interface on a socket is listed with its path
+    conductor
+        .clone()
+        .add_app_unix_socket_interface(None, app_path.clone(), 0o600, None, None)
+        .await
+        .unwrap();
+    let response: AdminResponse = admin_sender
+        .request(AdminRequest::ListAppInterfaces)
+        .await
+        .unwrap();
+    let interfaces = match response {
+        AdminResponse::AppInterfacesListed(interfaces) => interfaces,
+        _ => panic!("unexpected response"),
+    };
+    assert_eq!(1, interfaces.len());
+    assert_eq!(0, interfaces[0].port);
+    assert_eq!(Some(app_path.clone()), interfaces[0].socket_path);
+
+    // and takes the same authentication as one on a port
+    let (app_sender, rx) = connect_unix(Arc::new(WebsocketConfig::CLIENT_DEFAULT), &app_path)
+        .await
+        .unwrap();
+    let _app_rx = WsPollRecv::new::<AppResponse>(rx);
+    authenticate_app_ws_client(app_sender.clone(), admin_port, "".to_string()).await;
+
+    let response: AppResponse = app_sender.request(AppRequest::AppInfo).await.unwrap();
+    match response {
+        AppResponse::AppInfo(_) => (),
+        _ => panic!("unexpected response"),
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn holochain_websockets_listen_on_ipv4_and_ipv6() {
     holochain_trace::test_run();
@@ -41,6 +41,7 @@ pub async fn websocket_stress() {
             driver: InterfaceDriver::Websocket {
                 port: 0,
                 allowed_origins: AllowedOrigins::Any,
+                tls: None,
             },
         }]),
         data_root_path: Some(data_root_path.into()),
@@ -14,6 +14,8 @@ pub struct GetLinksOpsQuery {
     base: Arc<AnyLinkableHash>,
     type_query: LinkTypeFilter,
     tag: Option<Arc<LinkTag>>,
+    cursor: Option<LinkCursor>,
+    limit: Option<u32>,
 }
 
 impl GetLinksOpsQuery {
@@ -22,6 +24,8 @@ impl GetLinksOpsQuery {
             base: Arc::new(key.base),
             type_query: key.type_query,
             tag: key.tag.map(Arc::new),
+            cursor: key.cursor,
+            limit: key.limit,
         }
     }
     pub fn tag_to_hex(tag: &LinkTag) -> String {
@@ -77,7 +81,51 @@ impl Query for GetLinksOpsQuery {
             common_query,
             self.type_query.to_sql_statement(),
         );
-        let create_query = format!("{}{}", create, common_query);
+        // Only the creates after the cursor are needed, along with the deletes
+        // of those creates.
+        common_query = match &self.cursor {
+            Some(_) => format!(
+                "
+                {}
+                AND (
+                    DhtOp.authored_timestamp > :cursor_timestamp
+                    OR (
+                        DhtOp.authored_timestamp = :cursor_timestamp
+                        AND Action.hash > :cursor_hash
+                    )
+                )
+                ",
+                common_query
+            ),
+            None => format!(
+                "{} AND :cursor_timestamp IS NULL AND :cursor_hash IS NULL",
+                common_query
+            ),
+        };
+        let mut create_query = format!("{}{}", create, common_query);
+        // Creates which have been deleted are left out of the page so they
+        // don't take up room in it. All deletes are still returned.
+        create_query = match self.limit {
+            Some(_) => format!(
+                "
+                SELECT action_blob, dht_type, status FROM (
+                    {}
+                    AND NOT EXISTS (
+                        SELECT 1 FROM Action AS DeleteAction
+                        JOIN DhtOp AS DeleteOp ON DeleteOp.action_hash = DeleteAction.hash
+                        WHERE DeleteAction.create_link_hash = Action.hash
+                        AND DeleteOp.type = :delete
+                        AND DeleteOp.validation_status = :valid
+                        AND DeleteOp.when_integrated IS NOT NULL
+                    )
+                    ORDER BY DhtOp.authored_timestamp, Action.hash
+                    LIMIT :limit
+                )
+                ",
+                create_query
+            ),
+            None => format!("{} AND :limit IS NULL", create_query),
+        };
         let sub_create_query = format!("{}{}", sub_create, common_query);
         let delete_query = format!(
             "
@@ -101,6 +149,10 @@ impl Query for GetLinksOpsQuery {
             ":create": ChainOpType::RegisterAddLink,
             ":delete": ChainOpType::RegisterRemoveLink,
             ":base_hash": self.base,
+            ":valid": ValidationStatus::Valid,
+            ":cursor_timestamp": self.cursor.as_ref().map(|c| c.timestamp),
+            ":cursor_hash": self.cursor.as_ref().map(|c| c.create_link_hash.clone()),
+            ":limit": self.limit,
         }
         .to_vec()
     }
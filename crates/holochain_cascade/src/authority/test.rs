@@ -188,6 +188,46 @@ async fn get_links() {
     assert_eq!(result, expected);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn get_links_paged() {
+    holochain_trace::test_run();
+    let db = test_dht_db();
+
+    let td = EntryTestData::create();
+
+    fill_db(&db.to_db(), td.store_entry_op.clone()).await;
+    fill_db(&db.to_db(), td.create_link_op.clone()).await;
+    fill_db(&db.to_db(), td.delete_link_op.clone()).await;
+    let options = actor::GetLinksOptions::default();
+
+    // The deleted create is left out of the page but its delete is returned.
+    let key = WireLinkKey {
+        limit: Some(1),
+        ..td.link_key_tag.clone()
+    };
+    let result = handle_get_links(db.to_db().into(), key, (&options).into())
+        .await
+        .unwrap();
+    let expected = WireLinkOps {
+        creates: vec![],
+        deletes: vec![td.wire_delete_link.clone()],
+    };
+    assert_eq!(result, expected);
+
+    // Nothing comes after the only link.
+    let key = WireLinkKey {
+        cursor: Some(LinkCursor {
+            timestamp: td.create_link_action.action().timestamp(),
+            create_link_hash: td.create_link_action.as_hash().clone(),
+        }),
+        ..td.link_key_tag.clone()
+    };
+    let result = handle_get_links(db.to_db().into(), key, (&options).into())
+        .await
+        .unwrap();
+    assert_eq!(result, WireLinkOps::new());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn get_agent_activity() {
     holochain_trace::test_run();
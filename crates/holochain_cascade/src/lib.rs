@@ -879,6 +879,8 @@ impl CascadeImpl {
                 after: key.after,
                 before: key.before,
                 author: key.author,
+                cursor: key.cursor,
+                limit: key.limit,
             },
         );
 
@@ -920,6 +922,7 @@ impl CascadeImpl {
             }
         }
 
+        let limit = query.limit;
         let get_links_query = GetLinksQuery::new(
             query.base.clone(),
             query.link_type.clone(),
@@ -934,7 +937,8 @@ impl CascadeImpl {
                 .map(|l| l.create_link_hash),
         );
 
-        Ok(links.len())
+        // Remote and local results are limited separately, so their union may exceed the limit.
+        Ok(limit.map_or(links.len(), |limit| links.len().min(limit as usize)))
     }
 
     /// Request a hash bounded chain query.
@@ -165,6 +165,8 @@ impl EntryTestData {
             after: None,
             before: None,
             author: None,
+            cursor: None,
+            limit: None,
         };
         let link_key_tag = WireLinkKey {
             base: create_link.base_address.clone(),
@@ -173,6 +175,8 @@ impl EntryTestData {
             after: None,
             before: None,
             author: None,
+            cursor: None,
+            limit: None,
         };
 
         let link = Link {
@@ -193,6 +197,8 @@ impl EntryTestData {
             before: None,
             after: None,
             author: None,
+            cursor: None,
+            limit: None,
         };
 
         let entry = EntryData {
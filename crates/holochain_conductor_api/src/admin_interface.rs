@@ -4,7 +4,8 @@ use holochain_types::websocket::AllowedOrigins;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 
-use crate::{AppInfo, FullStateDump, StorageInfo};
+use crate::cell_archive::CellArchive;
+use crate::{AppInfo, DhtOpsFilter, FullStateDump, StorageInfo, WarrantInfo};
 
 /// Represents the available conductor functions to call over an admin interface.
 ///
@@ -73,6 +74,19 @@ pub enum AdminRequest {
     /// [`EnableApp`]: AdminRequest::EnableApp
     InstallApp(Box<InstallAppPayload>),
 
+    /// Install an app by the hash of its [`AppBundle`], fetching the bundle
+    /// from the conductor's app store service.
+    ///
+    /// The app store is set up through the `app_store` field of the conductor config.
+    /// Apart from where the bundle comes from, this behaves exactly like [`InstallApp`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppInstalled`]
+    ///
+    /// [`InstallApp`]: AdminRequest::InstallApp
+    InstallAppFromStore(Box<InstallAppFromStorePayload>),
+
     /// Uninstalls the app specified by argument `installed_app_id` from the conductor.
     ///
     /// The app will be removed from the list of installed apps, and any cells
@@ -148,6 +162,38 @@ pub enum AdminRequest {
         installed_app_id: InstalledAppId,
     },
 
+    /// Migrate the agent of an app role to a new DNA.
+    ///
+    /// A cell of the new DNA is first created for the same agent, which runs its
+    /// genesis. The `migrate_agent` callback is then invoked on the new cell and its
+    /// source chain is opened from the previous DNA. Only then is the callback
+    /// invoked on the role's current cell, which must be running, and its source
+    /// chain closed towards the new DNA, so that a refusal by the new DNA leaves
+    /// the current chain open. Finally the role is reassigned to the new
+    /// cell. The previous cell is stopped, but its source chain is kept. Clone
+    /// cells of the role are not migrated.
+    ///
+    /// If any step fails, the role keeps its current cell. Steps which are already
+    /// done are skipped when the request is sent again, so a failed migration can
+    /// be resumed.
+    ///
+    /// The new DNA must have been registered with [`AdminRequest::RegisterDna`]
+    /// beforehand.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AgentMigrated`]
+    MigrateAgent {
+        /// The app containing the role to migrate
+        installed_app_id: InstalledAppId,
+        /// The role to migrate
+        role_name: RoleName,
+        /// The hash of the DNA to migrate the agent to
+        new_dna_hash: DnaHash,
+        /// The membrane proof for the genesis of the new cell
+        membrane_proof: Option<MembraneProof>,
+    },
+
     /// Open up a new websocket for processing [`AppRequest`]s. Any active app will be
     /// callable via the attached app interface.
     ///
@@ -165,6 +211,11 @@ pub enum AdminRequest {
     /// Optionally a `port` parameter can be passed to this request. If it is `None`,
     /// a free port is chosen by the conductor.
     ///
+    /// Alternatively a `socket_path` can be passed to listen on a Unix domain socket
+    /// instead of a port.
+    ///
+    /// A `tls` parameter can be passed to only accept connections over TLS.
+    ///
     /// An `allowed_origins` parameter to control which origins are allowed to connect
     /// to the app interface.
     ///
@@ -190,6 +241,34 @@ pub enum AdminRequest {
         /// If this is `Some` then the interface will only accept connections for the specified app.
         /// Those connections will only be able to make calls to and receive signals from that app.
         installed_app_id: Option<InstalledAppId>,
+
+        /// Optionally listen on a Unix domain socket at this path instead of a port.
+        ///
+        /// If this is `Some` then `port` must be `None`. Access to the interface is
+        /// controlled by the permissions of the socket file, and `allowed_origins` is ignored.
+        #[serde(default)]
+        socket_path: Option<std::path::PathBuf>,
+
+        /// The permissions of the socket file at `socket_path`, which default to
+        /// [`DEFAULT_UNIX_SOCKET_MODE`](crate::config::DEFAULT_UNIX_SOCKET_MODE).
+        #[serde(default)]
+        socket_mode: Option<u32>,
+
+        /// Optionally serve this app interface over TLS with the given certificate.
+        ///
+        /// Only supported for interfaces listening on a port.
+        #[serde(default)]
+        tls: Option<crate::config::InterfaceTls>,
+
+        /// Optionally keep this many of the most recent signals of each app that is
+        /// connected to through this interface, so that a client which reconnects
+        /// can resume receiving signals where it left off.
+        ///
+        /// If this is `Some`, signals are sent as [`SequencedSignal`](crate::SequencedSignal)s
+        /// and a client can set [`AppAuthenticationRequest::resume_signals_from`](crate::AppAuthenticationRequest::resume_signals_from)
+        /// when it reconnects.
+        #[serde(default)]
+        signal_replay_buffer_size: Option<usize>,
     },
 
     /// List all the app interfaces currently attached with [`AttachAppInterface`].
@@ -229,7 +308,9 @@ pub enum AdminRequest {
     /// This is meant to be used by introspection tooling.
     ///
     /// Note that the response to this call can be very big, as it's requesting for
-    /// the full database of the cell.
+    /// the full database of the cell. Use `dht_ops_filter` and `dht_ops_limit` to
+    /// page through the DHT shard instead of dumping it all at once. The source chain
+    /// and the peers are only dumped on the first page, which has no `dht_ops_cursor`.
     ///
     /// Also note that while DHT ops about private entries will be returned (like `StoreRecord`),
     /// the entry in itself will be missing, as it's not actually stored publicly in the DHT shard.
@@ -243,6 +324,50 @@ pub enum AdminRequest {
         /// The last seen DhtOp RowId, returned in the full dump state.
         /// Only DhtOps with RowId greater than the cursor will be returned.
         dht_ops_cursor: Option<u64>,
+        /// Only DhtOps matching this filter will be returned.
+        #[serde(default)]
+        dht_ops_filter: Option<DhtOpsFilter>,
+        /// The maximum number of DhtOps to return. If more ops match,
+        /// the dump is marked as having more and its cursor points
+        /// at the last op returned. A limit of 0 is rejected.
+        #[serde(default)]
+        dht_ops_limit: Option<u32>,
+    },
+
+    /// Export the cell specified by argument `cell_id` as a signed archive.
+    ///
+    /// The archive contains the full source chain of the cell, including private
+    /// entries such as capability grants, and the integrated DHT ops it authored.
+    /// It is signed with the cell's agent key and can be restored on another
+    /// conductor with [`AdminRequest::ImportCell`].
+    ///
+    /// Note that the archive can be very big, as it contains the full source chain of the cell.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::CellExported`]
+    ExportCell {
+        /// The cell ID of the cell to export
+        cell_id: Box<CellId>,
+    },
+
+    /// Restore a cell from an archive created by [`AdminRequest::ExportCell`].
+    ///
+    /// The archive must be of a supported version and be signed by the agent of the
+    /// archived cell. The cell must already be installed on this conductor, for example
+    /// by installing the same app with the same agent key. The records of the archive are
+    /// grafted onto its source chain, like with [`AdminRequest::GraftRecords`], and the DHT ops
+    /// of the archive are validated before they are integrated.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::CellImported`]
+    ImportCell {
+        /// The archive to restore the cell from.
+        archive: Box<CellArchive>,
+        /// If this is `true`, then the records of the source chain will be validated before
+        /// they are inserted. This is much slower, but guards against an invalid chain.
+        validate: bool,
     },
 
     /// Dump the network metrics tracked by kitsune.
@@ -360,6 +485,107 @@ pub enum AdminRequest {
     /// Info about storage used by apps
     StorageInfo,
 
+    /// List the warrants which this conductor has received, along with their validation status.
+    ///
+    /// Once a warrant has been found to be valid, the agent it was issued against is blocked
+    /// for the DNA of the warrant, unless that agent is running on this conductor. The block
+    /// lasts for the conductor's `warrant_block_duration` and can be lifted with
+    /// [`AdminRequest::UnblockWarrantee`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::WarrantsListed`]
+    ListWarrants {
+        /// Only list the warrants received for this DNA.
+        /// If `None`, the warrants of all DNAs are listed.
+        dna_hash: Option<DnaHash>,
+    },
+
+    /// Lift the block which a valid warrant put on the agent it was issued against.
+    ///
+    /// The warrant is kept and still listed by [`AdminRequest::ListWarrants`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::WarranteeUnblocked`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no warrant with this op hash is held for the DNA.
+    UnblockWarrantee {
+        /// The DNA the warrant was received for.
+        dna_hash: DnaHash,
+        /// The hash of the warrant op, as listed by [`AdminRequest::ListWarrants`].
+        op_hash: DhtOpHash,
+    },
+
+    /// List the functions scheduled for a cell, the next due first.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionsListed`]
+    ListScheduledFunctions {
+        /// The cell to list the scheduled functions of.
+        cell_id: CellId,
+    },
+
+    /// Run a scheduled function of a cell right away, whether it is due or not.
+    ///
+    /// The function is called with its current schedule, and the schedule it returns
+    /// replaces the current one as usual. An ephemeral schedule is used up by the run.
+    /// The request returns once the run has finished.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionTriggered`]
+    TriggerScheduledFunction {
+        /// The cell the function is scheduled for.
+        cell_id: CellId,
+        /// The zome of the scheduled function.
+        zome_name: ZomeName,
+        /// The name of the scheduled function.
+        fn_name: FunctionName,
+    },
+
+    /// Remove a function from the schedule of a cell, so that it doesn't run again
+    /// unless the zome schedules it anew. Runs in progress are not interrupted.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::FunctionUnscheduled`]
+    UnscheduleFunction {
+        /// The cell the function is scheduled for.
+        cell_id: CellId,
+        /// The zome of the scheduled function.
+        zome_name: ZomeName,
+        /// The name of the scheduled function.
+        fn_name: FunctionName,
+    },
+
+    /// List the most recent runs of the scheduled functions of a cell, newest first.
+    ///
+    /// Every run records when it was dispatched, how long it took and, if it did not
+    /// succeed, its error. Runs skipped because the function was still running from a
+    /// previous dispatch are listed with an error too. Only the last 100 runs of each
+    /// function are kept.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ScheduledFunctionRunsListed`]
+    ListScheduledFunctionRuns {
+        /// The cell to list the runs of.
+        cell_id: CellId,
+        /// Only list the runs of the functions of this zome.
+        #[serde(default)]
+        zome_name: Option<ZomeName>,
+        /// Only list the runs of functions with this name.
+        #[serde(default)]
+        fn_name: Option<FunctionName>,
+        /// The maximum number of runs to list. Defaults to 100.
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+
     /// Connecting to an app over an app websocket requires an authentication token. This endpoint
     /// is used to issue those tokens for use by app clients.
     ///
@@ -401,7 +627,8 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::UpdateCoordinators`]
     CoordinatorsUpdated,
 
-    /// The successful response to an [`AdminRequest::InstallApp`].
+    /// The successful response to an [`AdminRequest::InstallApp`]
+    /// or [`AdminRequest::InstallAppFromStore`].
     ///
     /// The resulting [`AppInfo`] contains the app ID,
     /// the [`RoleName`]s and, most usefully, [`CellInfo`](crate::CellInfo)s
@@ -442,7 +669,8 @@ pub enum AdminResponse {
     ///
     /// Contains the port number of the attached app interface.
     AppInterfaceAttached {
-        /// Networking port of the new `AppInterfaceApi`
+        /// Networking port of the new `AppInterfaceApi`,
+        /// or `0` if it listens on a Unix domain socket.
         port: u16,
     },
 
@@ -464,6 +692,11 @@ pub enum AdminResponse {
     /// It means the app was disabled successfully.
     AppDisabled,
 
+    /// The successful response to an [`AdminRequest::MigrateAgent`].
+    ///
+    /// Contains the id of the new cell the role is now assigned to.
+    AgentMigrated(CellId),
+
     /// The successful response to an [`AdminRequest::DumpState`].
     ///
     /// The result contains a string of serialized JSON data which can be deserialized to access the
@@ -478,6 +711,14 @@ pub enum AdminResponse {
     /// Note that this result can be very big, as it's requesting the full database of the cell.
     FullStateDumped(FullStateDump),
 
+    /// The successful response to an [`AdminRequest::ExportCell`].
+    ///
+    /// Note that this result can be very big, as it contains the full database of the cell.
+    CellExported(Box<CellArchive>),
+
+    /// The successful response to an [`AdminRequest::ImportCell`].
+    CellImported,
+
     /// The successful response to an [`AdminRequest::DumpConductorState`].
     ///
     /// Simply a JSON serialized snapshot of `Conductor` and `ConductorState` from the `holochain` crate.
@@ -516,6 +757,27 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::StorageInfo`].
     StorageInfo(StorageInfo),
 
+    /// The successful response to an [`AdminRequest::ListWarrants`].
+    WarrantsListed(Vec<WarrantInfo>),
+
+    /// The successful response to an [`AdminRequest::UnblockWarrantee`].
+    WarranteeUnblocked,
+
+    /// The successful response to an [`AdminRequest::ListScheduledFunctions`].
+    ScheduledFunctionsListed(Vec<ScheduledFnInfo>),
+
+    /// The successful response to an [`AdminRequest::TriggerScheduledFunction`].
+    ///
+    /// Contains the finished run. If the function had too many runs in progress
+    /// already, the run was skipped and has an error saying so.
+    ScheduledFunctionTriggered(ScheduledFnRun),
+
+    /// The successful response to an [`AdminRequest::UnscheduleFunction`].
+    FunctionUnscheduled,
+
+    /// The successful response to an [`AdminRequest::ListScheduledFunctionRuns`].
+    ScheduledFunctionRunsListed(Vec<ScheduledFnRun>),
+
     /// The successful response to an [`AdminRequest::IssueAppAuthenticationToken`].
     AppAuthenticationTokenIssued(AppAuthenticationTokenIssued),
 
@@ -544,6 +806,12 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized.
     ZomeCallUnauthorized(String),
+    /// Too many zome calls were made, and this one was refused by the
+    /// conductor's zome call policy.
+    ZomeCallRateLimited {
+        /// How long to wait before the call may be made again, in milliseconds.
+        retry_after_ms: u64,
+    },
     /// A countersigning session has failed.
     CountersigningSessionError(String),
 }
@@ -570,14 +838,27 @@ pub enum AppStatusFilter {
 /// Informational response for listing app interfaces.
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
 pub struct AppInterfaceInfo {
-    /// The port that the app interface is listening on.
+    /// The port that the app interface is listening on,
+    /// or `0` if it listens on a Unix domain socket.
     pub port: u16,
 
+    /// The path of the Unix domain socket that the app interface is listening on, if any.
+    #[serde(default)]
+    pub socket_path: Option<std::path::PathBuf>,
+
     /// The allowed origins for this app interface.
     pub allowed_origins: AllowedOrigins,
 
+    /// Whether the app interface only accepts connections over TLS.
+    #[serde(default)]
+    pub tls: bool,
+
     /// The optional association with a specific installed app.
     pub installed_app_id: Option<InstalledAppId>,
+
+    /// The number of signals kept per app for clients to resume from, if any.
+    #[serde(default)]
+    pub signal_replay_buffer_size: Option<usize>,
 }
 
 /// Request payload for [AdminRequest::IssueAppAuthenticationToken].
@@ -1,3 +1,4 @@
+use crate::signal_subscription::SignalSubscription;
 use crate::{AppAuthenticationToken, ExternalApiWireError};
 use holo_hash::AgentPubKey;
 use holochain_keystore::LairResult;
@@ -30,9 +31,27 @@ pub enum AppRequest {
     ///
     /// # Returns
     ///
-    /// [`AppResponse::ZomeCalled`]
+    /// [`AppResponse::ZomeCalled`], or [`AppResponse::ZomeCalledMetered`] if the
+    /// call sets a [`ZomeCall::metering_limit`]
     CallZome(Box<ZomeCall>),
 
+    /// Call several zome functions in one request.
+    ///
+    /// Unless the batch is atomic, the calls are made concurrently, as if each
+    /// had been sent in its own [`AppRequest::CallZome`].
+    ///
+    /// The calls of an atomic batch must all be made to the same cell. They are made
+    /// in order on one workspace, so every call sees what the previous calls wrote,
+    /// and the source chain is only written to once all calls have succeeded. After
+    /// a call fails, the remaining calls are not made and nothing is written.
+    ///
+    /// See [`ZomeCallBatch`].
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCallBatchCompleted`]
+    CallZomeBatch(Box<ZomeCallBatch>),
+
     /// Clone a DNA (in the biological sense), thus creating a new `Cell`.
     ///
     /// Using the provided, already-registered DNA, create a new DNA with a unique
@@ -76,6 +95,18 @@ pub enum AppRequest {
     ///
     /// [`AppResponse::ListWasmHostFunctions`]
     ListWasmHostFunctions,
+
+    /// Set which signals are sent to this connection.
+    ///
+    /// The subscription applies to the connection it is sent over, replacing any previous
+    /// subscription of that connection. Other connections to the same app are not affected.
+    /// The [`InstalledAppId`] of the subscription must be that of the app the connection has
+    /// been authenticated for.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::SignalSubscriptionSet`]
+    SetSignalSubscription(Box<SignalSubscription>),
 }
 
 /// Represents the possible responses to an [`AppRequest`].
@@ -100,6 +131,23 @@ pub enum AppResponse {
     /// [msgpack]: https://msgpack.org/
     ZomeCalled(Box<ExternIO>),
 
+    /// The successful response to an [`AppRequest::CallZome`] which sets a
+    /// [`ZomeCall::metering_limit`].
+    ///
+    /// Like [`AppResponse::ZomeCalled`], and also reports the wasm metering
+    /// points the call consumed, which helps to tune its limit.
+    ZomeCalledMetered {
+        /// The output of the zome function.
+        output: Box<ExternIO>,
+        /// The wasm metering points the call consumed.
+        metering_points_used: u64,
+    },
+
+    /// The successful response to an [`AppRequest::CallZomeBatch`].
+    ///
+    /// Contains the result of every call of the batch, in the order of the calls.
+    ZomeCallBatchCompleted(Vec<ZomeCallBatchResult>),
+
     /// The successful response to an [`AppRequest::CreateCloneCell`].
     ///
     /// The response contains the created clone [`ClonedCell`].
@@ -121,6 +169,9 @@ pub enum AppResponse {
 
     /// All the wasm host functions supported by this conductor.
     ListWasmHostFunctions(Vec<String>),
+
+    /// The successful response to an [`AppRequest::SetSignalSubscription`].
+    SignalSubscriptionSet,
 }
 
 /// The data provided over an app interface in order to make a zome call
@@ -146,6 +197,16 @@ pub struct ZomeCall {
     pub signature: Signature,
     pub nonce: Nonce256Bits,
     pub expires_at: Timestamp,
+    /// The maximum number of wasm metering points this call may consume.
+    /// Not part of the signed data, so it can only lower the limit of the
+    /// DNA, never raise it.
+    ///
+    /// Use a lower limit to keep interactive calls tightly bounded. Heavy
+    /// functions like batch imports need a higher limit set on the DNA.
+    /// Over an app interface, a call which sets a limit is answered with
+    /// [`AppResponse::ZomeCalledMetered`], which reports the points it consumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metering_limit: Option<u64>,
 }
 
 impl From<ZomeCall> for ZomeCallUnsigned {
@@ -187,6 +248,7 @@ impl ZomeCall {
             nonce: unsigned_zome_call.nonce,
             expires_at: unsigned_zome_call.expires_at,
             signature,
+            metering_limit: None,
         })
     }
 
@@ -195,6 +257,7 @@ impl ZomeCall {
         keystore: &MetaLairClient,
         agent_key: AgentPubKey,
     ) -> LairResult<Self> {
+        let metering_limit = self.metering_limit;
         let zome_call_unsigned = ZomeCallUnsigned {
             provenance: agent_key,
             cell_id: self.cell_id,
@@ -205,10 +268,39 @@ impl ZomeCall {
             nonce: self.nonce,
             expires_at: self.expires_at,
         };
-        ZomeCall::try_from_unsigned_zome_call(keystore, zome_call_unsigned).await
+        let zome_call = ZomeCall::try_from_unsigned_zome_call(keystore, zome_call_unsigned).await?;
+        Ok(ZomeCall {
+            metering_limit,
+            ..zome_call
+        })
     }
 }
 
+/// The zome calls of an [`AppRequest::CallZomeBatch`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ZomeCallBatch {
+    /// The calls to make, each signed like the call of an [`AppRequest::CallZome`].
+    pub calls: Vec<ZomeCall>,
+    /// Make the calls on one cell as one unit, committing their writes
+    /// to the source chain together or not at all.
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// The result of one call of an [`AppRequest::CallZomeBatch`].
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum ZomeCallBatchResult {
+    /// The call succeeded, with the same output as [`AppResponse::ZomeCalled`].
+    Ok(ExternIO),
+    /// The call failed, with the same error as [`AppResponse::Error`].
+    ///
+    /// In an atomic batch, nothing has been written to the source chain.
+    Error(ExternalApiWireError),
+    /// The call was not made because an earlier call of the atomic batch failed.
+    Skipped,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CellInfo {
@@ -439,6 +531,48 @@ pub struct AppAuthenticationRequest {
     // once this is in stable, replace the pinned version in the URL by `latest``
     /// The authentication token that was provided by the conductor when [`AdminRequest::IssueAppInterfaceToken`](https://docs.rs/holochain_conductor_api/0.3.0-beta-dev.47/holochain_conductor_api/enum.AdminRequest.html#variant.IssueAppAuthenticationToken) was called.
     pub token: AppAuthenticationToken,
+
+    /// Replay the signals the app has emitted from this point onwards,
+    /// before sending new signals.
+    ///
+    /// Set this to the [`SequencedSignal::epoch`] and the [`SequencedSignal::seq`] after
+    /// the last signal received when reconnecting to an app interface with a signal replay
+    /// buffer. Signals which are no longer in the buffer are lost, which shows as a gap in
+    /// the sequence numbers. Ignored by app interfaces without a signal replay buffer.
+    ///
+    /// Resuming is refused if the epoch is not the current one, for example because the
+    /// conductor restarted, since the signals emitted in between are lost. The connection
+    /// is then sent a `SystemSignal::SignalResumeRefused` with the current epoch instead of
+    /// being authenticated, and the token is not used. Authenticate again over the same
+    /// connection without resuming in that case.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_signals_from: Option<SignalResumePoint>,
+}
+
+/// The point in the signals of an app to resume receiving them from,
+/// see [`AppAuthenticationRequest::resume_signals_from`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SignalResumePoint {
+    /// The epoch of the signals, as received in [`SequencedSignal::epoch`].
+    pub epoch: u64,
+    /// The sequence number of the first signal to replay.
+    pub seq: u64,
+}
+
+/// A signal sent by an app interface with a signal replay buffer.
+///
+/// The signals of an app are numbered in the order they were emitted,
+/// across all connections to the app.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct SequencedSignal {
+    /// Identifies the replay buffer of the app which numbered the signal. The
+    /// sequence numbers start over in a new epoch, when the conductor restarts
+    /// or the app is reinstalled.
+    pub epoch: u64,
+    /// The sequence number of the signal within its epoch.
+    pub seq: u64,
+    /// The signal.
+    pub signal: Signal,
 }
 
 #[cfg(test)]
@@ -0,0 +1,108 @@
+//! Archives of a cell, for moving a cell between conductors and for backups.
+//!
+//! An archive holds the full source chain of a cell, including private entries such as
+//! capability grants, and the integrated DHT ops authored by the cell's agent. The contents are signed by
+//! the cell's agent, so the importing conductor can check that the archive has not been
+//! tampered with.
+
+use holo_hash::AgentPubKey;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::LairResult;
+use holochain_keystore::MetaLairClient;
+use holochain_serialized_bytes::prelude::*;
+use holochain_types::dht_op::DhtOp;
+use holochain_zome_types::prelude::*;
+use std::sync::Arc;
+
+/// The version of the archive format produced by this conductor.
+/// Archives of other versions are rejected on import.
+pub const CELL_ARCHIVE_VERSION: u32 = 1;
+
+/// The contents of a [`CellArchive`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct CellArchiveContent {
+    /// The version of the archive format, see [`CELL_ARCHIVE_VERSION`].
+    pub version: u32,
+    /// The cell which was exported.
+    pub cell_id: CellId,
+    /// When the archive was created.
+    pub exported_at: Timestamp,
+    /// All records of the source chain in ascending order, including their entries.
+    pub records: Vec<Record>,
+    /// The integrated DHT ops authored by the cell's agent at the time of export.
+    pub dht_ops: Vec<DhtOp>,
+}
+
+/// A signed, versioned archive of a cell.
+///
+/// The content is kept encoded, so that the signature can be checked
+/// against exactly the bytes which were signed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct CellArchive {
+    content: SerializedBytes,
+    signature: Signature,
+}
+
+/// The errors which can occur when opening a [`CellArchive`].
+#[derive(thiserror::Error, Debug)]
+pub enum CellArchiveError {
+    /// The archive content could not be decoded.
+    #[error("Cell archive could not be decoded: {0}")]
+    Serialization(#[from] SerializedBytesError),
+
+    /// The archive was produced with a format this conductor does not support.
+    #[error("Cell archive version {0} is not supported, expected version {CELL_ARCHIVE_VERSION}")]
+    UnsupportedVersion(u32),
+
+    /// The signature does not match the content and the cell's agent.
+    #[error("Cell archive signature is not valid for agent {0}")]
+    InvalidSignature(AgentPubKey),
+
+    /// The archive contains records or DHT ops which were not authored by the cell's agent.
+    #[error("Cell archive for agent {0} contains records or ops by another author")]
+    ForeignRecords(AgentPubKey),
+
+    /// The signature could not be checked.
+    #[error("Cell archive signature could not be checked: {0}")]
+    Keystore(String),
+}
+
+impl CellArchive {
+    /// Encode the content and sign it with the key of the cell's agent.
+    pub async fn sign(keystore: &MetaLairClient, content: CellArchiveContent) -> LairResult<Self> {
+        let agent = content.cell_id.agent_pubkey().clone();
+        let content = SerializedBytes::try_from(content).map_err(|e| e.to_string())?;
+        let signature = agent
+            .sign_raw(keystore, Arc::from(content.bytes().as_slice()))
+            .await?;
+        Ok(Self { content, signature })
+    }
+
+    /// Decode the content and check its integrity.
+    ///
+    /// The content must be of the current version, be signed by the agent of the
+    /// archived cell, and only contain records and DHT ops authored by that agent.
+    pub async fn open(self) -> Result<CellArchiveContent, CellArchiveError> {
+        let content = CellArchiveContent::try_from(self.content.clone())?;
+        if content.version != CELL_ARCHIVE_VERSION {
+            return Err(CellArchiveError::UnsupportedVersion(content.version));
+        }
+        let agent = content.cell_id.agent_pubkey().clone();
+        let valid = agent
+            .verify_signature_raw(&self.signature, Arc::from(self.content.bytes().as_slice()))
+            .await
+            .map_err(|e| CellArchiveError::Keystore(e.to_string()))?;
+        if !valid {
+            return Err(CellArchiveError::InvalidSignature(agent));
+        }
+        if content
+            .records
+            .iter()
+            .any(|record| record.action().author() != &agent)
+            || content.dht_ops.iter().any(|op| op.author() != agent)
+        {
+            return Err(CellArchiveError::ForeignRecords(agent));
+        }
+        Ok(content)
+    }
+}
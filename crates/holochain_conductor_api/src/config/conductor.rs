@@ -9,6 +9,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 mod admin_interface_config;
+mod app_store_config;
 mod dpki_config;
 #[allow(missing_docs)]
 mod error;
@@ -16,14 +17,17 @@ mod keystore_config;
 /// Defines subdirectories of the config directory.
 pub mod paths;
 pub mod process;
+mod zome_call_policy_config;
 //mod logger_config;
 //mod signal_config;
 
 pub use super::*;
+pub use app_store_config::AppStoreConfig;
 pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
 pub use keystore_config::KeystoreConfig;
+pub use zome_call_policy_config::ZomeCallPolicyConfig;
 //pub use signal_config::SignalConfig;
 use std::path::Path;
 
@@ -51,6 +55,10 @@ pub struct ConductorConfig {
     /// keys for new instances.
     pub dpki: Option<DpkiConfig>,
 
+    /// Optional app store configuration, which allows apps to be installed by their hash.
+    #[serde(default)]
+    pub app_store: Option<AppStoreConfig>,
+
     /// Setup admin interfaces to control this conductor through a websocket connection.
     pub admin_interfaces: Option<Vec<AdminInterfaceConfig>>,
 
@@ -61,6 +69,8 @@ pub struct ConductorConfig {
     /// Optional specification of Chain Head Coordination service URL.
     /// If set, each cell's commit workflow will include synchronizing with the specified CHC service.
     /// If you don't know what this means, leave this setting alone (as `None`)
+    ///
+    /// The `hc-chc-service` binary of the `hc_chc_service` crate is a reference CHC service.
     #[serde(default)]
     #[cfg(feature = "chc")]
     pub chc_url: Option<url2::Url2>,
@@ -79,6 +89,12 @@ pub struct ConductorConfig {
     /// Tuning parameters to adjust the behaviour of the conductor.
     #[serde(default)]
     pub tuning_params: Option<ConductorTuningParams>,
+
+    /// Optional policy for zome calls made over app interfaces, e.g. to
+    /// limit their rate. If not set, every call which passes the capability
+    /// checks is made.
+    #[serde(default)]
+    pub zome_call_policy: Option<ZomeCallPolicyConfig>,
 }
 
 /// Helper function to load a config from a YAML string.
@@ -145,6 +161,9 @@ pub struct ConductorTuningParams {
     /// The delay between retries of sys validation when there are missing dependencies waiting to be found on the DHT.
     /// Default: 10 seconds
     pub sys_validation_retry_delay: Option<std::time::Duration>,
+    /// How long an agent stays blocked for a DNA after a valid warrant against it was received.
+    /// Default: 7 days
+    pub warrant_block_duration: Option<std::time::Duration>,
 }
 
 impl ConductorTuningParams {
@@ -152,6 +171,7 @@ impl ConductorTuningParams {
     pub fn new() -> Self {
         Self {
             sys_validation_retry_delay: None,
+            warrant_block_duration: None,
         }
     }
 
@@ -160,6 +180,12 @@ impl ConductorTuningParams {
         self.sys_validation_retry_delay
             .unwrap_or_else(|| std::time::Duration::from_secs(10))
     }
+
+    /// Get the current value of `warrant_block_duration` or its default value.
+    pub fn warrant_block_duration(&self) -> std::time::Duration {
+        self.warrant_block_duration
+            .unwrap_or_else(|| std::time::Duration::from_secs(7 * 24 * 60 * 60))
+    }
 }
 
 impl Default for ConductorTuningParams {
@@ -167,6 +193,7 @@ impl Default for ConductorTuningParams {
         let empty = Self::new();
         Self {
             sys_validation_retry_delay: Some(empty.sys_validation_retry_delay()),
+            warrant_block_duration: Some(empty.warrant_block_duration()),
         }
     }
 }
@@ -214,12 +241,14 @@ mod tests {
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 network: Default::default(),
                 dpki: None,
+                app_store: None,
                 keystore: KeystoreConfig::DangerTestKeystore,
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::default(),
                 #[cfg(feature = "chc")]
                 chc_url: None,
                 tuning_params: None,
+                zome_call_policy: None,
             }
         );
     }
@@ -266,6 +295,12 @@ mod tests {
       network_type: quic_bootstrap
 
     db_sync_strategy: Fast
+
+    zome_call_policy:
+      type: token_bucket
+      capacity: 10
+      refill_amount: 1
+      refill_interval_ms: 1000
     "#;
         let result: ConductorConfigResult<ConductorConfig> = config_from_yaml(yaml);
         let mut network_config = KitsuneP2pConfig::default();
@@ -299,7 +334,8 @@ mod tests {
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
                     driver: InterfaceDriver::Websocket {
                         port: 1234,
-                        allowed_origins: AllowedOrigins::Any
+                        allowed_origins: AllowedOrigins::Any,
+                        tls: None,
                     }
                 }]),
                 network: network_config,
@@ -307,10 +343,105 @@ mod tests {
                 #[cfg(feature = "chc")]
                 chc_url: None,
                 tuning_params: None,
+                zome_call_policy: Some(ZomeCallPolicyConfig::TokenBucket {
+                    capacity: 10,
+                    refill_amount: 1,
+                    refill_interval_ms: 1000,
+                }),
             }
         );
     }
 
+    #[test]
+    fn test_config_unix_socket_admin_interfaces() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+
+    keystore:
+      type: danger_test_keystore
+
+    admin_interfaces:
+      - driver:
+          type: unix_socket
+          path: /run/holochain/admin.sock
+      - driver:
+          type: unix_socket
+          path: /run/holochain/group-admin.sock
+          mode: 0o660
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.admin_interfaces,
+            Some(vec![
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::UnixSocket {
+                        path: PathBuf::from("/run/holochain/admin.sock"),
+                        mode: DEFAULT_UNIX_SOCKET_MODE,
+                    }
+                },
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::UnixSocket {
+                        path: PathBuf::from("/run/holochain/group-admin.sock"),
+                        mode: 0o660,
+                    }
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_config_tls_admin_interfaces() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+
+    keystore:
+      type: danger_test_keystore
+
+    admin_interfaces:
+      - driver:
+          type: websocket
+          port: 1234
+          allowed_origins: "*"
+          tls:
+            type: pem
+            cert_path: /etc/holochain/cert.pem
+            key_path: /etc/holochain/key.pem
+      - driver:
+          type: websocket
+          port: 1235
+          allowed_origins: "*"
+          tls:
+            type: lair
+            tag: admin-interface
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.admin_interfaces,
+            Some(vec![
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::Websocket {
+                        port: 1234,
+                        allowed_origins: AllowedOrigins::Any,
+                        tls: Some(InterfaceTls::Pem {
+                            cert_path: PathBuf::from("/etc/holochain/cert.pem"),
+                            key_path: PathBuf::from("/etc/holochain/key.pem"),
+                        }),
+                    }
+                },
+                AdminInterfaceConfig {
+                    driver: InterfaceDriver::Websocket {
+                        port: 1235,
+                        allowed_origins: AllowedOrigins::Any,
+                        tls: Some(InterfaceTls::Lair {
+                            tag: "admin-interface".to_string(),
+                            cert_path: None,
+                        }),
+                    }
+                },
+            ])
+        );
+    }
+
     #[test]
     fn test_config_new_lair_keystore() {
         let yaml = r#"---
@@ -329,6 +460,7 @@ mod tests {
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 network: Default::default(),
                 dpki: None,
+                app_store: None,
                 keystore: KeystoreConfig::LairServer {
                     connection_url: url2::url2!("unix:///var/run/lair-keystore/socket?k=EcRDnP3xDIZ9Rk_1E-egPE0mGZi5CcszeRxVkb2QXXQ"),
                 },
@@ -337,6 +469,7 @@ mod tests {
                 #[cfg(feature = "chc")]
                 chc_url: None,
                 tuning_params: None,
+                zome_call_policy: None,
             }
         );
     }
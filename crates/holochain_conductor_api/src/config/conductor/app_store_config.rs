@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Configure where the conductor's app store service fetches bundles from.
+///
+/// Apps in the store can be installed by their hash via the admin interface.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AppStoreConfig {
+    /// Bundles are read from a directory on the local filesystem,
+    /// where each bundle file is named after its hash.
+    LocalDir {
+        /// The directory containing the bundles
+        path: PathBuf,
+    },
+    /// Bundles are fetched by calling into a DNA of an app installed on this conductor.
+    InstalledApp {
+        /// The installed app which runs the app store DNA
+        installed_app_id: String,
+        /// The role of the app store DNA within that app
+        role_name: String,
+    },
+}
@@ -9,6 +9,10 @@ pub const DATABASES_DIRECTORY: &str = "databases";
 /// compiled wasm.
 pub const WASM_DIRECTORY: &str = "wasm";
 
+/// Subdirectory of the data directory where the conductor caches the app
+/// bundles it downloaded.
+pub const APP_BUNDLES_DIRECTORY: &str = "app_bundles";
+
 /// Name of the file that conductor config is written to.
 pub const CONDUCTOR_CONFIG: &str = "conductor-config.yaml";
 
@@ -0,0 +1,26 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Configure the policy which decides whether zome calls coming in over
+/// app interfaces may be made, on top of capability grants.
+///
+/// This lets conductors hosting untrusted UIs throttle clients which make
+/// too many calls.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ZomeCallPolicyConfig {
+    /// Limit the rate of zome calls with a token bucket for every app,
+    /// zome function and calling agent.
+    ///
+    /// Every call takes a token from its bucket, and calls are refused
+    /// while the bucket is empty.
+    TokenBucket {
+        /// The number of tokens a full bucket holds, which is the number
+        /// of calls that can be made in a burst.
+        capacity: u32,
+        /// The number of tokens added to a bucket every `refill_interval_ms`.
+        refill_amount: u32,
+        /// How often tokens are added to a bucket, in milliseconds.
+        refill_interval_ms: u64,
+    },
+}
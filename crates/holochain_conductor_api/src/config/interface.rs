@@ -1,12 +1,22 @@
 use holochain_types::websocket::AllowedOrigins;
 use serde::Deserialize;
 use serde::Serialize;
+use std::path::PathBuf;
+
+/// The default permissions of the socket file of an [`InterfaceDriver::UnixSocket`],
+/// which only allow the owner to connect.
+pub const DEFAULT_UNIX_SOCKET_MODE: u32 = 0o600;
+
+fn default_unix_socket_mode() -> u32 {
+    DEFAULT_UNIX_SOCKET_MODE
+}
 
 /// Information neeeded to spawn an admin interface
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 pub struct AdminInterfaceConfig {
     /// By what means the interface will be exposed.
-    /// Currently the only option is a local websocket running on a configurable port.
+    /// This is either a local websocket running on a configurable port,
+    /// or a websocket on a Unix domain socket.
     pub driver: InterfaceDriver,
 }
 
@@ -36,23 +46,93 @@ pub enum InterfaceDriver {
         ///
         /// Connections from any origin which is not permitted by this config will be rejected.
         allowed_origins: AllowedOrigins,
+
+        /// Serve the interface over TLS with this certificate.
+        /// Plaintext connections are refused if this is set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tls: Option<InterfaceTls>,
+    },
+
+    /// An interface implemented via websockets over a Unix domain socket.
+    ///
+    /// Access to the interface is controlled by the filesystem permissions of
+    /// the socket file rather than by allowed origins. Only available on Unix.
+    UnixSocket {
+        /// The path at which to create the socket file.
+        path: PathBuf,
+
+        /// The permissions of the socket file, e.g. `0o660` to also allow
+        /// the owning group to connect.
+        /// Defaults to [`DEFAULT_UNIX_SOCKET_MODE`].
+        #[serde(default = "default_unix_socket_mode")]
+        mode: u32,
+    },
+}
+
+/// The certificate a websocket interface uses for TLS.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InterfaceTls {
+    /// A certificate chain and private key read from PEM files.
+    Pem {
+        /// Path to the PEM encoded certificate chain, starting with the
+        /// certificate of the interface.
+        cert_path: PathBuf,
+
+        /// Path to the PEM encoded PKCS#8, PKCS#1 or SEC1 private key.
+        key_path: PathBuf,
+    },
+
+    /// A self-signed certificate created by lair and kept in the keystore
+    /// under `tag`, so it stays the same across restarts.
+    ///
+    /// Clients can't verify this certificate through a certificate authority
+    /// and should instead pin it, for example by reading it from `cert_path`.
+    Lair {
+        /// The lair tag of the certificate.
+        tag: String,
+
+        /// If set, the DER encoded certificate is written to this path
+        /// when the interface is started.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cert_path: Option<PathBuf>,
     },
 }
 
 impl InterfaceDriver {
-    /// Get the port for this driver.
-    pub fn port(&self) -> u16 {
+    /// Get the port for this driver, if it listens on one.
+    pub fn port(&self) -> Option<u16> {
         match self {
-            InterfaceDriver::Websocket { port, .. } => *port,
+            InterfaceDriver::Websocket { port, .. } => Some(*port),
+            InterfaceDriver::UnixSocket { .. } => None,
         }
     }
 
     /// Get the allowed origins for this driver.
+    ///
+    /// A Unix socket allows any origin, as it relies on filesystem permissions instead.
     pub fn allowed_origins(&self) -> &AllowedOrigins {
         match self {
             InterfaceDriver::Websocket {
                 allowed_origins, ..
             } => allowed_origins,
+            InterfaceDriver::UnixSocket { .. } => &AllowedOrigins::Any,
+        }
+    }
+
+    /// Get the TLS settings of this driver, if it is served over TLS.
+    pub fn tls(&self) -> Option<&InterfaceTls> {
+        match self {
+            InterfaceDriver::Websocket { tls, .. } => tls.as_ref(),
+            InterfaceDriver::UnixSocket { .. } => None,
+        }
+    }
+
+    /// Get the socket path for this driver, if it listens on a Unix socket.
+    pub fn socket_path(&self) -> Option<&PathBuf> {
+        match self {
+            InterfaceDriver::Websocket { .. } => None,
+            InterfaceDriver::UnixSocket { path, .. } => Some(path),
         }
     }
 }
@@ -26,13 +26,16 @@
 
 mod admin_interface;
 mod app_interface;
+pub mod cell_archive;
 pub mod config;
 pub mod signal_subscription;
 pub mod state_dump;
 pub mod storage_info;
+pub mod warrant_info;
 
 pub use admin_interface::*;
 pub use app_interface::*;
 pub use config::*;
 pub use state_dump::*;
 pub use storage_info::*;
+pub use warrant_info::*;
@@ -1,8 +1,10 @@
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::app::InstalledAppId;
+use holochain_types::signal::Signal;
 use holochain_zome_types::cell::CellId;
+use holochain_zome_types::prelude::ZomeName;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Declares updated Signal subscription settings for an App.
 /// This message is part of the AppInterfaceApi
@@ -14,12 +16,40 @@ pub struct SignalSubscription {
     filters: SignalFilterSet,
 }
 
+impl SignalSubscription {
+    /// Constructor
+    pub fn new(installed_app_id: InstalledAppId, filters: SignalFilterSet) -> Self {
+        Self {
+            installed_app_id,
+            filters,
+        }
+    }
+
+    /// The app for which to manage subscription
+    pub fn installed_app_id(&self) -> &InstalledAppId {
+        &self.installed_app_id
+    }
+
+    /// The per-cell filters of this subscription
+    pub fn filters(&self) -> &SignalFilterSet {
+        &self.filters
+    }
+
+    /// Take the per-cell filters of this subscription
+    pub fn into_filters(self) -> SignalFilterSet {
+        self.filters
+    }
+}
+
 /// Associate a SignalFilter with each Cell in an App.
 /// The filtering can be interpreted as inclusive or exclusive,
 /// depending on the use case.
 ///
 /// An empty Exclude filter means "allow all signals" (subscribe to all).
 /// An empty Include filter means "block all signals" (unsubscribe from all).
+///
+/// System signals are not emitted by any particular Cell, so they are
+/// checked against the filters of every Cell in the set.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
 pub enum SignalFilterSet {
     /// Only allow signals from the specified Cells with the specified filters,
@@ -47,11 +77,54 @@ impl SignalFilterSet {
     pub fn block_all() -> Self {
         SignalFilterSet::Include(HashMap::new())
     }
+
+    /// Whether a signal should be passed on to the subscriber
+    pub fn allows(&self, signal: &Signal) -> bool {
+        match self {
+            SignalFilterSet::Include(filters) => Self::any_matches(filters, signal),
+            SignalFilterSet::Exclude(filters) => !Self::any_matches(filters, signal),
+        }
+    }
+
+    fn any_matches(filters: &HashMap<CellId, SignalFilter>, signal: &Signal) -> bool {
+        match signal {
+            Signal::App { cell_id, .. } => filters
+                .get(cell_id)
+                .map_or(false, |filter| filter.matches(signal)),
+            Signal::System(_) => filters.values().any(|filter| filter.matches(signal)),
+        }
+    }
+}
+
+/// The kinds of signal which can be told apart by a [`SignalFilter`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalKind {
+    /// Signals emitted by zomes via `emit_signal`
+    App,
+    /// Signals emitted by Holochain itself
+    System,
 }
 
-/// Specifies fine-grained filter controls for the signals
+/// Specifies fine-grained filter controls for the signals.
+///
+/// A signal matches the filter only if it satisfies every predicate that is set.
+/// An empty filter matches every signal.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
-pub struct SignalFilter;
+pub struct SignalFilter {
+    /// Only match app signals emitted by one of these zomes.
+    /// If empty, signals from any zome match.
+    /// System signals are not emitted by a zome, so they never match a non-empty set.
+    #[serde(default)]
+    pub zome_names: HashSet<ZomeName>,
+    /// Only match signals of this kind. If `None`, signals of both kinds match.
+    #[serde(default)]
+    pub kind: Option<SignalKind>,
+    /// Only match app signals whose payload is a map containing this key.
+    /// For enums this is the name of the variant, for structs the name of a field.
+    #[serde(default)]
+    pub payload_key: Option<String>,
+}
 
 impl Default for SignalFilter {
     fn default() -> Self {
@@ -63,6 +136,135 @@ impl Default for SignalFilter {
 impl SignalFilter {
     /// A passthrough filter which filters nothing
     pub fn empty() -> Self {
-        SignalFilter
+        SignalFilter {
+            zome_names: HashSet::new(),
+            kind: None,
+            payload_key: None,
+        }
+    }
+
+    /// Whether the signal satisfies all predicates of this filter
+    pub fn matches(&self, signal: &Signal) -> bool {
+        match signal {
+            Signal::App {
+                zome_name, signal, ..
+            } => {
+                self.kind.map_or(true, |kind| kind == SignalKind::App)
+                    && (self.zome_names.is_empty() || self.zome_names.contains(zome_name))
+                    && self.payload_key.as_ref().map_or(true, |key| {
+                        signal
+                            .as_extern_io()
+                            .decode::<HashMap<String, serde::de::IgnoredAny>>()
+                            .map_or(false, |payload| payload.contains_key(key))
+                    })
+            }
+            Signal::System(_) => {
+                self.kind.map_or(true, |kind| kind == SignalKind::System)
+                    && self.zome_names.is_empty()
+                    && self.payload_key.is_none()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_types::signal::test_signal;
+    use holochain_zome_types::prelude::*;
+
+    #[derive(Debug, serde::Serialize)]
+    enum TestSignal {
+        Created { id: u32 },
+    }
+
+    fn test_cell_id(n: u8) -> CellId {
+        CellId::new(
+            DnaHash::from_raw_36(vec![n; 36]),
+            AgentPubKey::from_raw_36(vec![n; 36]),
+        )
+    }
+
+    fn app_signal(
+        cell_id: &CellId,
+        zome_name: &str,
+        payload: impl serde::Serialize + std::fmt::Debug,
+    ) -> Signal {
+        Signal::App {
+            cell_id: cell_id.clone(),
+            zome_name: zome_name.into(),
+            signal: AppSignal::new(ExternIO::encode(payload).unwrap()),
+        }
+    }
+
+    #[test]
+    fn signal_filter_predicates() {
+        let cell_id = test_cell_id(0);
+        let created = app_signal(&cell_id, "posts", TestSignal::Created { id: 1 });
+        let other_zome = app_signal(&cell_id, "comments", TestSignal::Created { id: 1 });
+        let not_a_map = app_signal(&cell_id, "posts", 42);
+        let system = test_signal("test");
+
+        let empty = SignalFilter::empty();
+        assert!(empty.matches(&created));
+        assert!(empty.matches(&system));
+
+        let by_zome = SignalFilter {
+            zome_names: ["posts".into()].into_iter().collect(),
+            ..SignalFilter::empty()
+        };
+        assert!(by_zome.matches(&created));
+        assert!(!by_zome.matches(&other_zome));
+        assert!(!by_zome.matches(&system));
+
+        let by_kind = SignalFilter {
+            kind: Some(SignalKind::System),
+            ..SignalFilter::empty()
+        };
+        assert!(!by_kind.matches(&created));
+        assert!(by_kind.matches(&system));
+
+        let by_key = SignalFilter {
+            payload_key: Some("Created".into()),
+            ..SignalFilter::empty()
+        };
+        assert!(by_key.matches(&created));
+        assert!(!by_key.matches(&not_a_map));
+        assert!(!by_key.matches(&system));
+        let by_other_key = SignalFilter {
+            payload_key: Some("Deleted".into()),
+            ..SignalFilter::empty()
+        };
+        assert!(!by_other_key.matches(&created));
+    }
+
+    #[test]
+    fn signal_filter_set_include_exclude() {
+        let cell_id = test_cell_id(0);
+        let other_cell_id = test_cell_id(1);
+        let posts = app_signal(&cell_id, "posts", ());
+        let other_cell = app_signal(&other_cell_id, "posts", ());
+        let system = test_signal("test");
+
+        assert!(SignalFilterSet::allow_all().allows(&posts));
+        assert!(SignalFilterSet::allow_all().allows(&system));
+        assert!(!SignalFilterSet::block_all().allows(&posts));
+        assert!(!SignalFilterSet::block_all().allows(&system));
+
+        let app_only = SignalFilter {
+            kind: Some(SignalKind::App),
+            ..SignalFilter::empty()
+        };
+        let filters: HashMap<_, _> = [(cell_id.clone(), app_only)].into_iter().collect();
+
+        let include = SignalFilterSet::Include(filters.clone());
+        assert!(include.allows(&posts));
+        assert!(!include.allows(&other_cell));
+        assert!(!include.allows(&system));
+
+        let exclude = SignalFilterSet::Exclude(filters);
+        assert!(!exclude.allows(&posts));
+        assert!(exclude.allows(&other_cell));
+        assert!(exclude.allows(&system));
     }
 }
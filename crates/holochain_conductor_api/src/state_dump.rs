@@ -2,6 +2,9 @@ use holo_hash::AgentPubKey;
 use holo_hash::DnaHash;
 use holochain_state_types::SourceChainDump;
 use holochain_types::dht_op::DhtOp;
+use holochain_types::dht_op::DhtOpType;
+use holochain_zome_types::prelude::Timestamp;
+use holochain_zome_types::prelude::ValidationStatus;
 use kitsune_p2p_bin_data::{KitsuneAgent, KitsuneSpace};
 use serde::Deserialize;
 use serde::Serialize;
@@ -16,8 +19,14 @@ pub struct JsonDump {
 
 #[derive(Serialize, Clone, Debug, Deserialize, PartialEq, Eq)]
 pub struct FullStateDump {
-    pub peer_dump: P2pAgentsDump,
-    pub source_chain_dump: SourceChainDump,
+    /// The peers known to the cell. Left out of the later pages
+    /// of a dump which is paged with a DHT ops limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_dump: Option<P2pAgentsDump>,
+    /// The source chain of the cell. Left out of the later pages
+    /// of a dump which is paged with a DHT ops limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_chain_dump: Option<SourceChainDump>,
     pub integration_dump: FullIntegrationStateDump,
 }
 
@@ -62,6 +71,28 @@ pub struct FullIntegrationStateDump {
     /// Useful for subsequent calls to `FullStateDump`
     /// to return only what they haven't seen
     pub dht_ops_cursor: u64,
+
+    /// Whether the dump was cut short by the requested limit.
+    /// If so, the remaining ops can be fetched by passing
+    /// `dht_ops_cursor` to the next `FullStateDump` call.
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+/// Restricts the DHT ops included in a [`FullStateDump`].
+/// Every filter that is set must match for an op to be included.
+pub struct DhtOpsFilter {
+    /// Only include ops of these types.
+    pub op_types: Option<Vec<DhtOpType>>,
+    /// Only include ops for actions by this author.
+    pub author: Option<AgentPubKey>,
+    /// Only include ops authored at or after this time.
+    pub authored_after: Option<Timestamp>,
+    /// Only include ops authored before this time.
+    pub authored_before: Option<Timestamp>,
+    /// Only include ops which have been validated with this status.
+    pub validation_status: Option<ValidationStatus>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -0,0 +1,17 @@
+use holochain_types::prelude::*;
+
+/// A warrant held by this conductor, together with its validation status and
+/// its consequence for the agent it was issued against.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct WarrantInfo {
+    /// The DNA the warrant was received for
+    pub dna_hash: DnaHash,
+    /// The hash of the warrant op
+    pub op_hash: DhtOpHash,
+    /// The warrant, with its author, signature and timestamp
+    pub warrant_op: WarrantOp,
+    /// The outcome of validating the warrant, or `None` while it is being validated
+    pub validation_status: Option<ValidationStatus>,
+    /// Whether the agent the warrant was issued against is currently blocked for the DNA
+    pub warrantee_blocked: bool,
+}
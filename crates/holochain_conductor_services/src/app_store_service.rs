@@ -1,9 +1,13 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use holochain_types::prelude::*;
 
 use crate::CellRunner;
 
+/// The zome in the app store DNA which exposes the store functions
+pub const APP_STORE_ZOME_NAME: &str = "app_store";
+
 /// Interface for the AppStore service
 #[async_trait::async_trait]
 #[mockall::automock]
@@ -21,50 +25,311 @@ pub trait AppStoreService: Send + Sync {
 
 /// The errors which can be produced by the AppStoreService
 #[derive(thiserror::Error, Debug)]
-pub enum AppStoreServiceError {}
+#[allow(missing_docs)]
+pub enum AppStoreServiceError {
+    #[error("AppStore DNA could not be called: {0}")]
+    ZomeCallFailed(anyhow::Error),
+
+    #[error("AppStore bundle could not be read: {0}")]
+    Io(#[from] ffs::IoError),
+
+    #[error("AppStore app bundle could not be decoded: {0}")]
+    Bundle(#[from] mr_bundle::error::MrBundleError),
+
+    #[error("AppStore DNA bundle could not be decoded: {0}")]
+    Dna(#[from] DnaError),
+
+    #[error("AppStore response could not be deserialized: {0}")]
+    Serialization(#[from] SerializedBytesError),
+
+    #[error("AppStore returned a bundle which does not match the requested hash. Expected: {expected}, actual: {actual}")]
+    HashMismatch { expected: String, actual: String },
+}
 /// Alias
 pub type AppStoreServiceResult<T> = Result<T, AppStoreServiceError>;
 
-/// This doesn't exist yet. We need to define it.
-pub enum AppHash {}
-
-/// The built-in implementation of the app store service, which runs a DNA
+/// The built-in implementation of the app store service, which runs a DNA.
+///
+/// The DNA is expected to expose the functions `get_dna_bundle` and `get_app_bundle`
+/// in the [`APP_STORE_ZOME_NAME`] zome, which take the requested hash as input
+/// and return the encoded bundle bytes, if any.
 pub struct AppStoreBuiltin {
-    _runner: Arc<dyn CellRunner>,
-    _cell_id: CellId,
+    runner: Arc<dyn CellRunner>,
+    cell_id: CellId,
 }
 
 impl AppStoreBuiltin {
     /// Constructor
     pub fn new(runner: Arc<impl CellRunner>, cell_id: CellId) -> Arc<Self> {
-        Arc::new(Self {
-            _runner: runner,
-            _cell_id: cell_id,
-        })
+        Arc::new(Self { runner, cell_id })
+    }
+
+    async fn fetch_bundle_bytes<I>(
+        &self,
+        fn_name: &str,
+        input: I,
+    ) -> AppStoreServiceResult<Option<Vec<u8>>>
+    where
+        I: serde::Serialize + std::fmt::Debug,
+    {
+        let cell_id = self.cell_id.clone();
+        let provenance = cell_id.agent_pubkey().clone();
+        let response = self
+            .runner
+            .call_zome(
+                &provenance,
+                None,
+                cell_id,
+                APP_STORE_ZOME_NAME.into(),
+                fn_name.into(),
+                ExternIO::encode(input)?,
+            )
+            .await
+            .map_err(AppStoreServiceError::ZomeCallFailed)?;
+        let bytes: Option<serde_bytes::ByteBuf> = response.decode()?;
+        Ok(bytes.map(|b| b.into_vec()))
     }
 }
 
 #[async_trait::async_trait]
 #[allow(clippy::needless_lifetimes)]
 impl AppStoreService for AppStoreBuiltin {
-    async fn get_dna_bundle(&self, _dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
-        todo!("placeholder")
+    async fn get_dna_bundle(&self, dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
+        match self
+            .fetch_bundle_bytes("get_dna_bundle", dna_hash.clone())
+            .await?
+        {
+            Some(bytes) => Ok(Some(decode_dna_bundle_checked(&dna_hash, &bytes).await?)),
+            None => Ok(None),
+        }
     }
 
-    async fn get_app_bundle(&self, _app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
-        todo!("placeholder")
+    async fn get_app_bundle(&self, app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
+        self.fetch_bundle_bytes("get_app_bundle", app_hash.clone())
+            .await?
+            .map(|bytes| decode_app_bundle_checked(&app_hash, &bytes))
+            .transpose()
     }
 
     fn cell_ids<'a>(&'a self) -> std::collections::HashSet<&'a CellId> {
-        [&self._cell_id].into_iter().collect()
+        [&self.cell_id].into_iter().collect()
+    }
+}
+
+/// An app store backed by a directory on the local filesystem.
+///
+/// App bundles are stored as `<app_hash>.happ` and DNA bundles as `<dna_hash>.dna`,
+/// where the hashes are in their usual string representation.
+/// This store does not run any cells.
+#[derive(Clone, Debug)]
+pub struct AppStoreLocalDir {
+    dir: PathBuf,
+}
+
+impl AppStoreLocalDir {
+    /// Constructor
+    pub fn new(dir: PathBuf) -> Arc<Self> {
+        Arc::new(Self { dir })
+    }
+
+    /// The path at which an app bundle with the given hash is expected
+    pub fn app_bundle_path(&self, app_hash: &AppHash) -> PathBuf {
+        self.dir.join(format!("{}.happ", app_hash))
+    }
+
+    /// The path at which a DNA bundle with the given hash is expected
+    pub fn dna_bundle_path(&self, dna_hash: &DnaHash) -> PathBuf {
+        self.dir.join(format!("{}.dna", dna_hash))
+    }
+
+    /// Write an app bundle into the store, returning its hash
+    pub async fn put_app_bundle(&self, bundle: &AppBundle) -> AppStoreServiceResult<AppHash> {
+        let bytes = bundle.encode()?;
+        let app_hash = AppHash::from_bundle_bytes(&bytes);
+        ffs::write(&self.app_bundle_path(&app_hash), &bytes).await?;
+        Ok(app_hash)
+    }
+
+    async fn read_if_exists(path: PathBuf) -> AppStoreServiceResult<Option<Vec<u8>>> {
+        if path.is_file() {
+            Ok(Some(ffs::read(&path).await?))
+        } else {
+            Ok(None)
+        }
     }
 }
 
-/// Create a minimal usable mock of the app store
+#[async_trait::async_trait]
+#[allow(clippy::needless_lifetimes)]
+impl AppStoreService for AppStoreLocalDir {
+    async fn get_dna_bundle(&self, dna_hash: DnaHash) -> AppStoreServiceResult<Option<DnaBundle>> {
+        match Self::read_if_exists(self.dna_bundle_path(&dna_hash)).await? {
+            Some(bytes) => Ok(Some(decode_dna_bundle_checked(&dna_hash, &bytes).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_app_bundle(&self, app_hash: AppHash) -> AppStoreServiceResult<Option<AppBundle>> {
+        Self::read_if_exists(self.app_bundle_path(&app_hash))
+            .await?
+            .map(|bytes| decode_app_bundle_checked(&app_hash, &bytes))
+            .transpose()
+    }
+
+    fn cell_ids<'a>(&'a self) -> std::collections::HashSet<&'a CellId> {
+        std::collections::HashSet::new()
+    }
+}
+
+/// Decode an app bundle, checking that the bytes actually hash to the requested hash
+fn decode_app_bundle_checked(app_hash: &AppHash, bytes: &[u8]) -> AppStoreServiceResult<AppBundle> {
+    let actual = AppHash::from_bundle_bytes(bytes);
+    if actual != *app_hash {
+        return Err(AppStoreServiceError::HashMismatch {
+            expected: app_hash.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+    Ok(mr_bundle::Bundle::decode(bytes)?.into())
+}
+
+/// Decode a DNA bundle, checking that it is actually the DNA with the requested hash
+async fn decode_dna_bundle_checked(
+    dna_hash: &DnaHash,
+    bytes: &[u8],
+) -> AppStoreServiceResult<DnaBundle> {
+    let (_, actual) = DnaBundle::decode(bytes)?
+        .into_dna_file(DnaModifiersOpt::none())
+        .await?;
+    if actual != *dna_hash {
+        return Err(AppStoreServiceError::HashMismatch {
+            expected: dna_hash.to_string(),
+            actual: actual.to_string(),
+        });
+    }
+    Ok(DnaBundle::decode(bytes)?)
+}
+
+/// Create a minimal usable mock of the app store, which contains nothing
 pub fn mock_app_store() -> MockAppStoreService {
+    use futures::FutureExt;
     let mut app_store = MockAppStoreService::new();
+    app_store
+        .expect_get_dna_bundle()
+        .returning(|_| async move { Ok(None) }.boxed());
+    app_store
+        .expect_get_app_bundle()
+        .returning(|_| async move { Ok(None) }.boxed());
     app_store
         .expect_cell_ids()
         .return_const(std::collections::HashSet::new());
     app_store
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn local_dir_store_roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = AppStoreLocalDir::new(tmp.path().to_path_buf());
+
+        let manifest = AppManifestCurrentBuilder::default()
+            .name("app".into())
+            .description(None)
+            .roles(vec![])
+            .build()
+            .unwrap();
+        let bundle = AppBundle::new(manifest.into(), vec![], tmp.path().to_path_buf())
+            .await
+            .unwrap();
+        let app_hash = store.put_app_bundle(&bundle).await.unwrap();
+        assert_eq!(app_hash, bundle.app_hash().unwrap());
+
+        let fetched = store.get_app_bundle(app_hash.clone()).await.unwrap();
+        assert_eq!(fetched.unwrap().manifest(), bundle.manifest());
+
+        let missing = AppHash::from_bundle_bytes(b"nothing");
+        assert!(store.get_app_bundle(missing).await.unwrap().is_none());
+
+        // A tampered bundle is rejected
+        ffs::write(&store.app_bundle_path(&app_hash), b"garbage")
+            .await
+            .unwrap();
+        assert!(matches!(
+            store.get_app_bundle(app_hash).await,
+            Err(AppStoreServiceError::HashMismatch { .. })
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn local_dir_store_checks_dna_hash() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = AppStoreLocalDir::new(tmp.path().to_path_buf());
+
+        let dna_bundle = |network_seed: &str| {
+            let manifest = DnaManifest::current(
+                "dna".into(),
+                Some(network_seed.into()),
+                None,
+                Timestamp::HOLOCHAIN_EPOCH.into(),
+                vec![ZomeManifest {
+                    name: "zome".into(),
+                    hash: None,
+                    location: mr_bundle::Location::Bundled("zome.wasm".into()),
+                    resource_hash: None,
+                    dependencies: None,
+                    dylib: None,
+                    post_commit_retry: None,
+                }],
+                vec![],
+            );
+            DnaBundle::new(
+                manifest.try_into().unwrap(),
+                vec![("zome.wasm".into(), vec![1, 2, 3].into())],
+                tmp.path().to_path_buf(),
+            )
+            .unwrap()
+        };
+        let dna_hash = |bundle: DnaBundle| async move {
+            bundle
+                .into_dna_file(DnaModifiersOpt::none())
+                .await
+                .unwrap()
+                .1
+        };
+        let bundle = dna_bundle("a");
+        let requested = dna_hash(dna_bundle("a")).await;
+        let other = dna_hash(dna_bundle("b")).await;
+
+        ffs::write(
+            &store.dna_bundle_path(&requested),
+            &bundle.encode().unwrap(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            store
+                .get_dna_bundle(requested.clone())
+                .await
+                .unwrap()
+                .unwrap()
+                .manifest(),
+            bundle.manifest()
+        );
+
+        // A bundle of another DNA stored under the requested hash is rejected
+        ffs::write(
+            &store.dna_bundle_path(&requested),
+            &dna_bundle("b").encode().unwrap(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            store.get_dna_bundle(requested.clone()).await,
+            Err(AppStoreServiceError::HashMismatch { expected, actual })
+                if expected == requested.to_string() && actual == other.to_string()
+        ));
+    }
+}
@@ -1,7 +1,10 @@
 //! Rate limiting data types
 
+use std::collections::BTreeMap;
+
 use holochain_serialized_bytes::prelude::*;
 
+use crate::timestamp::Timestamp;
 use crate::{Create, CreateLink, Delete, Entry, Update};
 
 /// Input to the `weigh` callback. Includes an "unweighed" action, and Entry
@@ -108,3 +111,206 @@ impl From<EntryRateWeight> for RateWeight {
         }
     }
 }
+
+impl From<RateWeight> for EntryRateWeight {
+    fn from(w: RateWeight) -> Self {
+        Self {
+            bucket_id: w.bucket_id,
+            units: w.units,
+            rate_bytes: 0,
+        }
+    }
+}
+
+/// The definition of a rate limiting bucket, as specified in the DNA.
+///
+/// Buckets are "leaky": every weighed action adds its units to the bucket of
+/// its [`RateBucketId`], and the bucket drains by `drain_amount` units every
+/// `drain_interval_ms` milliseconds. An action which would cause its bucket to
+/// exceed `capacity` is rejected during system validation.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    SerializedBytes,
+    Hash,
+    PartialOrd,
+    Ord,
+)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct RateLimit {
+    /// The maximum fill level of the bucket
+    pub capacity: RateBucketCapacity,
+    /// The number of units drained from the bucket every interval
+    pub drain_amount: RateBucketCapacity,
+    /// The length of the drain interval, in milliseconds
+    pub drain_interval_ms: u64,
+}
+
+/// The rate limits defined by a DNA, keyed by the bucket they apply to.
+///
+/// Actions weighed into a bucket which has no limit defined are never rate limited.
+pub type RateLimits = BTreeMap<RateBucketId, RateLimit>;
+
+impl RateLimit {
+    /// The time it takes for a completely full bucket to drain completely.
+    ///
+    /// Actions older than this can have no influence on the current fill level
+    /// of the bucket. A bucket which never drains has no window, which is why
+    /// DNAs with a `drain_amount` of 0 are rejected.
+    pub fn drain_window(&self) -> core::time::Duration {
+        if self.drain_amount == 0 {
+            return core::time::Duration::MAX;
+        }
+        let intervals = self.capacity.div_ceil(self.drain_amount) as u64;
+        core::time::Duration::from_millis(intervals.saturating_mul(self.drain_interval_ms))
+    }
+}
+
+/// The errors which can occur when filling a [`RateBucket`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateBucketError {
+    /// Adding the units would cause the bucket to exceed its capacity
+    BucketOverflow {
+        /// The level the bucket would have reached
+        level: u64,
+        /// The capacity of the bucket
+        capacity: RateBucketCapacity,
+    },
+    /// The units were added with a timestamp before the last addition
+    NonMonotonicTimestamp,
+}
+
+impl core::fmt::Display for RateBucketError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BucketOverflow { level, capacity } => write!(
+                f,
+                "Rate limit bucket overflow: level {} exceeds capacity {}",
+                level, capacity
+            ),
+            Self::NonMonotonicTimestamp => {
+                write!(f, "Rate limit bucket filled out of timestamp order")
+            }
+        }
+    }
+}
+
+/// The fill state of one [`RateLimit`] bucket for a single author.
+///
+/// The fill level is computed by replaying the author's weighed actions in
+/// timestamp order, draining the bucket by whole intervals in between.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateBucket {
+    limit: RateLimit,
+    level: RateBucketCapacity,
+    last_drain: Option<Timestamp>,
+}
+
+impl RateBucket {
+    /// An empty bucket with the given limit
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            level: 0,
+            last_drain: None,
+        }
+    }
+
+    /// The current fill level of the bucket
+    pub fn level(&self) -> RateBucketCapacity {
+        self.level
+    }
+
+    /// Drain the bucket for all whole intervals which elapsed before `timestamp`
+    fn drain(&mut self, timestamp: Timestamp) -> Result<(), RateBucketError> {
+        let last_drain = match self.last_drain {
+            Some(last_drain) => last_drain,
+            None => {
+                self.last_drain = Some(timestamp);
+                return Ok(());
+            }
+        };
+        if timestamp < last_drain {
+            return Err(RateBucketError::NonMonotonicTimestamp);
+        }
+        let interval_us = (self.limit.drain_interval_ms as i64).saturating_mul(1000);
+        if interval_us == 0 {
+            self.level = 0;
+            self.last_drain = Some(timestamp);
+            return Ok(());
+        }
+        let intervals = (timestamp.as_micros() - last_drain.as_micros()) / interval_us;
+        let drained = (intervals as u64).saturating_mul(self.limit.drain_amount as u64);
+        self.level = (self.level as u64).saturating_sub(drained) as RateBucketCapacity;
+        self.last_drain = Some(Timestamp::from_micros(
+            last_drain.as_micros() + intervals * interval_us,
+        ));
+        Ok(())
+    }
+
+    /// Add units to the bucket at the given time, returning the new fill level.
+    ///
+    /// If the bucket would overflow, the units are not added.
+    pub fn add(
+        &mut self,
+        units: RateUnits,
+        timestamp: Timestamp,
+    ) -> Result<RateBucketCapacity, RateBucketError> {
+        self.drain(timestamp)?;
+        let level = self.level as u64 + units as u64;
+        if level > self.limit.capacity as u64 {
+            return Err(RateBucketError::BucketOverflow {
+                level,
+                capacity: self.limit.capacity,
+            });
+        }
+        self.level = level as RateBucketCapacity;
+        Ok(self.level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(millis: i64) -> Timestamp {
+        Timestamp::from_micros(millis * 1000)
+    }
+
+    #[test]
+    fn bucket_fills_and_drains() {
+        let limit = RateLimit {
+            capacity: 10,
+            drain_amount: 2,
+            drain_interval_ms: 100,
+        };
+        let mut bucket = RateBucket::new(limit);
+
+        assert_eq!(bucket.add(6, ts(0)), Ok(6));
+        assert_eq!(bucket.add(4, ts(50)), Ok(10));
+        assert_eq!(
+            bucket.add(1, ts(99)),
+            Err(RateBucketError::BucketOverflow {
+                level: 11,
+                capacity: 10
+            })
+        );
+        // One interval has elapsed since the first addition
+        assert_eq!(bucket.add(2, ts(100)), Ok(10));
+        // Two more intervals
+        assert_eq!(bucket.add(0, ts(399)), Ok(6));
+        assert_eq!(
+            bucket.add(1, ts(250)),
+            Err(RateBucketError::NonMonotonicTimestamp)
+        );
+        assert_eq!(limit.drain_window(), core::time::Duration::from_millis(500));
+    }
+}
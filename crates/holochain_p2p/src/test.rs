@@ -686,6 +686,8 @@ mod tests {
             after: None,
             before: None,
             author: None,
+            cursor: None,
+            limit: None,
         };
 
         let res = p2p
@@ -1,6 +1,7 @@
 use crate::db::conn::PConn;
 use crate::db::databases::DATABASE_HANDLES;
 use crate::db::guard::{PConnGuard, PTxnGuard};
+use crate::db::key::DbKey;
 use crate::db::kind::{DbKind, DbKindT};
 use crate::db::pool::{
     initialize_connection, new_connection_pool, num_read_threads, ConnectionPool, DbSyncLevel,
@@ -226,17 +227,18 @@ pub struct DbWrite<Kind: DbKindT>(DbRead<Kind>);
 
 impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
     /// Create or open an existing database reference,
-    pub fn open(path_prefix: &Path, kind: Kind) -> DatabaseResult<Self> {
-        Self::open_with_sync_level(path_prefix, kind, DbSyncLevel::default())
+    pub fn open(path_prefix: &Path, kind: Kind, key: DbKey) -> DatabaseResult<Self> {
+        Self::open_with_sync_level(path_prefix, kind, DbSyncLevel::default(), key)
     }
 
     pub fn open_with_sync_level(
         path_prefix: &Path,
         kind: Kind,
         sync_level: DbSyncLevel,
+        key: DbKey,
     ) -> DatabaseResult<Self> {
         DATABASE_HANDLES.get_or_insert(&kind, path_prefix, |kind| {
-            Self::new(Some(path_prefix), kind, sync_level, None)
+            Self::new(Some(path_prefix), kind, sync_level, key, None)
         })
     }
 
@@ -244,6 +246,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         path_prefix: Option<&Path>,
         kind: Kind,
         sync_level: DbSyncLevel,
+        key: DbKey,
         statement_trace_fn: Option<fn(&str)>,
     ) -> DatabaseResult<Self> {
         let path = match path_prefix {
@@ -258,7 +261,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                 }
                 // Check if the database is valid and take the appropriate
                 // action if it isn't.
-                match Self::check_database_file(&path, sync_level) {
+                match Self::check_database_file(&path, sync_level, &key) {
                     Ok(path) => path,
                     // These are the two errors that can
                     // occur if the database is not valid.
@@ -276,14 +279,27 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                         },
                         ..,
                     )) => {
+                        // Check if the database is still encrypted with the
+                        // key that all databases used to share.
+                        #[cfg(feature = "sqlite-encrypted")]
+                        let rekeyed = rekey(&path, super::pool::LEGACY_KEY, &key).is_ok();
+                        #[cfg(not(feature = "sqlite-encrypted"))]
+                        let rekeyed = false;
+
+                        if rekeyed {
+                            tracing::warn!(
+                                "Rekeyed database {:?}, which was encrypted with the legacy shared key",
+                                path
+                            );
+                        }
                         // Check if the database might be unencrypted.
-                        if "true"
+                        else if "true"
                             == std::env::var("HOLOCHAIN_MIGRATE_UNENCRYPTED")
                                 .unwrap_or_default()
                                 .as_str()
                         {
                             #[cfg(feature = "sqlite-encrypted")]
-                            encrypt_unencrypted_database(&path)?;
+                            encrypt_unencrypted_database(&path, &key)?;
                         }
                         // Check if this database kind requires wiping.
                         else if kind.if_corrupt_wipe() {
@@ -294,7 +310,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
                         }
 
                         // Now that we've taken the appropriate action we can try again.
-                        match Self::check_database_file(&path, sync_level) {
+                        match Self::check_database_file(&path, sync_level, &key) {
                             Ok(path) => path,
                             Err(e) => return Err(e.into()),
                         }
@@ -307,7 +323,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
         };
 
         // Now we know the database file is valid we can open a connection pool.
-        let pool = new_connection_pool(path.as_ref().map(|p| p.as_ref()), sync_level);
+        let pool = new_connection_pool(path.as_ref().map(|p| p.as_ref()), sync_level, key);
         let mut conn = pool.get()?;
         // set to faster write-ahead-log mode
         conn.pragma_update(None, "journal_mode", "WAL".to_string())?;
@@ -408,11 +424,12 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
     fn check_database_file(
         path: &Path,
         sync_level: DbSyncLevel,
+        key: &DbKey,
     ) -> rusqlite::Result<Option<PathBuf>> {
         Connection::open(path)
             // For some reason calling pragma_update is necessary to prove the database file is valid.
             .and_then(|mut c| {
-                initialize_connection(&mut c, sync_level)?;
+                initialize_connection(&mut c, sync_level, key)?;
                 c.pragma_update(None, "synchronous", "0".to_string())?;
                 Ok(c.path().map(PathBuf::from))
             })
@@ -422,12 +439,18 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
     /// connection pool, useful for testing.
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test(path: &Path, kind: Kind) -> DatabaseResult<Self> {
-        Self::new(Some(path), kind, DbSyncLevel::default(), None)
+        Self::new(
+            Some(path),
+            kind,
+            DbSyncLevel::default(),
+            DbKey::test(),
+            None,
+        )
     }
 
     #[cfg(any(test, feature = "test_utils"))]
     pub fn test_in_mem(kind: Kind) -> DatabaseResult<Self> {
-        Self::new(None, kind, DbSyncLevel::default(), None)
+        Self::new(None, kind, DbSyncLevel::default(), DbKey::test(), None)
     }
 
     #[cfg(all(any(test, feature = "test_utils"), not(loom)))]
@@ -446,7 +469,7 @@ impl<Kind: DbKindT + Send + Sync + 'static> DbWrite<Kind> {
 
 // The method for this function is taken from https://discuss.zetetic.net/t/how-to-encrypt-a-plaintext-sqlite-database-to-use-sqlcipher-and-avoid-file-is-encrypted-or-is-not-a-database-errors/868
 #[cfg(feature = "sqlite-encrypted")]
-pub fn encrypt_unencrypted_database(path: &Path) -> DatabaseResult<()> {
+pub fn encrypt_unencrypted_database(path: &Path, key: &DbKey) -> DatabaseResult<()> {
     // e.g. conductor/conductor.sqlite3 -> conductor/conductor-encrypted.sqlite3
     let encrypted_path = path
         .parent()
@@ -483,7 +506,7 @@ pub fn encrypt_unencrypted_database(path: &Path) -> DatabaseResult<()> {
             "ATTACH DATABASE :db_name AS encrypted KEY :key",
             rusqlite::named_params! {
                 ":db_name": encrypted_path.to_str(),
-                ":key": super::pool::FAKE_KEY,
+                ":key": key.pragma_value(),
             },
         )?;
 
@@ -502,6 +525,40 @@ pub fn encrypt_unencrypted_database(path: &Path) -> DatabaseResult<()> {
     Ok(())
 }
 
+/// Re-encrypt a database file with a new key, using `PRAGMA rekey`.
+/// Nothing is done if the database is already encrypted with the new key.
+///
+/// The database must not be opened by anything else while it is rekeyed.
+#[cfg(feature = "sqlite-encrypted")]
+pub fn rekey_database(path: &Path, key: &DbKey, new_key: &DbKey) -> DatabaseResult<()> {
+    match rekey(path, &key.pragma_value(), new_key) {
+        Err(_) if check_key(path, &new_key.pragma_value()).is_ok() => Ok(()),
+        result => result,
+    }
+}
+
+#[cfg(feature = "sqlite-encrypted")]
+fn rekey(path: &Path, key: &str, new_key: &DbKey) -> DatabaseResult<()> {
+    let conn = check_key(path, key)?;
+
+    // Rekeying is not supported in WAL mode.
+    // WAL mode is switched on again when the database is opened.
+    conn.pragma_update(None, "journal_mode", "DELETE".to_string())?;
+    conn.pragma_update(None, "rekey", new_key.pragma_value())?;
+    conn.close().map_err(|(_, err)| err)?;
+
+    Ok(())
+}
+
+/// Open a database with a key, failing if it is not the key the database is encrypted with.
+#[cfg(feature = "sqlite-encrypted")]
+fn check_key(path: &Path, key: &str) -> DatabaseResult<Connection> {
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "key", key)?;
+    conn.query_row("SELECT count(*) FROM sqlite_master", (), |_| Ok(()))?;
+    Ok(conn)
+}
+
 #[cfg(feature = "test_utils")]
 pub fn set_acquire_timeout(timeout_ms: u64) {
     ACQUIRE_TIMEOUT_MS.store(timeout_ms, Ordering::Relaxed);
@@ -0,0 +1,59 @@
+use crate::error::DatabaseResult;
+use std::sync::Arc;
+
+/// The key databases are encrypted with, when the `sqlite-encrypted`
+/// feature is enabled.
+///
+/// Every conductor has its own key, which is stored locked by the keystore.
+/// Without the `sqlite-encrypted` feature the key is ignored.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DbKey(Arc<[u8; 32]>);
+
+impl std::fmt::Debug for DbKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DbKey").field(&"<redacted>").finish()
+    }
+}
+
+impl DbKey {
+    /// Generate a new random key.
+    pub fn generate() -> DatabaseResult<Self> {
+        let mut key = [0; 32];
+        getrandom::getrandom(&mut key)?;
+        Ok(Self::from_bytes(key))
+    }
+
+    /// Use existing key material as a key.
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        Self(Arc::new(key))
+    }
+
+    /// The key material.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// A fixed key for test databases, which may be opened more than once
+    /// without keeping track of their key.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub fn test() -> Self {
+        Self::from_bytes([
+            26, 111, 7, 31, 52, 204, 156, 103, 203, 171, 156, 89, 98, 51, 158, 143, 57, 134, 93,
+            56, 199, 225, 53, 141, 39, 77, 145, 130, 136, 108, 96, 201,
+        ])
+    }
+
+    /// The key as a raw key blob literal, as `PRAGMA key` and `PRAGMA rekey`
+    /// expect it.
+    #[cfg(feature = "sqlite-encrypted")]
+    pub(crate) fn pragma_value(&self) -> String {
+        use std::fmt::Write;
+        let mut value = String::with_capacity(67);
+        value.push_str("x'");
+        for b in self.0.iter() {
+            write!(value, "{:02X}", b).expect("Writing to a string can't fail");
+        }
+        value.push('\'');
+        value
+    }
+}
@@ -4,6 +4,7 @@ mod access;
 mod conn;
 mod databases;
 mod guard;
+mod key;
 mod kind;
 mod metrics;
 mod pool;
@@ -13,12 +14,16 @@ mod tests;
 
 pub use access::{DbRead, DbWrite, ReadAccess};
 pub use guard::PTxnGuard;
+pub use key::DbKey;
 pub use kind::{
     DbKind, DbKindAuthored, DbKindCache, DbKindConductor, DbKindDht, DbKindOp, DbKindP2pAgents,
     DbKindP2pMetrics, DbKindT, DbKindWasm,
 };
 pub use pool::{DbSyncLevel, DbSyncStrategy};
 
+#[cfg(feature = "sqlite-encrypted")]
+pub use access::{encrypt_unencrypted_database, rekey_database};
+
 #[cfg(feature = "test_utils")]
 pub use access::set_acquire_timeout;
 #[cfg(feature = "test_utils")]
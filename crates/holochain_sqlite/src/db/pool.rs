@@ -1,3 +1,4 @@
+use crate::db::key::DbKey;
 use crate::functions::add_custom_functions;
 use holochain_serialized_bytes::prelude::*;
 use once_cell::sync::Lazy;
@@ -11,8 +12,10 @@ static CONNECTION_TIMEOUT_MS: AtomicU64 = AtomicU64::new(3_000);
 
 const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// The key all databases were encrypted with before every conductor had its own
+/// [`DbKey`]. Databases still encrypted with it are rekeyed when opened.
 #[cfg(feature = "sqlite-encrypted")]
-pub(super) const FAKE_KEY: &str = "x'98483C6EB40B6C31A448C22A66DED3B5E5E8D5119CAC8327B655C8B5C483648101010101010101010101010101010101'";
+pub(super) const LEGACY_KEY: &str = "x'98483C6EB40B6C31A448C22A66DED3B5E5E8D5119CAC8327B655C8B5C483648101010101010101010101010101010101'";
 
 static R2D2_THREADPOOL: Lazy<Arc<ScheduledThreadPool>> = Lazy::new(|| {
     let t = ScheduledThreadPool::new(1);
@@ -55,13 +58,17 @@ pub enum DbSyncStrategy {
 pub(super) fn new_connection_pool(
     path: Option<&Path>,
     synchronous_level: DbSyncLevel,
+    key: DbKey,
 ) -> ConnectionPool {
     use r2d2_sqlite::SqliteConnectionManager;
     let manager = match path {
         Some(path) => SqliteConnectionManager::file(path),
         None => SqliteConnectionManager::memory(),
     };
-    let customizer = Box::new(ConnCustomizer { synchronous_level });
+    let customizer = Box::new(ConnCustomizer {
+        synchronous_level,
+        key,
+    });
 
     /*
      * We want
@@ -90,11 +97,12 @@ pub(super) fn new_connection_pool(
 #[derive(Debug)]
 struct ConnCustomizer {
     synchronous_level: DbSyncLevel,
+    key: DbKey,
 }
 
 impl r2d2::CustomizeConnection<Connection, rusqlite::Error> for ConnCustomizer {
     fn on_acquire(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
-        initialize_connection(conn, self.synchronous_level)?;
+        initialize_connection(conn, self.synchronous_level, &self.key)?;
         Ok(())
     }
 }
@@ -102,24 +110,15 @@ impl r2d2::CustomizeConnection<Connection, rusqlite::Error> for ConnCustomizer {
 pub(super) fn initialize_connection(
     conn: &mut Connection,
     synchronous_level: DbSyncLevel,
+    key: &DbKey,
 ) -> Result<()> {
     // Tell SQLite to wait this long during write contention.
     conn.busy_timeout(SQLITE_BUSY_TIMEOUT)?;
 
     #[cfg(feature = "sqlite-encrypted")]
-    {
-        use std::io::Write;
-        let key = get_encryption_key_shim();
-        let mut hex = *br#"0000000000000000000000000000000000000000000000000000000000000000"#;
-        let mut c = std::io::Cursor::new(&mut hex[..]);
-        for b in &key {
-            write!(c, "{:02X}", b)
-                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
-        }
-        let _keyval = std::str::from_utf8(&hex).unwrap();
-        // conn.pragma_update(None, "key", &keyval)?;
-        conn.pragma_update(None, "key", FAKE_KEY)?;
-    }
+    conn.pragma_update(None, "key", key.pragma_value())?;
+    #[cfg(not(feature = "sqlite-encrypted"))]
+    let _ = key;
 
     // this is recommended to always be off:
     // https://sqlite.org/pragma.html#pragma_trusted_schema
@@ -145,15 +144,6 @@ pub fn num_read_threads() -> usize {
     std::cmp::max(num_threads, 4)
 }
 
-#[cfg(feature = "sqlite-encrypted")]
-/// Simulate getting an encryption key from Lair.
-fn get_encryption_key_shim() -> [u8; 32] {
-    [
-        26, 111, 7, 31, 52, 204, 156, 103, 203, 171, 156, 89, 98, 51, 158, 143, 57, 134, 93, 56,
-        199, 225, 53, 141, 39, 77, 145, 130, 136, 108, 96, 201,
-    ]
-}
-
 #[cfg(feature = "test_utils")]
 pub fn set_connection_timeout(timeout_ms: u64) {
     CONNECTION_TIMEOUT_MS.store(timeout_ms, Ordering::Relaxed);
@@ -4,6 +4,8 @@ use crate::prelude::{DatabaseResult, DbKindWasm};
 
 use super::pool::num_read_threads;
 use super::DbWrite;
+#[cfg(feature = "sqlite-encrypted")]
+use super::{rekey_database, DbKey, DbKindT};
 
 /// This test does prove that making all transactions
 /// synchronous fixes the db timeout issue but it's slow
@@ -135,3 +137,51 @@ async fn db_connection_doesnt_timeout() {
     // without taking permits.
     assert!(result.is_err());
 }
+
+#[cfg(feature = "sqlite-encrypted")]
+#[tokio::test(flavor = "multi_thread")]
+async fn rekeyed_database_opens_with_the_new_key_only() {
+    use super::pool::DbSyncLevel;
+
+    let td = TempDir::new().unwrap();
+    let path = td.path().join(DbKindWasm.filename());
+    let key = DbKey::generate().unwrap();
+    let new_key = DbKey::generate().unwrap();
+    let open = |key: &DbKey| {
+        DbWrite::new(
+            Some(td.path()),
+            DbKindWasm,
+            DbSyncLevel::default(),
+            key.clone(),
+            None,
+        )
+    };
+
+    let db = open(&key).unwrap();
+    db.write_async(|txn| {
+        txn.execute(
+            "INSERT INTO Wasm (hash, blob) VALUES(?, ?)",
+            [vec![0], vec![0]],
+        )?;
+        DatabaseResult::Ok(())
+    })
+    .await
+    .unwrap();
+    drop(db);
+
+    rekey_database(&path, &key, &new_key).unwrap();
+    // Rekeying again, as an interrupted rotation does, changes nothing.
+    rekey_database(&path, &key, &new_key).unwrap();
+
+    assert!(open(&key).is_err());
+    let db = open(&new_key).unwrap();
+    let count: usize = db
+        .read_async(|txn| {
+            DatabaseResult::Ok(
+                txn.query_row("SELECT COUNT(rowid) FROM Wasm", [], |row| row.get(0))?,
+            )
+        })
+        .await
+        .unwrap();
+    assert_eq!(1, count);
+}
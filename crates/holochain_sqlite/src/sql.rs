@@ -45,14 +45,14 @@ pub mod sql_cell {
             include_str!("sql/cell/schedule/delete_all_ephemeral.sql");
         pub const DELETE_LIVE_EPHEMERAL: &str =
             include_str!("sql/cell/schedule/delete_live_ephemeral.sql");
+        pub const INSERT_RUN: &str = include_str!("sql/cell/schedule/insert_run.sql");
+        pub const PRUNE_RUNS: &str = include_str!("sql/cell/schedule/prune_runs.sql");
+        pub const RUNS: &str = include_str!("sql/cell/schedule/runs.sql");
     }
     pub mod state_dump {
-        pub const DHT_OPS_IN_INTEGRATION_LIMBO: &str =
-            include_str!("sql/cell/state_dump/dht_ops_in_integration_limbo.sql");
+        pub const DHT_OPS: &str = include_str!("sql/cell/state_dump/dht_ops.sql");
         pub const DHT_OPS_INTEGRATED: &str =
             include_str!("sql/cell/state_dump/dht_ops_integrated.sql");
-        pub const DHT_OPS_IN_VALIDATION_LIMBO: &str =
-            include_str!("sql/cell/state_dump/dht_ops_in_validation_limbo.sql");
         pub const DHT_OPS_ROW_ID: &str = include_str!("sql/cell/state_dump/dht_ops_row_id.sql");
     }
 }
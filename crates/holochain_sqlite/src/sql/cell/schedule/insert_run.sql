@@ -0,0 +1,18 @@
+INSERT INTO
+  ScheduledFunctionRun (
+    author,
+    zome_name,
+    scheduled_fn,
+    started_at,
+    duration_micros,
+    error
+  )
+VALUES
+  (
+    :author,
+    :zome_name,
+    :scheduled_fn,
+    :started_at,
+    :duration_micros,
+    :error
+  )
@@ -0,0 +1,20 @@
+DELETE FROM
+  ScheduledFunctionRun
+WHERE
+  author = :author
+  AND zome_name = :zome_name
+  AND scheduled_fn = :scheduled_fn
+  AND rowid NOT IN (
+    SELECT
+      rowid
+    FROM
+      ScheduledFunctionRun
+    WHERE
+      author = :author
+      AND zome_name = :zome_name
+      AND scheduled_fn = :scheduled_fn
+    ORDER BY
+      started_at DESC
+    LIMIT
+      :keep
+  )
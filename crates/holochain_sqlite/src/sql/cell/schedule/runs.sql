@@ -0,0 +1,22 @@
+SELECT
+  zome_name,
+  scheduled_fn,
+  started_at,
+  duration_micros,
+  error
+FROM
+  ScheduledFunctionRun
+WHERE
+  author = :author
+  AND (
+    :zome_name IS NULL
+    OR zome_name = :zome_name
+  )
+  AND (
+    :scheduled_fn IS NULL
+    OR scheduled_fn = :scheduled_fn
+  )
+ORDER BY
+  started_at DESC
+LIMIT
+  :limit
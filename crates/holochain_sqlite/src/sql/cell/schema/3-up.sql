@@ -0,0 +1,13 @@
+-- no-sql-format --
+
+CREATE TABLE IF NOT EXISTS ScheduledFunctionRun (
+    author BLOB NOT NULL,
+    zome_name TEXT NOT NULL,
+    scheduled_fn TEXT NOT NULL,
+    -- When the run was dispatched, as a Timestamp (microseconds)
+    started_at INTEGER NOT NULL,
+    duration_micros INTEGER NOT NULL,
+    -- NULL if the run succeeded
+    error TEXT NULL
+);
+CREATE INDEX IF NOT EXISTS ScheduledFunctionRun_fn_idx ON ScheduledFunctionRun ( author, zome_name, scheduled_fn, started_at );
@@ -1,14 +1,16 @@
 -- no-sql-format --
 SELECT
   Action.blob as action_blob,
+  Action.author as author,
   Entry.blob as entry_blob,
   DhtOp.type as dht_type,
   DhtOp.hash as dht_hash,
-  DhtOp.rowid as rowid
+  DhtOp.rowid as rowid,
+  DhtOp.when_integrated as when_integrated,
+  DhtOp.validation_stage as validation_stage
 FROM
   DhtOp
   JOIN Action ON DhtOp.action_hash = Action.hash
   LEFT JOIN Entry ON Action.entry_hash = Entry.hash
 WHERE
-  when_integrated IS NULL
-  AND validation_stage = 3
+  DhtOp.rowid > ?
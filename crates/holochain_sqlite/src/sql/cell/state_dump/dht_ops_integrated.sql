@@ -1,6 +1,7 @@
 -- no-sql-format --
 SELECT
   Action.blob as action_blob,
+  Action.author as author,
   Entry.blob as entry_blob,
   DhtOp.type as dht_type,
   DhtOp.hash as dht_hash,
@@ -2,7 +2,7 @@
 #[tokio::test]
 async fn migrate_unencrypted() {
     use holochain_sqlite::{
-        db::{DbKindConductor, DbWrite},
+        db::{DbKey, DbKindConductor, DbWrite},
         error::DatabaseResult,
     };
     use rusqlite::Connection;
@@ -30,14 +30,20 @@ async fn migrate_unencrypted() {
     }
 
     // Without the HOLOCHAIN_MIGRATE_UNENCRYPTED variable set, it should fail to open
-    let err = DbWrite::open(std::path::Path::new(tmp_dir.path()), DbKindConductor).unwrap_err();
+    let key = DbKey::generate().unwrap();
+    let err = DbWrite::open(
+        std::path::Path::new(tmp_dir.path()),
+        DbKindConductor,
+        key.clone(),
+    )
+    .unwrap_err();
     assert_eq!(err.to_string(), "file is not a database");
 
     std::env::set_var("HOLOCHAIN_MIGRATE_UNENCRYPTED", "true");
 
     // Now it should open and read just fine, because it will be encrypted automatically
     let db: DbWrite<DbKindConductor> =
-        DbWrite::open(std::path::Path::new(tmp_dir.path()), DbKindConductor).unwrap();
+        DbWrite::open(std::path::Path::new(tmp_dir.path()), DbKindConductor, key).unwrap();
     let msg = db
         .read_async(|txn| -> DatabaseResult<String> {
             Ok(txn.query_row(
@@ -50,3 +56,60 @@ async fn migrate_unencrypted() {
         .unwrap();
     assert_eq!(msg, "hello_migrated".to_string());
 }
+
+#[cfg(feature = "sqlite-encrypted")]
+#[tokio::test]
+async fn migrate_legacy_key() {
+    use holochain_sqlite::{
+        db::{DbKey, DbKindWasm, DbWrite},
+        error::DatabaseResult,
+    };
+    use rusqlite::Connection;
+    use std::fs::create_dir_all;
+
+    holochain_trace::test_run();
+
+    let tmp_dir = tempfile::TempDir::new().unwrap();
+    create_dir_all(tmp_dir.path().join("wasm")).unwrap();
+
+    // Set up a database encrypted with the key all databases used to share
+    {
+        let conn = Connection::open(tmp_dir.path().join("wasm/wasm.sqlite3")).unwrap();
+        conn.pragma_update(None, "key", "x'98483C6EB40B6C31A448C22A66DED3B5E5E8D5119CAC8327B655C8B5C483648101010101010101010101010101010101'")
+            .unwrap();
+        conn.execute("CREATE TABLE migrate_me (name TEXT NOT NULL)", ())
+            .unwrap();
+        conn.execute("INSERT INTO migrate_me (name) VALUES ('hello_rekeyed')", ())
+            .unwrap();
+
+        conn.close().unwrap();
+    }
+
+    // It is rekeyed with the given key when opened
+    let key = DbKey::generate().unwrap();
+    let db: DbWrite<DbKindWasm> = DbWrite::open(
+        std::path::Path::new(tmp_dir.path()),
+        DbKindWasm,
+        key.clone(),
+    )
+    .unwrap();
+    let msg = db
+        .read_async(|txn| -> DatabaseResult<String> {
+            Ok(txn.query_row(
+                "SELECT name FROM migrate_me LIMIT 1",
+                (),
+                |row| -> Result<String, rusqlite::Error> { row.get(0) },
+            )?)
+        })
+        .await
+        .unwrap();
+    assert_eq!(msg, "hello_rekeyed".to_string());
+
+    // and can't be read with the legacy key anymore
+    let conn = Connection::open(tmp_dir.path().join("wasm/wasm.sqlite3")).unwrap();
+    conn.pragma_update(None, "key", "x'98483C6EB40B6C31A448C22A66DED3B5E5E8D5119CAC8327B655C8B5C483648101010101010101010101010101010101'")
+        .unwrap();
+    assert!(conn
+        .query_row("SELECT count(*) FROM sqlite_master", (), |_| Ok(()))
+        .is_err());
+}
@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use holo_hash::AgentPubKey;
 use holochain_keystore::MetaLairClient;
@@ -20,8 +21,13 @@ pub struct HostFnWorkspace<
     /// This is needed so that we don't run init recursively inside
     /// init calls.
     init_is_root: bool,
+    /// How long the calls of the call chain have slept for, in total.
+    slept: SleptTime,
 }
 
+/// How long a zome call chain has slept for, shared by all its calls.
+pub type SleptTime = Arc<parking_lot::Mutex<Duration>>;
+
 #[derive(Clone, shrinkwraprs::Shrinkwrap)]
 pub struct SourceChainWorkspace {
     #[shrinkwrap(main_field)]
@@ -127,11 +133,25 @@ impl SourceChainWorkspace {
                 dna_def,
                 cache,
                 init_is_root,
+                // The calls made by init may not sleep, as they would hold up
+                // the calls waiting for init to finish.
+                slept: Arc::new(parking_lot::Mutex::new(if init_is_root {
+                    Duration::MAX
+                } else {
+                    Duration::ZERO
+                })),
             },
             source_chain,
         })
     }
 
+    /// Make this workspace part of the call chain which has slept for the given time,
+    /// for a call made by another cell.
+    pub fn with_slept(mut self, slept: SleptTime) -> Self {
+        self.inner.slept = slept;
+        self
+    }
+
     /// Did this zome call chain originate from within
     /// an init callback.
     pub fn called_from_init(&self) -> bool {
@@ -173,9 +193,15 @@ where
             cache,
             dna_def,
             init_is_root: false,
+            slept: Default::default(),
         })
     }
 
+    /// How long the calls of this zome call chain have slept for, in total.
+    pub fn slept(&self) -> &SleptTime {
+        &self.slept
+    }
+
     pub fn source_chain(&self) -> &Option<SourceChain<SourceChainDb, SourceChainDht>> {
         &self.source_chain
     }
@@ -219,6 +245,7 @@ impl From<HostFnWorkspace> for HostFnWorkspaceRead {
             cache: workspace.cache,
             dna_def: workspace.dna_def,
             init_is_root: workspace.init_is_root,
+            slept: workspace.slept,
         }
     }
 }
@@ -238,6 +265,7 @@ impl From<SourceChainWorkspace> for HostFnWorkspaceRead {
             cache: workspace.inner.cache,
             dna_def: workspace.inner.dna_def,
             init_is_root: workspace.inner.init_is_root,
+            slept: workspace.inner.slept,
         }
     }
 }
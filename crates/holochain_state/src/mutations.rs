@@ -13,6 +13,7 @@ use holochain_sqlite::rusqlite::types::Null;
 use holochain_sqlite::rusqlite::Transaction;
 use holochain_sqlite::sql::sql_conductor;
 use holochain_types::dht_op::ChainOpHashed;
+use holochain_types::dht_op::DhtOp;
 use holochain_types::dht_op::DhtOpHashed;
 use holochain_types::dht_op::DhtOpLite;
 use holochain_types::dht_op::OpOrder;
@@ -21,6 +22,7 @@ use holochain_types::prelude::DnaWasmHashed;
 use holochain_types::prelude::SysValDep;
 use holochain_types::prelude::{DhtOpError, SignedValidationReceipt};
 use holochain_types::sql::AsSql;
+use holochain_types::warrant::WarrantOp;
 use holochain_zome_types::block::Block;
 use holochain_zome_types::block::BlockTargetId;
 use holochain_zome_types::block::BlockTargetReason;
@@ -122,6 +124,8 @@ pub fn insert_op(txn: &mut Transaction, op: &DhtOpHashed) -> StateMutationResult
         let action_hashed = ActionHashed::with_pre_hashed(action, op_lite.action_hash().to_owned());
         let action_hashed = SignedActionHashed::with_presigned(action_hashed, signature);
         insert_action(txn, &action_hashed)?;
+    } else if let DhtOp::WarrantOp(warrant_op) = op {
+        insert_warrant(txn, hash, warrant_op)?;
     }
 
     let dependency = op.sys_validation_dependency();
@@ -157,7 +161,10 @@ pub fn insert_op_lite(
     order: &OpOrder,
     timestamp: &Timestamp,
 ) -> StateMutationResult<()> {
-    let action_hash = op_lite.as_chain_op().map(|op| op.action_hash().clone());
+    let action_hash = match op_lite.as_chain_op() {
+        Some(op) => op.action_hash().clone(),
+        None => warrant_action_hash(hash),
+    };
     let basis = op_lite.dht_basis();
     sql_insert!(txn, DhtOp, {
         "hash": hash,
@@ -172,6 +179,31 @@ pub fn insert_op_lite(
     Ok(())
 }
 
+/// Warrants are stored in the Action table, so that their ops can be joined
+/// with their content like the ops of actions are. They are keyed by the hash
+/// of their op, recast as an [`ActionHash`].
+pub fn warrant_action_hash(op_hash: &DhtOpHash) -> ActionHash {
+    ActionHash::from_raw_32(op_hash.get_raw_32().to_vec())
+}
+
+/// Insert the [`SignedWarrant`] of a [`WarrantOp`] into the database.
+pub fn insert_warrant(
+    txn: &mut Transaction,
+    op_hash: &DhtOpHash,
+    warrant_op: &WarrantOp,
+) -> StateMutationResult<()> {
+    let warrant_type = warrant_op.get_type();
+    let author = warrant_op.author.clone();
+    let signed_warrant = warrant_op.clone().into_signed_warrant();
+    sql_insert!(txn, Action, {
+        "hash": warrant_action_hash(op_hash),
+        "type": warrant_type,
+        "author": author,
+        "blob": to_blob(&signed_warrant)?,
+    })?;
+    Ok(())
+}
+
 /// Insert a [`SignedValidationReceipt`] into the database.
 pub fn insert_validation_receipt(
     txn: &mut Transaction,
@@ -750,6 +782,40 @@ pub fn reschedule_expired(
     Ok(())
 }
 
+/// The next time after `now` at which a crontab schedule is due, in its
+/// timezone and with its jitter applied, or `None` if it is never due again.
+fn next_cron_start(
+    cron_schedule: &CronSchedule,
+    now: Timestamp,
+) -> StateMutationResult<Option<Timestamp>> {
+    let schedule = cron::Schedule::from_str(&cron_schedule.cron)
+        .map_err(|e| ScheduleError::Cron(e.to_string()))?;
+    let now = chrono::DateTime::<chrono::Utc>::try_from(now).map_err(ScheduleError::Timestamp)?;
+    let next = match cron_schedule.timezone {
+        Some(ref timezone) => {
+            let timezone = chrono_tz::Tz::from_str(timezone)
+                .map_err(|e| ScheduleError::Cron(e.to_string()))?;
+            schedule
+                .after(&now.with_timezone(&timezone))
+                .next()
+                .map(|next| next.with_timezone(&chrono::Utc))
+        }
+        None => schedule.after(&now).next(),
+    };
+    let next = match next {
+        Some(next) => Timestamp::from(next),
+        None => return Ok(None),
+    };
+    let jitter = match cron_schedule.jitter {
+        Some(jitter) if !jitter.is_zero() => {
+            use rand::Rng;
+            rand::thread_rng().gen_range(std::time::Duration::ZERO..=jitter)
+        }
+        _ => std::time::Duration::ZERO,
+    };
+    Ok(Some((next + jitter).map_err(ScheduleError::Timestamp)?))
+}
+
 pub fn schedule_fn(
     txn: &mut Transaction,
     author: &AgentPubKey,
@@ -757,18 +823,18 @@ pub fn schedule_fn(
     maybe_schedule: Option<Schedule>,
     now: Timestamp,
 ) -> StateMutationResult<()> {
-    let (start, end, ephemeral) = match maybe_schedule {
+    let cron_schedule = match maybe_schedule {
         Some(Schedule::Persisted(ref schedule_string)) => {
+            Some(CronSchedule::new(schedule_string.as_str()))
+        }
+        Some(Schedule::Cron(ref cron_schedule)) => Some(cron_schedule.clone()),
+        Some(Schedule::Ephemeral(_)) | None => None,
+    };
+    let (start, end, ephemeral) = match (cron_schedule, &maybe_schedule) {
+        (Some(cron_schedule), _) => {
             // If this cron doesn't parse cleanly we don't even want to
             // write it to the db.
-            let start = if let Some(start) = cron::Schedule::from_str(schedule_string)
-                .map_err(|e| ScheduleError::Cron(e.to_string()))?
-                .after(
-                    &chrono::DateTime::<chrono::Utc>::try_from(now)
-                        .map_err(ScheduleError::Timestamp)?,
-                )
-                .next()
-            {
+            let start = if let Some(start) = next_cron_start(&cron_schedule, now)? {
                 start
             } else {
                 // If there are no further executions then scheduling is a
@@ -783,17 +849,16 @@ pub fn schedule_fn(
                 )?;
                 return Ok(());
             };
-            let end = start
-                + chrono::Duration::from_std(holochain_zome_types::schedule::PERSISTED_TIMEOUT)
-                    .map_err(|e| ScheduleError::Cron(e.to_string()))?;
-            (Timestamp::from(start), Timestamp::from(end), false)
+            let end = (start + holochain_zome_types::schedule::PERSISTED_TIMEOUT)
+                .map_err(ScheduleError::Timestamp)?;
+            (start, end, false)
         }
-        Some(Schedule::Ephemeral(duration)) => (
-            (now + duration).map_err(ScheduleError::Timestamp)?,
+        (None, Some(Schedule::Ephemeral(duration))) => (
+            (now + *duration).map_err(ScheduleError::Timestamp)?,
             Timestamp::max(),
             true,
         ),
-        None => (now, Timestamp::max(), true),
+        (None, _) => (now, Timestamp::max(), true),
     };
     if fn_is_scheduled(txn, scheduled_fn.clone(), author)? {
         txn.execute(
@@ -821,3 +886,55 @@ pub fn schedule_fn(
     }
     Ok(())
 }
+
+/// Remove a function from the schedule of this author.
+///
+/// Returns `false` if the function was not scheduled.
+pub fn unschedule_fn(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    scheduled_fn: &ScheduledFn,
+) -> StateMutationResult<bool> {
+    let deleted = txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::DELETE,
+        named_params! {
+            ":zome_name": scheduled_fn.zome_name().to_string(),
+            ":scheduled_fn": scheduled_fn.fn_name().to_string(),
+            ":author" : author,
+        },
+    )?;
+    Ok(deleted > 0)
+}
+
+/// The number of runs kept in the history of each scheduled function.
+pub const SCHEDULED_FN_RUN_HISTORY_LIMIT: u32 = 100;
+
+/// Add a run to the history of a scheduled function, dropping the oldest runs
+/// beyond [`SCHEDULED_FN_RUN_HISTORY_LIMIT`].
+pub fn insert_scheduled_fn_run(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    run: &ScheduledFnRun,
+) -> StateMutationResult<()> {
+    txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::INSERT_RUN,
+        named_params! {
+            ":author": author,
+            ":zome_name": run.zome_name.to_string(),
+            ":scheduled_fn": run.fn_name.to_string(),
+            ":started_at": run.started_at,
+            ":duration_micros": i64::try_from(run.duration.as_micros()).unwrap_or(i64::MAX),
+            ":error": run.error,
+        },
+    )?;
+    txn.execute(
+        holochain_sqlite::sql::sql_cell::schedule::PRUNE_RUNS,
+        named_params! {
+            ":author": author,
+            ":zome_name": run.zome_name.to_string(),
+            ":scheduled_fn": run.fn_name.to_string(),
+            ":keep": SCHEDULED_FN_RUN_HISTORY_LIMIT,
+        },
+    )?;
+    Ok(())
+}
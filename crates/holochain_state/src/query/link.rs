@@ -17,6 +17,10 @@ pub struct GetLinksFilter {
     pub after: Option<Timestamp>,
     pub before: Option<Timestamp>,
     pub author: Option<AgentPubKey>,
+    /// Only return links after this cursor, ordered by timestamp then create link hash.
+    pub cursor: Option<LinkCursor>,
+    /// Return at most this many links.
+    pub limit: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -102,6 +106,8 @@ impl LinksQuery {
         s = Self::add_after(s, filter.after);
         s = Self::add_before(s, filter.before);
         s = Self::add_author(s, filter.author.as_ref());
+        s = Self::add_cursor(s, filter.cursor.as_ref());
+        s = Self::add_limit(s, filter.limit);
 
         s
     }
@@ -145,6 +151,54 @@ impl LinksQuery {
         }
     }
 
+    // Links are ordered by timestamp, and links with the same timestamp by their hash.
+    fn add_cursor(q: String, cursor: Option<&LinkCursor>) -> String {
+        match cursor {
+            Some(_) => format!(
+                "{}
+                AND (
+                    DhtOp.authored_timestamp > :cursor_timestamp
+                    OR (
+                        DhtOp.authored_timestamp = :cursor_timestamp
+                        AND Action.hash > :cursor_hash
+                    )
+                )",
+                q
+            ),
+            None => format!(
+                "{} AND :cursor_timestamp IS NULL AND :cursor_hash IS NULL",
+                q
+            ),
+        }
+    }
+
+    // Deleted links are left out before the page is cut, so that they don't
+    // take up room in it. Links deleted in another store are only removed when
+    // rendering, so a page may hold fewer links than the limit.
+    fn add_limit(q: String, limit: Option<u32>) -> String {
+        match limit {
+            Some(_) => format!(
+                "
+                SELECT action_blob FROM (
+                    {}
+                    AND NOT EXISTS (
+                        SELECT 1 FROM Action AS DeleteAction
+                        JOIN DhtOp AS DeleteOp ON DeleteOp.action_hash = DeleteAction.hash
+                        WHERE DeleteAction.create_link_hash = Action.hash
+                        AND DeleteOp.type = :delete
+                        AND DeleteOp.validation_status = :status
+                        AND DeleteOp.when_integrated IS NOT NULL
+                    )
+                    ORDER BY DhtOp.authored_timestamp, Action.hash
+                    LIMIT :limit
+                )
+                ",
+                q
+            ),
+            None => format!("{} AND :limit IS NULL", q),
+        }
+    }
+
     fn delete_query_string(type_query: &LinkTypeFilter, tag: Option<String>) -> String {
         let mut sub_create_query = format!(
             "
@@ -182,6 +236,9 @@ impl LinksQuery {
                 ":after": self.filter.after,
                 ":before": self.filter.before,
                 ":author": self.filter.author,
+                ":cursor_timestamp": self.filter.cursor.as_ref().map(|c| c.timestamp),
+                ":cursor_hash": self.filter.cursor.as_ref().map(|c| c.create_link_hash.clone()),
+                ":limit": self.filter.limit,
             }
         }
         .to_vec()
@@ -280,7 +337,16 @@ impl Query for GetLinksQuery {
         S: Store,
     {
         let mut links: Self::Output = state.creates.into_values().collect();
-        links.sort_by_key(|l| l.timestamp);
+        links.sort_by_key(|l| (l.timestamp, l.create_link_hash.clone()));
+        // Each store is paged by the query, so the pages of all stores are
+        // merged into one here.
+        let filter = &self.query.filter;
+        if let Some(cursor) = &filter.cursor {
+            links.retain(|l| l.cursor() > *cursor);
+        }
+        if let Some(limit) = filter.limit {
+            links.truncate(limit as usize);
+        }
         Ok(links)
     }
 }
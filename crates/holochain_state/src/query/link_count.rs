@@ -9,6 +9,8 @@ impl From<WireLinkQuery> for GetLinksFilter {
             before: value.before,
             after: value.after,
             author: value.author,
+            cursor: value.cursor,
+            limit: value.limit,
         }
     }
 }
@@ -1074,3 +1074,74 @@ async fn link_type_ranges() {
     )
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn paged_links() {
+    holochain_trace::test_run();
+    let test_db = test_dht_db();
+    let arc = test_db.to_db();
+
+    let mut td = fixtures(arc.clone(), 10);
+    let base_hash = td[0].base_hash.clone();
+
+    for (i, d) in td.iter_mut().enumerate() {
+        d.base_hash = base_hash.clone();
+        d.link_add.base_address = base_hash.clone().into();
+        // Pairs of links share a timestamp, so they are ordered by hash.
+        d.link_add.timestamp = Timestamp::from_micros(1000 + (i / 2) as i64);
+
+        // Create the new hash
+        let link_add_hash = ActionHash::with_data_sync(&Action::CreateLink(d.link_add.clone()));
+        d.expected_link.create_link_hash = link_add_hash.clone();
+        d.expected_link.base = d.link_add.base_address.clone();
+        d.expected_link.timestamp = d.link_add.timestamp;
+        d.link_remove.link_add_address = link_add_hash;
+        d.link_remove.base_address = base_hash.clone().into();
+    }
+    for d in &td {
+        d.add_link().await;
+    }
+    // A deleted link is skipped by paging
+    td[3].delete_link().await;
+
+    let mut expected = td
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != 3)
+        .map(|(_, d)| d.expected_link.clone())
+        .collect::<Vec<_>>();
+    expected.sort_by_key(|l| (l.timestamp, l.create_link_hash.clone()));
+
+    let type_filter = LinkTypeFilter::Dependencies(td.iter().map(|d| d.zome_index).collect());
+    let mut pages = Vec::new();
+    let mut cursor = None;
+    loop {
+        let query = GetLinksQuery::new(
+            base_hash.clone().into(),
+            type_filter.clone(),
+            None,
+            GetLinksFilter {
+                cursor: cursor.clone(),
+                limit: Some(4),
+                ..Default::default()
+            },
+        );
+        let page = arc
+            .read_async(move |txn| -> StateQueryResult<Vec<Link>> { query.run(Txn::from(&txn)) })
+            .await
+            .unwrap();
+        assert!(page.len() <= 4);
+        match page.last() {
+            Some(last) => cursor = Some(last.cursor()),
+            None => break,
+        }
+        pages.push(page);
+    }
+
+    // The deleted link doesn't take up room in a page.
+    assert_eq!(
+        vec![4, 4, 1],
+        pages.iter().map(Vec::len).collect::<Vec<_>>()
+    );
+    assert_eq!(expected, pages.concat());
+}
@@ -70,3 +70,107 @@ pub fn live_scheduled_fns(
     }
     Ok(ret)
 }
+
+/// The schedule of a scheduled function of this author, or `None` if the
+/// function is not scheduled.
+pub fn get_schedule(
+    txn: &Transaction,
+    scheduled_fn: &ScheduledFn,
+    author: &AgentPubKey,
+) -> StateMutationResult<Option<Option<Schedule>>> {
+    let maybe_schedule = txn
+        .query_row(
+            "
+            SELECT maybe_schedule
+            FROM ScheduledFunctions
+            WHERE
+            zome_name=:zome_name
+            AND scheduled_fn=:scheduled_fn
+            AND author = :author
+            ",
+            named_params! {
+                ":zome_name": scheduled_fn.zome_name().to_string(),
+                ":scheduled_fn": scheduled_fn.fn_name().to_string(),
+                ":author": author,
+            },
+            |row| row.get::<_, Vec<u8>>(0),
+        )
+        .optional()?;
+    Ok(maybe_schedule.map(from_blob).transpose()?)
+}
+
+/// All functions scheduled by this author, the next due first.
+pub fn scheduled_fns(
+    txn: &Transaction,
+    author: &AgentPubKey,
+) -> StateMutationResult<Vec<ScheduledFnInfo>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT
+        zome_name,
+        scheduled_fn,
+        maybe_schedule,
+        start,
+        ephemeral
+        FROM ScheduledFunctions
+        WHERE
+        author = :author
+        ORDER BY start ASC",
+    )?;
+    let rows = stmt.query_map(
+        named_params! {
+            ":author": author,
+        },
+        |row| {
+            Ok((
+                ZomeName(row.get::<_, String>(0)?.into()),
+                FunctionName(row.get(1)?),
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            ))
+        },
+    )?;
+    let mut ret = vec![];
+    for row in rows {
+        let (zome_name, fn_name, maybe_schedule_serialized, next_run, ephemeral) = row?;
+        ret.push(ScheduledFnInfo {
+            zome_name,
+            fn_name,
+            schedule: from_blob(maybe_schedule_serialized)?,
+            next_run,
+            ephemeral,
+        });
+    }
+    Ok(ret)
+}
+
+/// The most recent runs of the scheduled functions of this author, newest first.
+/// Only the runs of a zome, or of a function of that zome, are returned if given.
+pub fn scheduled_fn_runs(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    zome_name: Option<&ZomeName>,
+    fn_name: Option<&FunctionName>,
+    limit: u32,
+) -> StateMutationResult<Vec<ScheduledFnRun>> {
+    let mut stmt = txn.prepare(holochain_sqlite::sql::sql_cell::schedule::RUNS)?;
+    let rows = stmt.query_map(
+        named_params! {
+            ":author": author,
+            ":zome_name": zome_name.map(|z| z.to_string()),
+            ":scheduled_fn": fn_name.map(|f| f.to_string()),
+            ":limit": limit,
+        },
+        |row| {
+            Ok(ScheduledFnRun {
+                zome_name: ZomeName(row.get::<_, String>(0)?.into()),
+                fn_name: FunctionName(row.get(1)?),
+                started_at: row.get(2)?,
+                duration: std::time::Duration::from_micros(row.get::<_, i64>(3)?.max(0) as u64),
+                error: row.get(4)?,
+            })
+        },
+    )?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
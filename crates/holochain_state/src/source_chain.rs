@@ -184,6 +184,28 @@ impl SourceChain {
         chain_top_ordering: ChainTopOrdering,
         weight: W,
     ) -> SourceChainResult<ActionHash> {
+        let common = self.next_action_common()?;
+        self.put_with_action(
+            action_builder.build(common).weighed(weight).into(),
+            maybe_entry,
+            chain_top_ordering,
+        )
+        .await
+    }
+
+    /// Build the unweighed action which would be put next onto the source chain
+    /// using this ActionBuilder, without putting it.
+    /// This is the input for weighing an action before it is put with
+    /// [`SourceChain::put_weighed`].
+    pub fn preview_unweighed<U: ActionUnweighed, B: ActionBuilder<U>>(
+        &self,
+        action_builder: B,
+    ) -> SourceChainResult<U> {
+        Ok(action_builder.build(self.next_action_common()?))
+    }
+
+    /// The common action fields for the next action on this chain.
+    fn next_action_common(&self) -> SourceChainResult<ActionBuilderCommon> {
         let HeadInfo {
             action: prev_action,
             seq: chain_head_seq,
@@ -191,8 +213,7 @@ impl SourceChain {
         } = self.chain_head_nonempty()?;
         let action_seq = chain_head_seq + 1;
 
-        // Build the action.
-        let common = ActionBuilderCommon {
+        Ok(ActionBuilderCommon {
             author: (*self.author).clone(),
             // If the current time is equal to the current chain head timestamp,
             // or even has drifted to be before it, just set the next timestamp
@@ -207,13 +228,7 @@ impl SourceChain {
             ),
             action_seq,
             prev_action,
-        };
-        self.put_with_action(
-            action_builder.build(common).weighed(weight).into(),
-            maybe_entry,
-            chain_top_ordering,
-        )
-        .await
+        })
     }
 
     // TODO: when we fully hook up rate limiting, make this test-only
@@ -34,9 +34,12 @@ impl AppClient {
 
         let rx = tokio::task::spawn(async move { while rx.recv::<AppResponse>().await.is_ok() {} });
 
-        tx.authenticate(AppAuthenticationRequest { token })
-            .await
-            .context("Failed to authenticate app client")?;
+        tx.authenticate(AppAuthenticationRequest {
+            token,
+            resume_signals_from: None,
+        })
+        .await
+        .context("Failed to authenticate app client")?;
 
         Ok(AppClient { tx, rx })
     }
@@ -173,6 +176,10 @@ impl AdminClient {
             port: Some(port),
             allowed_origins: HC_TERM_ORIGIN.to_string().into(),
             installed_app_id: None,
+            socket_path: None,
+            socket_mode: None,
+            tls: None,
+            signal_replay_buffer_size: None,
         };
         let response = self.send(msg).await?;
         match response {
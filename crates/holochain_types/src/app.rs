@@ -150,6 +150,29 @@ pub struct InstallAppPayload {
     pub ignore_genesis_failure: bool,
 }
 
+/// The hash of an [AppBundle] in the conductor's app store, along with an [AgentPubKey]
+/// and optional [InstalledAppId]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct InstallAppFromStorePayload {
+    /// The hash of the app bundle to fetch from the app store.
+    pub app_hash: AppHash,
+
+    /// The agent to use when creating Cells for this App.
+    pub agent_key: AgentPubKey,
+
+    /// The unique identifier for an installed app in this conductor.
+    /// If not specified, it will be derived from the app name in the bundle manifest.
+    pub installed_app_id: Option<InstalledAppId>,
+
+    /// Include proof-of-membrane-membership data for cells that require it,
+    /// keyed by the RoleName specified in the app bundle manifest.
+    #[serde(default)]
+    pub membrane_proofs: HashMap<RoleName, MembraneProof>,
+
+    /// Optional: overwrites all network seeds for all DNAs of Cells created by this app.
+    pub network_seed: Option<NetworkSeed>,
+}
+
 /// The possible locations of an AppBundle
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -158,8 +181,14 @@ pub enum AppBundleSource {
     Bundle(AppBundle),
     /// A local file path
     Path(PathBuf),
-    // /// A URL
-    // Url(String),
+    /// A URL to download the bundle from. The downloaded bundle is only
+    /// used if it has the given hash.
+    Url {
+        /// The URL of the `.happ` file
+        url: String,
+        /// The expected hash of the `.happ` file
+        app_hash: AppHash,
+    },
 }
 
 impl AppBundleSource {
@@ -168,7 +197,9 @@ impl AppBundleSource {
         Ok(match self {
             Self::Bundle(bundle) => bundle,
             Self::Path(path) => AppBundle::decode(&ffs::read(&path).await?)?,
-            // Self::Url(url) => todo!("reqwest::get"),
+            Self::Url { url, app_hash } => {
+                AppBundle::decode_checked(&app_hash, &mr_bundle::resolve_remote(&url).await?)?
+            }
         })
     }
 }
@@ -448,6 +479,33 @@ impl InstalledAppCommon {
             .filter_map(|(role_name, role)| role.provisioned_cell().map(|c| (role_name, c)))
     }
 
+    /// The provisioned cells which were created for this app,
+    /// as opposed to the cells of other apps it uses.
+    pub fn owned_cells(&self) -> impl Iterator<Item = &CellId> {
+        self.role_assignments
+            .values()
+            .filter(|role| !role.is_existing)
+            .filter_map(|role| role.provisioned_cell())
+    }
+
+    /// The provisioned cells of this app which other apps may use, because their
+    /// roles are listed in the `shared_roles` of the app's manifest.
+    pub fn shared_cells(&self) -> impl Iterator<Item = &CellId> {
+        let shared_roles = self.manifest.shared_roles();
+        self.role_assignments
+            .iter()
+            .filter(move |(role_name, role)| !role.is_existing && shared_roles.contains(role_name))
+            .filter_map(|(_, role)| role.provisioned_cell())
+    }
+
+    /// The provisioned cells of other apps which this app uses.
+    pub fn existing_cells(&self) -> impl Iterator<Item = &CellId> {
+        self.role_assignments
+            .values()
+            .filter(|role| role.is_existing)
+            .filter_map(|role| role.provisioned_cell())
+    }
+
     /// Accessor
     pub fn into_provisioned_cells(self) -> impl Iterator<Item = (RoleName, CellId)> {
         self.role_assignments
@@ -575,6 +633,19 @@ impl InstalledAppCommon {
         Ok(clone_id)
     }
 
+    /// Point a role at a new DNA for the same agent, as part of migrating the
+    /// agent to that DNA. Clone cells of the role are left untouched.
+    ///
+    /// Returns the cell id the role was previously assigned to.
+    pub fn migrate_role(&mut self, role_name: &RoleName, dna_hash: DnaHash) -> AppResult<CellId> {
+        let app_role_assignment = self.role_mut(role_name)?;
+        let new_cell_id = CellId::new(dna_hash, app_role_assignment.agent_key().clone());
+        Ok(std::mem::replace(
+            &mut app_role_assignment.base_cell_id,
+            new_cell_id,
+        ))
+    }
+
     /// Get a clone cell id from its clone id.
     pub fn get_clone_cell_id(&self, clone_cell_id: &CloneCellId) -> AppResult<CellId> {
         let cell_id = match clone_cell_id {
@@ -766,6 +837,7 @@ impl InstalledAppCommon {
                 let role = AppRoleAssignment {
                     base_cell_id: cell_id,
                     is_provisioned: true,
+                    is_existing: false,
                     clones: HashMap::new(),
                     clone_limit: 256,
                     next_clone_index: 0,
@@ -1006,6 +1078,11 @@ pub struct AppRoleAssignment {
     /// If false, then `base_cell_id` is just recording what that cell will be
     /// called in the future.
     is_provisioned: bool,
+    /// Records whether the base cell was created by another app and is
+    /// shared with this one, because of the `UseExisting` or `CreateIfNotExists`
+    /// provisioning strategy.
+    #[serde(default)]
+    is_existing: bool,
     /// The number of allowed clone cells.
     clone_limit: u32,
     /// The index of the next clone cell to be created.
@@ -1025,6 +1102,7 @@ impl AppRoleAssignment {
         Self {
             base_cell_id,
             is_provisioned,
+            is_existing: false,
             clone_limit,
             clones: HashMap::new(),
             next_clone_index: 0,
@@ -1032,6 +1110,15 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Constructor for a role whose base cell was created by another app.
+    /// List of clones always starts empty.
+    pub fn new_existing(base_cell_id: CellId, clone_limit: u32) -> Self {
+        Self {
+            is_existing: true,
+            ..Self::new(base_cell_id, true, clone_limit)
+        }
+    }
+
     /// Accessor
     pub fn cell_id(&self) -> &CellId {
         &self.base_cell_id
@@ -1056,6 +1143,11 @@ impl AppRoleAssignment {
         }
     }
 
+    /// Accessor
+    pub fn is_existing(&self) -> bool {
+        self.is_existing
+    }
+
     /// Accessor
     pub fn clone_ids(&self) -> impl Iterator<Item = &CloneId> {
         self.clones.keys()
@@ -1191,4 +1283,39 @@ mod tests {
         // Assert the deleted cell cannot be enabled
         assert!(app.enable_clone_cell(&clone_id_0).is_err());
     }
+
+    #[test]
+    fn migrate_role_keeps_agent_and_clones() {
+        let base_cell_id = fixt!(CellId);
+        let agent = base_cell_id.agent_pubkey().clone();
+        let clone_cell_id = CellId::new(fixt!(DnaHash), agent.clone());
+        let role = AppRoleAssignment::new(base_cell_id.clone(), true, 1);
+        let role_name: RoleName = "role_name".into();
+        let manifest = AppManifest::arbitrary(&mut unstructured_noise()).unwrap();
+        let mut app: RunningApp = InstalledAppCommon::new(
+            "app",
+            agent.clone(),
+            vec![(role_name.clone(), role)],
+            manifest,
+        )
+        .unwrap()
+        .into();
+        app.add_clone(&role_name, &clone_cell_id).unwrap();
+
+        let new_dna_hash = fixt!(DnaHash);
+        let old_cell_id = app.migrate_role(&role_name, new_dna_hash.clone()).unwrap();
+        assert_eq!(old_cell_id, base_cell_id);
+
+        let role = app.role(&role_name).unwrap();
+        assert_eq!(role.cell_id(), &CellId::new(new_dna_hash, agent));
+        assert_eq!(
+            app.clone_cell_ids().collect::<Vec<_>>(),
+            vec![&clone_cell_id]
+        );
+
+        matches::assert_matches!(
+            app.migrate_role(&"missing".into(), fixt!(DnaHash)),
+            Err(AppError::RoleNameMissing(_))
+        );
+    }
 }
@@ -1,6 +1,10 @@
 //! An App Bundle is an AppManifest bundled together with DNA bundles.
 
-use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use super::{AppManifest, AppManifestValidated};
 use crate::prelude::*;
@@ -13,6 +17,37 @@ use futures::future::join_all;
 #[cfg(test)]
 mod tests;
 
+/// The hash of an encoded [`AppBundle`], used to identify bundles in an app store.
+///
+/// This is the 32 byte blake2b hash of the bytes produced by encoding the bundle,
+/// i.e. the hash of the contents of a `.happ` file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AppHash(#[serde(with = "serde_bytes")] Vec<u8>);
+
+impl AppHash {
+    /// Hash the encoded bytes of an app bundle
+    pub fn from_bundle_bytes(bytes: &[u8]) -> Self {
+        Self(holo_hash::encode::blake2b_256(bytes))
+    }
+
+    /// Construct from the raw 32 hash bytes
+    pub fn from_raw_32(bytes: Vec<u8>) -> Self {
+        holo_hash::assert_length!(32, &bytes);
+        Self(bytes)
+    }
+
+    /// Get the raw 32 hash bytes
+    pub fn get_raw_32(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for AppHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", holo_hash::encode::holo_hash_encode(&self.0))
+    }
+}
+
 /// A bundle of an AppManifest and collection of DNAs
 #[derive(Debug, Serialize, Deserialize, derive_more::From, shrinkwraprs::Shrinkwrap)]
 pub struct AppBundle(mr_bundle::Bundle<AppManifest>);
@@ -40,6 +75,23 @@ impl AppBundle {
             .map_err(Into::into)
     }
 
+    /// Construct from raw bytes, which must have the given [`AppHash`]
+    pub fn decode_checked(app_hash: &AppHash, bytes: &[u8]) -> AppBundleResult<Self> {
+        let actual = AppHash::from_bundle_bytes(bytes);
+        if actual != *app_hash {
+            return Err(AppBundleError::AppHashMismatch {
+                expected: app_hash.clone(),
+                actual,
+            });
+        }
+        Self::decode(bytes)
+    }
+
+    /// Compute the [`AppHash`] of this bundle, i.e. the hash of its encoded bytes
+    pub fn app_hash(&self) -> AppBundleResult<AppHash> {
+        Ok(AppHash::from_bundle_bytes(&self.encode()?))
+    }
+
     /// Convert to the inner Bundle
     pub fn into_inner(self) -> mr_bundle::Bundle<AppManifest> {
         self.0
@@ -58,20 +110,30 @@ impl AppBundle {
 
     /// Given a partial list of already available DnaFiles, fetch the missing others via
     /// mr_bundle::Location resolution
+    ///
+    /// Roles which use existing cells can only be bound to one of the given
+    /// `shared_cells`, which other apps allow to be used by listing their roles in
+    /// the `shared_roles` of their manifests, and only to a cell of the given agent.
     pub async fn resolve_cells(
         self,
         dna_store: &impl DnaStore,
         agent: AgentPubKey,
         membrane_proofs: HashMap<RoleName, MembraneProof>,
+        shared_cells: &HashSet<CellId>,
     ) -> AppBundleResult<AppRoleResolution> {
         let AppManifestValidated { name: _, roles } = self.manifest().clone().validate()?;
         let bundle = Arc::new(self);
-        let tasks = roles.into_iter().map(|(role_name, role)| async {
+        let tasks = roles.into_iter().map(|(role_name, role)| {
+            let agent = agent.clone();
             let bundle = bundle.clone();
-            Ok((
-                role_name.clone(),
-                bundle.resolve_cell(dna_store, role_name, role).await?,
-            ))
+            async move {
+                Ok((
+                    role_name.clone(),
+                    bundle
+                        .resolve_cell(dna_store, shared_cells, agent, role_name, role)
+                        .await?,
+                ))
+            }
         });
         let resolution = futures::future::join_all(tasks)
             .await
@@ -94,7 +156,7 @@ impl AppBundle {
                         }
 
                         CellProvisioningOp::Existing(cell_id, clone_limit) => {
-                            let role = AppRoleAssignment::new(cell_id, true, clone_limit);
+                            let role = AppRoleAssignment::new_existing(cell_id, clone_limit);
                             resolution.role_assignments.push((role_name, role));
                         }
                         CellProvisioningOp::ProvisionOnly(dna, clone_limit) => {
@@ -131,6 +193,8 @@ impl AppBundle {
     async fn resolve_cell(
         &self,
         dna_store: &impl DnaStore,
+        shared_cells: &HashSet<CellId>,
+        agent: AgentPubKey,
         role_name: RoleName,
         role: AppRoleManifestValidated,
     ) -> AppBundleResult<CellProvisioningOp> {
@@ -158,37 +222,44 @@ impl AppBundle {
                 installed_hash,
                 clone_limit,
                 deferred: _,
-            } => self.resolve_cell_existing(&installed_hash, clone_limit),
+            } => {
+                // Only the cells of the agent this app is installed for can be used,
+                // and only if the app which created the cell shares its role. The
+                // providing app can't be uninstalled while the cell is in use, see
+                // `ConductorState::dependent_apps`.
+                let cell_id = CellId::new(installed_hash.into(), agent);
+                if !shared_cells.contains(&cell_id) {
+                    return Err(AppBundleError::CellResolutionFailure(
+                        role_name,
+                        format!("No existing shared cell to use: {}", cell_id),
+                    ));
+                }
+                CellProvisioningOp::Existing(cell_id, clone_limit)
+            }
             AppRoleManifestValidated::CreateIfNotExists {
                 location,
                 installed_hash,
                 clone_limit,
                 modifiers,
                 deferred: _,
-            } => match self.resolve_cell_existing(&installed_hash, clone_limit) {
-                op @ CellProvisioningOp::Existing(_, _) => op,
-                CellProvisioningOp::HashMismatch(_, _) => {
-                    let dna = self
-                        .resolve_dna(
-                            role_name,
-                            dna_store,
-                            &location,
-                            Some(&installed_hash),
-                            modifiers,
-                        )
-                        .await?;
+            } => {
+                let dna = self
+                    .resolve_dna(
+                        role_name,
+                        dna_store,
+                        &location,
+                        Some(&installed_hash),
+                        modifiers,
+                    )
+                    .await?;
+                // The cell exists if it was created from the same DNA with the same modifiers
+                let cell_id = CellId::new(dna.dna_hash().clone(), agent);
+                if shared_cells.contains(&cell_id) {
+                    CellProvisioningOp::Existing(cell_id, clone_limit)
+                } else {
                     CellProvisioningOp::CreateFromDnaFile(dna, clone_limit)
                 }
-                CellProvisioningOp::Conflict(_) => {
-                    unimplemented!("conflicts are not handled, or even possible yet")
-                }
-                CellProvisioningOp::CreateFromDnaFile(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a Create op")
-                }
-                CellProvisioningOp::ProvisionOnly(_, _) => {
-                    unreachable!("resolve_cell_existing will never return a ProvisionOnly")
-                }
-            },
+            }
             AppRoleManifestValidated::CloneOnly {
                 clone_limit,
                 location,
@@ -240,14 +311,6 @@ impl AppBundle {
         Ok(dna_file)
     }
 
-    fn resolve_cell_existing(
-        &self,
-        _version: &DnaHashB64,
-        _clone_limit: u32,
-    ) -> CellProvisioningOp {
-        unimplemented!("Reusing existing cells is not yet implemented")
-    }
-
     async fn resolve_location(
         &self,
         location: &mr_bundle::Location,
@@ -260,12 +323,6 @@ impl AppBundle {
     }
 }
 
-/// This function is called in places where it will be necessary to rework that
-/// area after use_existing has been implemented
-pub fn we_must_remember_to_rework_cell_panic_handling_after_implementing_use_existing_cell_resolution(
-) {
-}
-
 /// The answer to the question:
 /// "how do we concretely assign DNAs to the open roles of this App?"
 /// Includes the DNAs selected to fill the roles and the details of the role assignments.
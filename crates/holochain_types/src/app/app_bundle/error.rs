@@ -1,7 +1,7 @@
 use holochain_util::ffs;
 use mr_bundle::error::MrBundleError;
 
-use crate::prelude::{AppManifestError, DnaError, RoleName};
+use crate::prelude::{AppHash, AppManifestError, DnaError, RoleName};
 
 /// Errors occurring while installing an AppBundle
 #[derive(thiserror::Error, Debug)]
@@ -9,6 +9,9 @@ pub enum AppBundleError {
     #[error("Could not resolve the app role '{0}'. Detail: {1}")]
     CellResolutionFailure(RoleName, String),
 
+    #[error("The app bundle has the hash {actual}, but {expected} was expected")]
+    AppHashMismatch { expected: AppHash, actual: AppHash },
+
     #[error(transparent)]
     AppManifestError(#[from] AppManifestError),
 
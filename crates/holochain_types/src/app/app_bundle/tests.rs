@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::prelude::*;
@@ -34,6 +35,17 @@ async fn app_bundle_fixture(modifiers: DnaModifiersOpt<YamlProperties>) -> (AppB
     (bundle, dna1)
 }
 
+/// Change the provisioning strategy of the single role of the bundle fixture
+fn with_provisioning(bundle: AppBundle, provisioning: CellProvisioning) -> AppBundle {
+    let AppManifest::V1(mut manifest) = bundle.manifest().clone();
+    manifest.roles[0].provisioning = Some(provisioning);
+    bundle
+        .into_inner()
+        .update_manifest(manifest.into())
+        .unwrap()
+        .into()
+}
+
 /// Test that an app with a single Created cell can be provisioned
 #[tokio::test]
 async fn provisioning_1_create() {
@@ -61,6 +73,7 @@ async fn provisioning_1_create() {
             &std::collections::HashMap::new(),
             agent.clone(),
             Default::default(),
+            &HashSet::new(),
         )
         .await
         .unwrap();
@@ -76,3 +89,122 @@ async fn provisioning_1_create() {
     };
     assert_eq!(resolution, expected);
 }
+
+/// Test that a role can use an existing cell of the same agent, but not
+/// a cell which doesn't exist
+#[tokio::test]
+async fn provisioning_1_use_existing() {
+    holochain_trace::test_run();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+    let bundle = with_provisioning(bundle, CellProvisioning::UseExisting { deferred: false });
+    let copy = AppBundle::decode(&bundle.encode().unwrap()).unwrap();
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+
+    let resolution = bundle
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[cell_id.clone()].into(),
+        )
+        .await
+        .unwrap();
+
+    let expected = AppRoleResolution {
+        agent,
+        dnas_to_register: vec![],
+        role_assignments: vec![(
+            "role_name".into(),
+            AppRoleAssignment::new_existing(cell_id.clone(), 50),
+        )],
+    };
+    assert_eq!(resolution, expected);
+    assert!(resolution.cells_to_create().is_empty());
+
+    // The cell of another agent can't be used
+    let result = copy
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            fixt!(AgentPubKey),
+            Default::default(),
+            &[cell_id].into(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(AppBundleError::CellResolutionFailure(_, _))
+    ));
+}
+
+/// Test that a role only creates a cell if it doesn't exist yet
+#[tokio::test]
+async fn provisioning_1_create_if_not_exists() {
+    holochain_trace::test_run();
+    let agent = fixt!(AgentPubKey);
+    let (bundle, dna) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+    let bundle = with_provisioning(
+        bundle,
+        CellProvisioning::CreateIfNotExists { deferred: false },
+    );
+    let copy = AppBundle::decode(&bundle.encode().unwrap()).unwrap();
+    let cell_id = CellId::new(dna.dna_hash().to_owned(), agent.clone());
+
+    let resolution = bundle
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &HashSet::new(),
+        )
+        .await
+        .unwrap();
+    let expected = AppRoleResolution {
+        agent: agent.clone(),
+        dnas_to_register: vec![(dna, None)],
+        role_assignments: vec![(
+            "role_name".into(),
+            AppRoleAssignment::new(cell_id.clone(), true, 50),
+        )],
+    };
+    assert_eq!(resolution, expected);
+
+    let resolution = copy
+        .resolve_cells(
+            &std::collections::HashMap::new(),
+            agent.clone(),
+            Default::default(),
+            &[cell_id.clone()].into(),
+        )
+        .await
+        .unwrap();
+    let expected = AppRoleResolution {
+        agent,
+        dnas_to_register: vec![],
+        role_assignments: vec![(
+            "role_name".into(),
+            AppRoleAssignment::new_existing(cell_id, 50),
+        )],
+    };
+    assert_eq!(resolution, expected);
+}
+
+#[tokio::test]
+async fn decode_checked_verifies_app_hash() {
+    let (bundle, _) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+    let bytes = bundle.encode().unwrap();
+    let app_hash = AppHash::from_bundle_bytes(&bytes);
+    assert_eq!(
+        AppBundle::decode_checked(&app_hash, &bytes)
+            .unwrap()
+            .manifest(),
+        bundle.manifest()
+    );
+
+    let wrong_hash = AppHash::from_bundle_bytes(&[]);
+    assert!(matches!(
+        AppBundle::decode_checked(&wrong_hash, &bytes),
+        Err(AppBundleError::AppHashMismatch { expected, actual })
+            if expected == wrong_hash && actual == app_hash
+    ));
+}
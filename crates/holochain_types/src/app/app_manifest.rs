@@ -75,6 +75,16 @@ impl Manifest for AppManifest {
         }
     }
 
+    fn resource_hash(&self, location: &Location) -> Option<mr_bundle::ResourceHash> {
+        match self {
+            AppManifest::V1(m) => m
+                .roles
+                .iter()
+                .find(|role| role.dna.location.as_ref() == Some(location))
+                .and_then(|role| role.dna.resource_hash),
+        }
+    }
+
     fn path() -> PathBuf {
         "happ.yaml".into()
     }
@@ -92,6 +102,13 @@ impl AppManifest {
         }
     }
 
+    /// Get the roles whose cells other apps may use
+    pub fn shared_roles(&self) -> &[RoleName] {
+        match self {
+            Self::V1(AppManifestV1 { shared_roles, .. }) => shared_roles,
+        }
+    }
+
     /// Convert this human-focused manifest into a validated, concise representation
     pub fn validate(self) -> AppManifestResult<AppManifestValidated> {
         match self {
@@ -127,6 +144,7 @@ impl AppManifest {
                         location: Some(mr_bundle::Location::Bundled(path)),
                         modifiers: Default::default(),
                         installed_hash: Some(cell_id.dna_hash().clone().into()),
+                        resource_hash: None,
                         clone_limit: 256,
                     },
                 }
@@ -137,6 +155,7 @@ impl AppManifest {
             name: "[autogenerated manifest]".into(),
             description: Some("Generated by `fn new_legacy`".into()),
             roles,
+            shared_roles: vec![],
         }
         .into()
     }
@@ -37,6 +37,13 @@ pub struct AppManifestV1 {
 
     /// The roles that need to be filled (by DNAs) for this app.
     pub roles: Vec<AppRoleManifest>,
+
+    /// The roles whose cells other apps installed for the same agent may use,
+    /// with the `UseExisting` or `CreateIfNotExists` provisioning strategy.
+    /// The cells of all other roles are never shared.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    pub shared_roles: Vec<RoleName>,
 }
 
 /// Description of an app "role" defined by this app.
@@ -109,6 +116,15 @@ pub struct AppRoleDnaManifest {
     #[serde(default)]
     pub installed_hash: Option<DnaHashB64>,
 
+    /// The hash of the DNA bundle file at `location`. If specified, the bundle
+    /// is only used if its bytes have this hash, which makes it safe to get
+    /// the DNA from a URL.
+    ///
+    /// This is the SHA-256 hash of the file as a hex string, as printed by e.g.
+    /// `sha256sum`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_hash: Option<mr_bundle::ResourceHash>,
+
     /// Allow up to this many "clones" to be created at runtime.
     /// Default: 0
     #[serde(default)]
@@ -124,6 +140,7 @@ impl AppRoleDnaManifest {
             )),
             modifiers: DnaModifiersOpt::none(),
             installed_hash: None,
+            resource_hash: None,
             clone_limit: 0,
         }
     }
@@ -145,18 +162,18 @@ pub enum CellProvisioning {
     /// Always create a new Cell when installing this App
     Create { deferred: bool },
 
-    /*
-        TODO: implement
-
-        /// Require that a Cell is already installed which matches the DNA installed_hash
-        /// spec, and which has an Agent that's associated with this App's agent
-        /// via DPKI. If no such Cell exists, *app installation fails*.
-        UseExisting { deferred: bool },
+    /// Require that a Cell is already installed by another App, with the DNA
+    /// of the `installed_hash` and this App's agent. The Cell is shared with the
+    /// other App. If no such Cell exists, *app installation fails*.
+    ///
+    /// The other App must allow this by listing the role the Cell was created
+    /// for in its `shared_roles`.
+    UseExisting { deferred: bool },
 
-        /// Try `UseExisting`, and if that fails, fallback to `Create`
-        CreateIfNotExists { deferred: bool },
+    /// Try `UseExisting` with the DNA this role would be created from, and if
+    /// that fails, fallback to `Create`
+    CreateIfNotExists { deferred: bool },
 
-    */
     /// Install or locate the DNA, but never create a Cell for this DNA.
     /// Only allow clones to be created from the DNA specified.
     /// This case requires `clone_limit > 0`, otherwise no Cells will ever be created.
@@ -184,6 +201,8 @@ impl AppManifestV1 {
                 CellProvisioning::Create { .. } | CellProvisioning::CloneOnly => {
                     role.dna.modifiers.network_seed = Some(network_seed.clone());
                 }
+                CellProvisioning::UseExisting { .. }
+                | CellProvisioning::CreateIfNotExists { .. } => {}
             }
         }
     }
@@ -194,7 +213,14 @@ impl AppManifestV1 {
             name,
             roles,
             description: _,
+            shared_roles,
         } = self;
+        if let Some(role_name) = shared_roles
+            .iter()
+            .find(|role_name| !roles.iter().any(|role| role.name == **role_name))
+        {
+            return Err(AppManifestError::UnknownSharedRole(role_name.clone()));
+        }
         let roles = roles
             .into_iter()
             .map(
@@ -208,6 +234,7 @@ impl AppManifestV1 {
                         installed_hash,
                         clone_limit,
                         modifiers,
+                        resource_hash: _,
                     } = dna;
                     let modifiers = modifiers.serialized()?;
                     // Go from "flexible" enum into proper DnaVersionSpec.
@@ -220,28 +247,28 @@ impl AppManifestV1 {
                             modifiers,
                             installed_hash,
                         },
-                        // CellProvisioning::UseExisting { deferred } => {
-                        //     AppRoleManifestValidated::UseExisting {
-                        //         deferred,
-                        //         clone_limit,
-                        //         installed_hash: Self::require(
-                        //             installed_hash,
-                        //             "roles.dna.installed_hash",
-                        //         )?,
-                        //     }
-                        // }
-                        // CellProvisioning::CreateIfNotExists { deferred } => {
-                        //     AppRoleManifestValidated::CreateIfNotExists {
-                        //         deferred,
-                        //         clone_limit,
-                        //         location: Self::require(location, "roles.dna.(path|url)")?,
-                        //         installed_hash: Self::require(
-                        //             installed_hash,
-                        //             "roles.dna.installed_hash",
-                        //         )?,
-                        //         modifiers,
-                        //     }
-                        // }
+                        CellProvisioning::UseExisting { deferred } => {
+                            AppRoleManifestValidated::UseExisting {
+                                deferred,
+                                clone_limit,
+                                installed_hash: Self::require(
+                                    installed_hash,
+                                    "roles.dna.installed_hash",
+                                )?,
+                            }
+                        }
+                        CellProvisioning::CreateIfNotExists { deferred } => {
+                            AppRoleManifestValidated::CreateIfNotExists {
+                                deferred,
+                                clone_limit,
+                                location: Self::require(location, "roles.dna.(path|url)")?,
+                                installed_hash: Self::require(
+                                    installed_hash,
+                                    "roles.dna.installed_hash",
+                                )?,
+                                modifiers,
+                            }
+                        }
                         CellProvisioning::CloneOnly => AppRoleManifestValidated::CloneOnly {
                             clone_limit,
                             location: Self::require(location, "roles.dna.(path|url)")?,
@@ -297,6 +324,7 @@ pub mod tests {
                 location,
                 modifiers,
                 installed_hash: Some(installed_hash.into()),
+                resource_hash: None,
                 clone_limit: 50,
             },
             provisioning: Some(CellProvisioning::Create { deferred: false }),
@@ -305,6 +333,7 @@ pub mod tests {
             name: "Test app".to_string(),
             description: Some("Serialization roundtrip test".to_string()),
             roles,
+            shared_roles: vec!["role_name".into()],
         }
     }
 
@@ -56,15 +56,16 @@ pub enum AppRoleManifestValidated {
         modifiers: DnaModifiersOpt,
         installed_hash: Option<DnaHashB64>,
     },
-    /// Require that a Cell is already installed with a specified DNA hash,
-    /// and which has an Agent that's associated with this App's agent
-    /// via DPKI. If no such Cell exists, *app installation fails*.
+    /// Require that a Cell is already installed by another App, with the
+    /// specified DNA hash and this App's agent.
+    /// If no such Cell exists, *app installation fails*.
     UseExisting {
         clone_limit: u32,
         deferred: bool,
         installed_hash: DnaHashB64,
     },
-    /// Try `UseExisting`, and if that fails, fallback to `Create`
+    /// Try `UseExisting` with the DNA resolved from the location and modifiers,
+    /// and if that fails, fallback to `Create`
     CreateIfNotExists {
         clone_limit: u32,
         deferred: bool,
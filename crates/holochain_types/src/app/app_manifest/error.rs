@@ -12,6 +12,9 @@ pub enum AppManifestError {
     #[error("Invalid manifest for app role '{0}': Using strategy 'clone-only' with clone_limit == 0 is pointless")]
     InvalidStrategyCloneOnly(RoleName),
 
+    #[error("Invalid manifest: shared role '{0}' is not a role of the app")]
+    UnknownSharedRole(RoleName),
+
     #[error(transparent)]
     SerializationError(#[from] SerializedBytesError),
 }
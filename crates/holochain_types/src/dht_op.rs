@@ -430,6 +430,11 @@ impl DhtOp {
                         Some(action_pair.0 .0.clone())
                     }
                 },
+                Warrant::ValidationReceipt(w) => match w {
+                    ValidationReceiptWarrant::InvalidOpReceipted { action, .. } => {
+                        Some(action.0.clone())
+                    }
+                },
             },
         }
     }
@@ -713,6 +718,11 @@ impl DhtOpLite {
                         action_pair.0 .0.clone().into()
                     }
                 },
+                Warrant::ValidationReceipt(w) => match w {
+                    ValidationReceiptWarrant::InvalidOpReceipted { action, .. } => {
+                        action.0.clone().into()
+                    }
+                },
             },
         }
     }
@@ -1311,6 +1321,7 @@ pub enum OpNumericalOrder {
     RegisterAddLink,
     RegisterRemoveLink,
     ChainIntegrityWarrant,
+    ValidationReceiptWarrant,
 }
 
 /// This is used as an index for ordering ops in our database.
@@ -1363,6 +1374,9 @@ impl OpOrder {
             DhtOpType::Warrant(WarrantOpType::ChainIntegrityWarrant) => {
                 OpNumericalOrder::ChainIntegrityWarrant
             }
+            DhtOpType::Warrant(WarrantOpType::ValidationReceiptWarrant) => {
+                OpNumericalOrder::ValidationReceiptWarrant
+            }
         };
         Self { order, timestamp }
     }
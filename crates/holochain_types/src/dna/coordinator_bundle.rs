@@ -29,6 +29,13 @@ impl Manifest for CoordinatorManifest {
             .collect()
     }
 
+    fn resource_hash(&self, location: &mr_bundle::Location) -> Option<mr_bundle::ResourceHash> {
+        self.zomes
+            .iter()
+            .find(|zome| zome.location == *location)
+            .and_then(|zome| zome.resource_hash)
+    }
+
     fn path() -> std::path::PathBuf {
         "coordinators.yaml".into()
     }
@@ -45,18 +52,21 @@ impl CoordinatorBundle {
         let coordinator = hash_bytes(self.manifest().zomes.iter().cloned(), &mut resources).await?;
         let coordinator_zomes = coordinator
             .iter()
-            .map(|(zome_name, hash, _, dependencies, preserialized_path)| {
-                let zome_def = ZomeDef::Wasm(WasmZome {
-                    wasm_hash: hash.clone(),
-                    dependencies: dependencies.clone(),
-                    preserialized_path: preserialized_path.clone(),
-                });
-                (zome_name.clone(), zome_def.into())
-            })
+            .map(
+                |(zome_name, hash, _, dependencies, preserialized_path, post_commit_retry)| {
+                    let zome_def = ZomeDef::Wasm(WasmZome {
+                        wasm_hash: hash.clone(),
+                        dependencies: dependencies.clone(),
+                        preserialized_path: preserialized_path.clone(),
+                        post_commit_retry: *post_commit_retry,
+                    });
+                    (zome_name.clone(), zome_def.into())
+                },
+            )
             .collect();
         let wasms = coordinator
             .into_iter()
-            .map(|(_, _, wasm, _, _)| wasm)
+            .map(|(_, _, wasm, _, _, _)| wasm)
             .collect();
 
         Ok((coordinator_zomes, wasms))
@@ -75,30 +75,34 @@ impl DnaBundle {
 
         let integrity_zomes = data[0]
             .iter()
-            .map(|(zome_name, hash, _, dependencies, dylib_path)| {
+            .map(|(zome_name, hash, _, dependencies, dylib_path, _)| {
                 let zome_def = ZomeDef::Wasm(WasmZome {
                     wasm_hash: hash.clone(),
                     dependencies: dependencies.clone(),
                     preserialized_path: dylib_path.clone(),
+                    post_commit_retry: None,
                 });
                 (zome_name.clone(), zome_def.into())
             })
             .collect();
         let coordinator_zomes = data[1]
             .iter()
-            .map(|(zome_name, hash, _, dependencies, dylib_path)| {
-                let zome_def = ZomeDef::Wasm(WasmZome {
-                    wasm_hash: hash.clone(),
-                    dependencies: dependencies.clone(),
-                    preserialized_path: dylib_path.clone(),
-                });
-                (zome_name.clone(), zome_def.into())
-            })
+            .map(
+                |(zome_name, hash, _, dependencies, dylib_path, post_commit_retry)| {
+                    let zome_def = ZomeDef::Wasm(WasmZome {
+                        wasm_hash: hash.clone(),
+                        dependencies: dependencies.clone(),
+                        preserialized_path: dylib_path.clone(),
+                        post_commit_retry: *post_commit_retry,
+                    });
+                    (zome_name.clone(), zome_def.into())
+                },
+            )
             .collect();
         let code: BTreeMap<_, _> = data
             .into_iter()
             .flatten()
-            .map(|(_, hash, wasm, _, _)| (hash, wasm))
+            .map(|(_, hash, wasm, _, _, _)| (hash, wasm))
             .collect();
 
         let wasms = WasmMap::from(code);
@@ -126,9 +130,14 @@ impl DnaBundle {
                         quantum_time: kitsune_p2p_dht::spacetime::STANDARD_QUANTUM_TIME,
                     },
                     integrity_zomes,
+                    rate_limits: manifest.integrity.rate_limits.clone(),
+                    wasm_metering_limit: manifest.integrity.wasm_metering_limit,
                     coordinator_zomes,
                 };
 
+                if let Some(bucket_id) = dna_def.never_draining_rate_limit() {
+                    return Err(DnaError::RateLimitNeverDrains(bucket_id));
+                }
                 let original_hash = DnaHash::with_data_sync(&dna_def);
                 let ddh = DnaDefHashed::from_content_sync(dna_def.update_modifiers(modifiers));
                 Ok((ddh, original_hash))
@@ -168,8 +177,10 @@ impl DnaBundle {
                         name,
                         hash: Some(hash),
                         location: Location::Bundled(PathBuf::from(filename)),
+                        resource_hash: None,
                         dylib: None,
                         dependencies: Some(dependencies),
+                        post_commit_retry: None,
                     }
                 })
             })
@@ -185,6 +196,7 @@ impl DnaBundle {
                     .cloned()
                     .map(|name| ZomeDependency { name })
                     .collect();
+                let post_commit_retry = zome.post_commit_retry().copied();
                 zome.wasm_hash(&name).ok().map(|hash| {
                     let hash = WasmHashB64::from(hash);
                     let filename = format!("{}", hash);
@@ -192,8 +204,10 @@ impl DnaBundle {
                         name,
                         hash: Some(hash),
                         location: Location::Bundled(PathBuf::from(filename)),
+                        resource_hash: None,
                         dylib: None,
                         dependencies: Some(dependencies),
+                        post_commit_retry,
                     }
                 })
             })
@@ -210,6 +224,8 @@ impl DnaBundle {
                 })?),
                 origin_time: dna_def.modifiers.origin_time.into(),
                 zomes: integrity,
+                rate_limits: dna_def.rate_limits,
+                wasm_metering_limit: dna_def.wasm_metering_limit,
             },
             coordinator: CoordinatorManifest { zomes: coordinator },
         }
@@ -217,10 +233,20 @@ impl DnaBundle {
     }
 }
 
+/// A zome of a manifest with its wasm and the hash of the wasm.
+pub(super) type HashedZome = (
+    ZomeName,
+    WasmHash,
+    DnaWasm,
+    Vec<ZomeName>,
+    Option<PathBuf>,
+    Option<PostCommitRetryPolicy>,
+);
+
 pub(super) async fn hash_bytes(
     zomes: impl Iterator<Item = ZomeManifest>,
     resources: &mut HashMap<Location, ResourceBytes>,
-) -> DnaResult<Vec<(ZomeName, WasmHash, DnaWasm, Vec<ZomeName>, Option<PathBuf>)>> {
+) -> DnaResult<Vec<HashedZome>> {
     let iter = zomes.map(|z| {
         let bytes = resources
             .remove(&z.location)
@@ -232,6 +258,7 @@ pub(super) async fn hash_bytes(
             deps.into_iter().map(|d| d.name).collect()
         });
         let dylib_path = z.dylib;
+        let post_commit_retry = z.post_commit_retry;
         async move {
             let hash = wasm.to_hash().await;
             if let Some(expected) = expected_hash {
@@ -239,7 +266,14 @@ pub(super) async fn hash_bytes(
                     return Err(DnaError::WasmHashMismatch(expected, hash));
                 }
             }
-            DnaResult::Ok((zome_name, hash, wasm, dependencies, dylib_path))
+            DnaResult::Ok((
+                zome_name,
+                hash,
+                wasm,
+                dependencies,
+                dylib_path,
+                post_commit_retry,
+            ))
         }
     });
     futures::stream::iter(iter)
@@ -275,18 +309,24 @@ mod tests {
                         name: "zome1".into(),
                         hash: None,
                         location: mr_bundle::Location::Bundled(path1.clone()),
+                        resource_hash: None,
                         dylib: None,
                         dependencies: Default::default(),
+                        post_commit_retry: None,
                     },
                     ZomeManifest {
                         name: "zome2".into(),
                         // Intentional wrong hash
                         hash: Some(hash1.clone().into()),
                         location: mr_bundle::Location::Bundled(path2.clone()),
+                        resource_hash: None,
                         dylib: None,
                         dependencies: Default::default(),
+                        post_commit_retry: None,
                     },
                 ],
+                rate_limits: Default::default(),
+                wasm_metering_limit: None,
             },
             coordinator: CoordinatorManifest { zomes: vec![] },
         };
@@ -347,4 +387,97 @@ mod tests {
             SerializedBytes::try_from(properties).unwrap()
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn zome_resource_hashes_are_checked() {
+        let path = PathBuf::from("1");
+        let wasm = vec![1, 2, 3];
+        let bundle = |resource_hash| -> DnaBundle {
+            let manifest = DnaManifestCurrent {
+                name: "name".into(),
+                integrity: IntegrityManifest {
+                    network_seed: None,
+                    properties: None,
+                    origin_time: Timestamp::HOLOCHAIN_EPOCH.into(),
+                    zomes: vec![ZomeManifest {
+                        name: "zome1".into(),
+                        hash: None,
+                        location: mr_bundle::Location::Bundled(path.clone()),
+                        resource_hash: Some(resource_hash),
+                        dylib: None,
+                        dependencies: Default::default(),
+                        post_commit_retry: None,
+                    }],
+                    rate_limits: Default::default(),
+                    wasm_metering_limit: None,
+                },
+                coordinator: CoordinatorManifest { zomes: vec![] },
+            };
+            mr_bundle::Bundle::new_unchecked(
+                manifest.try_into().unwrap(),
+                vec![(path.clone(), wasm.clone().into())],
+            )
+            .unwrap()
+            .into()
+        };
+
+        let wrong_hash = mr_bundle::ResourceHash::of(&[4, 5, 6]);
+        matches::assert_matches!(
+            bundle(wrong_hash).into_dna_file(DnaModifiersOpt::none()).await,
+            Err(DnaError::MrBundleError(mr_bundle::error::MrBundleError::BundleError(
+                mr_bundle::error::BundleError::ResourceHashMismatch { expected, .. }
+            ))) if expected == wrong_hash
+        );
+
+        let dna_file = bundle(mr_bundle::ResourceHash::of(&wasm))
+            .into_dna_file(DnaModifiersOpt::none())
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(dna_file.code().len(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rate_limits_must_drain() {
+        let path = PathBuf::from("1");
+        let wasm = vec![1, 2, 3];
+        let manifest = DnaManifestCurrent {
+            name: "name".into(),
+            integrity: IntegrityManifest {
+                network_seed: None,
+                properties: None,
+                origin_time: Timestamp::HOLOCHAIN_EPOCH.into(),
+                zomes: vec![ZomeManifest {
+                    name: "zome1".into(),
+                    hash: None,
+                    location: mr_bundle::Location::Bundled(path.clone()),
+                    resource_hash: None,
+                    dylib: None,
+                    dependencies: Default::default(),
+                    post_commit_retry: None,
+                }],
+                rate_limits: [(
+                    3,
+                    RateLimit {
+                        capacity: 10,
+                        drain_amount: 0,
+                        drain_interval_ms: 1000,
+                    },
+                )]
+                .into(),
+                wasm_metering_limit: None,
+            },
+            coordinator: CoordinatorManifest { zomes: vec![] },
+        };
+        let bundle: DnaBundle = mr_bundle::Bundle::new_unchecked(
+            manifest.try_into().unwrap(),
+            vec![(path, wasm.into())],
+        )
+        .unwrap()
+        .into();
+        matches::assert_matches!(
+            bundle.into_dna_file(DnaModifiersOpt::none()).await,
+            Err(DnaError::RateLimitNeverDrains(3))
+        );
+    }
 }
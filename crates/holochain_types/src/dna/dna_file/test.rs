@@ -35,6 +35,7 @@ async fn test_update_coordinators() {
                 wasm_hash: WasmHash::with_data(&dna_wasms[2]).await,
                 dependencies: vec!["b".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
         (
@@ -43,6 +44,7 @@ async fn test_update_coordinators() {
                 wasm_hash: WasmHash::with_data(&dna_wasms[3]).await,
                 dependencies: vec!["b".into(), "a".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
     ];
@@ -68,6 +70,7 @@ async fn test_update_coordinators() {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["b".into()],
             preserialized_path: None,
+            post_commit_retry: None,
         })),
     )];
     let old_wasm = dna
@@ -100,6 +103,7 @@ async fn test_update_coordinators() {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["a".into()],
             preserialized_path: None,
+            post_commit_retry: None,
         })),
     )];
     let old_wasm = dna
@@ -140,6 +144,7 @@ async fn test_update_coordinators() {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
                 dependencies: vec!["a".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
         (
@@ -148,6 +153,7 @@ async fn test_update_coordinators() {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[1]).await,
                 dependencies: vec!["a".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
         (
@@ -156,6 +162,7 @@ async fn test_update_coordinators() {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[2]).await,
                 dependencies: vec!["a".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
         (
@@ -164,6 +171,7 @@ async fn test_update_coordinators() {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[3]).await,
                 dependencies: vec!["a".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
     ];
@@ -236,6 +244,7 @@ async fn test_update_coordinators_checks_deps() {
                 wasm_hash: WasmHash::with_data(&dna_wasms[2]).await,
                 dependencies: vec!["b".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
         (
@@ -244,6 +253,7 @@ async fn test_update_coordinators_checks_deps() {
                 wasm_hash: WasmHash::with_data(&dna_wasms[3]).await,
                 dependencies: vec!["b".into(), "a".into()],
                 preserialized_path: None,
+                post_commit_retry: None,
             })),
         ),
     ];
@@ -267,6 +277,7 @@ async fn test_update_coordinators_checks_deps() {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["z".into()],
             preserialized_path: None,
+            post_commit_retry: None,
         })),
     )];
     let err = dna
@@ -286,6 +297,7 @@ async fn test_update_coordinators_checks_deps() {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["z".into()],
             preserialized_path: None,
+            post_commit_retry: None,
         })),
     )];
     let err = dna
@@ -25,6 +25,12 @@ use serde_with::serde_as;
 ///       bundled: ../dna1/zomes/zome1.wasm
 ///     - name: zome2
 ///       bundled: ../dna2/zomes/zome1.wasm
+///   rate_limits:
+///     0:
+///       capacity: 100
+///       drain_amount: 10
+///       drain_interval_ms: 1000
+///   wasm_metering_limit: 50000000000
 /// coordinator:
 ///   zomes:
 ///     - name: zome3
@@ -36,6 +42,9 @@ use serde_with::serde_as;
 ///       dependencies:
 ///         - name: zome1
 ///         - name: zome2
+///       post_commit_retry:
+///         max_retries: 3
+///         backoff_ms: 500
 /// ```
 ///
 /// When there's only one integrity zome, it will automatically be a dependency
@@ -127,6 +136,25 @@ pub struct IntegrityManifest {
     /// The order is significant: it determines initialization order.
     /// The integrity zome manifests.
     pub zomes: Vec<ZomeManifest>,
+
+    /// Rate limiting buckets, keyed by bucket ID.
+    ///
+    /// The `weigh` callback of an integrity zome assigns each action to a bucket,
+    /// and actions which would overflow their bucket are rejected by
+    /// system validation. Actions weighed into a bucket which is not defined
+    /// here are not rate limited.
+    #[serde(default, skip_serializing_if = "RateLimits::is_empty")]
+    #[builder(default)]
+    pub rate_limits: RateLimits,
+
+    /// The maximum number of wasm metering points a single call into a zome
+    /// of this DNA may consume before it is trapped.
+    ///
+    /// Defaults to the conductor's built-in limit. Zome calls can override
+    /// this limit for themselves, but callbacks such as validation always use it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub wasm_metering_limit: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
@@ -151,6 +179,15 @@ pub struct ZomeManifest {
     #[serde(flatten)]
     pub location: ZomeLocation,
 
+    /// The hash of the wasm file at `location`. If specified, the wasm is only
+    /// used if its bytes have this hash, which makes it safe to get the wasm
+    /// from a URL.
+    ///
+    /// This is the SHA-256 hash of the file as a hex string, as printed by e.g.
+    /// `sha256sum`, not the [`WasmHash`](holo_hash::WasmHash) of the `hash` field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_hash: Option<mr_bundle::ResourceHash>,
+
     /// The integrity zomes this zome depends on.
     /// The order of these must match the order the types
     /// are used in the zome.
@@ -160,6 +197,11 @@ pub struct ZomeManifest {
     /// Useful for iOS.
     #[serde(default)]
     pub dylib: Option<PathBuf>,
+
+    /// How the `post_commit` callback of this zome is retried when it fails.
+    /// Only used for coordinator zomes. If not set, it is not retried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_commit_retry: Option<PostCommitRetryPolicy>,
 }
 
 /// Manifest for integrity zomes that another zome
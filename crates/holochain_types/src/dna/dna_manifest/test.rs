@@ -50,6 +50,95 @@ coordinator:
     let _manifest: DnaManifest = serde_yaml::from_str(&manifest_yaml).unwrap();
 }
 
+#[test]
+fn can_deserialize_dna_manifest_rate_limits() {
+    let manifest_yaml = r#"
+---
+manifest_version: "1"
+name: test_dna
+integrity:
+  network_seed: blablabla
+  origin_time: 2022-02-11T23:29:00.789576Z
+  properties: ~
+  zomes:
+    - name: zome1
+      bundled: zome-1.wasm
+  rate_limits:
+    0:
+      capacity: 100
+      drain_amount: 10
+      drain_interval_ms: 1000
+        "#;
+
+    let manifest: DnaManifest = serde_yaml::from_str(&manifest_yaml).unwrap();
+    let DnaManifest::V1(manifest) = manifest;
+    assert_eq!(
+        manifest.integrity.rate_limits.get(&0),
+        Some(&RateLimit {
+            capacity: 100,
+            drain_amount: 10,
+            drain_interval_ms: 1000,
+        })
+    );
+}
+
+#[test]
+fn can_deserialize_dna_manifest_wasm_metering_limit() {
+    let manifest_yaml = r#"
+---
+manifest_version: "1"
+name: test_dna
+integrity:
+  network_seed: blablabla
+  origin_time: 2022-02-11T23:29:00.789576Z
+  properties: ~
+  zomes:
+    - name: zome1
+      bundled: zome-1.wasm
+  wasm_metering_limit: 1000000
+        "#;
+
+    let manifest: DnaManifest = serde_yaml::from_str(&manifest_yaml).unwrap();
+    let DnaManifest::V1(manifest) = manifest;
+    assert_eq!(manifest.integrity.wasm_metering_limit, Some(1_000_000));
+}
+
+#[test]
+fn can_deserialize_dna_manifest_post_commit_retry() {
+    let manifest_yaml = r#"
+---
+manifest_version: "1"
+name: test_dna
+integrity:
+  network_seed: blablabla
+  origin_time: 2022-02-11T23:29:00.789576Z
+  properties: ~
+  zomes:
+    - name: zome1
+      bundled: zome-1.wasm
+coordinator:
+  zomes:
+    - name: zome2
+      bundled: zome-2.wasm
+      post_commit_retry:
+        max_retries: 3
+        backoff_ms: 500
+    - name: zome3
+      bundled: zome-3.wasm
+        "#;
+
+    let manifest: DnaManifest = serde_yaml::from_str(&manifest_yaml).unwrap();
+    let DnaManifest::V1(manifest) = manifest;
+    assert_eq!(
+        manifest.coordinator.zomes[0].post_commit_retry,
+        Some(PostCommitRetryPolicy {
+            max_retries: 3,
+            backoff_ms: 500,
+        })
+    );
+    assert_eq!(manifest.coordinator.zomes[1].post_commit_retry, None);
+}
+
 #[test]
 fn deserialize_dna_manifest_coordinator_only() {
     let manifest_yaml = r#"
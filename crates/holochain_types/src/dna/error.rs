@@ -71,6 +71,11 @@ pub enum DnaError {
 
     #[error("Zome dependency {0} for {1} is not pointing at an existing integrity zome that is not itself")]
     DanglingZomeDependency(String, String),
+
+    /// The rate limit of a bucket never drains, so every action weighed
+    /// into it would count against it forever.
+    #[error("The rate limit of bucket {0} has a drain_amount of 0")]
+    RateLimitNeverDrains(holochain_zome_types::rate_limit::RateBucketId),
 }
 
 impl From<std::io::Error> for DnaError {
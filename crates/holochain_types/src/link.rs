@@ -28,6 +28,13 @@ pub struct WireLinkKey {
     pub before: Option<Timestamp>,
     /// Only get links created by this author.
     pub author: Option<AgentPubKey>,
+    /// Only get links which come after this cursor.
+    /// Authorities respond with the deletes of those links as well.
+    #[serde(default)]
+    pub cursor: Option<LinkCursor>,
+    /// Get at most this many links.
+    #[serde(default)]
+    pub limit: Option<u32>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes, Default)]
@@ -254,6 +261,14 @@ pub struct WireLinkQuery {
 
     /// Only include links created by this author.
     pub author: Option<AgentPubKey>,
+
+    /// Only include links which come after this cursor.
+    #[serde(default)]
+    pub cursor: Option<LinkCursor>,
+
+    /// Include at most this many links, starting from the cursor.
+    #[serde(default)]
+    pub limit: Option<u32>,
 }
 
 /// Response type for a `WireLinkQuery`.
@@ -43,6 +43,29 @@ pub enum SystemSignal {
     Test(String),
     /// A countersigning session has successfully completed.
     SuccessfulCountersigning(holo_hash::EntryHash),
+    /// The `post_commit` callback of a zome failed, also on every retry its
+    /// retry policy allowed.
+    PostCommitFailed {
+        /// The cell the actions were committed to
+        cell_id: CellId,
+        /// The zome whose callback failed
+        zome_name: ZomeName,
+        /// The actions the callback was run for
+        action_hashes: Vec<holo_hash::ActionHash>,
+        /// How many times the callback was run
+        attempts: u32,
+        /// The error of the last run
+        error: String,
+    },
+    /// A connection to an app interface with a signal replay buffer could not resume
+    /// the signals from the requested point, as it is not in the current epoch.
+    ///
+    /// This is sent instead of authenticating the connection, and the authentication
+    /// token is not used. The client may authenticate again without resuming.
+    SignalResumeRefused {
+        /// The current epoch of the app's signals
+        epoch: u64,
+    },
 }
 
 /// Create a test signal
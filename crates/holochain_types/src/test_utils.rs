@@ -48,6 +48,8 @@ pub fn fake_dna_zomes_named(
             quantum_time: kitsune_p2p_dht::spacetime::STANDARD_QUANTUM_TIME,
         },
         integrity_zomes: Vec::new(),
+        rate_limits: Default::default(),
+        wasm_metering_limit: None,
         coordinator_zomes: Vec::new(),
     };
     tokio_helper::block_forever_on(async move {
@@ -61,6 +63,7 @@ pub fn fake_dna_zomes_named(
                     wasm_hash,
                     dependencies: Default::default(),
                     preserialized_path: None,
+                    post_commit_retry: None,
                 })
                 .into(),
             ));
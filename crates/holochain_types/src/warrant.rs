@@ -36,6 +36,7 @@ impl WarrantOp {
     pub fn get_type(&self) -> WarrantOpType {
         match self.warrant {
             Warrant::ChainIntegrity(_) => WarrantOpType::ChainIntegrityWarrant,
+            Warrant::ValidationReceipt(_) => WarrantOpType::ValidationReceiptWarrant,
         }
     }
 
@@ -56,11 +57,14 @@ impl WarrantOp {
     PartialEq,
     Hash,
     derive_more::Display,
+    strum_macros::EnumIter,
     strum_macros::EnumString,
 )]
 pub enum WarrantOpType {
     /// A chain integrity warrant
     ChainIntegrityWarrant,
+    /// A validation receipt warrant
+    ValidationReceiptWarrant,
 }
 
 impl holochain_sqlite::rusqlite::ToSql for WarrantOpType {
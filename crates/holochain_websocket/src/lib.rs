@@ -8,6 +8,8 @@ use holochain_types::websocket::AllowedOrigins;
 use std::io::ErrorKind;
 pub use std::io::{Error, Result};
 use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::net::ToSocketAddrs;
 use tokio::select;
@@ -16,6 +18,9 @@ use tokio_tungstenite::tungstenite::handshake::server::{Callback, ErrorResponse,
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue, StatusCode};
 use tokio_tungstenite::tungstenite::protocol::Message;
 
+mod tls;
+pub use tls::*;
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type")]
 /// The messages actually sent over the wire by this library.
@@ -143,6 +148,11 @@ pub struct WebsocketConfig {
     /// Allowed origins access control for a [WebsocketListener].
     /// Not used by the [WebsocketSender].
     pub allowed_origins: Option<AllowedOrigins>,
+
+    /// TLS configuration for a [WebsocketListener]. If set, the listener
+    /// only accepts connections over TLS. [default = None]
+    /// Not used by the [WebsocketSender], see [ConnectRequest::with_tls].
+    pub tls: Option<TlsServerConfig>,
 }
 
 impl WebsocketConfig {
@@ -152,6 +162,7 @@ impl WebsocketConfig {
         max_message_size: 64 << 20,
         max_frame_size: 16 << 20,
         allowed_origins: None,
+        tls: None,
     };
 
     /// The default listener WebsocketConfig.
@@ -160,6 +171,7 @@ impl WebsocketConfig {
         max_message_size: 64 << 20,
         max_frame_size: 16 << 20,
         allowed_origins: Some(AllowedOrigins::Any),
+        tls: None,
     };
 
     /// Internal convert to tungstenite config.
@@ -219,7 +231,12 @@ impl RMap {
     }
 }
 
-type WsStream = tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>;
+/// A byte stream a websocket connection can run over.
+trait Io: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send {}
+
+impl<T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send> Io for T {}
+
+type WsStream = tokio_tungstenite::WebSocketStream<Box<dyn Io>>;
 type WsSend =
     futures::stream::SplitSink<WsStream, tokio_tungstenite::tungstenite::protocol::Message>;
 type WsSendSync = Arc<tokio::sync::Mutex<WsSend>>;
@@ -336,11 +353,7 @@ where
 /// Note, This receiver must be polled (recv()) for responses to requests
 /// made on the Sender side to be received.
 /// If this receiver is dropped, the sender side will also be closed.
-pub struct WebsocketReceiver(
-    WsCoreSync,
-    std::net::SocketAddr,
-    tokio::task::JoinHandle<()>,
-);
+pub struct WebsocketReceiver(WsCoreSync, PeerAddr, tokio::task::JoinHandle<()>);
 
 impl Drop for WebsocketReceiver {
     fn drop(&mut self) {
@@ -350,7 +363,7 @@ impl Drop for WebsocketReceiver {
 }
 
 impl WebsocketReceiver {
-    fn new(core: WsCoreSync, addr: std::net::SocketAddr) -> Self {
+    fn new(core: WsCoreSync, addr: PeerAddr) -> Self {
         let core2 = core.clone();
         let ping_task = tokio::task::spawn(async move {
             loop {
@@ -377,8 +390,8 @@ impl WebsocketReceiver {
     }
 
     /// Peer address.
-    pub fn peer_addr(&self) -> std::net::SocketAddr {
-        self.1
+    pub fn peer_addr(&self) -> PeerAddr {
+        self.1.clone()
     }
 
     /// Receive the next message.
@@ -469,6 +482,29 @@ impl WebsocketReceiver {
     }
 }
 
+/// The address of the remote end of a websocket connection.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PeerAddr {
+    /// The remote is connected over TCP.
+    Tcp(SocketAddr),
+
+    /// The remote is connected over a Unix domain socket.
+    /// Connecting sockets are usually unnamed, so only the path
+    /// of the listening socket is known.
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl std::fmt::Display for PeerAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PeerAddr::Tcp(addr) => addr.fmt(f),
+            #[cfg(unix)]
+            PeerAddr::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
 /// Send requests and signals to the remote end of this websocket connection.
 /// Note, this receiver side must be polled (recv()) for responses to requests
 /// made on this sender to be received.
@@ -613,7 +649,7 @@ impl WebsocketSender {
 fn split(
     stream: WsStream,
     timeout: std::time::Duration,
-    peer_addr: std::net::SocketAddr,
+    peer_addr: PeerAddr,
 ) -> Result<(WebsocketSender, WebsocketReceiver)> {
     let (sink, stream) = futures::stream::StreamExt::split(stream);
 
@@ -646,6 +682,10 @@ pub async fn connect(
     let request = request.into();
     let stream = tokio::net::TcpStream::connect(request.addr).await?;
     let peer_addr = stream.peer_addr()?;
+    let stream: Box<dyn Io> = match &request.tls {
+        Some(tls) => tls.connect(Box::new(stream)).await?,
+        None => Box::new(stream),
+    };
     let (stream, _addr) = tokio_tungstenite::client_async_with_config(
         request.into_client_request()?,
         stream,
@@ -653,13 +693,47 @@ pub async fn connect(
     )
     .await
     .map_err(Error::other)?;
-    split(stream, config.default_request_timeout, peer_addr)
+    split(
+        stream,
+        config.default_request_timeout,
+        PeerAddr::Tcp(peer_addr),
+    )
+}
+
+/// Establish a new outgoing websocket connection over the Unix domain socket
+/// at `path`.
+///
+/// The connection is made with the default `Origin` of a [ConnectRequest].
+#[cfg(unix)]
+pub async fn connect_unix(
+    config: Arc<WebsocketConfig>,
+    path: impl AsRef<Path>,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    let path = path.as_ref().to_path_buf();
+    let stream = tokio::net::UnixStream::connect(&path).await?;
+    let mut request = "ws://localhost"
+        .into_client_request()
+        .map_err(Error::other)?;
+    request
+        .headers_mut()
+        .insert("Origin", HeaderValue::from_static("holochain_websocket"));
+    let (stream, _addr) = tokio_tungstenite::client_async_with_config(
+        request,
+        Box::new(stream) as Box<dyn Io>,
+        Some(config.as_tungstenite()),
+    )
+    .await
+    .map_err(Error::other)?;
+    split(stream, config.default_request_timeout, PeerAddr::Unix(path))
 }
 
 /// A request to connect to a websocket server.
 pub struct ConnectRequest {
     addr: std::net::SocketAddr,
     headers: HeaderMap<HeaderValue>,
+    tls: Option<TlsClientConfig>,
 }
 
 impl From<std::net::SocketAddr> for ConnectRequest {
@@ -674,6 +748,7 @@ impl ConnectRequest {
         let mut cr = ConnectRequest {
             addr,
             headers: HeaderMap::new(),
+            tls: None,
         };
 
         // Set a default Origin so that the connection request will be allowed by default when the listener is
@@ -695,12 +770,19 @@ impl ConnectRequest {
         Ok(self)
     }
 
+    /// Connect over TLS, verifying the server as configured in `tls`.
+    pub fn with_tls(mut self, tls: TlsClientConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
     fn into_client_request(
         self,
     ) -> Result<impl tokio_tungstenite::tungstenite::client::IntoClientRequest + Unpin> {
         use tokio_tungstenite::tungstenite::client::IntoClientRequest;
-        let mut req =
-            String::into_client_request(format!("ws://{}", self.addr)).map_err(Error::other)?;
+        let scheme = if self.tls.is_some() { "wss" } else { "ws" };
+        let mut req = String::into_client_request(format!("{}://{}", scheme, self.addr))
+            .map_err(Error::other)?;
         for (name, value) in self.headers {
             if let Some(name) = name {
                 req.headers_mut().insert(name, value);
@@ -721,16 +803,17 @@ impl ConnectRequest {
 
 // TODO async_trait still needed for dynamic dispatch https://blog.rust-lang.org/2023/12/21/async-fn-rpit-in-traits.html#dynamic-dispatch
 #[async_trait::async_trait]
-trait TcpListener: Send + Sync {
-    async fn accept(&self) -> Result<(tokio::net::TcpStream, SocketAddr)>;
+trait Listener: Send + Sync {
+    async fn accept(&self) -> Result<(Box<dyn Io>, PeerAddr)>;
 
     fn local_addrs(&self) -> Result<Vec<SocketAddr>>;
 }
 
 #[async_trait::async_trait]
-impl TcpListener for tokio::net::TcpListener {
-    async fn accept(&self) -> Result<(tokio::net::TcpStream, SocketAddr)> {
-        self.accept().await
+impl Listener for tokio::net::TcpListener {
+    async fn accept(&self) -> Result<(Box<dyn Io>, PeerAddr)> {
+        let (stream, addr) = self.accept().await?;
+        Ok((Box::new(stream), PeerAddr::Tcp(addr)))
     }
 
     fn local_addrs(&self) -> Result<Vec<SocketAddr>> {
@@ -744,13 +827,13 @@ struct DualStackListener {
 }
 
 #[async_trait::async_trait]
-impl TcpListener for DualStackListener {
-    async fn accept(&self) -> Result<(tokio::net::TcpStream, SocketAddr)> {
+impl Listener for DualStackListener {
+    async fn accept(&self) -> Result<(Box<dyn Io>, PeerAddr)> {
         let (stream, addr) = select! {
             res = self.v4.accept() => res?,
             res = self.v6.accept() => res?,
         };
-        Ok((stream, addr))
+        Ok((Box::new(stream), PeerAddr::Tcp(addr)))
     }
 
     fn local_addrs(&self) -> Result<Vec<SocketAddr>> {
@@ -758,11 +841,96 @@ impl TcpListener for DualStackListener {
     }
 }
 
+/// A listener on a Unix domain socket, which removes its socket file on drop.
+///
+/// The listener holds a lock on a file next to the socket file for as long as
+/// it runs, see [`UnixSocketLock`].
+#[cfg(unix)]
+struct UnixSocketListener {
+    listener: tokio::net::UnixListener,
+    path: PathBuf,
+    _lock: UnixSocketLock,
+}
+
+#[cfg(unix)]
+impl Drop for UnixSocketListener {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// An exclusive lock on the file `<socket path>.lock`, which tells whether the
+/// socket file at the path belongs to a running listener without connecting to it.
+#[cfg(unix)]
+struct UnixSocketLock {
+    _file: std::fs::File,
+    path: PathBuf,
+}
+
+#[cfg(unix)]
+impl UnixSocketLock {
+    /// Take the lock for the socket at `socket_path`, failing with
+    /// [`ErrorKind::AddrInUse`] if another listener holds it.
+    fn acquire(socket_path: &Path) -> Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let mut path = socket_path.as_os_str().to_owned();
+        path.push(".lock");
+        let path = PathBuf::from(path);
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        // SAFETY: the file descriptor stays valid for as long as `file` is open,
+        // and the lock is released when it is closed.
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let e = Error::last_os_error();
+            return Err(if e.kind() == ErrorKind::WouldBlock {
+                Error::new(
+                    ErrorKind::AddrInUse,
+                    format!("{} is in use by another listener", socket_path.display()),
+                )
+            } else {
+                e
+            });
+        }
+        Ok(Self { _file: file, path })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for UnixSocketLock {
+    fn drop(&mut self) {
+        // The lock is released when `_file` is closed, after the lock file is gone.
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(unix)]
+#[async_trait::async_trait]
+impl Listener for UnixSocketListener {
+    async fn accept(&self) -> Result<(Box<dyn Io>, PeerAddr)> {
+        let (stream, _addr) = self.listener.accept().await?;
+        Ok((Box::new(stream), PeerAddr::Unix(self.path.clone())))
+    }
+
+    fn local_addrs(&self) -> Result<Vec<SocketAddr>> {
+        Ok(vec![])
+    }
+}
+
+/// The time an accepted connection has to complete the TLS and websocket handshakes.
+const HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+type Handshakes = tokio::task::JoinSet<Result<(WebsocketSender, WebsocketReceiver)>>;
+
 /// A Holochain websocket listener.
 pub struct WebsocketListener {
     config: Arc<WebsocketConfig>,
     access_control: Arc<AllowedOrigins>,
-    listener: Box<dyn TcpListener>,
+    listener: Box<dyn Listener>,
+    handshakes: tokio::sync::Mutex<Handshakes>,
 }
 
 impl Drop for WebsocketListener {
@@ -772,6 +940,19 @@ impl Drop for WebsocketListener {
 }
 
 impl WebsocketListener {
+    fn new(
+        config: Arc<WebsocketConfig>,
+        access_control: Arc<AllowedOrigins>,
+        listener: Box<dyn Listener>,
+    ) -> Self {
+        Self {
+            config,
+            access_control,
+            listener,
+            handshakes: Default::default(),
+        }
+    }
+
     /// Bind a new websocket listener.
     pub async fn bind(config: Arc<WebsocketConfig>, addr: impl ToSocketAddrs) -> Result<Self> {
         let access_control = Arc::new(config.allowed_origins.clone().ok_or_else(|| {
@@ -783,11 +964,7 @@ impl WebsocketListener {
         let addr = listener.local_addr()?;
         tracing::info!(?addr, "WebsocketListener Listening");
 
-        Ok(Self {
-            config,
-            access_control,
-            listener: Box::new(listener),
-        })
+        Ok(Self::new(config, access_control, Box::new(listener)))
     }
 
     /// Bind a new websocket listener on the same port using a v4 and a v6 socket.
@@ -851,11 +1028,7 @@ impl WebsocketListener {
                     tracing::info!(?e, "Failed to bind IPv4 listener because IPv4 appears to be disabled, falling back to IPv6 only");
                     // No need to re-bind the v6 listener, it's already bound. Just create a new Self
                     // from the v6 listener and return it.
-                    return Ok(Self {
-                        config,
-                        access_control,
-                        listener: Box::new(v6_listener),
-                    });
+                    return Ok(Self::new(config, access_control, Box::new(v6_listener)));
                 }
                 // If the port for IPv6 was selected by the OS but it isn't available for IPv4, retry and let the OS pick a new port for IPv6
                 // and hopefully it will be available for IPv4.
@@ -887,33 +1060,132 @@ impl WebsocketListener {
         let addr = listener.v6.local_addr()?;
         tracing::info!(?addr, "WebsocketListener listening");
 
-        Ok(Self {
-            config,
-            access_control,
-            listener: Box::new(listener),
-        })
+        Ok(Self::new(config, access_control, Box::new(listener)))
+    }
+
+    /// Bind a new websocket listener on a Unix domain socket at `path`.
+    ///
+    /// Access to the listener is controlled by the permissions of the socket
+    /// file, which are set to `mode`, e.g. `0o600` to only allow the owner to
+    /// connect. A socket file left at `path` by a listener that is no longer
+    /// running is replaced, and the socket file is removed when the listener
+    /// is dropped.
+    ///
+    /// While it runs, the listener holds a lock on the file `<path>.lock`, which
+    /// is how another listener finds out that the socket is in use.
+    ///
+    /// The permissions are applied right after the socket is bound, so the
+    /// socket should be placed in a directory which only the intended users
+    /// can access.
+    ///
+    /// If the config has no `allowed_origins`, connections from any origin are
+    /// allowed.
+    #[cfg(unix)]
+    pub async fn bind_unix(
+        config: Arc<WebsocketConfig>,
+        path: impl AsRef<Path>,
+        mode: u32,
+    ) -> Result<Self> {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+        let access_control = Arc::new(
+            config
+                .allowed_origins
+                .clone()
+                .unwrap_or(AllowedOrigins::Any),
+        );
+
+        let path = path.as_ref().to_path_buf();
+        let lock = UnixSocketLock::acquire(&path)?;
+        match std::fs::symlink_metadata(&path) {
+            // No running listener holds the lock, so the socket is stale.
+            Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(&path)?,
+            Ok(_) => {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("{} exists and is not a socket", path.display()),
+                ))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => (),
+            Err(e) => return Err(e),
+        }
+
+        let listener = UnixSocketListener {
+            listener: tokio::net::UnixListener::bind(&path)?,
+            path: path.clone(),
+            _lock: lock,
+        };
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode))?;
+        tracing::info!(?path, "WebsocketListener Listening");
+
+        Ok(Self::new(config, access_control, Box::new(listener)))
     }
 
     /// Get the bound local address of this listener.
+    ///
+    /// This is empty for a listener on a Unix domain socket.
     pub fn local_addrs(&self) -> Result<Vec<std::net::SocketAddr>> {
         self.listener.local_addrs()
     }
 
     /// Accept an incoming connection.
+    ///
+    /// The handshakes of incoming connections run in the background, so a
+    /// client which is slow to complete them doesn't hold up other clients.
+    /// A connection which doesn't complete them within 10 seconds is dropped,
+    /// and an error is returned for it.
     pub async fn accept(&self) -> Result<(WebsocketSender, WebsocketReceiver)> {
-        let (stream, addr) = self.listener.accept().await?;
-        tracing::debug!(?addr, "Accept Incoming Websocket Connection");
-        let stream = tokio_tungstenite::accept_hdr_async_with_config(
+        let mut handshakes = self.handshakes.lock().await;
+        loop {
+            select! {
+                res = self.listener.accept() => {
+                    let (stream, addr) = res?;
+                    tracing::debug!(?addr, "Accept Incoming Websocket Connection");
+                    handshakes.spawn(handshake(
+                        self.config.clone(),
+                        self.access_control.clone(),
+                        stream,
+                        addr,
+                    ));
+                }
+                Some(res) = handshakes.join_next(), if !handshakes.is_empty() => {
+                    return res.map_err(Error::other)?;
+                }
+            }
+        }
+    }
+}
+
+/// Run the TLS and websocket handshakes of an accepted connection.
+async fn handshake(
+    config: Arc<WebsocketConfig>,
+    access_control: Arc<AllowedOrigins>,
+    stream: Box<dyn Io>,
+    addr: PeerAddr,
+) -> Result<(WebsocketSender, WebsocketReceiver)> {
+    let stream = tokio::time::timeout(HANDSHAKE_TIMEOUT, async {
+        let stream = match &config.tls {
+            Some(tls) => tls.accept(stream).await?,
+            None => stream,
+        };
+        tokio_tungstenite::accept_hdr_async_with_config(
             stream,
             ConnectCallback {
-                allowed_origin: self.access_control.clone(),
+                allowed_origin: access_control,
             },
-            Some(self.config.as_tungstenite()),
+            Some(config.as_tungstenite()),
         )
         .await
-        .map_err(Error::other)?;
-        split(stream, self.config.default_request_timeout, addr)
-    }
+        .map_err(Error::other)
+    })
+    .await
+    .map_err(|_| {
+        Error::new(
+            ErrorKind::TimedOut,
+            format!("{addr:?} did not complete the handshake in time"),
+        )
+    })??;
+    split(stream, config.default_request_timeout, addr)
 }
 
 struct ConnectCallback {
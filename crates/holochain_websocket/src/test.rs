@@ -397,3 +397,200 @@ async fn handle_client_close() {
         .expect("Error joining the signal sender task")
         .expect("Other error than WebsocketClosed while sending signals");
 }
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn unix_socket() {
+    use std::os::unix::fs::PermissionsExt;
+
+    holochain_trace::test_run();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    enum TestMsg {
+        Hello,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("admin.sock");
+
+    // A socket file left behind by a listener that is gone is replaced
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    let l = WebsocketListener::bind_unix(Arc::new(WebsocketConfig::LISTENER_DEFAULT), &path, 0o600)
+        .await
+        .unwrap();
+    assert!(l.local_addrs().unwrap().is_empty());
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(0o600, mode & 0o777);
+
+    // A second listener can't take over the socket of a running one
+    assert_eq!(
+        ErrorKind::AddrInUse,
+        WebsocketListener::bind_unix(Arc::new(WebsocketConfig::LISTENER_DEFAULT), &path, 0o600)
+            .await
+            .err()
+            .unwrap()
+            .kind()
+    );
+
+    let l_task = tokio::task::spawn(async move {
+        let (_send, mut recv) = l.accept().await.unwrap();
+        match recv.recv::<TestMsg>().await.unwrap() {
+            ReceiveMessage::Request(data, res) => {
+                assert_eq!(TestMsg::Hello, data);
+                res.respond(TestMsg::Hello).await.unwrap();
+            }
+            oth => panic!("unexpected: {oth:?}"),
+        }
+        // Keep the connection open until the client has its response
+        let _ = recv.recv::<TestMsg>().await;
+    });
+
+    let (send, mut recv) = connect_unix(Arc::new(WebsocketConfig::CLIENT_DEFAULT), &path)
+        .await
+        .unwrap();
+    assert_eq!(PeerAddr::Unix(path.clone()), recv.peer_addr());
+    let r_task =
+        tokio::task::spawn(async move { while let Ok(_r) = recv.recv::<TestMsg>().await {} });
+
+    let res: TestMsg = send
+        .request_timeout(TestMsg::Hello, std::time::Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(TestMsg::Hello, res);
+
+    drop(send);
+    r_task.abort();
+    l_task.await.unwrap();
+
+    // The socket and lock files are removed with the listener
+    assert!(!path.exists());
+    assert!(!dir.path().join("admin.sock.lock").exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tls() {
+    holochain_trace::test_run();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+    enum TestMsg {
+        Hello,
+    }
+
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_der = cert.serialize_der().unwrap();
+
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.tls = Some(
+        TlsServerConfig::from_der(vec![cert_der.clone()], cert.serialize_private_key_der())
+            .unwrap(),
+    );
+    let l = WebsocketListener::bind(Arc::new(config), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        // A plaintext client is refused
+        assert!(l.accept().await.is_err());
+
+        for _ in 0..2 {
+            let (_send, mut recv) = l.accept().await.unwrap();
+            match recv.recv::<TestMsg>().await.unwrap() {
+                ReceiveMessage::Request(data, res) => {
+                    assert_eq!(TestMsg::Hello, data);
+                    res.respond(TestMsg::Hello).await.unwrap();
+                }
+                oth => panic!("unexpected: {oth:?}"),
+            }
+        }
+    });
+
+    let config = Arc::new(WebsocketConfig::CLIENT_DEFAULT);
+    assert!(connect(config.clone(), addr).await.is_err());
+
+    for tls in [
+        TlsClientConfig::with_root_certs_der(vec![cert_der.clone()], "localhost").unwrap(),
+        TlsClientConfig::with_pinned_cert(cert_der.clone()).unwrap(),
+    ] {
+        let (send, mut recv) = connect(config.clone(), ConnectRequest::new(addr).with_tls(tls))
+            .await
+            .unwrap();
+        let s_task =
+            tokio::task::spawn(async move { while let Ok(_r) = recv.recv::<TestMsg>().await {} });
+        let res: TestMsg = send
+            .request_timeout(TestMsg::Hello, std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(TestMsg::Hello, res);
+        s_task.abort();
+    }
+
+    l_task.await.unwrap();
+
+    // A certificate other than the pinned one is not trusted
+    let other = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.tls = Some(
+        TlsServerConfig::from_der(
+            vec![other.serialize_der().unwrap()],
+            other.serialize_private_key_der(),
+        )
+        .unwrap(),
+    );
+    let l = WebsocketListener::bind(Arc::new(config), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+    let l_task = tokio::task::spawn(async move { l.accept().await.is_err() });
+    assert!(connect(
+        Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+        ConnectRequest::new(addr).with_tls(TlsClientConfig::with_pinned_cert(cert_der).unwrap()),
+    )
+    .await
+    .is_err());
+    assert!(l_task.await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stalled_tls_handshake_does_not_block_accept() {
+    holochain_trace::test_run();
+
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert_der = cert.serialize_der().unwrap();
+
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.tls = Some(
+        TlsServerConfig::from_der(vec![cert_der.clone()], cert.serialize_private_key_der())
+            .unwrap(),
+    );
+    let l = WebsocketListener::bind(Arc::new(config), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        let _conn = l.accept().await.unwrap();
+    });
+
+    // A client which never starts the TLS handshake
+    let _stalled = tokio::net::TcpStream::connect(addr).await.unwrap();
+
+    // doesn't keep the next client from being accepted
+    let _conn = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new(addr)
+                .with_tls(TlsClientConfig::with_pinned_cert(cert_der).unwrap()),
+        ),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    tokio::time::timeout(std::time::Duration::from_secs(5), l_task)
+        .await
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,162 @@
+//! TLS support for websocket listeners and outgoing connections.
+
+use crate::{Error, Io, Result};
+use std::path::Path;
+use std::sync::Arc;
+
+/// TLS configuration of a [WebsocketListener](crate::WebsocketListener),
+/// set with [WebsocketConfig::tls](crate::WebsocketConfig::tls).
+#[derive(Clone)]
+pub struct TlsServerConfig(Arc<rustls::ServerConfig>);
+
+impl std::fmt::Debug for TlsServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsServerConfig").finish()
+    }
+}
+
+impl TlsServerConfig {
+    /// Create a TLS server configuration from a DER encoded certificate chain,
+    /// starting with the end-entity certificate, and its PKCS#8 private key.
+    ///
+    /// This is the format of the self-signed certificates created by lair.
+    pub fn from_der(cert_chain: Vec<Vec<u8>>, priv_key: Vec<u8>) -> Result<Self> {
+        let config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(
+                cert_chain.into_iter().map(rustls::Certificate).collect(),
+                rustls::PrivateKey(priv_key),
+            )
+            .map_err(Error::other)?;
+        Ok(Self(Arc::new(config)))
+    }
+
+    /// Create a TLS server configuration from a PEM encoded certificate chain
+    /// and a PEM encoded PKCS#8, PKCS#1 or SEC1 private key.
+    pub fn from_pem(cert_chain: &[u8], priv_key: &[u8]) -> Result<Self> {
+        let certs = rustls_pemfile::certs(&mut &cert_chain[..])?;
+        if certs.is_empty() {
+            return Err(Error::other("No certificate found in PEM"));
+        }
+        let key = rustls_pemfile::read_all(&mut &priv_key[..])?
+            .into_iter()
+            .find_map(|item| match item {
+                rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::ECKey(key) => Some(key),
+                _ => None,
+            })
+            .ok_or_else(|| Error::other("No private key found in PEM"))?;
+        Self::from_der(certs, key)
+    }
+
+    /// Read a PEM encoded certificate chain and private key from files.
+    /// See [TlsServerConfig::from_pem].
+    pub fn from_pem_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> Result<Self> {
+        let cert_chain = std::fs::read(cert_path)?;
+        let priv_key = std::fs::read(key_path)?;
+        Self::from_pem(&cert_chain, &priv_key)
+    }
+
+    /// Run the server side of the TLS handshake over an accepted stream.
+    pub(crate) async fn accept(&self, stream: Box<dyn Io>) -> Result<Box<dyn Io>> {
+        let stream = tokio_rustls::TlsAcceptor::from(self.0.clone())
+            .accept(stream)
+            .await?;
+        Ok(Box::new(stream))
+    }
+}
+
+/// TLS configuration of an outgoing connection,
+/// set with [ConnectRequest::with_tls](crate::ConnectRequest::with_tls).
+#[derive(Clone)]
+pub struct TlsClientConfig {
+    config: Arc<rustls::ClientConfig>,
+    server_name: rustls::ServerName,
+}
+
+impl std::fmt::Debug for TlsClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsClientConfig")
+            .field("server_name", &self.server_name)
+            .finish()
+    }
+}
+
+impl TlsClientConfig {
+    /// Trust server certificates issued by one of the given DER encoded root
+    /// certificates. The server certificate must be valid for `server_name`.
+    pub fn with_root_certs_der(roots: Vec<Vec<u8>>, server_name: &str) -> Result<Self> {
+        let mut root_store = rustls::RootCertStore::empty();
+        for root in roots {
+            root_store
+                .add(&rustls::Certificate(root))
+                .map_err(Error::other)?;
+        }
+        let config = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_store)
+            .with_no_client_auth();
+        Ok(Self {
+            config: Arc::new(config),
+            server_name: rustls::ServerName::try_from(server_name).map_err(Error::other)?,
+        })
+    }
+
+    /// Trust server certificates issued by one of the given PEM encoded root
+    /// certificates. See [TlsClientConfig::with_root_certs_der].
+    pub fn with_root_certs_pem(roots: &[u8], server_name: &str) -> Result<Self> {
+        let roots = rustls_pemfile::certs(&mut &roots[..])?;
+        if roots.is_empty() {
+            return Err(Error::other("No certificate found in PEM"));
+        }
+        Self::with_root_certs_der(roots, server_name)
+    }
+
+    /// Only trust a server presenting exactly this DER encoded certificate,
+    /// e.g. the self-signed certificate lair created for a conductor.
+    ///
+    /// The name and validity period of the certificate are not checked.
+    pub fn with_pinned_cert(cert: Vec<u8>) -> Result<Self> {
+        let config = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(Arc::new(PinnedCertVerifier(cert)))
+            .with_no_client_auth();
+        Ok(Self {
+            config: Arc::new(config),
+            server_name: rustls::ServerName::try_from("localhost").map_err(Error::other)?,
+        })
+    }
+
+    /// Run the client side of the TLS handshake over a connected stream.
+    pub(crate) async fn connect(&self, stream: Box<dyn Io>) -> Result<Box<dyn Io>> {
+        let stream = tokio_rustls::TlsConnector::from(self.config.clone())
+            .connect(self.server_name.clone(), stream)
+            .await?;
+        Ok(Box::new(stream))
+    }
+}
+
+/// Accepts only one specific server certificate.
+struct PinnedCertVerifier(Vec<u8>);
+
+impl rustls::client::ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        _intermediates: &[rustls::Certificate],
+        _server_name: &rustls::ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> std::result::Result<rustls::client::ServerCertVerified, rustls::Error> {
+        if end_entity.0 == self.0 {
+            Ok(rustls::client::ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::UnknownIssuer,
+            ))
+        }
+    }
+}
@@ -29,6 +29,8 @@ pub enum CellBlockReason {
     App(Vec<u8>),
     /// Invalid validation result.
     InvalidOp(DhtOpHash),
+    /// A warrant against the agent was found to be valid.
+    Warrant(DhtOpHash),
     /// Some bad cryptography.
     BadCrypto,
 }
@@ -42,3 +42,28 @@ pub struct GrantZomeCallCapabilityPayload {
     /// signing for as well as access level, secret and assignees.
     pub cap_grant: ZomeCallCapGrant,
 }
+
+/// A capability grant on the local source chain which has been neither
+/// updated nor deleted, as returned by the `capability_grants` host function.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapGrantInfo {
+    /// The hash of the action which created the grant.
+    /// Use it to update or delete the grant.
+    pub action_hash: ActionHash,
+    /// When the grant was created.
+    pub created_at: Timestamp,
+    /// The grant itself.
+    pub cap_grant: ZomeCallCapGrant,
+}
+
+/// Input to the `capability_claims` host function.
+///
+/// Only the claims matching every given field are returned,
+/// so the default input returns all claims.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapClaimsInput {
+    /// Only return claims with this tag.
+    pub tag: Option<String>,
+    /// Only return claims on grants by this agent.
+    pub grantor: Option<AgentPubKey>,
+}
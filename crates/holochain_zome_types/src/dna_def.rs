@@ -29,7 +29,10 @@ pub type CoordinatorZomes = Vec<(ZomeName, CoordinatorZomeDef)>;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, SerializedBytes)]
 #[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "full-dna-def", derive(derive_builder::Builder))]
-#[cfg_attr(feature = "full-dna-def", builder(public))]
+#[cfg_attr(
+    feature = "full-dna-def",
+    builder(public, build_fn(validate = "Self::validate"))
+)]
 pub struct DnaDef {
     /// The friendly "name" of a Holochain DNA.
     #[cfg_attr(
@@ -46,6 +49,21 @@ pub struct DnaDef {
     /// A vector of zomes associated with your DNA.
     pub integrity_zomes: IntegrityZomes,
 
+    /// The rate limiting buckets of this DNA, keyed by bucket ID.
+    /// Rate limits are part of the [`DnaHash`] computation, but only when
+    /// any are defined.
+    #[serde(default, skip_serializing_if = "RateLimits::is_empty")]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub rate_limits: RateLimits,
+
+    /// The maximum number of wasm metering points a single call into a zome
+    /// of this DNA may consume, if it differs from the conductor default.
+    /// The limit is part of the [`DnaHash`] computation, but only when set,
+    /// since it decides whether validation callbacks complete.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub wasm_metering_limit: Option<u64>,
+
     /// A vector of zomes that do not affect
     /// the [`DnaHash`].
     pub coordinator_zomes: CoordinatorZomes,
@@ -56,6 +74,10 @@ pub struct DnaDef {
 struct DnaDefHash<'a> {
     modifiers: &'a DnaModifiers,
     integrity_zomes: &'a IntegrityZomes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limits: Option<&'a RateLimits>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wasm_metering_limit: Option<u64>,
 }
 
 #[cfg(feature = "test_utils")]
@@ -77,6 +99,13 @@ impl DnaDef {
 }
 
 impl DnaDef {
+    /// The ID of a rate limiting bucket of this DNA which never drains, if any.
+    /// DNAs with such a bucket are refused, as every action weighed into it
+    /// would count against it forever.
+    pub fn never_draining_rate_limit(&self) -> Option<RateBucketId> {
+        never_draining_rate_limit(&self.rate_limits)
+    }
+
     /// Get all zomes including the integrity and coordinator zomes.
     pub fn all_zomes(&self) -> impl Iterator<Item = (&ZomeName, &ZomeDef)> {
         self.integrity_zomes
@@ -204,8 +233,29 @@ pub fn random_network_seed() -> String {
     nanoid::nanoid!()
 }
 
+fn never_draining_rate_limit(rate_limits: &RateLimits) -> Option<RateBucketId> {
+    rate_limits
+        .iter()
+        .find(|(_, limit)| limit.drain_amount == 0)
+        .map(|(bucket_id, _)| *bucket_id)
+}
+
 #[cfg(feature = "full-dna-def")]
 impl DnaDefBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self
+            .rate_limits
+            .as_ref()
+            .and_then(never_draining_rate_limit)
+        {
+            Some(bucket_id) => Err(format!(
+                "The rate limit of bucket {} has a drain_amount of 0",
+                bucket_id
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Provide a random network seed
     pub fn random_network_seed(&mut self) -> &mut Self {
         self.modifiers = Some(
@@ -234,6 +284,8 @@ impl HashableContent for DnaDef {
         let hash = DnaDefHash {
             modifiers: &self.modifiers,
             integrity_zomes: &self.integrity_zomes,
+            rate_limits: Some(&self.rate_limits).filter(|r| !r.is_empty()),
+            wasm_metering_limit: self.wasm_metering_limit,
         };
         HashableContentBytes::Content(
             holochain_serialized_bytes::UnsafeBytes::from(
@@ -283,4 +335,40 @@ mod tests {
 
         assert_eq!(mods.update(opt), expected);
     }
+
+    #[test]
+    #[cfg(feature = "full-dna-def")]
+    fn rate_limits_must_drain() {
+        let rate_limits = |drain_amount| {
+            [(
+                3,
+                RateLimit {
+                    capacity: 10,
+                    drain_amount,
+                    drain_interval_ms: 1000,
+                },
+            )]
+            .into_iter()
+            .collect::<RateLimits>()
+        };
+
+        let builder = || {
+            let mut builder = DnaDefBuilder::default();
+            builder
+                .integrity_zomes(vec![])
+                .coordinator_zomes(vec![])
+                .random_network_seed();
+            builder
+        };
+
+        let dna_def = builder().rate_limits(rate_limits(1)).build().unwrap();
+        assert_eq!(dna_def.never_draining_rate_limit(), None);
+
+        assert!(builder().rate_limits(rate_limits(0)).build().is_err());
+        let dna_def = DnaDef {
+            rate_limits: rate_limits(0),
+            ..dna_def
+        };
+        assert_eq!(dna_def.never_draining_rate_limit(), Some(3));
+    }
 }
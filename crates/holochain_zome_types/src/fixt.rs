@@ -739,6 +739,8 @@ fixturator!(
         integrity_zomes: IntegrityZomesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
+        rate_limits: Default::default(),
+        wasm_metering_limit: None,
         coordinator_zomes: CoordinatorZomesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
@@ -761,6 +763,8 @@ fixturator!(
         integrity_zomes: IntegrityZomesFixturator::new_indexed(Unpredictable, get_fixt_index!())
             .next()
             .unwrap(),
+        rate_limits: Default::default(),
+        wasm_metering_limit: None,
         coordinator_zomes: CoordinatorZomesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
@@ -783,6 +787,8 @@ fixturator!(
         integrity_zomes: IntegrityZomesFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
+        rate_limits: Default::default(),
+        wasm_metering_limit: None,
         coordinator_zomes: CoordinatorZomesFixturator::new_indexed(Empty, get_fixt_index!())
             .next()
             .unwrap(),
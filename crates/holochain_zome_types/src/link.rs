@@ -108,6 +108,54 @@ pub struct GetLinksInput {
 
     /// Only include links created by this author.
     pub author: Option<AgentPubKey>,
+
+    /// Only include links which come after this cursor.
+    #[serde(default)]
+    pub cursor: Option<LinkCursor>,
+
+    /// Include at most this many links, starting from the cursor.
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// A position in the list of links on a base.
+///
+/// Paged link queries order links by their timestamp and then by the hash of
+/// their create action, and only return links which come strictly after the cursor.
+/// The cursor of the last link of a page is the cursor for the next page.
+#[derive(
+    Debug,
+    PartialOrd,
+    Ord,
+    Clone,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Eq,
+    SerializedBytes,
+)]
+pub struct LinkCursor {
+    /// The timestamp of the link
+    pub timestamp: Timestamp,
+    /// The hash of the link's create action
+    pub create_link_hash: ActionHash,
+}
+
+impl From<&Link> for LinkCursor {
+    fn from(link: &Link) -> Self {
+        Self {
+            timestamp: link.timestamp,
+            create_link_hash: link.create_link_hash.clone(),
+        }
+    }
+}
+
+impl Link {
+    /// The cursor pointing at this link
+    pub fn cursor(&self) -> LinkCursor {
+        self.into()
+    }
 }
 
 type CreateLinkWithDeleteLinks = Vec<(SignedActionHashed, Vec<SignedActionHashed>)>;
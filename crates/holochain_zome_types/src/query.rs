@@ -104,6 +104,14 @@ pub struct LinkQuery {
 
     /// Only include links created by this author.
     pub author: Option<AgentPubKey>,
+
+    /// Only include links which come after this cursor.
+    #[serde(default)]
+    pub cursor: Option<LinkCursor>,
+
+    /// Include at most this many links, starting from the cursor.
+    #[serde(default)]
+    pub limit: Option<u32>,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -376,6 +384,8 @@ impl LinkQuery {
             before: None,
             after: None,
             author: None,
+            cursor: None,
+            limit: None,
         }
     }
 
@@ -402,6 +412,18 @@ impl LinkQuery {
         self.author = Some(author);
         self
     }
+
+    /// Only include links which come after `cursor`.
+    pub fn cursor(mut self, cursor: LinkCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Include at most `limit` links.
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
 }
 
 #[cfg(test)]
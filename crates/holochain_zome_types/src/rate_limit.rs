@@ -1,3 +1,17 @@
 //! Types for rate limiting
 
 pub use holochain_integrity_types::rate_limit::*;
+
+use crate::prelude::*;
+use holochain_wasmer_common::*;
+
+/// The result of the `weigh` callback is the weight of the action.
+/// The first zome to weigh an action determines its weight.
+impl CallbackResult for RateWeight {
+    fn is_definitive(&self) -> bool {
+        true
+    }
+    fn try_from_wasm_error(wasm_error: WasmError) -> Result<Self, WasmError> {
+        Err(wasm_error)
+    }
+}
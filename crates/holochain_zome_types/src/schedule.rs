@@ -27,6 +27,73 @@ pub enum Schedule {
     Persisted(String),
     /// Ephemeral schedules are defined by a Duration.
     Ephemeral(Duration),
+    /// Persisted schedules defined by a crontab syntax string, along with
+    /// the timezone it is read in, a random jitter and a concurrency limit.
+    ///
+    /// Unlike [`Schedule::Persisted`], the function is rescheduled for its
+    /// next run as soon as it is dispatched, so a run which takes longer than
+    /// the interval of the crontab doesn't hold back the next one.
+    Cron(CronSchedule),
+}
+
+/// A crontab schedule with the options of [`Schedule::Cron`].
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+pub struct CronSchedule {
+    /// The crontab syntax string.
+    pub cron: String,
+    /// The IANA name of the timezone the crontab is read in, e.g. `Europe/Berlin`.
+    /// Defaults to UTC.
+    pub timezone: Option<String>,
+    /// Delay each run by a random duration of up to this long,
+    /// to spread the load of many agents on the same schedule.
+    pub jitter: Option<Duration>,
+    /// The maximum number of runs of the function in progress at the same time.
+    /// Runs which would exceed it are skipped. Defaults to 1.
+    pub max_concurrency: Option<u32>,
+}
+
+impl CronSchedule {
+    /// Constructor, reading the crontab in UTC without jitter.
+    pub fn new(cron: impl Into<String>) -> Self {
+        Self {
+            cron: cron.into(),
+            timezone: None,
+            jitter: None,
+            max_concurrency: None,
+        }
+    }
+
+    /// Read the crontab in this timezone.
+    pub fn with_timezone(mut self, timezone: impl Into<String>) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    /// Delay each run by a random duration of up to `jitter`.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = Some(jitter);
+        self
+    }
+
+    /// Allow up to `max_concurrency` runs in progress at the same time.
+    pub fn with_max_concurrency(mut self, max_concurrency: u32) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+}
+
+impl Schedule {
+    /// The maximum number of runs of a function with this schedule
+    /// in progress at the same time, which is at least 1.
+    pub fn max_concurrency(&self) -> u32 {
+        match self {
+            Self::Cron(CronSchedule {
+                max_concurrency: Some(max_concurrency),
+                ..
+            }) => (*max_concurrency).max(1),
+            _ => 1,
+        }
+    }
 }
 
 impl From<String> for Schedule {
@@ -35,6 +102,12 @@ impl From<String> for Schedule {
     }
 }
 
+impl From<CronSchedule> for Schedule {
+    fn from(cron: CronSchedule) -> Self {
+        Self::Cron(cron)
+    }
+}
+
 impl From<Duration> for Schedule {
     fn from(timeout: Duration) -> Self {
         Self::Ephemeral(timeout)
@@ -42,7 +115,7 @@ impl From<Duration> for Schedule {
 }
 
 /// A fully qualified scheduled function.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ScheduledFn(ZomeName, FunctionName);
 
 impl ScheduledFn {
@@ -61,3 +134,33 @@ impl ScheduledFn {
         &self.1
     }
 }
+
+/// A function scheduled for a cell, as listed for admins.
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+pub struct ScheduledFnInfo {
+    /// The zome of the scheduled function.
+    pub zome_name: ZomeName,
+    /// The name of the scheduled function.
+    pub fn_name: FunctionName,
+    /// The schedule of the function, or `None` if it runs once as soon as possible.
+    pub schedule: Option<Schedule>,
+    /// When the function is due to run next.
+    pub next_run: Timestamp,
+    /// Whether the function is unscheduled when the conductor restarts.
+    pub ephemeral: bool,
+}
+
+/// A past run of a scheduled function, as kept in the run history of a cell.
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
+pub struct ScheduledFnRun {
+    /// The zome of the scheduled function.
+    pub zome_name: ZomeName,
+    /// The name of the scheduled function.
+    pub fn_name: FunctionName,
+    /// When the run was dispatched.
+    pub started_at: Timestamp,
+    /// How long the run took.
+    pub duration: Duration,
+    /// Why the run failed or was skipped, if it did not succeed.
+    pub error: Option<String>,
+}
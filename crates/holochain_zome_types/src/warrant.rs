@@ -18,6 +18,8 @@ use crate::signature::Signed;
 pub enum Warrant {
     /// Signifies evidence of a breach of chain integrity
     ChainIntegrity(ChainIntegrityWarrant),
+    /// Signifies evidence of a validator vouching for invalid data
+    ValidationReceipt(ValidationReceiptWarrant),
 }
 
 /// A warrant which is sent to AgentActivity authorities
@@ -51,6 +53,34 @@ pub enum ChainIntegrityWarrant {
     },
 }
 
+/// A warrant against a validator, which is sent to the validator's AgentActivity authorities
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub enum ValidationReceiptWarrant {
+    /// A validator signed a receipt stating that an op of an invalid action is valid.
+    /// When we receive this warrant, we check the validator's signature on the receipt,
+    /// then fetch the Action and validate it like for [`ChainIntegrityWarrant::InvalidChainOp`].
+    InvalidOpReceipted {
+        /// The validator who signed the receipt
+        validator: AgentPubKey,
+        /// The op which the receipt was signed for
+        dht_op_hash: DhtOpHash,
+        /// The validators listed in the receipt
+        validators: Vec<AgentPubKey>,
+        /// When the validator integrated the op, as stated in the receipt
+        when_integrated: Timestamp,
+        /// The validator's signature of the receipt
+        receipt_signature: Signature,
+        /// The hash of the action which the op was produced from, to fetch by
+        action: ActionHashAndSig,
+        /// Whether to run app or sys validation
+        validation_type: ValidationType,
+    },
+}
+
 /// Action hash with the signature of the action at that hash
 pub type ActionHashAndSig = (ActionHash, Signature);
 
@@ -59,14 +89,18 @@ impl Warrant {
     /// Warrants always have the authoring agent as a basis, so that warrants
     /// can be accumulated by the agent activity authorities.
     pub fn dht_basis(&self) -> OpBasis {
+        self.warrantee().clone().into()
+    }
+
+    /// The agent against whom the warrant was issued.
+    pub fn warrantee(&self) -> &AgentPubKey {
         match self {
             Warrant::ChainIntegrity(w) => match w {
-                ChainIntegrityWarrant::InvalidChainOp { action_author, .. } => {
-                    action_author.clone().into()
-                }
-                ChainIntegrityWarrant::ChainFork { chain_author, .. } => {
-                    chain_author.clone().into()
-                }
+                ChainIntegrityWarrant::InvalidChainOp { action_author, .. } => action_author,
+                ChainIntegrityWarrant::ChainFork { chain_author, .. } => chain_author,
+            },
+            Warrant::ValidationReceipt(w) => match w {
+                ValidationReceiptWarrant::InvalidOpReceipted { validator, .. } => validator,
             },
         }
     }
@@ -148,6 +148,48 @@ pub struct WasmZome {
     /// Useful for iOS and other targets.
     #[serde(default)]
     pub preserialized_path: Option<PathBuf>,
+
+    /// How the `post_commit` callback of this zome is retried when it fails.
+    /// Only used for coordinator zomes. If not set, it is not retried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_commit_retry: Option<PostCommitRetryPolicy>,
+}
+
+/// How often and how long apart a failed `post_commit` callback is run again.
+///
+/// Every retry waits twice as long as the one before, starting with `backoff_ms`.
+#[derive(
+    Serialize, Deserialize, Hash, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PostCommitRetryPolicy {
+    /// The number of times the callback is run again after failing,
+    /// up to [`MAX_POST_COMMIT_RETRIES`].
+    pub max_retries: u32,
+    /// The delay before the first retry, in milliseconds.
+    #[serde(default)]
+    pub backoff_ms: u64,
+}
+
+/// The most times a failed `post_commit` callback is run again, whatever its policy.
+pub const MAX_POST_COMMIT_RETRIES: u32 = 10;
+
+/// The longest delay before a retry of a `post_commit` callback, whatever its policy.
+pub const MAX_POST_COMMIT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+impl PostCommitRetryPolicy {
+    /// The number of times the callback is run again, capped at [`MAX_POST_COMMIT_RETRIES`].
+    pub fn retries(&self) -> u32 {
+        self.max_retries.min(MAX_POST_COMMIT_RETRIES)
+    }
+
+    /// The delay before the given retry, counting from 0,
+    /// capped at [`MAX_POST_COMMIT_RETRY_DELAY`].
+    pub fn delay(&self, retry: u32) -> std::time::Duration {
+        std::time::Duration::from_millis(self.backoff_ms.saturating_mul(2u64.saturating_pow(retry)))
+            .min(MAX_POST_COMMIT_RETRY_DELAY)
+    }
 }
 
 /// Just the definition of a Zome, without the name included. This exists
@@ -218,6 +260,18 @@ impl CoordinatorZomeDef {
         &self.0
     }
 
+    /// The retry policy of the `post_commit` callback of this zome, if any.
+    pub fn post_commit_retry(&self) -> Option<&PostCommitRetryPolicy> {
+        match &self.0 {
+            ZomeDef::Wasm(WasmZome {
+                post_commit_retry, ..
+            }) => post_commit_retry.as_ref(),
+
+            #[cfg(feature = "full-dna-def")]
+            ZomeDef::Inline { .. } => None,
+        }
+    }
+
     /// Add a dependency to this zome.
     pub fn set_dependency(&mut self, zome_name: impl Into<ZomeName>) {
         match &mut self.0 {
@@ -347,6 +401,7 @@ impl WasmZome {
             wasm_hash,
             dependencies: Default::default(),
             preserialized_path: None,
+            post_commit_retry: None,
         }
     }
 }
@@ -358,6 +413,7 @@ impl ZomeDef {
             wasm_hash,
             dependencies: Default::default(),
             preserialized_path: None,
+            post_commit_retry: None,
         })
     }
 }
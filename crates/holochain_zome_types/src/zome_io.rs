@@ -53,14 +53,14 @@ wasm_io_types! {
 
     fn call (Vec<zt::call::Call>) -> Vec<zt::prelude::ZomeCallResponse>;
 
-    // @todo List all the local capability claims.
-    fn capability_claims (()) -> ();
+    // List the local capability claims matching the input.
+    fn capability_claims (zt::capability::CapClaimsInput) -> Vec<zt::capability::CapClaim>;
 
-    // @todo List all the local capability grants.
-    fn capability_grants (()) -> ();
+    // List the local capability grants which have not been updated or deleted.
+    fn capability_grants (()) -> Vec<zt::capability::CapGrantInfo>;
 
-    // @todo Get the capability for the current zome call.
-    fn capability_info (()) -> ();
+    // Get the capability grant which authorized the current zome call.
+    fn capability_info (()) -> zt::capability::CapGrant;
 
     // Returns ActionHash of the newly created record.
     fn create (zt::entry::CreateInput) -> holo_hash::ActionHash;
@@ -127,7 +127,7 @@ wasm_io_types! {
     // Schedule a schedulable function if it is not already.
     fn schedule (String) -> ();
 
-    // @todo
+    // Pause the call for some time.
     fn sleep (core::time::Duration) -> ();
 
     // @todo
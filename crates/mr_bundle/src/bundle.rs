@@ -2,7 +2,7 @@ use crate::{
     error::{BundleError, MrBundleResult},
     location::Location,
     manifest::Manifest,
-    resource::ResourceBytes,
+    resource::{ResourceBytes, ResourceHash},
 };
 use holochain_util::ffs;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -129,7 +129,8 @@ where
     }
 
     /// Retrieve the bytes for a resource at a Location, downloading it if
-    /// necessary
+    /// necessary. If the manifest pins the hash of the resource, the bytes
+    /// are checked against it.
     pub async fn resolve(&self, location: &Location) -> MrBundleResult<Cow<'_, ResourceBytes>> {
         let bytes = match &location.normalize(self.root_dir.as_ref())? {
             Location::Bundled(path) => Cow::Borrowed(
@@ -140,6 +141,17 @@ where
             Location::Path(path) => Cow::Owned(crate::location::resolve_local(path).await?),
             Location::Url(url) => Cow::Owned(crate::location::resolve_remote(url).await?),
         };
+        if let Some(expected) = self.manifest.resource_hash(location) {
+            let actual = ResourceHash::of(&bytes);
+            if actual != expected {
+                return Err(BundleError::ResourceHashMismatch {
+                    location: location.clone(),
+                    expected,
+                    actual,
+                }
+                .into());
+            }
+        }
         Ok(bytes)
     }
 
@@ -226,7 +238,7 @@ impl<M: serde::de::DeserializeOwned> RawBundle<M> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use crate::error::MrBundleError;
 
     use super::*;
@@ -253,6 +265,57 @@ mod tests {
     #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
     struct Thing(u32);
 
+    /// A manifest which pins the hash of every resource
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct PinnedManifest(Vec<(Location, ResourceHash)>);
+
+    impl Manifest for PinnedManifest {
+        fn locations(&self) -> Vec<Location> {
+            self.0
+                .iter()
+                .map(|(location, _)| location.clone())
+                .collect()
+        }
+
+        fn resource_hash(&self, location: &Location) -> Option<ResourceHash> {
+            self.0
+                .iter()
+                .find(|(l, _)| l == location)
+                .map(|(_, hash)| *hash)
+        }
+
+        #[cfg(feature = "packing")]
+        fn path() -> PathBuf {
+            unimplemented!()
+        }
+
+        #[cfg(feature = "packing")]
+        fn bundle_extension() -> &'static str {
+            unimplemented!()
+        }
+    }
+
+    /// Serve the given bytes over HTTP, at any path, returning the base URL
+    pub(crate) async fn serve(body: Vec<u8>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 1024];
+                let _ = stream.read(&mut request).await;
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(&body).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
     #[tokio::test]
     async fn bundle_validation() {
         let manifest = TestManifest(vec![
@@ -269,4 +332,57 @@ mod tests {
             Err(MrBundleError::BundleError(BundleError::BundledPathNotInManifest(path))) if path == PathBuf::from("3.thing")
         );
     }
+
+    #[tokio::test]
+    async fn resolve_checks_pinned_resource_hashes() {
+        let bytes = vec![1, 2, 3];
+        let url = format!("{}/thing", serve(bytes.clone()).await);
+        let hash = ResourceHash::of(&bytes);
+        let wrong_hash = ResourceHash::of(&[4]);
+
+        let bundled = Location::Bundled("1.thing".into());
+        let remote = Location::Url(url);
+        let bundle = Bundle::new_unchecked(
+            PinnedManifest(vec![(bundled.clone(), hash), (remote.clone(), hash)]),
+            vec![("1.thing".into(), bytes.clone().into())],
+        )
+        .unwrap();
+        assert_eq!(bundle.resolve(&bundled).await.unwrap().inner(), &bytes);
+        assert_eq!(bundle.resolve(&remote).await.unwrap().inner(), &bytes);
+
+        let bundle = Bundle::new_unchecked(
+            PinnedManifest(vec![
+                (bundled.clone(), wrong_hash),
+                (remote.clone(), wrong_hash),
+            ]),
+            vec![("1.thing".into(), bytes.into())],
+        )
+        .unwrap();
+        for location in [bundled, remote] {
+            matches::assert_matches!(
+                bundle.resolve(&location).await,
+                Err(MrBundleError::BundleError(BundleError::ResourceHashMismatch { expected, actual, .. }))
+                    if expected == wrong_hash && actual == hash
+            );
+        }
+    }
+
+    #[test]
+    fn resource_hash_roundtrip() {
+        let hash = ResourceHash::of(b"");
+        assert_eq!(
+            hash.to_string(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(hash, hash.to_string().parse().unwrap());
+        assert_eq!(
+            serde_yaml::from_str::<ResourceHash>(&serde_yaml::to_string(&hash).unwrap()).unwrap(),
+            hash
+        );
+        assert!("e3b0".parse::<ResourceHash>().is_err());
+        // Only hex digits, not the signs which `u8::from_str_radix` accepts
+        assert!(format!("+3{}", &hash.to_string()[2..])
+            .parse::<ResourceHash>()
+            .is_err());
+    }
 }
@@ -52,6 +52,21 @@ pub enum BundleError {
         "Cannot use relative paths for local locations. The following local path is relative: {0}"
     )]
     RelativeLocalPath(std::path::PathBuf),
+
+    #[error("Not a valid resource hash, which must be 64 hex digits: {0}")]
+    InvalidResourceHash(String),
+
+    #[error(
+        "The resource at {location:?} has the hash {actual}, but the manifest expects {expected}"
+    )]
+    ResourceHashMismatch {
+        location: crate::Location,
+        expected: crate::ResourceHash,
+        actual: crate::ResourceHash,
+    },
+
+    #[error("The resource at {url} is larger than {max_size} bytes")]
+    RemoteResourceTooLarge { url: String, max_size: u64 },
 }
 pub type BundleResult<T> = Result<T, BundleError>;
 
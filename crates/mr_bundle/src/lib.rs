@@ -27,6 +27,8 @@ mod packing;
 
 pub use bundle::{Bundle, RawBundle};
 pub use encoding::{decode, encode};
-pub use location::Location;
+pub use location::{
+    resolve_remote, Location, MAX_REMOTE_RESOURCE_SIZE, REMOTE_CONNECT_TIMEOUT, REMOTE_TIMEOUT,
+};
 pub use manifest::Manifest;
-pub use resource::ResourceBytes;
+pub use resource::{ResourceBytes, ResourceHash};
@@ -4,6 +4,7 @@ use crate::{
 };
 use holochain_util::ffs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Where to find a Resource.
 ///
@@ -64,14 +65,44 @@ pub(crate) async fn resolve_local(path: &Path) -> MrBundleResult<ResourceBytes>
     Ok(ffs::read(path).await?.into())
 }
 
-pub(crate) async fn resolve_remote(url: &str) -> MrBundleResult<ResourceBytes> {
-    Ok(reqwest::get(url)
-        .await?
-        .bytes()
-        .await?
-        .into_iter()
-        .collect::<Vec<_>>()
-        .into())
+/// How long to wait for the connection to the server of a remote resource.
+pub const REMOTE_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long downloading a remote resource may take in total, including connecting.
+pub const REMOTE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// The size in bytes of the largest remote resource which is downloaded.
+pub const MAX_REMOTE_RESOURCE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Download a resource. Responses with an error status are errors, and so are
+/// downloads which take longer than [`REMOTE_TIMEOUT`] or are larger than
+/// [`MAX_REMOTE_RESOURCE_SIZE`].
+pub async fn resolve_remote(url: &str) -> MrBundleResult<ResourceBytes> {
+    download(url, REMOTE_TIMEOUT, MAX_REMOTE_RESOURCE_SIZE).await
+}
+
+async fn download(url: &str, timeout: Duration, max_size: u64) -> MrBundleResult<ResourceBytes> {
+    let too_large = || BundleError::RemoteResourceTooLarge {
+        url: url.to_string(),
+        max_size,
+    };
+    let client = reqwest::Client::builder()
+        .connect_timeout(REMOTE_CONNECT_TIMEOUT)
+        .timeout(timeout)
+        .build()?;
+    let mut response = client.get(url).send().await?.error_for_status()?;
+    if response.content_length().unwrap_or(0) > max_size {
+        return Err(too_large().into());
+    }
+    // The content length is only a hint, so the size is checked while downloading too.
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if (bytes.len() + chunk.len()) as u64 > max_size {
+            return Err(too_large().into());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes.into())
 }
 
 #[cfg(test)]
@@ -125,4 +156,38 @@ mod tests {
         );
         assert_eq!(val["url"], Value::from("http://r.co"));
     }
+
+    #[tokio::test]
+    async fn download_refuses_resources_which_are_too_large() {
+        let url = crate::bundle::tests::serve(vec![0; 11]).await;
+
+        assert_eq!(
+            download(&url, REMOTE_TIMEOUT, 11).await.unwrap().inner(),
+            &vec![0; 11]
+        );
+        matches::assert_matches!(
+            download(&url, REMOTE_TIMEOUT, 10).await,
+            Err(crate::error::MrBundleError::BundleError(
+                BundleError::RemoteResourceTooLarge { max_size: 10, .. }
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn download_times_out() {
+        // A server which accepts connections but never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+
+        matches::assert_matches!(
+            download(&url, Duration::from_millis(100), MAX_REMOTE_RESOURCE_SIZE).await,
+            Err(crate::error::MrBundleError::HttpError(e)) if e.is_timeout()
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::location::Location;
+use crate::{location::Location, resource::ResourceHash};
 
 /// A Manifest describes the resources in a [`Bundle`](crate::Bundle) and how
 /// to pack and unpack them.
@@ -18,6 +18,13 @@ pub trait Manifest:
     /// correctly implemented to enable resource resolution.
     fn locations(&self) -> Vec<Location>;
 
+    /// The hash which the resource at one of the Locations of the manifest
+    /// must have, if the manifest pins it. Resolving a resource which doesn't
+    /// match its hash fails.
+    fn resource_hash(&self, _location: &Location) -> Option<ResourceHash> {
+        None
+    }
+
     /// When unpacking the bundle into a directory structure, this becomes
     /// the relative path of the manifest file.
     #[cfg(feature = "packing")]
@@ -1,3 +1,5 @@
+use crate::error::BundleError;
+
 /// Arbitrary opaque bytes representing a Resource in a [`Bundle`](crate::Bundle)
 #[derive(
     Clone,
@@ -32,3 +34,71 @@ impl std::fmt::Debug for ResourceBytes {
         ))
     }
 }
+
+/// The SHA-256 hash of the bytes of a resource, with which a
+/// [`Manifest`](crate::Manifest) can pin the content of a resource.
+///
+/// In manifests it is written as a lowercase hex string, as e.g. `sha256sum`
+/// prints it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "String", into = "String")]
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(arbitrary::Arbitrary, proptest_derive::Arbitrary)
+)]
+pub struct ResourceHash([u8; 32]);
+
+impl ResourceHash {
+    /// Hash the bytes of a resource
+    pub fn of(bytes: &[u8]) -> Self {
+        use sha2::Digest;
+        Self(sha2::Sha256::digest(bytes).into())
+    }
+
+    /// Accessor
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ResourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&holochain_util::hex::bytes_to_hex(&self.0, false))
+    }
+}
+
+impl std::fmt::Debug for ResourceHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mr_bundle::ResourceHash({})", self)
+    }
+}
+
+impl std::str::FromStr for ResourceHash {
+    type Err = BundleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BundleError::InvalidResourceHash(s.to_string());
+        if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let mut hash = [0; 32];
+        for (i, b) in hash.iter_mut().enumerate() {
+            *b = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Self(hash))
+    }
+}
+
+impl TryFrom<String> for ResourceHash {
+    type Error = BundleError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ResourceHash> for String {
+    fn from(hash: ResourceHash) -> Self {
+        hash.to_string()
+    }
+}
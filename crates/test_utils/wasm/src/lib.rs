@@ -509,5 +509,6 @@ async fn path_to_def(path: PathBuf, dependencies: Vec<ZomeName>) -> ZomeDef {
         wasm_hash,
         dependencies,
         preserialized_path: None,
+        post_commit_retry: None,
     })
 }
@@ -79,6 +79,21 @@ pub fn delete_cap_grant(action_hash: ActionHash) -> ExternResult<ActionHash> {
     hdk::prelude::delete_cap_grant(action_hash)
 }
 
+#[hdk_extern]
+fn list_cap_grants(_: ()) -> ExternResult<Vec<CapGrantInfo>> {
+    capability_grants()
+}
+
+#[hdk_extern]
+fn list_cap_claims(input: CapClaimsInput) -> ExternResult<Vec<CapClaim>> {
+    capability_claims(input)
+}
+
+#[hdk_extern]
+fn cap_info(_: ()) -> ExternResult<CapGrant> {
+    capability_info()
+}
+
 #[hdk_extern]
 fn get_entry(action_hash: ActionHash) -> ExternResult<Option<Record>> {
     get(action_hash, GetOptions::network())
@@ -3,6 +3,30 @@ use integrity::LinkTypes;
 
 mod integrity;
 
+/// A base path, the depth of the paths below it to list, the start and end
+/// bounds and whether to list them in reverse.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct RangeInput(String, usize, Vec<String>, Vec<String>, bool);
+
+/// The start and end of a time range and whether to list it in reverse.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct TimeRangeInput(Timestamp, Timestamp, bool);
+
+fn time_index() -> ExternResult<TimeIndex> {
+    Ok(TimeIndex::new(
+        Path::from("time").typed(LinkTypes::Path)?,
+        TimeBucket::Hour,
+    ))
+}
+
+fn path_string(path: TypedPath) -> ExternResult<String> {
+    TryInto::<String>::try_into(path.path).map_err(|e| wasm_error!(e))
+}
+
+fn path_strings(paths: impl Iterator<Item = ExternResult<TypedPath>>) -> ExternResult<Vec<String>> {
+    paths.map(|path| path_string(path?)).collect()
+}
+
 #[hdk_extern]
 fn path_entry_hash(path_string: String) -> ExternResult<EntryHash> {
     Path::from(path_string).path_entry_hash()
@@ -37,3 +61,29 @@ fn children_details(path_string: String) -> ExternResult<LinkDetails> {
         .typed(LinkTypes::Path)?
         .children_details()
 }
+
+#[hdk_extern]
+fn range(input: RangeInput) -> ExternResult<Vec<String>> {
+    let RangeInput(path_string, depth, start, end, reverse) = input;
+    let mut range = PathRange::new(depth).start(start).end(end);
+    if reverse {
+        range = range.reverse();
+    }
+    path_strings(Path::from(path_string).typed(LinkTypes::Path)?.range(range))
+}
+
+#[hdk_extern]
+fn ensure_time_bucket(timestamp: Timestamp) -> ExternResult<String> {
+    path_string(time_index()?.ensure_bucket(timestamp)?)
+}
+
+#[hdk_extern]
+fn time_buckets(input: TimeRangeInput) -> ExternResult<Vec<String>> {
+    let TimeRangeInput(start, end, reverse) = input;
+    let index = time_index()?;
+    if reverse {
+        path_strings(index.buckets_rev(start, end))
+    } else {
+        path_strings(index.buckets(start, end))
+    }
+}
@@ -5,6 +5,11 @@ fn sys_time(_: ()) -> ExternResult<Timestamp> {
     hdk::prelude::sys_time()
 }
 
+#[hdk_extern]
+fn sleep(wake_after: std::time::Duration) -> ExternResult<()> {
+    hdk::prelude::sleep(wake_after)
+}
+
 #[cfg(all(test, feature = "mock"))]
 pub mod test {
     use hdk::prelude::*;